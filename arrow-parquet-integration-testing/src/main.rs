@@ -170,6 +170,9 @@ fn main() -> Result<()> {
         write_statistics: true,
         compression: args.compression.into(),
         version: args.version.into(),
+        bloom_filter: None,
+        data_page_size: None,
+        data_page_max_rows: None,
     };
 
     let encodings = schema
@@ -17,6 +17,9 @@ fn write(array: &dyn Array, encoding: Encoding) -> Result<()> {
         write_statistics: false,
         compression: CompressionOptions::Uncompressed,
         version: Version::V1,
+        bloom_filter: None,
+        data_page_size: None,
+        data_page_max_rows: None,
     };
 
     let row_groups = RowGroupIterator::try_new(
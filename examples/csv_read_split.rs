@@ -0,0 +1,64 @@
+use std::fs::File;
+use std::thread;
+
+use arrow2::array::Array;
+use arrow2::chunk::Chunk;
+use arrow2::error::Result;
+use arrow2::io::csv::read;
+
+fn split_read(path: &str, n: usize) -> Result<Vec<Chunk<Box<dyn Array>>>> {
+    let chunk_size = 1024;
+    // the dialect of the file, used to both split and read it
+    let options = read::SplitOptions::default();
+
+    let mut reader = read::ReaderBuilder::new().from_path(path)?;
+    let (fields, _) = read::infer_schema(
+        &mut reader,
+        Some(chunk_size),
+        options.has_header,
+        &read::infer,
+    )?;
+
+    // split the file into `n` ranges of records, reading the bytes around their boundaries
+    let splits = read::split_file(&mut File::open(path)?, n, &options)?;
+
+    // tokenize and deserialize each range on its own thread (CPU bounded)
+    let children = splits
+        .into_iter()
+        .map(|split| {
+            let path = path.to_string();
+            let fields = fields.clone();
+            thread::spawn(move || {
+                let reader = read::SplitReader::try_new(
+                    File::open(path)?,
+                    &split,
+                    &options,
+                    fields,
+                    None,
+                    chunk_size,
+                    Default::default(),
+                )?;
+                reader.collect::<Result<Vec<_>>>()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // the chunks of the file, in order
+    let mut chunks = vec![];
+    for child in children {
+        chunks.extend(child.join().expect("child thread panicked")?);
+    }
+    Ok(chunks)
+}
+
+fn main() -> Result<()> {
+    use std::env;
+    let args: Vec<String> = env::args().collect();
+    let file_path = &args[1];
+
+    let chunks = split_read(file_path, 4)?;
+    for chunk in chunks {
+        println!("{}", chunk.len())
+    }
+    Ok(())
+}
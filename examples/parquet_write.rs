@@ -16,6 +16,9 @@ fn write_chunk(path: &str, schema: Schema, chunk: Chunk<Box<dyn Array>>) -> Resu
         write_statistics: true,
         compression: CompressionOptions::Uncompressed,
         version: Version::V2,
+        bloom_filter: None,
+        data_page_size: None,
+        data_page_max_rows: None,
     };
 
     let iter = vec![Ok(chunk)];
@@ -17,6 +17,9 @@ async fn write_batch(path: &str, schema: Schema, columns: Chunk<Box<dyn Array>>)
         write_statistics: true,
         compression: CompressionOptions::Uncompressed,
         version: Version::V2,
+        bloom_filter: None,
+        data_page_size: None,
+        data_page_max_rows: None,
     };
 
     let mut stream = futures::stream::iter(vec![Ok(columns)].into_iter());
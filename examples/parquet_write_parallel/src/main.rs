@@ -47,6 +47,9 @@ fn parallel_write(path: &str, schema: Schema, chunks: &[Chunk]) -> Result<()> {
         write_statistics: true,
         compression: CompressionOptions::Snappy,
         version: Version::V2,
+        bloom_filter: None,
+        data_page_size: None,
+        data_page_max_rows: None,
     };
 
     let encoding_map = |data_type: &DataType| {
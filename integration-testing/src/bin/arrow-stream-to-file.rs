@@ -28,7 +28,10 @@ fn main() -> Result<()> {
 
     let writer = io::stdout();
 
-    let options = write::WriteOptions { compression: None };
+    let options = write::WriteOptions {
+        compression: None,
+        ..Default::default()
+    };
     let mut writer = write::FileWriter::try_new(
         writer,
         &metadata.schema,
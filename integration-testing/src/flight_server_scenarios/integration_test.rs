@@ -107,7 +107,10 @@ impl FlightService for Service {
             .get(&key)
             .ok_or_else(|| Status::not_found(format!("Could not find flight. {}", key)))?;
 
-        let options = ipc::write::WriteOptions { compression: None };
+        let options = ipc::write::WriteOptions {
+            compression: None,
+            ..Default::default()
+        };
 
         let schema = serialize_schema(&flight.schema, Some(&flight.ipc_schema.fields));
 
@@ -8,7 +8,7 @@ use crate::{
 use super::{new_empty_array, specification::try_check_offsets, Array};
 
 mod ffi;
-mod fmt;
+pub(super) mod fmt;
 mod iterator;
 pub use iterator::*;
 
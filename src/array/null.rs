@@ -96,7 +96,7 @@ impl Array for NullArray {
 
     #[inline]
     fn data_type(&self) -> &DataType {
-        &DataType::Null
+        &self.data_type
     }
 
     fn validity(&self) -> Option<&Bitmap> {
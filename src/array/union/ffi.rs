@@ -1,8 +1,15 @@
-use crate::{array::FromFfi, error::Result, ffi};
+use crate::{array::FromFfi, buffer::Buffer, error::Result, ffi, types::NativeType};
 
 use super::super::{ffi::ToFfi, Array};
 use super::UnionArray;
 
+/// Returns a copy of `buffer` whose offset is `offset`.
+fn align<T: NativeType>(buffer: &Buffer<T>, offset: usize) -> Buffer<T> {
+    let mut values = vec![T::default(); offset];
+    values.extend_from_slice(buffer.as_slice());
+    Buffer::from(values).slice(offset, buffer.len())
+}
+
 unsafe impl ToFfi for UnionArray {
     fn buffers(&self) -> Vec<Option<*const u8>> {
         if let Some(offsets) = &self.offsets {
@@ -20,11 +27,39 @@ unsafe impl ToFfi for UnionArray {
     }
 
     fn offset(&self) -> Option<usize> {
-        Some(self.types.offset())
+        let offset = self.types.offset();
+        // the offset of sparse unions also applies to its fields
+        let aligned = match &self.offsets {
+            Some(offsets) => offsets.offset() == offset,
+            None => self.offset == offset,
+        };
+        if aligned {
+            Some(offset)
+        } else {
+            None
+        }
     }
 
     fn to_ffi_aligned(&self) -> Self {
-        self.clone()
+        let (types, offsets) = match &self.offsets {
+            Some(offsets) => {
+                let offset = self.types.offset();
+                let offsets = if offsets.offset() == offset {
+                    offsets.clone()
+                } else {
+                    align(offsets, offset)
+                };
+                (self.types.clone(), Some(offsets))
+            }
+            None if self.types.offset() == self.offset => (self.types.clone(), None),
+            None => (align(&self.types, self.offset), None),
+        };
+
+        Self {
+            types,
+            offsets,
+            ..self.clone()
+        }
     }
 }
 
@@ -33,15 +68,15 @@ impl<A: ffi::ArrowArrayRef> FromFfi<A> for UnionArray {
         let data_type = array.data_type().clone();
         let fields = Self::get_fields(&data_type);
 
-        let mut types = unsafe { array.buffer::<i8>(0) }?;
-        let offsets = if Self::is_sparse(&data_type) {
+        // the buffers are already sliced by the array's offset
+        let types = unsafe { array.buffer::<i8>(0) }?;
+        let is_sparse = Self::is_sparse(&data_type);
+        let offsets = if is_sparse {
             None
         } else {
             Some(unsafe { array.buffer::<i32>(1) }?)
         };
 
-        let length = array.array().len();
-        let offset = array.array().offset();
         let fields = (0..fields.len())
             .map(|index| {
                 let child = array.child(index)?;
@@ -49,10 +84,11 @@ impl<A: ffi::ArrowArrayRef> FromFfi<A> for UnionArray {
             })
             .collect::<Result<Vec<Box<dyn Array>>>>()?;
 
-        if offset > 0 {
-            types = types.slice(offset, length);
-        };
-
-        Self::try_new(data_type, types, fields, offsets)
+        let mut union = Self::try_new(data_type, types, fields, offsets)?;
+        if is_sparse {
+            // the fields of sparse unions are indexed by the array's offset
+            union.offset = array.array().offset();
+        }
+        Ok(union)
     }
 }
@@ -0,0 +1,368 @@
+//! Contains the [`group_by`] operator, that aggregates value columns by distinct keys.
+use hash_hasher::HashedMap;
+use num_traits::AsPrimitive;
+
+use crate::{
+    array::{Array, PrimitiveArray},
+    chunk::Chunk,
+    datatypes::{DataType, PhysicalType, PrimitiveType},
+    error::{Error, Result},
+    types::NativeType,
+};
+
+use super::hash::hash_rows;
+use super::take::take;
+use super::utils::build_rows_eq;
+
+/// An aggregation that [`group_by`] can compute over a value column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Aggregation {
+    /// The number of non-null values of each group, as `UInt64`
+    Count,
+    /// The sum of the non-null values of each group, of the same type as the values
+    Sum,
+    /// The minimum of the non-null values of each group, of the same type as the values
+    Min,
+    /// The maximum of the non-null values of each group, of the same type as the values
+    Max,
+    /// The mean of the non-null values of each group, as `Float64`
+    Mean,
+}
+
+/// The distinct keys of a set of columns, as computed by [`groups`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Groups {
+    /// The index of the first row of each group. Groups are ordered by their first appearance.
+    pub first: PrimitiveArray<u32>,
+    /// The group of each row, as an index of `first`.
+    pub ids: Vec<u32>,
+}
+
+impl Groups {
+    /// The number of groups
+    pub fn len(&self) -> usize {
+        self.first.len()
+    }
+
+    /// Whether there are no groups
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Assigns every row of `keys` to the group of rows with equal keys.
+/// Rows whose keys are null in the same columns and equal elsewhere belong to the same group.
+/// # Errors
+/// This function errors iff there are no keys, the keys cannot be hashed (see [`hash_rows`](super::hash::hash_rows))
+/// or compared (see [`build_compare`](crate::array::ord::build_compare)), or if they have
+/// different lengths.
+pub fn groups(keys: &[&dyn Array]) -> Result<Groups> {
+    if keys.is_empty() {
+        return Err(Error::InvalidArgumentError(
+            "Grouping requires at least one key column".to_string(),
+        ));
+    }
+    let hashes = hash_rows(keys)?;
+    let eq = build_rows_eq(keys, keys)?;
+
+    let mut first = Vec::<u32>::new();
+    let mut ids = Vec::<u32>::with_capacity(hashes.len());
+    // hash -> groups whose keys have that hash
+    let mut map = HashedMap::<u64, Vec<u32>>::default();
+    for (row, hash) in hashes.into_iter().enumerate() {
+        let candidates = map.entry(hash).or_default();
+        let group = candidates
+            .iter()
+            .copied()
+            .find(|group| eq(first[*group as usize] as usize, row));
+        let group = group.unwrap_or_else(|| {
+            let group = first.len() as u32;
+            first.push(row as u32);
+            candidates.push(group);
+            group
+        });
+        ids.push(group);
+    }
+
+    Ok(Groups {
+        first: PrimitiveArray::from_vec(first),
+        ids,
+    })
+}
+
+macro_rules! with_match_primitive_type {(
+    $key_type:expr, | $_:tt $T:ident | $($body:tt)*
+) => ({
+    macro_rules! __with_ty__ {( $_ $T:ident ) => ( $($body)* )}
+    use crate::datatypes::PrimitiveType::*;
+    match $key_type {
+        Int8 => __with_ty__! { i8 },
+        Int16 => __with_ty__! { i16 },
+        Int32 => __with_ty__! { i32 },
+        Int64 => __with_ty__! { i64 },
+        Int128 => __with_ty__! { i128 },
+        UInt8 => __with_ty__! { u8 },
+        UInt16 => __with_ty__! { u16 },
+        UInt32 => __with_ty__! { u32 },
+        UInt64 => __with_ty__! { u64 },
+        Float32 => __with_ty__! { f32 },
+        Float64 => __with_ty__! { f64 },
+        _ => unreachable!(),
+    }
+})}
+
+fn fold_groups<T, A, F>(array: &PrimitiveArray<T>, groups: &Groups, f: F) -> Vec<Option<A>>
+where
+    T: NativeType,
+    A: Copy,
+    F: Fn(Option<A>, T) -> A,
+{
+    let mut acc = vec![None; groups.len()];
+    array
+        .iter()
+        .zip(groups.ids.iter())
+        .for_each(|(value, group)| {
+            if let Some(value) = value {
+                let acc = &mut acc[*group as usize];
+                *acc = Some(f(*acc, *value));
+            }
+        });
+    acc
+}
+
+/// A [`NativeType`] that [`sum_groups`] can sum. Like in [`crate::compute::aggregate::sum`],
+/// sums of integers wrap around on overflow.
+pub trait GroupSum: NativeType {
+    /// Returns `self + rhs`, wrapping around on overflow
+    fn group_add(self, rhs: Self) -> Self;
+}
+
+macro_rules! group_sum_wrapping {
+    ($($type:ty),*) => {$(
+        impl GroupSum for $type {
+            #[inline]
+            fn group_add(self, rhs: Self) -> Self {
+                self.wrapping_add(rhs)
+            }
+        }
+    )*};
+}
+
+group_sum_wrapping!(i8, i16, i32, i64, i128, u8, u16, u32, u64);
+
+impl GroupSum for f32 {
+    #[inline]
+    fn group_add(self, rhs: Self) -> Self {
+        self + rhs
+    }
+}
+
+impl GroupSum for f64 {
+    #[inline]
+    fn group_add(self, rhs: Self) -> Self {
+        self + rhs
+    }
+}
+
+/// Returns the sum of the values of `array` per group. Sums of integers wrap around on
+/// overflow.
+pub fn sum_groups<T: GroupSum>(array: &PrimitiveArray<T>, groups: &Groups) -> PrimitiveArray<T> {
+    let sums = fold_groups(array, groups, |acc, value| {
+        acc.map_or(value, |acc: T| acc.group_add(value))
+    });
+    PrimitiveArray::<T>::from(sums).to(array.data_type().clone())
+}
+
+/// Returns the minimum of the values of `array` per group.
+pub fn min_groups<T>(array: &PrimitiveArray<T>, groups: &Groups) -> PrimitiveArray<T>
+where
+    T: NativeType + PartialOrd,
+{
+    let mins = fold_groups(array, groups, |acc, value| {
+        acc.map_or(value, |acc| if value < acc { value } else { acc })
+    });
+    PrimitiveArray::<T>::from(mins).to(array.data_type().clone())
+}
+
+/// Returns the maximum of the values of `array` per group.
+pub fn max_groups<T>(array: &PrimitiveArray<T>, groups: &Groups) -> PrimitiveArray<T>
+where
+    T: NativeType + PartialOrd,
+{
+    let maxs = fold_groups(array, groups, |acc, value| {
+        acc.map_or(value, |acc| if value > acc { value } else { acc })
+    });
+    PrimitiveArray::<T>::from(maxs).to(array.data_type().clone())
+}
+
+/// Returns the mean of the values of `array` per group.
+pub fn mean_groups<T>(array: &PrimitiveArray<T>, groups: &Groups) -> PrimitiveArray<f64>
+where
+    T: NativeType + AsPrimitive<f64>,
+{
+    let totals = fold_groups(array, groups, |acc, value| {
+        let (sum, count) = acc.unwrap_or((0.0f64, 0usize));
+        (sum + value.as_(), count + 1)
+    });
+    totals
+        .into_iter()
+        .map(|total| total.map(|(sum, count)| sum / count as f64))
+        .collect()
+}
+
+/// Returns the number of non-null values of `array` per group.
+pub fn count_groups(array: &dyn Array, groups: &Groups) -> PrimitiveArray<u64> {
+    let mut counts = vec![0u64; groups.len()];
+    if array.data_type().to_physical_type() == PhysicalType::Null {
+        // all values of a `NullArray` are null, even though it has no validity
+        return PrimitiveArray::from_vec(counts);
+    }
+    match array.validity() {
+        Some(validity) => validity
+            .iter()
+            .zip(groups.ids.iter())
+            .filter(|(is_valid, _)| *is_valid)
+            .for_each(|(_, group)| counts[*group as usize] += 1),
+        None => groups
+            .ids
+            .iter()
+            .for_each(|group| counts[*group as usize] += 1),
+    }
+    PrimitiveArray::from_vec(counts)
+}
+
+/// Whether [`aggregate`] supports `aggregation` over arrays of `data_type`.
+pub fn can_aggregate(data_type: &DataType, aggregation: Aggregation) -> bool {
+    if aggregation == Aggregation::Count {
+        return true;
+    }
+    if aggregation == Aggregation::Mean && matches!(data_type, DataType::Decimal(_, _)) {
+        return false;
+    }
+    if let PhysicalType::Primitive(primitive) = data_type.to_physical_type() {
+        use PrimitiveType::*;
+        matches!(
+            primitive,
+            Int8 | Int16
+                | Int32
+                | Int64
+                | Int128
+                | UInt8
+                | UInt16
+                | UInt32
+                | UInt64
+                | Float32
+                | Float64
+        )
+    } else {
+        false
+    }
+}
+
+/// Returns `aggregation` of `array` per group, with one slot per group.
+/// # Error
+/// Errors iff the aggregation is not supported for the type of `array`
+/// (see [`can_aggregate`]) or `array`'s length differs from the number of rows of `groups`.
+pub fn aggregate(
+    array: &dyn Array,
+    groups: &Groups,
+    aggregation: Aggregation,
+) -> Result<Box<dyn Array>> {
+    if !can_aggregate(array.data_type(), aggregation) {
+        return Err(Error::InvalidArgumentError(format!(
+            "The aggregation {:?} does not support type `{:?}`",
+            aggregation,
+            array.data_type(),
+        )));
+    }
+    if array.len() != groups.ids.len() {
+        return Err(Error::InvalidArgumentError(
+            "Values and keys must have the same length".to_string(),
+        ));
+    }
+
+    if aggregation == Aggregation::Count {
+        return Ok(Box::new(count_groups(array, groups)));
+    }
+
+    let primitive = match array.data_type().to_physical_type() {
+        PhysicalType::Primitive(primitive) => primitive,
+        _ => unreachable!(),
+    };
+    Ok(with_match_primitive_type!(primitive, |$T| {
+        let array = array.as_any().downcast_ref::<PrimitiveArray<$T>>().unwrap();
+        match aggregation {
+            Aggregation::Sum => Box::new(sum_groups(array, groups)),
+            Aggregation::Min => Box::new(min_groups(array, groups)),
+            Aggregation::Max => Box::new(max_groups(array, groups)),
+            Aggregation::Mean => Box::new(mean_groups(array, groups)),
+            Aggregation::Count => unreachable!(),
+        }
+    }))
+}
+
+/// Groups the rows of `chunk` by the columns at `keys` and computes `aggregations`, a list of
+/// `(column index, aggregation)`, per group.
+///
+/// The returned [`Chunk`] has one row per distinct key, ordered by first appearance. Its
+/// columns are the key columns followed by one column per aggregation.
+/// Null keys are grouped together.
+/// # Example
+/// ```
+/// use arrow2::array::{Array, PrimitiveArray, Utf8Array};
+/// use arrow2::chunk::Chunk;
+/// use arrow2::compute::group_by::{group_by, Aggregation};
+///
+/// # fn main() -> arrow2::error::Result<()> {
+/// let keys = Utf8Array::<i32>::from([Some("a"), Some("b"), Some("a"), None]);
+/// let values = PrimitiveArray::from([Some(1i64), Some(2), Some(3), Some(4)]);
+/// let chunk = Chunk::new(vec![keys.boxed(), values.boxed()]);
+///
+/// let result = group_by(&chunk, &[0], &[(1, Aggregation::Sum), (1, Aggregation::Count)])?;
+///
+/// let expected = Utf8Array::<i32>::from([Some("a"), Some("b"), None]);
+/// assert_eq!(result.arrays()[0].as_ref(), &expected as &dyn Array);
+/// let expected = PrimitiveArray::from_slice([4i64, 2, 4]);
+/// assert_eq!(result.arrays()[1].as_ref(), &expected as &dyn Array);
+/// let expected = PrimitiveArray::from_slice([2u64, 1, 1]);
+/// assert_eq!(result.arrays()[2].as_ref(), &expected as &dyn Array);
+/// # Ok(())
+/// # }
+/// ```
+/// # Errors
+/// This function errors iff an index is out of bounds, the keys are not supported by
+/// [`groups`] or an aggregation is not supported by [`aggregate`].
+pub fn group_by<A: AsRef<dyn Array>>(
+    chunk: &Chunk<A>,
+    keys: &[usize],
+    aggregations: &[(usize, Aggregation)],
+) -> Result<Chunk<Box<dyn Array>>> {
+    let column = |index: usize| {
+        chunk
+            .arrays()
+            .get(index)
+            .map(|x| x.as_ref())
+            .ok_or_else(|| {
+                Error::InvalidArgumentError(format!(
+                    "Column {} is out of bounds of a chunk with {} columns",
+                    index,
+                    chunk.arrays().len()
+                ))
+            })
+    };
+
+    let keys = keys
+        .iter()
+        .map(|x| column(*x))
+        .collect::<Result<Vec<_>>>()?;
+    let groups = groups(&keys)?;
+
+    let mut arrays = keys
+        .iter()
+        .map(|key| take(*key, &groups.first))
+        .collect::<Result<Vec<_>>>()?;
+    for (index, aggregation) in aggregations {
+        arrays.push(aggregate(column(*index)?, &groups, *aggregation)?);
+    }
+    Chunk::try_new(arrays)
+}
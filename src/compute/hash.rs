@@ -12,7 +12,7 @@ macro_rules! new_state {
 }
 
 use crate::{
-    array::{Array, BinaryArray, BooleanArray, Offset, PrimitiveArray, Utf8Array},
+    array::{Array, BinaryArray, BooleanArray, DictionaryArray, Offset, PrimitiveArray, Utf8Array},
     datatypes::{DataType, PhysicalType, PrimitiveType},
     error::{Error, Result},
     types::NativeType,
@@ -158,3 +158,61 @@ pub fn can_hash(data_type: &DataType) -> bool {
             | PhysicalType::LargeUtf8
     )
 }
+
+/// The hash assigned to null slots by [`hash_rows`].
+const NULL_HASH: u64 = 0x9E37_79B9_7F4A_7C15;
+
+#[inline]
+fn combine_hashes(lhs: u64, rhs: u64) -> u64 {
+    (lhs.rotate_left(5) ^ rhs).wrapping_mul(0x517C_C1B7_2722_0A95)
+}
+
+/// Returns the hash of every slot of `array`, with null slots hashed to the same value.
+fn hash_nullable(array: &dyn Array) -> Result<Vec<u64>> {
+    if let DataType::Dictionary(key_type, _, _) = array.data_type().to_logical_type() {
+        match_integer_type!(key_type, |$T| {
+            let array = array
+                .as_any()
+                .downcast_ref::<DictionaryArray<$T>>()
+                .unwrap();
+            let values = hash(array.values().as_ref())?;
+            let values = values.values();
+            Ok(array
+                .keys_iter()
+                .map(|key| key.map(|key| values[key]).unwrap_or(NULL_HASH))
+                .collect())
+        })
+    } else {
+        Ok(hash(array)?
+            .iter()
+            .map(|x| x.copied().unwrap_or(NULL_HASH))
+            .collect())
+    }
+}
+
+/// Returns the hash of every row of `columns`, obtained by combining the hash of each column.
+/// Null slots all hash to the same value, so that rows whose values are null in the same
+/// columns hash equally.
+/// Supported DataTypes:
+/// * All DataTypes supported by [`hash`]
+/// * `Dictionary` whose values are supported by [`hash`]
+/// # Errors
+/// This function errors whenever it does not support one of the `DataType`s or when
+/// the columns have different lengths.
+pub fn hash_rows(columns: &[&dyn Array]) -> Result<Vec<u64>> {
+    let length = columns.first().map(|x| x.len()).unwrap_or_default();
+    if columns.iter().any(|x| x.len() != length) {
+        return Err(Error::InvalidArgumentError(
+            "Arrays must have the same length".to_string(),
+        ));
+    }
+
+    let mut hashes = vec![0u64; length];
+    for column in columns {
+        hash_nullable(*column)?
+            .into_iter()
+            .zip(hashes.iter_mut())
+            .for_each(|(column_hash, hash)| *hash = combine_hashes(*hash, column_hash));
+    }
+    Ok(hashes)
+}
@@ -0,0 +1,237 @@
+//! Contains hash join operators, that compute the indices of matching rows of two sets of columns.
+//!
+//! The indices returned by [`join_indices`] can be used with [`take`] to
+//! materialize the joined columns, which is what [`join`] does.
+use hash_hasher::HashedMap;
+
+use crate::{
+    array::{Array, MutablePrimitiveArray, PrimitiveArray},
+    bitmap::MutableBitmap,
+    chunk::Chunk,
+    error::{Error, Result},
+    types::Index,
+};
+
+use super::hash::hash_rows;
+use super::take::take;
+use super::utils::build_rows_eq;
+
+/// The type of join computed by [`join_indices`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JoinType {
+    /// Pairs of matching rows
+    Inner,
+    /// Pairs of matching rows and the rows of the left side without a match
+    Left,
+    /// Pairs of matching rows and the rows of the right side without a match
+    Right,
+    /// Pairs of matching rows and the rows of both sides without a match
+    Full,
+    /// Rows of the left side with at least one match
+    Semi,
+    /// Rows of the left side without a match
+    Anti,
+}
+
+/// Returns the validity of each row of `columns`: whether none of its columns is null.
+fn rows_validity(columns: &[&dyn Array]) -> Option<MutableBitmap> {
+    columns
+        .iter()
+        .filter_map(|column| column.validity())
+        .fold(None, |acc, validity| {
+            Some(match acc {
+                None => validity.iter().collect(),
+                Some(acc) => acc
+                    .iter()
+                    .zip(validity.iter())
+                    .map(|(a, b)| a & b)
+                    .collect(),
+            })
+        })
+}
+
+fn check_length<I: Index>(columns: &[&dyn Array]) -> Result<usize> {
+    let length = columns.first().map(|x| x.len()).unwrap_or_default();
+    if I::from_usize(length).is_none() {
+        return Err(Error::InvalidArgumentError(format!(
+            "Joins of {} rows cannot be indexed by {:?}",
+            length,
+            I::PRIMITIVE
+        )));
+    }
+    Ok(length)
+}
+
+/// Returns the indices of the rows of `left` and `right` that match, according to `join_type`.
+///
+/// Both sides must have the same number of columns, pairwise of the same [`DataType`](crate::datatypes::DataType).
+/// Rows match when all their columns are equal. Like in SQL, null keys never match.
+///
+/// The indices are returned as a pair `(left, right)` of equal length, where each
+/// slot is a pair of joined rows. Rows without a match (in outer joins) are paired with a
+/// null index, so that [`take`] yields a null for them.
+/// [`JoinType::Semi`] and [`JoinType::Anti`] only select rows of the left side: their right
+/// indices are `None`.
+///
+/// Pairs are ordered by the left rows; the unmatched right rows of [`JoinType::Right`] and
+/// [`JoinType::Full`] are at the end, in their original order.
+/// # Example
+/// ```
+/// use arrow2::array::{Int32Array, UInt32Array};
+/// use arrow2::compute::join::{join_indices, JoinType};
+///
+/// # fn main() -> arrow2::error::Result<()> {
+/// let left = Int32Array::from([Some(1), Some(2), None]);
+/// let right = Int32Array::from([Some(2), Some(3), Some(1), Some(2)]);
+///
+/// let (left, right) = join_indices::<u32>(&[&left], &[&right], JoinType::Left)?;
+///
+/// assert_eq!(left, UInt32Array::from_slice([0, 1, 1, 2]));
+/// assert_eq!(right, Some(UInt32Array::from([Some(2), Some(0), Some(3), None])));
+/// # Ok(())
+/// # }
+/// ```
+/// # Errors
+/// This function errors iff the number of columns differ, the columns of each side
+/// have different lengths, the number of rows cannot be represented by `I`, or the columns
+/// cannot be hashed (see [`hash_rows`](super::hash::hash_rows)) or compared (see
+/// [`build_compare`](crate::array::ord::build_compare)).
+pub fn join_indices<I: Index>(
+    left: &[&dyn Array],
+    right: &[&dyn Array],
+    join_type: JoinType,
+) -> Result<(PrimitiveArray<I>, Option<PrimitiveArray<I>>)> {
+    if left.is_empty() {
+        return Err(Error::InvalidArgumentError(
+            "Joins require at least one key column".to_string(),
+        ));
+    }
+    let eq = build_rows_eq(left, right)?;
+    let left_length = check_length::<I>(left)?;
+    let right_length = check_length::<I>(right)?;
+    let left_hashes = hash_rows(left)?;
+    let right_hashes = hash_rows(right)?;
+    let left_validity = rows_validity(left);
+    let right_validity = rows_validity(right);
+
+    // hash -> rows of the right side with that hash
+    let mut map = HashedMap::<u64, Vec<usize>>::default();
+    for (row, hash) in right_hashes.into_iter().enumerate() {
+        if right_validity.as_ref().map(|x| x.get(row)).unwrap_or(true) {
+            map.entry(hash).or_default().push(row);
+        }
+    }
+
+    let to_index = |x: usize| I::from_usize(x).unwrap();
+    let mut left_indices = MutablePrimitiveArray::<I>::with_capacity(left_length);
+    let mut right_indices = MutablePrimitiveArray::<I>::with_capacity(left_length);
+    let mut right_matched = MutableBitmap::from_len_zeroed(right_length);
+
+    for (row, hash) in left_hashes.into_iter().enumerate() {
+        let is_valid = left_validity.as_ref().map(|x| x.get(row)).unwrap_or(true);
+        let candidates = if is_valid {
+            map.get(&hash).map(|x| x.as_slice()).unwrap_or_default()
+        } else {
+            &[]
+        };
+        let mut matches = candidates.iter().copied().filter(|other| eq(row, *other));
+
+        match join_type {
+            JoinType::Semi => {
+                if matches.next().is_some() {
+                    left_indices.push(Some(to_index(row)));
+                }
+            }
+            JoinType::Anti => {
+                if matches.next().is_none() {
+                    left_indices.push(Some(to_index(row)));
+                }
+            }
+            _ => {
+                let mut is_matched = false;
+                for other in matches {
+                    is_matched = true;
+                    right_matched.set(other, true);
+                    left_indices.push(Some(to_index(row)));
+                    right_indices.push(Some(to_index(other)));
+                }
+                if !is_matched && matches!(join_type, JoinType::Left | JoinType::Full) {
+                    left_indices.push(Some(to_index(row)));
+                    right_indices.push(None);
+                }
+            }
+        }
+    }
+
+    if matches!(join_type, JoinType::Right | JoinType::Full) {
+        right_matched
+            .iter()
+            .enumerate()
+            .filter(|(_, is_matched)| !is_matched)
+            .for_each(|(other, _)| {
+                left_indices.push(None);
+                right_indices.push(Some(to_index(other)));
+            });
+    }
+
+    let right_indices = if matches!(join_type, JoinType::Semi | JoinType::Anti) {
+        None
+    } else {
+        Some(right_indices.into())
+    };
+    Ok((left_indices.into(), right_indices))
+}
+
+fn columns<'a, A: AsRef<dyn Array>>(
+    chunk: &'a Chunk<A>,
+    indices: &[usize],
+) -> Result<Vec<&'a dyn Array>> {
+    indices
+        .iter()
+        .map(|index| {
+            chunk
+                .arrays()
+                .get(*index)
+                .map(|x| x.as_ref())
+                .ok_or_else(|| {
+                    Error::InvalidArgumentError(format!(
+                        "Column {} is out of bounds of a chunk with {} columns",
+                        index,
+                        chunk.arrays().len()
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Joins `left` and `right` on the columns at `left_on` and `right_on` respectively.
+///
+/// The returned [`Chunk`] contains the columns of `left` followed by the columns of `right`,
+/// or only the columns of `left` for [`JoinType::Semi`] and [`JoinType::Anti`].
+/// See [`join_indices`] for how rows are matched and ordered.
+/// # Errors
+/// This function errors iff an index is out of bounds or [`join_indices`] errors.
+pub fn join<A: AsRef<dyn Array>>(
+    left: &Chunk<A>,
+    left_on: &[usize],
+    right: &Chunk<A>,
+    right_on: &[usize],
+    join_type: JoinType,
+) -> Result<Chunk<Box<dyn Array>>> {
+    let left_keys = columns(left, left_on)?;
+    let right_keys = columns(right, right_on)?;
+
+    let (left_indices, right_indices) = join_indices::<u64>(&left_keys, &right_keys, join_type)?;
+
+    let mut arrays = left
+        .arrays()
+        .iter()
+        .map(|array| take(array.as_ref(), &left_indices))
+        .collect::<Result<Vec<_>>>()?;
+    if let Some(right_indices) = right_indices {
+        for array in right.arrays() {
+            arrays.push(take(array.as_ref(), &right_indices)?);
+        }
+    }
+    Chunk::try_new(arrays)
+}
@@ -23,3 +23,37 @@ pub fn check_same_len(lhs: &dyn Array, rhs: &dyn Array) -> Result<()> {
     }
     Ok(())
 }
+
+/// Returns a function that returns whether row `i` of `lhs` equals row `j` of `rhs`, where
+/// two null slots are considered equal.
+/// # Errors
+/// Iff the columns do not pairwise have the same [`DataType`](crate::datatypes::DataType)
+/// or their type does not have a natural order.
+#[cfg(any(feature = "compute_group_by", feature = "compute_join"))]
+pub fn build_rows_eq(
+    lhs: &[&dyn Array],
+    rhs: &[&dyn Array],
+) -> Result<impl Fn(usize, usize) -> bool> {
+    use crate::array::ord::build_compare;
+
+    if lhs.len() != rhs.len() {
+        return Err(Error::InvalidArgumentError(
+            "Both sides must have the same number of columns".to_string(),
+        ));
+    }
+    let columns = lhs
+        .iter()
+        .zip(rhs.iter())
+        .map(|(lhs, rhs)| Ok((lhs.to_boxed(), rhs.to_boxed(), build_compare(*lhs, *rhs)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(move |i: usize, j: usize| {
+        columns
+            .iter()
+            .all(|(lhs, rhs, cmp)| match (lhs.is_null(i), rhs.is_null(j)) {
+                (true, true) => true,
+                (false, false) => cmp(i, j) == std::cmp::Ordering::Equal,
+                _ => false,
+            })
+    })
+}
@@ -385,7 +385,7 @@ unsafe fn create_dictionary(
     data_type: &DataType,
     parent: InternalArrowArray,
 ) -> Result<Option<ArrowArrayChild<'static>>> {
-    if let DataType::Dictionary(_, values, _) = data_type {
+    if let DataType::Dictionary(_, values, _) = data_type.to_logical_type() {
         let data_type = values.as_ref().clone();
         // catch what we can
         if array.dictionary.is_null() {
@@ -1,10 +1,15 @@
+use std::any::Any;
 use std::collections::VecDeque;
 use std::sync::Arc;
 
+use arrow_format::ipc::CompressionType;
+
 use crate::array::{Array, DictionaryKey, FixedSizeListArray, ListArray, Offset, StructArray};
+use crate::bitmap::utils::bytes_for;
 use crate::datatypes::DataType;
 use crate::error::Error;
 
+use crate::io::ipc::compression;
 use crate::io::ipc::read::{Dictionaries, OutOfSpecKind};
 use crate::io::ipc::read::{IpcBuffer, Node};
 use crate::io::ipc::IpcField;
@@ -16,6 +21,8 @@ use super::{export_array_to_c, try_from, ArrowArray, InternalArrowArray};
 struct PrivateData<T> {
     // the owner of the pointers' regions
     data: T,
+    // the owner of the regions of buffers that were decompressed
+    decompressed: Vec<Box<dyn Any>>,
     buffers_ptr: Box<[*const std::os::raw::c_void]>,
     children_ptr: Box<[*mut ArrowArray]>,
     dictionary_ptr: Option<*mut ArrowArray>,
@@ -39,18 +46,78 @@ fn get_buffer_bounds(buffers: &mut VecDeque<IpcBuffer>) -> Result<(usize, usize)
     Ok((offset, length))
 }
 
-fn get_buffer<'a, T: NativeType>(
+fn get_bytes<'a>(
     data: &'a [u8],
     block_offset: usize,
     buffers: &mut VecDeque<IpcBuffer>,
-    num_rows: usize,
 ) -> Result<&'a [u8], Error> {
     let (offset, length) = get_buffer_bounds(buffers)?;
 
     // verify that they are in-bounds
-    let values = data
-        .get(block_offset + offset..block_offset + offset + length)
-        .ok_or_else(|| Error::OutOfSpec("buffer out of bounds".to_string()))?;
+    data.get(block_offset + offset..block_offset + offset + length)
+        .ok_or_else(|| Error::OutOfSpec("buffer out of bounds".to_string()))
+}
+
+/// Decompresses a compressed buffer. Returns `None` when the buffer is stored uncompressed,
+/// in which case its values are `bytes[8..]`.
+fn decompress<T: NativeType>(
+    bytes: &[u8],
+    compression: CompressionType,
+) -> Result<Option<Vec<T>>, Error> {
+    if bytes.len() < 8 {
+        return Err(Error::OutOfSpec(
+            "a compressed buffer must start with its uncompressed length".to_string(),
+        ));
+    }
+    let length = i64::from_le_bytes(bytes[..8].try_into().unwrap());
+    if length == -1 {
+        return Ok(None);
+    }
+    let length: usize = length
+        .try_into()
+        .map_err(|_| Error::from(OutOfSpecKind::NegativeFooterLength))?;
+    let size = std::mem::size_of::<T>();
+    if length / size * size != length {
+        return Err(Error::OutOfSpec(
+            "the uncompressed length of a buffer must be a multiple of its type's size".to_string(),
+        ));
+    }
+
+    let mut values = vec![T::default(); length / size];
+    let out_slice = bytemuck::cast_slice_mut(&mut values);
+    match compression {
+        CompressionType::Lz4Frame => compression::decompress_lz4(&bytes[8..], out_slice)?,
+        CompressionType::Zstd => compression::decompress_zstd(&bytes[8..], out_slice)?,
+    }
+    Ok(Some(values))
+}
+
+/// Returns a pointer to the values of the next buffer. Uncompressed buffers are not copied;
+/// compressed buffers are decompressed and moved to `decompressed`, which owns them.
+fn get_buffer<T: NativeType>(
+    data: &[u8],
+    block_offset: usize,
+    buffers: &mut VecDeque<IpcBuffer>,
+    num_rows: usize,
+    compression: Option<CompressionType>,
+    decompressed: &mut Vec<Box<dyn Any>>,
+) -> Result<*const u8, Error> {
+    let mut values = get_bytes(data, block_offset, buffers)?;
+
+    if let Some(compression) = compression.filter(|_| !values.is_empty()) {
+        if let Some(values) = decompress::<T>(values, compression)? {
+            if values.len() < num_rows {
+                return Err(Error::OutOfSpec(
+                    "buffer's length is too small in mmap".to_string(),
+                ));
+            }
+            // the values are heap-allocated and thus not moved by moving the `Vec`
+            let ptr = values.as_ptr() as *const u8;
+            decompressed.push(Box::new(values));
+            return Ok(ptr);
+        }
+        values = &values[8..];
+    }
 
     // validate alignment
     let v: &[T] = bytemuck::try_cast_slice(values)
@@ -62,25 +129,29 @@ fn get_buffer<'a, T: NativeType>(
         ));
     }
 
-    Ok(values)
+    Ok(values.as_ptr())
 }
 
-fn get_validity<'a>(
-    data: &'a [u8],
+fn get_validity(
+    data: &[u8],
     block_offset: usize,
     buffers: &mut VecDeque<IpcBuffer>,
+    num_rows: usize,
     null_count: usize,
-) -> Result<Option<&'a [u8]>, Error> {
-    let validity = get_buffer_bounds(buffers)?;
-    let (offset, length) = validity;
-
+    compression: Option<CompressionType>,
+    decompressed: &mut Vec<Box<dyn Any>>,
+) -> Result<Option<*const u8>, Error> {
     Ok(if null_count > 0 {
-        // verify that they are in-bounds and get its pointer
-        Some(
-            data.get(block_offset + offset..block_offset + offset + length)
-                .ok_or_else(|| Error::OutOfSpec("buffer out of bounds".to_string()))?,
-        )
+        Some(get_buffer::<u8>(
+            data,
+            block_offset,
+            buffers,
+            bytes_for(num_rows),
+            compression,
+            decompressed,
+        )?)
     } else {
+        get_buffer_bounds(buffers)?;
         None
     })
 }
@@ -91,6 +162,7 @@ fn create_array<
     II: Iterator<Item = ArrowArray>,
 >(
     data: Arc<T>,
+    decompressed: Vec<Box<dyn Any>>,
     num_rows: usize,
     null_count: usize,
     buffers: I,
@@ -114,6 +186,7 @@ fn create_array<
 
     let mut private_data = Box::new(PrivateData::<Arc<T>> {
         data,
+        decompressed,
         buffers_ptr,
         children_ptr,
         dictionary_ptr,
@@ -158,6 +231,7 @@ fn mmap_binary<O: Offset, T: AsRef<[u8]>>(
     node: &Node,
     block_offset: usize,
     buffers: &mut VecDeque<IpcBuffer>,
+    compression: Option<CompressionType>,
 ) -> Result<ArrowArray, Error> {
     let num_rows: usize = node
         .length()
@@ -171,14 +245,38 @@ fn mmap_binary<O: Offset, T: AsRef<[u8]>>(
 
     let data_ref = data.as_ref().as_ref();
 
-    let validity = get_validity(data_ref, block_offset, buffers, null_count)?.map(|x| x.as_ptr());
+    let mut decompressed = vec![];
+    let validity = get_validity(
+        data_ref,
+        block_offset,
+        buffers,
+        num_rows,
+        null_count,
+        compression,
+        &mut decompressed,
+    )?;
 
-    let offsets = get_buffer::<O>(data_ref, block_offset, buffers, num_rows + 1)?.as_ptr();
-    let values = get_buffer::<u8>(data_ref, block_offset, buffers, 0)?.as_ptr();
+    let offsets = get_buffer::<O>(
+        data_ref,
+        block_offset,
+        buffers,
+        num_rows + 1,
+        compression,
+        &mut decompressed,
+    )?;
+    let values = get_buffer::<u8>(
+        data_ref,
+        block_offset,
+        buffers,
+        0,
+        compression,
+        &mut decompressed,
+    )?;
 
     // NOTE: offsets and values invariants are _not_ validated
     Ok(create_array(
         data,
+        decompressed,
         num_rows,
         null_count,
         [validity, Some(offsets), Some(values)].into_iter(),
@@ -192,6 +290,7 @@ fn mmap_fixed_size_binary<T: AsRef<[u8]>>(
     node: &Node,
     block_offset: usize,
     buffers: &mut VecDeque<IpcBuffer>,
+    compression: Option<CompressionType>,
 ) -> Result<ArrowArray, Error> {
     let num_rows: usize = node
         .length()
@@ -205,12 +304,29 @@ fn mmap_fixed_size_binary<T: AsRef<[u8]>>(
 
     let data_ref = data.as_ref().as_ref();
 
-    let validity = get_validity(data_ref, block_offset, buffers, null_count)?.map(|x| x.as_ptr());
+    let mut decompressed = vec![];
+    let validity = get_validity(
+        data_ref,
+        block_offset,
+        buffers,
+        num_rows,
+        null_count,
+        compression,
+        &mut decompressed,
+    )?;
 
-    let values = get_buffer::<u8>(data_ref, block_offset, buffers, num_rows + 1)?.as_ptr();
+    let values = get_buffer::<u8>(
+        data_ref,
+        block_offset,
+        buffers,
+        num_rows + 1,
+        compression,
+        &mut decompressed,
+    )?;
 
     Ok(create_array(
         data,
+        decompressed,
         num_rows,
         null_count,
         [validity, Some(values)].into_iter(),
@@ -224,6 +340,7 @@ fn mmap_null<T: AsRef<[u8]>>(
     node: &Node,
     _block_offset: usize,
     _buffers: &mut VecDeque<IpcBuffer>,
+    _compression: Option<CompressionType>,
 ) -> Result<ArrowArray, Error> {
     let num_rows: usize = node
         .length()
@@ -237,6 +354,7 @@ fn mmap_null<T: AsRef<[u8]>>(
 
     Ok(create_array(
         data,
+        vec![],
         num_rows,
         null_count,
         [].into_iter(),
@@ -250,6 +368,7 @@ fn mmap_boolean<T: AsRef<[u8]>>(
     node: &Node,
     block_offset: usize,
     buffers: &mut VecDeque<IpcBuffer>,
+    compression: Option<CompressionType>,
 ) -> Result<ArrowArray, Error> {
     let num_rows: usize = node
         .length()
@@ -263,16 +382,29 @@ fn mmap_boolean<T: AsRef<[u8]>>(
 
     let data_ref = data.as_ref().as_ref();
 
-    let validity = get_validity(data_ref, block_offset, buffers, null_count)?.map(|x| x.as_ptr());
-
-    let values = get_buffer_bounds(buffers)?;
-    let (offset, length) = values;
+    let mut decompressed = vec![];
+    let validity = get_validity(
+        data_ref,
+        block_offset,
+        buffers,
+        num_rows,
+        null_count,
+        compression,
+        &mut decompressed,
+    )?;
 
-    // verify that they are in-bounds and get its pointer
-    let values = data_ref[block_offset + offset..block_offset + offset + length].as_ptr();
+    let values = get_buffer::<u8>(
+        data_ref,
+        block_offset,
+        buffers,
+        bytes_for(num_rows),
+        compression,
+        &mut decompressed,
+    )?;
 
     Ok(create_array(
         data,
+        decompressed,
         num_rows,
         null_count,
         [validity, Some(values)].into_iter(),
@@ -286,6 +418,7 @@ fn mmap_primitive<P: NativeType, T: AsRef<[u8]>>(
     node: &Node,
     block_offset: usize,
     buffers: &mut VecDeque<IpcBuffer>,
+    compression: Option<CompressionType>,
 ) -> Result<ArrowArray, Error> {
     let data_ref = data.as_ref().as_ref();
 
@@ -299,12 +432,29 @@ fn mmap_primitive<P: NativeType, T: AsRef<[u8]>>(
         .try_into()
         .map_err(|_| Error::from(OutOfSpecKind::NegativeFooterLength))?;
 
-    let validity = get_validity(data_ref, block_offset, buffers, null_count)?.map(|x| x.as_ptr());
+    let mut decompressed = vec![];
+    let validity = get_validity(
+        data_ref,
+        block_offset,
+        buffers,
+        num_rows,
+        null_count,
+        compression,
+        &mut decompressed,
+    )?;
 
-    let values = get_buffer::<P>(data_ref, block_offset, buffers, num_rows)?.as_ptr();
+    let values = get_buffer::<P>(
+        data_ref,
+        block_offset,
+        buffers,
+        num_rows,
+        compression,
+        &mut decompressed,
+    )?;
 
     Ok(create_array(
         data,
+        decompressed,
         num_rows,
         null_count,
         [validity, Some(values)].into_iter(),
@@ -323,6 +473,7 @@ fn mmap_list<O: Offset, T: AsRef<[u8]>>(
     dictionaries: &Dictionaries,
     field_nodes: &mut VecDeque<Node>,
     buffers: &mut VecDeque<IpcBuffer>,
+    compression: Option<CompressionType>,
 ) -> Result<ArrowArray, Error> {
     let child = ListArray::<O>::try_get_child(data_type)?.data_type();
 
@@ -338,9 +489,25 @@ fn mmap_list<O: Offset, T: AsRef<[u8]>>(
 
     let data_ref = data.as_ref().as_ref();
 
-    let validity = get_validity(data_ref, block_offset, buffers, null_count)?.map(|x| x.as_ptr());
+    let mut decompressed = vec![];
+    let validity = get_validity(
+        data_ref,
+        block_offset,
+        buffers,
+        num_rows,
+        null_count,
+        compression,
+        &mut decompressed,
+    )?;
 
-    let offsets = get_buffer::<O>(data_ref, block_offset, buffers, num_rows + 1)?.as_ptr();
+    let offsets = get_buffer::<O>(
+        data_ref,
+        block_offset,
+        buffers,
+        num_rows + 1,
+        compression,
+        &mut decompressed,
+    )?;
 
     let values = get_array(
         data.clone(),
@@ -350,11 +517,13 @@ fn mmap_list<O: Offset, T: AsRef<[u8]>>(
         dictionaries,
         field_nodes,
         buffers,
+        compression,
     )?;
 
     // NOTE: offsets and values invariants are _not_ validated
     Ok(create_array(
         data,
+        decompressed,
         num_rows,
         null_count,
         [validity, Some(offsets)].into_iter(),
@@ -373,6 +542,7 @@ fn mmap_fixed_size_list<T: AsRef<[u8]>>(
     dictionaries: &Dictionaries,
     field_nodes: &mut VecDeque<Node>,
     buffers: &mut VecDeque<IpcBuffer>,
+    compression: Option<CompressionType>,
 ) -> Result<ArrowArray, Error> {
     let child = FixedSizeListArray::try_child_and_size(data_type)?
         .0
@@ -390,7 +560,16 @@ fn mmap_fixed_size_list<T: AsRef<[u8]>>(
 
     let data_ref = data.as_ref().as_ref();
 
-    let validity = get_validity(data_ref, block_offset, buffers, null_count)?.map(|x| x.as_ptr());
+    let mut decompressed = vec![];
+    let validity = get_validity(
+        data_ref,
+        block_offset,
+        buffers,
+        num_rows,
+        null_count,
+        compression,
+        &mut decompressed,
+    )?;
 
     let values = get_array(
         data.clone(),
@@ -400,10 +579,12 @@ fn mmap_fixed_size_list<T: AsRef<[u8]>>(
         dictionaries,
         field_nodes,
         buffers,
+        compression,
     )?;
 
     Ok(create_array(
         data,
+        decompressed,
         num_rows,
         null_count,
         [validity].into_iter(),
@@ -422,6 +603,7 @@ fn mmap_struct<T: AsRef<[u8]>>(
     dictionaries: &Dictionaries,
     field_nodes: &mut VecDeque<Node>,
     buffers: &mut VecDeque<IpcBuffer>,
+    compression: Option<CompressionType>,
 ) -> Result<ArrowArray, Error> {
     let children = StructArray::try_get_fields(data_type)?;
 
@@ -437,7 +619,16 @@ fn mmap_struct<T: AsRef<[u8]>>(
 
     let data_ref = data.as_ref().as_ref();
 
-    let validity = get_validity(data_ref, block_offset, buffers, null_count)?.map(|x| x.as_ptr());
+    let mut decompressed = vec![];
+    let validity = get_validity(
+        data_ref,
+        block_offset,
+        buffers,
+        num_rows,
+        null_count,
+        compression,
+        &mut decompressed,
+    )?;
 
     let values = children
         .iter()
@@ -452,12 +643,14 @@ fn mmap_struct<T: AsRef<[u8]>>(
                 dictionaries,
                 field_nodes,
                 buffers,
+                compression,
             )
         })
         .collect::<Result<Vec<_>, Error>>()?;
 
     Ok(create_array(
         data,
+        decompressed,
         num_rows,
         null_count,
         [validity].into_iter(),
@@ -476,6 +669,7 @@ fn mmap_dict<K: DictionaryKey, T: AsRef<[u8]>>(
     dictionaries: &Dictionaries,
     _: &mut VecDeque<Node>,
     buffers: &mut VecDeque<IpcBuffer>,
+    compression: Option<CompressionType>,
 ) -> Result<ArrowArray, Error> {
     let num_rows: usize = node
         .length()
@@ -494,12 +688,29 @@ fn mmap_dict<K: DictionaryKey, T: AsRef<[u8]>>(
         .ok_or_else(|| Error::oos("Missing dictionary"))?
         .clone();
 
-    let validity = get_validity(data_ref, block_offset, buffers, null_count)?.map(|x| x.as_ptr());
+    let mut decompressed = vec![];
+    let validity = get_validity(
+        data_ref,
+        block_offset,
+        buffers,
+        num_rows,
+        null_count,
+        compression,
+        &mut decompressed,
+    )?;
 
-    let values = get_buffer::<K>(data_ref, block_offset, buffers, num_rows)?.as_ptr();
+    let values = get_buffer::<K>(
+        data_ref,
+        block_offset,
+        buffers,
+        num_rows,
+        compression,
+        &mut decompressed,
+    )?;
 
     Ok(create_array(
         data,
+        decompressed,
         num_rows,
         null_count,
         [validity, Some(values)].into_iter(),
@@ -508,6 +719,7 @@ fn mmap_dict<K: DictionaryKey, T: AsRef<[u8]>>(
     ))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn get_array<T: AsRef<[u8]>>(
     data: Arc<T>,
     block_offset: usize,
@@ -516,6 +728,7 @@ fn get_array<T: AsRef<[u8]>>(
     dictionaries: &Dictionaries,
     field_nodes: &mut VecDeque<Node>,
     buffers: &mut VecDeque<IpcBuffer>,
+    compression: Option<CompressionType>,
 ) -> Result<ArrowArray, Error> {
     use crate::datatypes::PhysicalType::*;
     let node = field_nodes
@@ -523,14 +736,16 @@ fn get_array<T: AsRef<[u8]>>(
         .ok_or_else(|| Error::from(OutOfSpecKind::ExpectedBuffer))?;
 
     match data_type.to_physical_type() {
-        Null => mmap_null(data, &node, block_offset, buffers),
-        Boolean => mmap_boolean(data, &node, block_offset, buffers),
+        Null => mmap_null(data, &node, block_offset, buffers, compression),
+        Boolean => mmap_boolean(data, &node, block_offset, buffers, compression),
         Primitive(p) => with_match_primitive_type!(p, |$T| {
-            mmap_primitive::<$T, _>(data, &node, block_offset, buffers)
+            mmap_primitive::<$T, _>(data, &node, block_offset, buffers, compression)
         }),
-        Utf8 | Binary => mmap_binary::<i32, _>(data, &node, block_offset, buffers),
-        FixedSizeBinary => mmap_fixed_size_binary(data, &node, block_offset, buffers),
-        LargeBinary | LargeUtf8 => mmap_binary::<i64, _>(data, &node, block_offset, buffers),
+        Utf8 | Binary => mmap_binary::<i32, _>(data, &node, block_offset, buffers, compression),
+        FixedSizeBinary => mmap_fixed_size_binary(data, &node, block_offset, buffers, compression),
+        LargeBinary | LargeUtf8 => {
+            mmap_binary::<i64, _>(data, &node, block_offset, buffers, compression)
+        }
         List => mmap_list::<i32, _>(
             data,
             &node,
@@ -540,6 +755,7 @@ fn get_array<T: AsRef<[u8]>>(
             dictionaries,
             field_nodes,
             buffers,
+            compression,
         ),
         LargeList => mmap_list::<i64, _>(
             data,
@@ -550,6 +766,7 @@ fn get_array<T: AsRef<[u8]>>(
             dictionaries,
             field_nodes,
             buffers,
+            compression,
         ),
         FixedSizeList => mmap_fixed_size_list(
             data,
@@ -560,6 +777,7 @@ fn get_array<T: AsRef<[u8]>>(
             dictionaries,
             field_nodes,
             buffers,
+            compression,
         ),
         Struct => mmap_struct(
             data,
@@ -570,6 +788,7 @@ fn get_array<T: AsRef<[u8]>>(
             dictionaries,
             field_nodes,
             buffers,
+            compression,
         ),
         Dictionary(key_type) => match_integer_type!(key_type, |$T| {
             mmap_dict::<$T, _>(
@@ -581,6 +800,7 @@ fn get_array<T: AsRef<[u8]>>(
                 dictionaries,
                 field_nodes,
                 buffers,
+                compression,
             )
         }),
         _ => todo!(),
@@ -588,6 +808,7 @@ fn get_array<T: AsRef<[u8]>>(
 }
 
 /// Maps a memory region to an [`Array`].
+#[allow(clippy::too_many_arguments)]
 pub(crate) unsafe fn mmap<T: AsRef<[u8]>>(
     data: Arc<T>,
     block_offset: usize,
@@ -596,6 +817,7 @@ pub(crate) unsafe fn mmap<T: AsRef<[u8]>>(
     dictionaries: &Dictionaries,
     field_nodes: &mut VecDeque<Node>,
     buffers: &mut VecDeque<IpcBuffer>,
+    compression: Option<CompressionType>,
 ) -> Result<Box<dyn Array>, Error> {
     let array = get_array(
         data,
@@ -605,6 +827,7 @@ pub(crate) unsafe fn mmap<T: AsRef<[u8]>>(
         dictionaries,
         field_nodes,
         buffers,
+        compression,
     )?;
     // The unsafety comes from the fact that `array` is not necessarily valid -
     // the IPC file may be corrupted (e.g. invalid offsets or non-utf8 data)
@@ -19,7 +19,9 @@ use crate::error::Result;
 use self::schema::to_field;
 
 pub use generated::{ArrowArray, ArrowArrayStream, ArrowSchema};
-pub use stream::{export_iterator, ArrowArrayStreamReader};
+pub use stream::{
+    export_chunk_iterator, export_iterator, ArrowArrayStreamReader, ArrowChunkStreamReader,
+};
 
 /// Exports an [`Box<dyn Array>`] to the C data interface.
 pub fn export_array_to_c(array: Box<dyn Array>) -> ArrowArray {
@@ -46,7 +46,7 @@ impl ArrowSchema {
         let mut flags = field.is_nullable as i64 * 2;
 
         // allocate (and hold) the children
-        let children_vec = match field.data_type() {
+        let children_vec = match field.data_type().to_logical_type() {
             DataType::List(field) => {
                 vec![Box::new(ArrowSchema::new(field.as_ref()))]
             }
@@ -77,7 +77,9 @@ impl ArrowSchema {
             .collect::<Box<_>>();
         let n_children = children_ptr.len() as i64;
 
-        let dictionary = if let DataType::Dictionary(_, values, is_ordered) = field.data_type() {
+        let dictionary = if let DataType::Dictionary(_, values, is_ordered) =
+            field.data_type().to_logical_type()
+        {
             flags += *is_ordered as i64;
             // we do not store field info in the dict values, so can't recover it all :(
             let field = Field::new("", values.as_ref().clone(), true);
@@ -267,6 +269,7 @@ unsafe fn to_data_type(schema: &ArrowSchema) -> Result<DataType> {
         "tDn" => DataType::Duration(TimeUnit::Nanosecond),
         "tiM" => DataType::Interval(IntervalUnit::YearMonth),
         "tiD" => DataType::Interval(IntervalUnit::DayTime),
+        "tin" => DataType::Interval(IntervalUnit::MonthDayNano),
         "+l" => {
             let child = schema.child(0);
             DataType::List(Box::new(to_field(child)?))
@@ -361,6 +364,7 @@ unsafe fn to_data_type(schema: &ArrowSchema) -> Result<DataType> {
                     let mode = UnionMode::sparse(union_type == "+us");
                     let type_ids = union_parts
                         .split(',')
+                        .filter(|x| !x.is_empty())
                         .map(|x| {
                             x.parse::<i32>().map_err(|_| {
                                 Error::OutOfSpec("Union type id is not a valid integer".to_string())
@@ -370,7 +374,11 @@ unsafe fn to_data_type(schema: &ArrowSchema) -> Result<DataType> {
                     let fields = (0..schema.n_children as usize)
                         .map(|x| to_field(schema.child(x)))
                         .collect::<Result<Vec<_>>>()?;
-                    DataType::Union(fields, Some(type_ids), mode)
+                    // the ids of the fields' positions are the ids of unions without type ids
+                    let is_positional = type_ids.len() == fields.len()
+                        && type_ids.iter().copied().eq(0..fields.len() as i32);
+                    let type_ids = if is_positional { None } else { Some(type_ids) };
+                    DataType::Union(fields, type_ids, mode)
                 }
                 _ => {
                     return Err(Error::OutOfSpec(format!(
@@ -421,9 +429,7 @@ fn to_format(data_type: &DataType) -> String {
         DataType::Duration(TimeUnit::Nanosecond) => "tDn".to_string(),
         DataType::Interval(IntervalUnit::YearMonth) => "tiM".to_string(),
         DataType::Interval(IntervalUnit::DayTime) => "tiD".to_string(),
-        DataType::Interval(IntervalUnit::MonthDayNano) => {
-            todo!("Spec for FFI for MonthDayNano still not defined.")
-        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => "tin".to_string(),
         DataType::Timestamp(unit, tz) => {
             let unit = match unit {
                 TimeUnit::Second => "s",
@@ -445,16 +451,12 @@ fn to_format(data_type: &DataType) -> String {
         DataType::FixedSizeList(_, size) => format!("+w:{}", size),
         DataType::Union(f, ids, mode) => {
             let sparsness = if mode.is_sparse() { 's' } else { 'd' };
-            let mut r = format!("+u{}:", sparsness);
             let ids = if let Some(ids) = ids {
-                ids.iter()
-                    .fold(String::new(), |a, b| a + &b.to_string() + ",")
+                ids.iter().map(|id| id.to_string()).collect::<Vec<_>>()
             } else {
-                (0..f.len()).fold(String::new(), |a, b| a + &b.to_string() + ",")
+                (0..f.len()).map(|id| id.to_string()).collect::<Vec<_>>()
             };
-            let ids = &ids[..ids.len() - 1]; // take away last ","
-            r.push_str(ids);
-            r
+            format!("+u{}:{}", sparsness, ids.join(","))
         }
         DataType::Map(_, _) => "+m".to_string(),
         DataType::Dictionary(index, _, _) => to_format(&(*index).into()),
@@ -463,7 +465,7 @@ fn to_format(data_type: &DataType) -> String {
 }
 
 pub(super) fn get_child(data_type: &DataType, index: usize) -> Result<DataType> {
-    match (index, data_type) {
+    match (index, data_type.to_logical_type()) {
         (0, DataType::List(field)) => Ok(field.data_type().clone()),
         (0, DataType::FixedSizeList(field, _)) => Ok(field.data_type().clone()),
         (0, DataType::LargeList(field)) => Ok(field.data_type().clone()),
@@ -598,7 +600,7 @@ mod tests {
                         true,
                     ),
                 ],
-                Some(vec![0, 1]),
+                None,
                 UnionMode::Sparse,
             ),
         ];
@@ -615,7 +617,7 @@ mod tests {
         for interval_type in [
             IntervalUnit::DayTime,
             IntervalUnit::YearMonth,
-            //IntervalUnit::MonthDayNano, // not yet defined on the C data interface
+            IntervalUnit::MonthDayNano,
         ] {
             dts.push(DataType::Interval(interval_type));
         }
@@ -1,6 +1,11 @@
 use std::ffi::{CStr, CString};
 
-use crate::{array::Array, datatypes::Field, error::Error};
+use crate::{
+    array::{Array, StructArray},
+    chunk::Chunk,
+    datatypes::{DataType, Field, Schema},
+    error::Error,
+};
 
 use super::{export_array_to_c, export_field_to_c, import_array_from_c, import_field_from_c};
 use super::{ArrowArray, ArrowArrayStream, ArrowSchema};
@@ -122,6 +127,70 @@ impl ArrowArrayStreamReader {
     }
 }
 
+/// Implements an iterator of [`Chunk`] consumed from the [C stream interface](https://arrow.apache.org/docs/format/CStreamInterface.html)
+/// whose arrays are struct arrays representing record batches, such as the streams of
+/// record batches exported by pyarrow or DuckDB.
+pub struct ArrowChunkStreamReader {
+    reader: ArrowArrayStreamReader,
+    schema: Schema,
+}
+
+impl ArrowChunkStreamReader {
+    /// Returns a new [`ArrowChunkStreamReader`]
+    /// # Error
+    /// Errors iff the [`ArrowArrayStream`] is out of specification or its schema is not a struct
+    /// # Safety
+    /// This method is intrinsically `unsafe` since it assumes that the `ArrowArrayStream`
+    /// contains a valid Arrow C stream interface.
+    /// In particular:
+    /// * The `ArrowArrayStream` fulfills the invariants of the C stream interface
+    /// * The schema `get_schema` produces fulfills the C data interface
+    pub unsafe fn try_new(iter: Box<ArrowArrayStream>) -> Result<Self, Error> {
+        let reader = unsafe { ArrowArrayStreamReader::try_new(iter)? };
+
+        let field = reader.field();
+        let fields = if let DataType::Struct(fields) = field.data_type() {
+            fields.clone()
+        } else {
+            return Err(Error::OutOfSpec(format!(
+                "A C stream of chunks MUST have a struct schema, but it has data type {:?}",
+                field.data_type()
+            )));
+        };
+        let schema = Schema::from(fields).with_metadata(field.metadata.clone());
+
+        Ok(Self { reader, schema })
+    }
+
+    /// Returns the schema provided by the stream
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// Advances this iterator by one chunk
+    /// # Error
+    /// Errors iff:
+    /// * The C stream interface returns an error
+    /// * The C stream interface returns an invalid array (that we can identify, see Safety below)
+    /// * The struct array has nulls
+    /// # Safety
+    /// Calling this iterator's `next` assumes that the [`ArrowArrayStream`] produces arrow arrays
+    /// that fulfill the C data interface
+    pub unsafe fn next(&mut self) -> Option<Result<Chunk<Box<dyn Array>>, Error>> {
+        let array = match unsafe { self.reader.next()? } {
+            Ok(array) => array,
+            Err(error) => return Some(Err(error)),
+        };
+        let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+        if array.null_count() > 0 {
+            return Some(Err(Error::OutOfSpec(
+                "A struct array representing a chunk MUST not have nulls".to_string(),
+            )));
+        }
+        Some(Chunk::try_new(array.values().to_vec()))
+    }
+}
+
 struct PrivateData {
     iter: Box<dyn Iterator<Item = Result<Box<dyn Array>, Error>>>,
     field: Field,
@@ -213,3 +282,22 @@ pub fn export_iterator(
         private_data: Box::into_raw(private_data) as *mut ::std::os::raw::c_void,
     }
 }
+
+/// Exports an iterator of [`Chunk`]s to the [C stream interface](https://arrow.apache.org/docs/format/CStreamInterface.html).
+///
+/// Each chunk is exported as a non-null struct array whose fields are `schema.fields`, the
+/// representation of record batches expected by e.g. pyarrow and DuckDB. Errors of `iter`
+/// are reported through the stream's `get_last_error`.
+pub fn export_chunk_iterator(
+    iter: Box<dyn Iterator<Item = Result<Chunk<Box<dyn Array>>, Error>>>,
+    schema: Schema,
+) -> ArrowArrayStream {
+    let data_type = DataType::Struct(schema.fields);
+    let field = Field::new("", data_type.clone(), false).with_metadata(schema.metadata);
+
+    let iter = iter.map(move |chunk| {
+        StructArray::try_new(data_type.clone(), chunk?.into_arrays(), None)
+            .map(|array| array.boxed())
+    });
+    export_iterator(Box::new(iter), field)
+}
@@ -4,7 +4,8 @@ use std::io::{Read, Seek};
 use crate::datatypes::{DataType, Field};
 use crate::error::Result;
 
-use super::super::utils::merge_schema;
+use super::super::read_utils::DeserializeOptions;
+use super::super::utils::{infer_with_options, is_null, merge_schema};
 use super::{ByteRecord, Reader};
 
 /// Infers the [`Field`]s of a CSV file by reading through the first n records up to `max_rows`.
@@ -15,6 +16,39 @@ pub fn infer_schema<R: Read + Seek, F: Fn(&[u8]) -> DataType>(
     max_rows: Option<usize>,
     has_header: bool,
     infer: &F,
+) -> Result<(Vec<Field>, usize)> {
+    infer_columns(reader, max_rows, has_header, |_, bytes| Some(infer(bytes)))
+}
+
+/// Infers the [`Field`]s of a CSV file according to `options` by reading through the first n
+/// records up to `max_rows`: booleans and floats are inferred with the spellings and decimal
+/// separator of `options`, temporal columns with their format in `options.formats`, and cells
+/// that are empty or one of `options.null_values` are ignored.
+/// Also returns the number of rows used to infer.
+/// Seeks back to the begining of the file _after_ the header
+pub fn infer_schema_with_options<R: Read + Seek>(
+    reader: &mut Reader<R>,
+    max_rows: Option<usize>,
+    has_header: bool,
+    options: &DeserializeOptions,
+) -> Result<(Vec<Field>, usize)> {
+    infer_columns(reader, max_rows, has_header, |header, bytes| {
+        if is_null(bytes, options) {
+            None
+        } else {
+            let format = options.formats.get(header).map(|x| x.as_str());
+            Some(infer_with_options(bytes, format, options))
+        }
+    })
+}
+
+/// Infers the [`Field`]s of a CSV file, where `infer` returns the [`DataType`] of a cell of a
+/// column given its header, or `None` when the cell is ignored
+fn infer_columns<R: Read + Seek, F: Fn(&str, &[u8]) -> Option<DataType>>(
+    reader: &mut Reader<R>,
+    max_rows: Option<usize>,
+    has_header: bool,
+    infer: F,
 ) -> Result<(Vec<Field>, usize)> {
     // get or create header names
     // when has_header is false, creates default column names with column_ prefix
@@ -44,9 +78,9 @@ pub fn infer_schema<R: Read + Seek, F: Fn(&[u8]) -> DataType>(
         }
         records_count += 1;
 
-        for (i, column) in column_types.iter_mut().enumerate() {
-            if let Some(string) = record.get(i) {
-                column.insert(infer(string));
+        for (i, (column, header)) in column_types.iter_mut().zip(headers.iter()).enumerate() {
+            if let Some(data_type) = record.get(i).and_then(|bytes| infer(header, bytes)) {
+                column.insert(data_type);
             }
         }
     }
@@ -6,8 +6,14 @@ mod reader;
 pub use csv::{ByteRecord, Reader, ReaderBuilder};
 
 mod infer_schema;
+mod split;
 
+pub use super::read_utils::{DeserializeOptions, ParseError, ParseMode};
 pub use super::utils::infer;
-pub use deserialize::{deserialize_batch, deserialize_column};
-pub use infer_schema::infer_schema;
+pub use deserialize::{
+    deserialize_batch, deserialize_batch_with_options, deserialize_column,
+    deserialize_column_with_options,
+};
+pub use infer_schema::{infer_schema, infer_schema_with_options};
 pub use reader::*;
+pub use split::{split_file, split_reader, FileSplit, SplitOptions, SplitRead, SplitReader};
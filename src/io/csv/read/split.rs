@@ -0,0 +1,424 @@
+use std::io::{Read, Seek, SeekFrom, Take};
+use std::ops::Range;
+
+use csv::Terminator;
+
+use crate::array::Array;
+use crate::chunk::Chunk;
+use crate::datatypes::Field;
+use crate::error::{Error, Result};
+
+use super::{deserialize_batch_with_options, read_rows, ByteRecord, Reader, ReaderBuilder};
+use super::{DeserializeOptions, ParseError};
+
+/// A byte range of a CSV file that starts and ends at record boundaries, whose records can
+/// thus be read independently of the other ranges of the file
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileSplit {
+    /// The byte range of the records in the file
+    pub range: Range<u64>,
+}
+
+/// The CSV dialect of a file split by [`split_file`] and read by [`split_reader`]: the
+/// settings of [`ReaderBuilder`] that determine where the records of a file end.
+///
+/// The defaults are the defaults of [`ReaderBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SplitOptions {
+    /// Whether the first record of the file is a header
+    pub has_header: bool,
+    /// The field delimiter
+    pub delimiter: u8,
+    /// The quote character
+    pub quote: u8,
+    /// Whether quotes are special. When `false`, `quote`, `double_quote` and `escape` are
+    /// ignored.
+    pub quoting: bool,
+    /// Whether two consecutive quotes in a quoted field are an escaped quote
+    pub double_quote: bool,
+    /// The character escaping the following character in a quoted field
+    pub escape: Option<u8>,
+    /// Records starting with this character are comments, which end at the next `\n`
+    pub comment: Option<u8>,
+    /// The record terminator. When `None`, `\r`, `\n` and `\r\n` terminate records.
+    pub terminator: Option<u8>,
+}
+
+impl Default for SplitOptions {
+    fn default() -> Self {
+        Self {
+            has_header: true,
+            delimiter: b',',
+            quote: b'"',
+            quoting: true,
+            double_quote: true,
+            escape: None,
+            comment: None,
+            terminator: None,
+        }
+    }
+}
+
+impl SplitOptions {
+    /// Returns a [`ReaderBuilder`] configured with this dialect that does not read headers,
+    /// since the header of a file is not part of any of its splits.
+    pub fn reader_builder(&self) -> ReaderBuilder {
+        let mut builder = ReaderBuilder::new();
+        builder
+            .has_headers(false)
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .quoting(self.quoting)
+            .double_quote(self.double_quote)
+            .escape(self.escape)
+            .comment(self.comment)
+            .terminator(match self.terminator {
+                Some(terminator) => Terminator::Any(terminator),
+                None => Terminator::CRLF,
+            });
+        builder
+    }
+
+    #[inline]
+    fn is_terminator(&self, byte: u8) -> bool {
+        match self.terminator {
+            Some(terminator) => byte == terminator,
+            None => byte == b'\r' || byte == b'\n',
+        }
+    }
+
+    /// Returns the state of the parser after `byte`, mirroring the parser of `csv-core`
+    #[inline]
+    fn transition(&self, state: State, byte: u8) -> State {
+        use State::*;
+        let is_quote = self.quoting && byte == self.quote;
+        match state {
+            StartRecord => {
+                if self.is_terminator(byte) {
+                    StartRecord
+                } else if self.comment == Some(byte) {
+                    InComment
+                } else {
+                    self.transition(StartField, byte)
+                }
+            }
+            StartField if is_quote => InQuotedField,
+            InQuotedField if is_quote => InDoubleEscapedQuote,
+            InQuotedField if self.quoting && self.escape == Some(byte) => InEscapedQuote,
+            InQuotedField => InQuotedField,
+            InEscapedQuote => InQuotedField,
+            InDoubleEscapedQuote if is_quote && self.double_quote => InQuotedField,
+            InComment if byte == b'\n' => StartRecord,
+            InComment => InComment,
+            StartField | InField | InDoubleEscapedQuote => {
+                if byte == self.delimiter {
+                    StartField
+                } else if self.is_terminator(byte) {
+                    StartRecord
+                } else {
+                    InField
+                }
+            }
+        }
+    }
+}
+
+/// The states of the CSV parser that determine where records start
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum State {
+    StartRecord,
+    StartField,
+    InField,
+    InQuotedField,
+    InEscapedQuote,
+    InDoubleEscapedQuote,
+    InComment,
+}
+
+impl State {
+    const ALL: [State; 7] = [
+        State::StartRecord,
+        State::StartField,
+        State::InField,
+        State::InQuotedField,
+        State::InEscapedQuote,
+        State::InDoubleEscapedQuote,
+        State::InComment,
+    ];
+}
+
+/// Whether a record starts at a byte that takes the parser from `previous` to `state`
+#[inline]
+fn starts_record(previous: State, state: State) -> bool {
+    previous == State::StartRecord && state != State::StartRecord && state != State::InComment
+}
+
+/// The number of bytes after an offset of [`split_file`] within which the parser must reach
+/// the same state from every possible state at the offset before the split boundary is
+/// speculated.
+const SPECULATION_WINDOW: u64 = 1024 * 1024;
+
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Splits the records of the CSV file `reader` into up to `n` [`FileSplit`]s of roughly
+/// equal size, in order, whose union are all records of the file except its header.
+///
+/// The file is not read sequentially: only its header and, for each of the `n - 1`
+/// boundaries, the bytes after an offset of `i / n` of the file are read, until the start
+/// of the next record. Since a line break only ends a record when it is not quoted, the
+/// parser state at an offset is unknown: the bytes after it are parsed in the dialect of
+/// `options` from every possible state until all of them reach the same state, after
+/// which the next record start is certain. When they do not within the first MiB after the
+/// offset (e.g. because it has no quotes), the offset is speculated to be outside of a
+/// quoted field. The speculation is verified when the splits are read: the reader of a
+/// split (see [`split_reader`]) errors when the split does not end at a record boundary, in
+/// which case the file must be read with less splits.
+///
+/// Less than `n` splits are returned when records are larger than the splits.
+/// # Errors
+/// This function errors when `n` is zero or `reader` errors.
+pub fn split_file<R: Read + Seek>(
+    reader: &mut R,
+    n: usize,
+    options: &SplitOptions,
+) -> Result<Vec<FileSplit>> {
+    if n == 0 {
+        return Err(Error::InvalidArgumentError(
+            "A CSV file must be split in at least one range".to_string(),
+        ));
+    }
+    let length = reader.seek(SeekFrom::End(0))?;
+    let start = match first_record(reader, options)? {
+        Some(start) => start,
+        None => return Ok(vec![]),
+    };
+    let split_length = (length - start) / n as u64;
+
+    let mut boundaries = vec![start];
+    if split_length > 0 {
+        for i in 1..n as u64 {
+            // the boundaries are increasing, also when records are larger than the splits
+            let offset = std::cmp::max(start + i * split_length, *boundaries.last().unwrap() + 1);
+            if let Some(boundary) = resynchronize(reader, offset, options)? {
+                boundaries.push(boundary);
+            }
+        }
+    }
+    boundaries.push(length);
+
+    Ok(boundaries
+        .windows(2)
+        .map(|window| FileSplit {
+            range: window[0]..window[1],
+        })
+        .collect())
+}
+
+/// Returns the position of the first record of the file after its header, if any
+fn first_record<R: Read + Seek>(reader: &mut R, options: &SplitOptions) -> Result<Option<u64>> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut in_header = options.has_header;
+    let mut state = State::StartRecord;
+    let mut position = 0;
+    let mut buffer = vec![0; BUFFER_SIZE];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        for byte in &buffer[..read] {
+            let previous = state;
+            state = options.transition(state, *byte);
+            if starts_record(previous, state) {
+                if !in_header {
+                    return Ok(Some(position));
+                }
+                in_header = false;
+            }
+            position += 1;
+        }
+    }
+}
+
+/// Returns the position of the first record that starts after `offset`, parsing from every
+/// possible state at `offset` (see [`split_file`]).
+fn resynchronize<R: Read + Seek>(
+    reader: &mut R,
+    offset: u64,
+    options: &SplitOptions,
+) -> Result<Option<u64>> {
+    reader.seek(SeekFrom::Start(offset))?;
+    // the states the parser may be in, deduplicated: once there is one, it is certain
+    let mut states = State::ALL
+        .into_iter()
+        .filter(|state| match state {
+            State::InQuotedField | State::InDoubleEscapedQuote => options.quoting,
+            State::InEscapedQuote => options.quoting && options.escape.is_some(),
+            State::InComment => options.comment.is_some(),
+            _ => true,
+        })
+        .collect::<Vec<_>>();
+    // the state when `offset` is within an unquoted field
+    let mut speculative = State::InField;
+    let mut speculative_start = None;
+
+    let mut position = offset;
+    let mut buffer = vec![0; BUFFER_SIZE];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        for byte in &buffer[..read] {
+            if let [state] = states.as_mut_slice() {
+                let previous = *state;
+                *state = options.transition(previous, *byte);
+                if starts_record(previous, *state) {
+                    return Ok(Some(position));
+                }
+            } else {
+                states
+                    .iter_mut()
+                    .for_each(|state| *state = options.transition(*state, *byte));
+                states.sort_unstable();
+                states.dedup();
+
+                let previous = speculative;
+                speculative = options.transition(previous, *byte);
+                if speculative_start.is_none() && starts_record(previous, speculative) {
+                    speculative_start = Some(position);
+                }
+                if speculative_start.is_some() && position - offset >= SPECULATION_WINDOW {
+                    return Ok(speculative_start);
+                }
+            }
+            position += 1;
+        }
+    }
+    // the end of the file
+    Ok(speculative_start)
+}
+
+/// A [`Read`] of the bytes of a [`FileSplit`] that errors at the end of the split when it
+/// does not end at a record boundary, verifying the boundaries speculated by [`split_file`].
+#[derive(Debug)]
+pub struct SplitRead<R> {
+    reader: Take<R>,
+    options: SplitOptions,
+    state: State,
+    is_last: bool,
+}
+
+impl<R: Read> Read for SplitRead<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        if read == 0 && !buf.is_empty() && !self.is_last && self.state != State::StartRecord {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "The CSV file split does not end at a record boundary",
+            ));
+        }
+        for byte in &buf[..read] {
+            self.state = self.options.transition(self.state, *byte);
+        }
+        Ok(read)
+    }
+}
+
+/// Returns a CSV [`Reader`] of the records of `split` of the file `reader`, in the dialect
+/// of `options` (see [`SplitOptions::reader_builder`]).
+///
+/// The reader errors at the end of `split` when it is not the last split of the file and
+/// does not end at a record boundary (see [`split_file`]).
+pub fn split_reader<R: Read + Seek>(
+    mut reader: R,
+    split: &FileSplit,
+    options: &SplitOptions,
+) -> Result<Reader<SplitRead<R>>> {
+    let length = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(split.range.start))?;
+    let reader = SplitRead {
+        reader: reader.take(split.range.end - split.range.start),
+        options: *options,
+        state: State::StartRecord,
+        is_last: split.range.end >= length,
+    };
+    Ok(options.reader_builder().from_reader(reader))
+}
+
+/// An [`Iterator`] of [`Chunk`]s of up to `chunk_size` records of a [`FileSplit`], which can
+/// be consumed independently of (e.g. in parallel with) the iterators of the other splits of
+/// the file. Concatenating the chunks of all splits in order yields the records of the file.
+pub struct SplitReader<R: Read> {
+    reader: Reader<SplitRead<R>>,
+    fields: Vec<Field>,
+    projection: Option<Vec<usize>>,
+    options: DeserializeOptions,
+    rows: Vec<ByteRecord>,
+    row: usize,
+    errors: Vec<ParseError>,
+}
+
+impl<R: Read + Seek> SplitReader<R> {
+    /// Creates a new [`SplitReader`] of `split` of the file `reader` in the dialect of
+    /// `split_options` (see [`split_reader`]), whose records are deserialized into `fields`
+    /// according to `options`.
+    ///
+    /// The [`ParseError::row`] of the errors is the index of their record in `split`: the
+    /// index in the file is known once the previous splits are read.
+    pub fn try_new(
+        reader: R,
+        split: &FileSplit,
+        split_options: &SplitOptions,
+        fields: Vec<Field>,
+        projection: Option<Vec<usize>>,
+        chunk_size: usize,
+        options: DeserializeOptions,
+    ) -> Result<Self> {
+        if chunk_size == 0 {
+            return Err(Error::InvalidArgumentError(
+                "The chunk size must be larger than zero".to_string(),
+            ));
+        }
+        Ok(Self {
+            reader: split_reader(reader, split, split_options)?,
+            fields,
+            projection,
+            options,
+            rows: vec![ByteRecord::default(); chunk_size],
+            row: 0,
+            errors: vec![],
+        })
+    }
+
+    /// Returns the cells that could not be parsed so far when `options.mode` is
+    /// [`ParseMode::Lenient`](super::ParseMode::Lenient), emptying them.
+    pub fn take_errors(&mut self) -> Vec<ParseError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    fn next_chunk(&mut self) -> Result<Option<Chunk<Box<dyn Array>>>> {
+        let rows_read = read_rows(&mut self.reader, 0, &mut self.rows)?;
+        if rows_read == 0 {
+            return Ok(None);
+        }
+        let (chunk, errors) = deserialize_batch_with_options(
+            &self.rows[..rows_read],
+            &self.fields,
+            self.projection.as_deref(),
+            self.row,
+            &self.options,
+        )?;
+        self.row += rows_read;
+        self.errors.extend(errors);
+        Ok(Some(chunk))
+    }
+}
+
+impl<R: Read + Seek> Iterator for SplitReader<R> {
+    type Item = Result<Chunk<Box<dyn Array>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_chunk().transpose()
+    }
+}
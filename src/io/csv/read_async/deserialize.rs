@@ -8,8 +8,11 @@ use crate::{
 };
 
 use super::super::read_utils::{
-    deserialize_batch as deserialize_batch_gen, deserialize_column as deserialize_column_gen,
-    ByteRecordGeneric,
+    deserialize_batch as deserialize_batch_gen,
+    deserialize_batch_with_options as deserialize_batch_with_options_gen,
+    deserialize_column as deserialize_column_gen,
+    deserialize_column_with_options as deserialize_column_with_options_gen, ByteRecordGeneric,
+    DeserializeOptions, ParseError,
 };
 
 impl ByteRecordGeneric for ByteRecord {
@@ -44,3 +47,39 @@ where
 {
     deserialize_batch_gen(rows, fields, projection, line_number, deserialize_column)
 }
+
+/// Deserializes `column` of `rows` into an [`Array`] of the [`DataType`] of `field` according
+/// to `options`.
+///
+/// The cells that could not be parsed are returned when `options.mode` is
+/// [`ParseMode::Lenient`](super::ParseMode::Lenient); their [`ParseError::row`] is `line_number`
+/// plus the index of their row in `rows`.
+/// # Errors
+/// This function errors with a [`ParseError`] when `options.mode` is
+/// [`ParseMode::Strict`](super::ParseMode::Strict) and a cell can't be parsed.
+pub fn deserialize_column_with_options(
+    rows: &[ByteRecord],
+    column: usize,
+    field: &Field,
+    line_number: usize,
+    options: &DeserializeOptions,
+) -> Result<(Box<dyn Array>, Vec<ParseError>)> {
+    deserialize_column_with_options_gen(rows, column, field, line_number, options)
+}
+
+/// Deserializes rows [`ByteRecord`] into [`Chunk`] according to `options`.
+///
+/// The cells that could not be parsed are returned, ordered by column and then by row, when
+/// `options.mode` is [`ParseMode::Lenient`](super::ParseMode::Lenient).
+/// # Errors
+/// This function errors with a [`ParseError`] when `options.mode` is
+/// [`ParseMode::Strict`](super::ParseMode::Strict) and a cell can't be parsed.
+pub fn deserialize_batch_with_options(
+    rows: &[ByteRecord],
+    fields: &[Field],
+    projection: Option<&[usize]>,
+    line_number: usize,
+    options: &DeserializeOptions,
+) -> Result<(Chunk<Box<dyn Array>>, Vec<ParseError>)> {
+    deserialize_batch_with_options_gen(rows, fields, projection, line_number, options)
+}
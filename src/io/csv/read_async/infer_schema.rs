@@ -3,7 +3,8 @@ use ahash::AHashSet;
 
 use crate::datatypes::{DataType, Field};
 use crate::error::Result;
-use crate::io::csv::utils::merge_schema;
+use crate::io::csv::read_utils::DeserializeOptions;
+use crate::io::csv::utils::{infer_with_options, is_null, merge_schema};
 
 use futures::{AsyncRead, AsyncSeek};
 
@@ -18,6 +19,46 @@ pub async fn infer_schema<R, F>(
 where
     R: AsyncRead + AsyncSeek + Unpin + Send + Sync,
     F: Fn(&[u8]) -> DataType,
+{
+    infer_columns(reader, max_rows, has_header, |_, bytes| Some(infer(bytes))).await
+}
+
+/// Infers the [`Field`]s of a CSV file according to `options` by reading through the first n
+/// records up to `max_rows`: booleans and floats are inferred with the spellings and decimal
+/// separator of `options`, temporal columns with their format in `options.formats`, and cells
+/// that are empty or one of `options.null_values` are ignored.
+/// Seeks back to the begining of the file _after_ the header
+pub async fn infer_schema_with_options<R>(
+    reader: &mut AsyncReader<R>,
+    max_rows: Option<usize>,
+    has_header: bool,
+    options: &DeserializeOptions,
+) -> Result<(Vec<Field>, usize)>
+where
+    R: AsyncRead + AsyncSeek + Unpin + Send + Sync,
+{
+    infer_columns(reader, max_rows, has_header, |header, bytes| {
+        if is_null(bytes, options) {
+            None
+        } else {
+            let format = options.formats.get(header).map(|x| x.as_str());
+            Some(infer_with_options(bytes, format, options))
+        }
+    })
+    .await
+}
+
+/// Infers the [`Field`]s of a CSV file, where `infer` returns the [`DataType`] of a cell of a
+/// column given its header, or `None` when the cell is ignored
+async fn infer_columns<R, F>(
+    reader: &mut AsyncReader<R>,
+    max_rows: Option<usize>,
+    has_header: bool,
+    infer: F,
+) -> Result<(Vec<Field>, usize)>
+where
+    R: AsyncRead + AsyncSeek + Unpin + Send + Sync,
+    F: Fn(&str, &[u8]) -> Option<DataType>,
 {
     // get or create header names
     // when has_header is false, creates default column names with column_ prefix
@@ -52,9 +93,9 @@ where
         }
         records_count += 1;
 
-        for (i, column) in column_types.iter_mut().enumerate() {
-            if let Some(string) = record.get(i) {
-                column.insert(infer(string));
+        for (i, (column, header)) in column_types.iter_mut().zip(headers.iter()).enumerate() {
+            if let Some(data_type) = record.get(i).and_then(|bytes| infer(header, bytes)) {
+                column.insert(data_type);
             }
         }
     }
@@ -7,9 +7,13 @@ mod deserialize;
 mod infer_schema;
 mod reader;
 
+pub use super::read_utils::{DeserializeOptions, ParseError, ParseMode};
 pub use super::utils::infer;
-pub use deserialize::{deserialize_batch, deserialize_column};
-pub use infer_schema::infer_schema;
+pub use deserialize::{
+    deserialize_batch, deserialize_batch_with_options, deserialize_column,
+    deserialize_column_with_options,
+};
+pub use infer_schema::{infer_schema, infer_schema_with_options};
 pub use reader::*;
 
 pub use csv_async::Error as CSVError;
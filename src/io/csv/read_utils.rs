@@ -1,4 +1,7 @@
-use chrono::Datelike;
+use std::collections::HashMap;
+
+use chrono::{Datelike, Timelike};
+use lexical_core::format::STANDARD;
 
 // Ideally this trait should not be needed and both `csv` and `csv_async` crates would share
 // the same `ByteRecord` struct. Unfortunately, they do not and thus we must use generics
@@ -13,10 +16,137 @@ use crate::{
     datatypes::*,
     error::{Error, Result},
     temporal_conversions,
-    types::NativeType,
+    types::{days_ms, months_days_ns, NativeType},
 };
 
-use super::utils::RFC3339;
+use super::utils::{float_options, parse_boolean, RFC3339};
+
+/// How cells that can't be parsed into the [`DataType`] of their column are deserialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ParseMode {
+    /// Cells that can't be parsed are deserialized as nulls.
+    #[default]
+    Permissive,
+    /// Deserialization fails with the [`ParseError`] of the first cell that can't be parsed.
+    Strict,
+    /// Cells that can't be parsed are deserialized as nulls and returned as [`ParseError`]s.
+    Lenient,
+}
+
+/// A cell that could not be parsed into the [`DataType`] of its column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// The index of the cell's row: the `line_number` passed to the deserializer plus the index
+    /// of the row in `rows`. When `line_number` is the number of rows read before `rows`, this
+    /// is the index of the record in the file excluding the header, not the line of the file.
+    pub row: usize,
+    /// The name of the cell's column
+    pub column: String,
+    /// The bytes of the cell
+    pub bytes: Vec<u8>,
+    /// The [`DataType`] the cell could not be parsed into
+    pub data_type: DataType,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "could not parse {:?} as {:?} in column \"{}\" at row {}",
+            String::from_utf8_lossy(&self.bytes),
+            self.data_type,
+            self.column,
+            self.row
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::External(
+            format!(" in column \"{}\" at row {}", error.column, error.row),
+            Box::new(error),
+        )
+    }
+}
+
+/// Options to deserialize CSV rows into arrays and to infer their schema.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeserializeOptions {
+    /// How cells that can't be parsed are deserialized
+    pub mode: ParseMode,
+    /// Cells equal to one of these strings (e.g. `"NA"`, `"NULL"` or `"\N"`) are deserialized
+    /// as nulls, whatever the [`DataType`] of their column
+    pub null_values: Vec<String>,
+    /// The [strftime](chrono::format::strftime) format of the temporal columns, by column name.
+    /// Columns without a format are parsed with chrono's default formats (RFC 3339 for
+    /// timestamps with a timezone). Timestamps whose format has no offset are local to the
+    /// timezone of their column.
+    pub formats: HashMap<String, String>,
+    /// The case insensitive spellings of `true`. When empty, `"true"` is used.
+    pub true_values: Vec<String>,
+    /// The case insensitive spellings of `false`. When empty, `"false"` is used.
+    pub false_values: Vec<String>,
+    /// Whether floats and decimals use a comma as decimal separator (e.g. `"1,5"`), as in
+    /// locales like `de_DE`
+    pub decimal_comma: bool,
+}
+
+/// A row whose cells equal to one of `null_values` are missing
+struct NullSentinels<'a, B> {
+    row: &'a B,
+    null_values: &'a [String],
+}
+
+impl<'a, B: ByteRecordGeneric> ByteRecordGeneric for NullSentinels<'a, B> {
+    #[inline]
+    fn get(&self, index: usize) -> Option<&[u8]> {
+        self.row.get(index).filter(|bytes| {
+            !self
+                .null_values
+                .iter()
+                .any(|null| null.as_bytes() == *bytes)
+        })
+    }
+}
+
+/// Records the cells of a column that could not be parsed, according to a [`ParseMode`]
+struct Reporter<'a> {
+    column: &'a str,
+    data_type: &'a DataType,
+    line_number: usize,
+    mode: ParseMode,
+    errors: Vec<ParseError>,
+}
+
+impl<'a> Reporter<'a> {
+    #[inline]
+    fn report(&mut self, row: usize, bytes: &[u8]) {
+        match self.mode {
+            ParseMode::Permissive => {}
+            ParseMode::Strict if !self.errors.is_empty() => {}
+            ParseMode::Strict | ParseMode::Lenient => self.errors.push(ParseError {
+                row: self.line_number + row,
+                column: self.column.to_string(),
+                bytes: bytes.to_vec(),
+                data_type: self.data_type.clone(),
+            }),
+        }
+    }
+
+    fn finish(self) -> Result<Vec<ParseError>> {
+        if self.mode == ParseMode::Strict {
+            match self.errors.into_iter().next() {
+                Some(error) => Err(error.into()),
+                None => Ok(vec![]),
+            }
+        } else {
+            Ok(self.errors)
+        }
+    }
+}
 
 #[inline]
 fn to_utf8(bytes: &[u8]) -> Option<&str> {
@@ -28,21 +158,29 @@ fn deserialize_primitive<T, B: ByteRecordGeneric, F>(
     rows: &[B],
     column: usize,
     datatype: DataType,
+    reporter: &mut Reporter,
     op: F,
 ) -> Box<dyn Array>
 where
-    T: NativeType + lexical_core::FromLexical,
+    T: NativeType,
     F: Fn(&[u8]) -> Option<T>,
 {
-    let iter = rows.iter().map(|row| match row.get(column) {
-        Some(bytes) => {
-            if bytes.is_empty() {
-                return None;
+    let iter = rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| match row.get(column) {
+            Some(bytes) => {
+                if bytes.is_empty() {
+                    return None;
+                }
+                let value = op(bytes);
+                if value.is_none() {
+                    reporter.report(index, bytes);
+                }
+                value
             }
-            op(bytes)
-        }
-        None => None,
-    });
+            None => None,
+        });
     Box::new(PrimitiveArray::<T>::from_trusted_len_iter(iter).to(datatype))
 }
 
@@ -54,8 +192,13 @@ fn significant_bytes(bytes: &[u8]) -> usize {
 /// Deserializes bytes to a single i128 representing a decimal
 /// The decimal precision and scale are not checked.
 #[inline]
-fn deserialize_decimal(bytes: &[u8], precision: usize, scale: usize) -> Option<i128> {
-    let mut a = bytes.split(|x| *x == b'.');
+fn deserialize_decimal(
+    bytes: &[u8],
+    precision: usize,
+    scale: usize,
+    separator: u8,
+) -> Option<i128> {
+    let mut a = bytes.split(|x| *x == separator);
     let lhs = a.next();
     let rhs = a.next();
     match (lhs, rhs) {
@@ -91,29 +234,54 @@ fn deserialize_decimal(bytes: &[u8], precision: usize, scale: usize) -> Option<i
 }
 
 #[inline]
-fn deserialize_boolean<B, F>(rows: &[B], column: usize, op: F) -> Box<dyn Array>
+fn deserialize_boolean<B, F>(
+    rows: &[B],
+    column: usize,
+    reporter: &mut Reporter,
+    op: F,
+) -> Box<dyn Array>
 where
     B: ByteRecordGeneric,
     F: Fn(&[u8]) -> Option<bool>,
 {
-    let iter = rows.iter().map(|row| match row.get(column) {
-        Some(bytes) => {
-            if bytes.is_empty() {
-                return None;
+    let iter = rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| match row.get(column) {
+            Some(bytes) => {
+                if bytes.is_empty() {
+                    return None;
+                }
+                let value = op(bytes);
+                if value.is_none() {
+                    reporter.report(index, bytes);
+                }
+                value
             }
-            op(bytes)
-        }
-        None => None,
-    });
+            None => None,
+        });
     Box::new(BooleanArray::from_trusted_len_iter(iter))
 }
 
 #[inline]
-fn deserialize_utf8<O: Offset, B: ByteRecordGeneric>(rows: &[B], column: usize) -> Box<dyn Array> {
-    let iter = rows.iter().map(|row| match row.get(column) {
-        Some(bytes) => to_utf8(bytes),
-        None => None,
-    });
+fn deserialize_utf8<O: Offset, B: ByteRecordGeneric>(
+    rows: &[B],
+    column: usize,
+    reporter: &mut Reporter,
+) -> Box<dyn Array> {
+    let iter = rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| match row.get(column) {
+            Some(bytes) => {
+                let value = to_utf8(bytes);
+                if value.is_none() {
+                    reporter.report(index, bytes);
+                }
+                value
+            }
+            None => None,
+        });
     Box::new(Utf8Array::<O>::from_trusted_len_iter(iter))
 }
 
@@ -127,16 +295,204 @@ fn deserialize_binary<O: Offset, B: ByteRecordGeneric>(
 }
 
 #[inline]
-fn deserialize_datetime<T: chrono::TimeZone>(string: &str, tz: &T) -> Option<i64> {
+fn deserialize_fixed_size_binary<B: ByteRecordGeneric>(
+    rows: &[B],
+    column: usize,
+    size: usize,
+    reporter: &mut Reporter,
+) -> Box<dyn Array> {
+    let mut array = MutableFixedSizeBinaryArray::with_capacity(size, rows.len());
+    for (index, row) in rows.iter().enumerate() {
+        match row.get(column) {
+            Some(bytes) if bytes.len() == size => array.push(Some(bytes)),
+            Some(bytes) => {
+                if !bytes.is_empty() {
+                    reporter.report(index, bytes);
+                }
+                array.push::<&[u8]>(None)
+            }
+            None => array.push::<&[u8]>(None),
+        }
+    }
+    let array: FixedSizeBinaryArray = array.into();
+    array.boxed()
+}
+
+/// Deserializes a column of strings into a [`DictionaryArray`] whose values are built while
+/// the rows are read.
+fn deserialize_dictionary<K: DictionaryKey, O: Offset, B: ByteRecordGeneric>(
+    rows: &[B],
+    column: usize,
+    datatype: DataType,
+    reporter: &mut Reporter,
+) -> Result<Box<dyn Array>> {
+    let mut array = MutableDictionaryArray::<K, MutableUtf8Array<O>>::new();
+    for (index, row) in rows.iter().enumerate() {
+        match row.get(column) {
+            Some(bytes) => match to_utf8(bytes) {
+                Some(value) => array.try_push(Some(value))?,
+                None => {
+                    reporter.report(index, bytes);
+                    array.push_null()
+                }
+            },
+            None => array.push_null(),
+        }
+    }
+    let array: DictionaryArray<K> = array.into();
+    DictionaryArray::try_new(datatype, array.keys().clone(), array.values().clone())
+        .map(|array| array.boxed())
+}
+
+#[inline]
+fn deserialize_null<B: ByteRecordGeneric>(
+    rows: &[B],
+    column: usize,
+    reporter: &mut Reporter,
+) -> Box<dyn Array> {
+    for (index, row) in rows.iter().enumerate() {
+        if let Some(bytes) = row.get(column) {
+            if !bytes.is_empty() {
+                reporter.report(index, bytes);
+            }
+        }
+    }
+    NullArray::new(DataType::Null, rows.len()).boxed()
+}
+
+/// Deserializes bytes in `format` (by default `HH:MM:SS[.fraction]`) to the number of
+/// nanoseconds since midnight
+#[inline]
+fn deserialize_time(bytes: &[u8], format: Option<&str>) -> Option<i64> {
+    let string = to_utf8(bytes)?;
+    match format {
+        Some(format) => chrono::NaiveTime::parse_from_str(string, format).ok(),
+        None => string.parse::<chrono::NaiveTime>().ok(),
+    }
+    .map(|x| x.num_seconds_from_midnight() as i64 * 1_000_000_000 + x.nanosecond() as i64)
+}
+
+#[inline]
+fn deserialize_naive_date(string: &str, format: Option<&str>) -> Option<chrono::NaiveDate> {
+    match format {
+        Some(format) => chrono::NaiveDate::parse_from_str(string, format).ok(),
+        None => string.parse::<chrono::NaiveDate>().ok(),
+    }
+}
+
+/// Deserializes a naive datetime in `format`, or a date in `format` at midnight
+#[inline]
+fn deserialize_naive_datetime(string: &str, format: Option<&str>) -> Option<chrono::NaiveDateTime> {
+    match format {
+        Some(format) => chrono::NaiveDateTime::parse_from_str(string, format)
+            .ok()
+            .or_else(|| {
+                chrono::NaiveDate::parse_from_str(string, format)
+                    .ok()
+                    .and_then(|x| x.and_hms_opt(0, 0, 0))
+            }),
+        None => string.parse::<chrono::NaiveDateTime>().ok(),
+    }
+}
+
+/// Splits the components of (a date or time part of) an ISO 8601 duration into their values
+/// and designators, e.g. `1Y2M` into `[("1", 'Y'), ("2", 'M')]`
+fn duration_components(string: &str) -> Option<Vec<(&str, char)>> {
+    let mut components = vec![];
+    let mut start = 0;
+    for (index, designator) in string.char_indices() {
+        if designator.is_ascii_alphabetic() {
+            if index == start {
+                return None;
+            }
+            components.push((&string[start..index], designator));
+            start = index + 1;
+        }
+    }
+    if start != string.len() {
+        return None;
+    }
+    Some(components)
+}
+
+/// Deserializes seconds with an optional fraction of up to 9 digits, e.g. `6.5`, to nanoseconds
+fn deserialize_seconds(string: &str) -> Option<i64> {
+    let (seconds, fraction) = string.split_once('.').unwrap_or((string, ""));
+    if fraction.len() > 9 || !fraction.bytes().all(|x| x.is_ascii_digit()) {
+        return None;
+    }
+    let nanoseconds = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<i64>().ok()? * 10i64.pow(9 - fraction.len() as u32)
+    };
+    seconds
+        .parse::<i64>()
+        .ok()?
+        .checked_mul(1_000_000_000)?
+        .checked_add(nanoseconds)
+}
+
+/// Deserializes an ISO 8601 duration (e.g. `P1Y2M3DT4H5M6.5S`, `-P1W` or `PT0.001S`) into its
+/// months, days and nanoseconds. Years are 12 months and weeks are 7 days.
+fn deserialize_interval(bytes: &[u8]) -> Option<months_days_ns> {
+    let string = to_utf8(bytes)?;
+    let (sign, string) = match string.strip_prefix('-') {
+        Some(string) => (-1, string),
+        None => (1, string),
+    };
+    let string = string.strip_prefix('P')?;
+    let (date, time) = match string.split_once('T') {
+        Some((date, time)) if !time.is_empty() => (date, time),
+        Some(_) => return None,
+        None if !string.is_empty() => (string, ""),
+        None => return None,
+    };
+
+    let mut months = 0i64;
+    let mut days = 0i64;
+    for (value, designator) in duration_components(date)? {
+        let value = value.parse::<i64>().ok()?;
+        match designator {
+            'Y' => months = months.checked_add(value.checked_mul(12)?)?,
+            'M' => months = months.checked_add(value)?,
+            'W' => days = days.checked_add(value.checked_mul(7)?)?,
+            'D' => days = days.checked_add(value)?,
+            _ => return None,
+        }
+    }
+    let mut nanoseconds = 0i64;
+    for (value, designator) in duration_components(time)? {
+        let value = match designator {
+            'H' => value.parse::<i64>().ok()?.checked_mul(3_600_000_000_000)?,
+            'M' => value.parse::<i64>().ok()?.checked_mul(60_000_000_000)?,
+            'S' => deserialize_seconds(value)?,
+            _ => return None,
+        };
+        nanoseconds = nanoseconds.checked_add(value)?;
+    }
+
+    Some(months_days_ns::new(
+        (sign * months).try_into().ok()?,
+        (sign * days).try_into().ok()?,
+        sign * nanoseconds,
+    ))
+}
+
+#[inline]
+fn deserialize_datetime<T: chrono::TimeZone>(string: &str, tz: &T, format: &str) -> Option<i64> {
     let mut parsed = chrono::format::Parsed::new();
-    let fmt = chrono::format::StrftimeItems::new(RFC3339);
+    let fmt = chrono::format::StrftimeItems::new(format);
     if chrono::format::parse(&mut parsed, string, fmt).is_ok() {
-        parsed
-            .to_datetime()
-            .map(|x| x.naive_utc())
-            .map(|x| tz.from_utc_datetime(&x))
-            .map(|x| x.timestamp_nanos())
-            .ok()
+        match parsed.to_datetime() {
+            Ok(x) => tz.from_utc_datetime(&x.naive_utc()).timestamp_nanos_opt(),
+            // datetimes without an offset are local to `tz`
+            Err(_) => parsed
+                .to_naive_datetime_with_offset(0)
+                .ok()
+                .and_then(|x| tz.from_local_datetime(&x).single())
+                .and_then(|x| x.timestamp_nanos_opt()),
+        }
     } else {
         None
     }
@@ -148,75 +504,139 @@ pub(crate) fn deserialize_column<B: ByteRecordGeneric>(
     rows: &[B],
     column: usize,
     datatype: DataType,
-    _line_number: usize,
+    line_number: usize,
+) -> Result<Box<dyn Array>> {
+    let data_type = datatype.clone();
+    let mut reporter = Reporter {
+        column: "",
+        data_type: &data_type,
+        line_number,
+        mode: ParseMode::Permissive,
+        errors: vec![],
+    };
+    deserialize(
+        rows,
+        column,
+        datatype,
+        None,
+        &DeserializeOptions::default(),
+        &mut reporter,
+    )
+}
+
+/// Deserializes `column` of `rows` into an [`Array`] of the [`DataType`] of `field` according to
+/// `options`, returning it with the cells that could not be parsed when `options.mode` is
+/// [`ParseMode::Lenient`].
+pub(crate) fn deserialize_column_with_options<B: ByteRecordGeneric>(
+    rows: &[B],
+    column: usize,
+    field: &Field,
+    line_number: usize,
+    options: &DeserializeOptions,
+) -> Result<(Box<dyn Array>, Vec<ParseError>)> {
+    let mut reporter = Reporter {
+        column: &field.name,
+        data_type: field.data_type(),
+        line_number,
+        mode: options.mode,
+        errors: vec![],
+    };
+    let data_type = field.data_type().clone();
+    let format = options.formats.get(&field.name).map(|x| x.as_str());
+    let array = if options.null_values.is_empty() {
+        deserialize(rows, column, data_type, format, options, &mut reporter)?
+    } else {
+        let rows = rows
+            .iter()
+            .map(|row| NullSentinels {
+                row,
+                null_values: &options.null_values,
+            })
+            .collect::<Vec<_>>();
+        deserialize(&rows, column, data_type, format, options, &mut reporter)?
+    };
+    Ok((array, reporter.finish()?))
+}
+
+/// Deserializes `column` of `rows` into an [`Array`] of [`DataType`] `datatype`, where `format`
+/// is the strftime format of temporal columns.
+fn deserialize<B: ByteRecordGeneric>(
+    rows: &[B],
+    column: usize,
+    datatype: DataType,
+    format: Option<&str>,
+    options: &DeserializeOptions,
+    reporter: &mut Reporter,
 ) -> Result<Box<dyn Array>> {
     use DataType::*;
     Ok(match datatype {
-        Boolean => deserialize_boolean(rows, column, |bytes| {
-            if bytes.eq_ignore_ascii_case(b"false") {
-                Some(false)
-            } else if bytes.eq_ignore_ascii_case(b"true") {
-                Some(true)
-            } else {
-                None
-            }
+        Boolean => deserialize_boolean(rows, column, reporter, |bytes| {
+            parse_boolean(bytes, options)
         }),
-        Int8 => deserialize_primitive(rows, column, datatype, |bytes| {
+        Int8 => deserialize_primitive(rows, column, datatype, reporter, |bytes| {
             lexical_core::parse::<i8>(bytes).ok()
         }),
-        Int16 => deserialize_primitive(rows, column, datatype, |bytes| {
+        Int16 => deserialize_primitive(rows, column, datatype, reporter, |bytes| {
             lexical_core::parse::<i16>(bytes).ok()
         }),
-        Int32 => deserialize_primitive(rows, column, datatype, |bytes| {
+        Int32 => deserialize_primitive(rows, column, datatype, reporter, |bytes| {
             lexical_core::parse::<i32>(bytes).ok()
         }),
-        Int64 => deserialize_primitive(rows, column, datatype, |bytes| {
+        Int64 => deserialize_primitive(rows, column, datatype, reporter, |bytes| {
             lexical_core::parse::<i64>(bytes).ok()
         }),
-        UInt8 => deserialize_primitive(rows, column, datatype, |bytes| {
+        UInt8 => deserialize_primitive(rows, column, datatype, reporter, |bytes| {
             lexical_core::parse::<u8>(bytes).ok()
         }),
-        UInt16 => deserialize_primitive(rows, column, datatype, |bytes| {
+        UInt16 => deserialize_primitive(rows, column, datatype, reporter, |bytes| {
             lexical_core::parse::<u16>(bytes).ok()
         }),
-        UInt32 => deserialize_primitive(rows, column, datatype, |bytes| {
+        UInt32 => deserialize_primitive(rows, column, datatype, reporter, |bytes| {
             lexical_core::parse::<u32>(bytes).ok()
         }),
-        UInt64 => deserialize_primitive(rows, column, datatype, |bytes| {
+        UInt64 => deserialize_primitive(rows, column, datatype, reporter, |bytes| {
             lexical_core::parse::<u64>(bytes).ok()
         }),
-        Float32 => deserialize_primitive(rows, column, datatype, |bytes| {
-            lexical_core::parse::<f32>(bytes).ok()
-        }),
-        Float64 => deserialize_primitive(rows, column, datatype, |bytes| {
-            lexical_core::parse::<f64>(bytes).ok()
-        }),
-        Date32 => deserialize_primitive(rows, column, datatype, |bytes| {
+        Float32 => {
+            let float_options = float_options(options.decimal_comma);
+            deserialize_primitive(rows, column, datatype, reporter, |bytes| {
+                lexical_core::parse_with_options::<f32, STANDARD>(bytes, &float_options).ok()
+            })
+        }
+        Float64 => {
+            let float_options = float_options(options.decimal_comma);
+            deserialize_primitive(rows, column, datatype, reporter, |bytes| {
+                lexical_core::parse_with_options::<f64, STANDARD>(bytes, &float_options).ok()
+            })
+        }
+        Date32 => deserialize_primitive(rows, column, datatype, reporter, |bytes| {
             to_utf8(bytes)
-                .and_then(|x| x.parse::<chrono::NaiveDate>().ok())
+                .and_then(|x| deserialize_naive_date(x, format))
                 .map(|x| x.num_days_from_ce() - temporal_conversions::EPOCH_DAYS_FROM_CE)
         }),
-        Date64 => deserialize_primitive(rows, column, datatype, |bytes| {
+        Date64 => deserialize_primitive(rows, column, datatype, reporter, |bytes| {
             to_utf8(bytes)
-                .and_then(|x| x.parse::<chrono::NaiveDateTime>().ok())
-                .map(|x| x.timestamp_millis())
-        }),
-        Timestamp(time_unit, None) => deserialize_primitive(rows, column, datatype, |bytes| {
-            to_utf8(bytes)
-                .and_then(|x| x.parse::<chrono::NaiveDateTime>().ok())
-                .map(|x| x.timestamp_nanos())
-                .map(|x| match time_unit {
-                    TimeUnit::Second => x / 1_000_000_000,
-                    TimeUnit::Millisecond => x / 1_000_000,
-                    TimeUnit::Microsecond => x / 1_000,
-                    TimeUnit::Nanosecond => x,
-                })
+                .and_then(|x| deserialize_naive_datetime(x, format))
+                .map(|x| x.and_utc().timestamp_millis())
         }),
+        Timestamp(time_unit, None) => {
+            deserialize_primitive(rows, column, datatype, reporter, |bytes| {
+                to_utf8(bytes)
+                    .and_then(|x| deserialize_naive_datetime(x, format))
+                    .and_then(|x| x.and_utc().timestamp_nanos_opt())
+                    .map(|x| match time_unit {
+                        TimeUnit::Second => x / 1_000_000_000,
+                        TimeUnit::Millisecond => x / 1_000_000,
+                        TimeUnit::Microsecond => x / 1_000,
+                        TimeUnit::Nanosecond => x,
+                    })
+            })
+        }
         Timestamp(time_unit, Some(ref tz)) => {
             let tz = temporal_conversions::parse_offset(tz)?;
-            deserialize_primitive(rows, column, datatype, |bytes| {
+            deserialize_primitive(rows, column, datatype, reporter, |bytes| {
                 to_utf8(bytes)
-                    .and_then(|x| deserialize_datetime(x, &tz))
+                    .and_then(|x| deserialize_datetime(x, &tz, format.unwrap_or(RFC3339)))
                     .map(|x| match time_unit {
                         TimeUnit::Second => x / 1_000_000_000,
                         TimeUnit::Millisecond => x / 1_000_000,
@@ -225,13 +645,71 @@ pub(crate) fn deserialize_column<B: ByteRecordGeneric>(
                     })
             })
         }
-        Decimal(precision, scale) => deserialize_primitive(rows, column, datatype, |x| {
-            deserialize_decimal(x, precision, scale)
+        Time32(TimeUnit::Second) => {
+            deserialize_primitive(rows, column, datatype, reporter, |bytes| {
+                deserialize_time(bytes, format).map(|x| (x / 1_000_000_000) as i32)
+            })
+        }
+        Time32(TimeUnit::Millisecond) => {
+            deserialize_primitive(rows, column, datatype, reporter, |bytes| {
+                deserialize_time(bytes, format).map(|x| (x / 1_000_000) as i32)
+            })
+        }
+        Time64(TimeUnit::Microsecond) => {
+            deserialize_primitive(rows, column, datatype, reporter, |bytes| {
+                deserialize_time(bytes, format).map(|x| x / 1_000)
+            })
+        }
+        Time64(TimeUnit::Nanosecond) => {
+            deserialize_primitive(rows, column, datatype, reporter, |bytes| {
+                deserialize_time(bytes, format)
+            })
+        }
+        Duration(_) => deserialize_primitive(rows, column, datatype, reporter, |bytes| {
+            lexical_core::parse::<i64>(bytes).ok()
         }),
-        Utf8 => deserialize_utf8::<i32, _>(rows, column),
-        LargeUtf8 => deserialize_utf8::<i64, _>(rows, column),
+        Interval(IntervalUnit::YearMonth) => {
+            deserialize_primitive(rows, column, datatype, reporter, |bytes| {
+                deserialize_interval(bytes)
+                    .filter(|x| x.days() == 0 && x.ns() == 0)
+                    .map(|x| x.months())
+            })
+        }
+        Interval(IntervalUnit::DayTime) => {
+            deserialize_primitive(rows, column, datatype, reporter, |bytes| {
+                deserialize_interval(bytes)
+                    .filter(|x| x.months() == 0 && x.ns() % 1_000_000 == 0)
+                    .and_then(|x| {
+                        let milliseconds = (x.ns() / 1_000_000).try_into().ok()?;
+                        Some(days_ms::new(x.days(), milliseconds))
+                    })
+            })
+        }
+        Interval(IntervalUnit::MonthDayNano) => {
+            deserialize_primitive(rows, column, datatype, reporter, deserialize_interval)
+        }
+        Decimal(precision, scale) => {
+            let separator = if options.decimal_comma { b',' } else { b'.' };
+            deserialize_primitive(rows, column, datatype, reporter, |x| {
+                deserialize_decimal(x, precision, scale, separator)
+            })
+        }
+        Utf8 => deserialize_utf8::<i32, _>(rows, column, reporter),
+        LargeUtf8 => deserialize_utf8::<i64, _>(rows, column, reporter),
         Binary => deserialize_binary::<i32, _>(rows, column),
         LargeBinary => deserialize_binary::<i64, _>(rows, column),
+        FixedSizeBinary(size) => deserialize_fixed_size_binary(rows, column, size, reporter),
+        Dictionary(key_type, ref values, _) if values.as_ref() == &Utf8 => {
+            match_integer_type!(key_type, |$T| {
+                deserialize_dictionary::<$T, i32, _>(rows, column, datatype, reporter)?
+            })
+        }
+        Dictionary(key_type, ref values, _) if values.as_ref() == &LargeUtf8 => {
+            match_integer_type!(key_type, |$T| {
+                deserialize_dictionary::<$T, i64, _>(rows, column, datatype, reporter)?
+            })
+        }
+        Null => deserialize_null(rows, column, reporter),
         other => {
             return Err(Error::NotYetImplemented(format!(
                 "Deserializing type \"{:?}\" is not implemented",
@@ -274,3 +752,40 @@ where
         .collect::<Result<Vec<_>>>()
         .and_then(Chunk::try_new)
 }
+
+/// Deserializes rows [`ByteRecord`] into a [`Chunk`] according to `options`, returning it with
+/// the cells that could not be parsed when `options.mode` is [`ParseMode::Lenient`], ordered
+/// by column and then by row.
+pub(crate) fn deserialize_batch_with_options<B: ByteRecordGeneric>(
+    rows: &[B],
+    fields: &[Field],
+    projection: Option<&[usize]>,
+    line_number: usize,
+    options: &DeserializeOptions,
+) -> Result<(Chunk<Box<dyn Array>>, Vec<ParseError>)> {
+    let projection: Vec<usize> = match projection {
+        Some(v) => v.to_vec(),
+        None => fields.iter().enumerate().map(|(i, _)| i).collect(),
+    };
+
+    if rows.is_empty() {
+        return Ok((Chunk::new(vec![]), vec![]));
+    }
+
+    let mut errors = vec![];
+    let arrays = projection
+        .iter()
+        .map(|column| {
+            let (array, column_errors) = deserialize_column_with_options(
+                rows,
+                *column,
+                &fields[*column],
+                line_number,
+                options,
+            )?;
+            errors.extend(column_errors);
+            Ok(array)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok((Chunk::try_new(arrays)?, errors))
+}
@@ -1,14 +1,42 @@
 use crate::datatypes::{DataType, Field, TimeUnit};
 use ahash::AHashSet;
+use lexical_core::{format::STANDARD, ParseFloatOptions};
+
+use super::read_utils::DeserializeOptions;
 
 pub(super) const RFC3339: &str = "%Y-%m-%dT%H:%M:%S%.f%:z";
 
-fn is_boolean(bytes: &[u8]) -> bool {
-    bytes.eq_ignore_ascii_case(b"true") | bytes.eq_ignore_ascii_case(b"false")
+/// Parses `bytes` as one of the (case insensitive) spellings of `true` or `false` of `options`
+pub(super) fn parse_boolean(bytes: &[u8], options: &DeserializeOptions) -> Option<bool> {
+    let is = |values: &[String], default: &[u8]| {
+        if values.is_empty() {
+            bytes.eq_ignore_ascii_case(default)
+        } else {
+            values
+                .iter()
+                .any(|value| bytes.eq_ignore_ascii_case(value.as_bytes()))
+        }
+    };
+    if is(&options.true_values, b"true") {
+        Some(true)
+    } else if is(&options.false_values, b"false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// The options to parse floats whose decimal separator is a comma when `decimal_comma`, and a
+/// point otherwise
+pub(super) fn float_options(decimal_comma: bool) -> ParseFloatOptions {
+    ParseFloatOptions::builder()
+        .decimal_point(if decimal_comma { b',' } else { b'.' })
+        .build()
+        .unwrap()
 }
 
-fn is_float(bytes: &[u8]) -> bool {
-    lexical_core::parse::<f64>(bytes).is_ok()
+fn is_float(bytes: &[u8], float_options: &ParseFloatOptions) -> bool {
+    lexical_core::parse_with_options::<f64, STANDARD>(bytes, float_options).is_ok()
 }
 
 fn is_integer(bytes: &[u8]) -> bool {
@@ -27,14 +55,14 @@ fn is_naive_datetime(string: &str) -> bool {
     string.parse::<chrono::NaiveDateTime>().is_ok()
 }
 
-fn is_datetime(string: &str) -> Option<String> {
+fn is_datetime(string: &str, format: &str) -> Option<String> {
     let mut parsed = chrono::format::Parsed::new();
-    let fmt = chrono::format::StrftimeItems::new(RFC3339);
+    let fmt = chrono::format::StrftimeItems::new(format);
     if chrono::format::parse(&mut parsed, string, fmt).is_ok() {
         parsed.offset.map(|x| {
-            let hours = x / 60 / 60;
-            let minutes = x / 60 - hours * 60;
-            format!("{:03}:{:02}", hours, minutes)
+            let sign = if x < 0 { '-' } else { '+' };
+            let x = x.abs();
+            format!("{}{:02}:{:02}", sign, x / 60 / 60, x / 60 % 60)
         })
     } else {
         None
@@ -49,15 +77,52 @@ fn is_datetime(string: &str) -> Option<String> {
 /// * parsable to date is mapped to [`DataType::Date32`]
 /// * parsable to time is mapped to [`DataType::Time32(TimeUnit::Millisecond)`]
 /// * parsable to naive datetime is mapped to [`DataType::Timestamp(TimeUnit::Millisecond, None)`]
-/// * parsable to time-aware datetime is mapped to [`DataType::Timestamp`] of milliseconds and parsed offset.
+/// * parsable to time-aware datetime is mapped to [`DataType::Timestamp`] of milliseconds and parsed offset,
+///   formatted as `+HH:MM` or `-HH:MM`.
 /// * other utf8 is mapped to [`DataType::Utf8`]
 /// * invalid utf8 is mapped to [`DataType::Binary`]
 pub fn infer(bytes: &[u8]) -> DataType {
-    if is_boolean(bytes) {
+    infer_with_options(bytes, None, &DeserializeOptions::default())
+}
+
+/// Infers the temporal [`DataType`] of `string` in the strftime `format`
+fn infer_temporal(string: &str, format: &str) -> Option<DataType> {
+    if let Some(offset) = is_datetime(string, format) {
+        Some(DataType::Timestamp(TimeUnit::Millisecond, Some(offset)))
+    } else if chrono::NaiveDateTime::parse_from_str(string, format).is_ok() {
+        Some(DataType::Timestamp(TimeUnit::Millisecond, None))
+    } else if chrono::NaiveDate::parse_from_str(string, format).is_ok() {
+        Some(DataType::Date32)
+    } else if chrono::NaiveTime::parse_from_str(string, format).is_ok() {
+        Some(DataType::Time32(TimeUnit::Millisecond))
+    } else {
+        None
+    }
+}
+
+/// Infers [`DataType`] from `bytes` like [`infer`], but with the spellings of booleans and the
+/// decimal separator of `options`. When the column of `bytes` has a strftime `format`, `bytes`
+/// parsable in it are first mapped to [`DataType::Timestamp`] (with the parsed offset, if any),
+/// [`DataType::Date32`] or [`DataType::Time32`].
+pub(crate) fn infer_with_options(
+    bytes: &[u8],
+    format: Option<&str>,
+    options: &DeserializeOptions,
+) -> DataType {
+    if let Some(format) = format {
+        let data_type = simdutf8::basic::from_utf8(bytes)
+            .ok()
+            .and_then(|string| infer_temporal(string, format));
+        if let Some(data_type) = data_type {
+            return data_type;
+        }
+    }
+
+    if parse_boolean(bytes, options).is_some() {
         DataType::Boolean
     } else if is_integer(bytes) {
         DataType::Int64
-    } else if is_float(bytes) {
+    } else if is_float(bytes, &float_options(options.decimal_comma)) {
         DataType::Float64
     } else if let Ok(string) = simdutf8::basic::from_utf8(bytes) {
         if is_date(string) {
@@ -66,7 +131,7 @@ pub fn infer(bytes: &[u8]) -> DataType {
             DataType::Time32(TimeUnit::Millisecond)
         } else if is_naive_datetime(string) {
             DataType::Timestamp(TimeUnit::Millisecond, None)
-        } else if let Some(offset) = is_datetime(string) {
+        } else if let Some(offset) = is_datetime(string, RFC3339) {
             DataType::Timestamp(TimeUnit::Millisecond, Some(offset))
         } else {
             DataType::Utf8
@@ -98,6 +163,16 @@ fn merge_fields(field_name: &str, possibilities: &mut AHashSet<DataType>) -> Fie
     Field::new(field_name, data_type, true)
 }
 
+/// Returns whether the cell `bytes` is ignored when inferring a schema according to `options`:
+/// empty cells and cells equal to one of `options.null_values` are nulls.
+pub(crate) fn is_null(bytes: &[u8], options: &DeserializeOptions) -> bool {
+    bytes.is_empty()
+        || options
+            .null_values
+            .iter()
+            .any(|null| null.as_bytes() == bytes)
+}
+
 pub(crate) fn merge_schema(
     headers: &[String],
     column_types: &mut [AHashSet<DataType>],
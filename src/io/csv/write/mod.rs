@@ -46,9 +46,9 @@ pub fn serialize<A: AsRef<dyn Array>>(
                 row.push(options.delimiter);
             });
         if !row.is_empty() {
-            // replace last delimiter with new line
-            let last_byte = row.len() - 1;
-            row[last_byte] = b'\n';
+            // replace last delimiter with the line terminator
+            row.pop();
+            row.extend_from_slice(options.line_terminator.as_bytes());
             rows.push(std::mem::take(&mut row));
         }
         Result::Ok(())
@@ -78,9 +78,9 @@ pub fn write_chunk<W: Write, A: AsRef<dyn Array>>(
                 row.extend_from_slice(field);
                 row.push(options.delimiter);
             });
-        // replace last delimiter with new line
-        let last_byte = row.len() - 1;
-        row[last_byte] = b'\n';
+        // replace last delimiter with the line terminator
+        row.pop();
+        row.extend_from_slice(options.line_terminator.as_bytes());
         writer.write_all(&row)?;
         row.clear();
         Result::Ok(())
@@ -88,7 +88,7 @@ pub fn write_chunk<W: Write, A: AsRef<dyn Array>>(
     Ok(())
 }
 
-/// Writes a CSV header to `writer`
+/// Writes a CSV header to `writer`. The names are quoted according to `options.quote_style`.
 pub fn write_header<W: Write, T>(
     writer: &mut W,
     names: &[T],
@@ -97,12 +97,8 @@ pub fn write_header<W: Write, T>(
 where
     T: AsRef<str>,
 {
-    let names = names.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
-    writer.write_all(
-        names
-            .join(std::str::from_utf8(&[options.delimiter]).unwrap())
-            .as_bytes(),
-    )?;
-    writer.write_all(&[b'\n'])?;
+    let mut header = vec![];
+    serialize::write_names(names, options, &mut header);
+    writer.write_all(&header)?;
     Ok(())
 }
@@ -1,21 +1,45 @@
-use lexical_core::ToLexical;
+use lexical_core::{FormattedSize, ToLexical};
 
-use crate::datatypes::IntegerType;
+use crate::bitmap::utils::BitmapIter;
 use crate::temporal_conversions;
 use crate::types::NativeType;
 use crate::util::lexical_to_bytes_mut;
 use crate::{
-    array::{Array, BinaryArray, BooleanArray, PrimitiveArray, Utf8Array},
+    array::{Array, BinaryArray, BooleanArray, FixedSizeBinaryArray, PrimitiveArray, Utf8Array},
     datatypes::{DataType, TimeUnit},
-    error::Result,
+    error::{Error, Result},
 };
 
 use super::super::super::iterator::{BufStreamingIterator, StreamingIterator};
 use crate::array::{DictionaryArray, DictionaryKey, Offset};
 use csv_core::WriteResult;
-use std::any::Any;
 use std::fmt::{Debug, Write};
 
+/// How binary values are written to CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BinaryFormat {
+    /// The bytes are written as they are
+    #[default]
+    Raw,
+    /// The bytes are written in lowercase hexadecimal, e.g. `0aff`
+    Hex,
+    /// The bytes are written in standard base64 with padding, e.g. `Cv8=`
+    Base64,
+}
+
+/// Which CSV fields are quoted. Nulls are never quoted and empty values are always quoted, so
+/// that they can be told apart when [`SerializeOptions::null`] is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum QuoteStyle {
+    /// Every field is quoted
+    Always,
+    /// Fields are quoted when they contain the delimiter, the quote character or a line break
+    #[default]
+    Necessary,
+    /// Every field that is not a number (i.e. an integer, a float or a decimal) is quoted
+    NonNumeric,
+}
+
 /// Options to serialize logical types to CSV
 /// The default is to format times and dates as `chrono` crate formats them.
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -34,6 +58,14 @@ pub struct SerializeOptions {
     pub delimiter: u8,
     /// quoting character
     pub quote: u8,
+    /// which fields are quoted
+    pub quote_style: QuoteStyle,
+    /// written for null values
+    pub null: String,
+    /// written at the end of each row (including the header)
+    pub line_terminator: String,
+    /// used for [`DataType::Binary`], [`DataType::LargeBinary`] and [`DataType::FixedSizeBinary`]
+    pub binary_format: BinaryFormat,
 }
 
 impl Default for SerializeOptions {
@@ -46,6 +78,10 @@ impl Default for SerializeOptions {
             timestamp_format: None,
             delimiter: b',',
             quote: b'"',
+            quote_style: QuoteStyle::Necessary,
+            null: String::new(),
+            line_terminator: "\n".to_string(),
+            binary_format: BinaryFormat::Raw,
         }
     }
 }
@@ -213,70 +249,291 @@ fn timestamp_with_tz<'a>(
     }
 }
 
-fn new_utf8_serializer<'a, O: Offset>(
+fn utf8_serializer<'a, O: Offset>(
     array: &'a Utf8Array<O>,
-    options: &'a SerializeOptions,
 ) -> Box<dyn StreamingIterator<Item = [u8]> + 'a> {
-    let mut local_buf = vec![0u8; 64];
-    let mut ser_writer = csv_core::WriterBuilder::new()
-        .quote(options.quote)
-        .delimiter(options.delimiter)
-        .build();
+    Box::new(BufStreamingIterator::new(
+        array.iter(),
+        |x, buf| {
+            if let Some(x) = x {
+                buf.extend_from_slice(x.as_bytes());
+            }
+        },
+        vec![],
+    ))
+}
 
-    let resize = |local_buf: &mut Vec<u8>, additional: usize| {
-        local_buf.extend(std::iter::repeat(0u8).take(additional))
-    };
+const HEX: &[u8; 16] = b"0123456789abcdef";
 
+fn binary_serializer<'a, I: Iterator<Item = Option<&'a [u8]>> + 'a>(
+    iter: I,
+    format: BinaryFormat,
+) -> Box<dyn StreamingIterator<Item = [u8]> + 'a> {
     Box::new(BufStreamingIterator::new(
-        array.iter(),
+        iter,
         move |x, buf| {
-            match x {
-                // Empty strings are quoted.
-                // This will ensure a csv parser will not read them as missing
-                // in a delimited field
-                Some("") => buf.extend_from_slice(b"\"\""),
-                Some(s) => {
-                    if s.len() * 3 > local_buf.len() {
-                        resize(&mut local_buf, s.len() * 3)
-                    }
-                    match ser_writer.field(s.as_bytes(), &mut local_buf) {
-                        (WriteResult::InputEmpty, _, n_out) => {
-                            // the writer::delimiter call writes a maximum of 2 bytes
-                            if local_buf.len() - n_out < 2 {
-                                resize(&mut local_buf, 2);
-                            }
-                            match ser_writer.delimiter(&mut local_buf[n_out..]) {
-                                (WriteResult::InputEmpty, n_out_delimiter) => {
-                                    // we subtract 1 because we never want to include the delimiter written by csv-core
-                                    buf.extend_from_slice(
-                                        &local_buf[..n_out + n_out_delimiter - 1],
-                                    );
-                                }
-                                _ => unreachable!(),
-                            }
-                        }
-                        _ => unreachable!(),
+            if let Some(x) = x {
+                match format {
+                    BinaryFormat::Raw => buf.extend_from_slice(x),
+                    BinaryFormat::Hex => {
+                        buf.extend(x.iter().flat_map(|byte| {
+                            [HEX[(byte >> 4) as usize], HEX[(byte & 0xf) as usize]]
+                        }))
                     }
+                    BinaryFormat::Base64 => buf.extend_from_slice(base64::encode(x).as_bytes()),
                 }
-                _ => {}
             }
         },
         vec![],
     ))
 }
 
+/// Writes `value` with `scale` digits after the decimal point to `buf`, e.g. `-5` with a
+/// scale of 2 as `-0.05`
+fn write_decimal(value: i128, scale: usize, buf: &mut Vec<u8>) {
+    let mut digits = [0u8; u128::FORMATTED_SIZE_DECIMAL];
+    let digits = lexical_core::write(value.unsigned_abs(), &mut digits);
+    if value < 0 {
+        buf.push(b'-');
+    }
+    if scale == 0 {
+        buf.extend_from_slice(digits);
+    } else if digits.len() > scale {
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        buf.extend_from_slice(integer);
+        buf.push(b'.');
+        buf.extend_from_slice(fraction);
+    } else {
+        buf.extend_from_slice(b"0.");
+        buf.resize(buf.len() + scale - digits.len(), b'0');
+        buf.extend_from_slice(digits);
+    }
+}
+
+fn decimal_serializer<'a>(
+    array: &'a PrimitiveArray<i128>,
+    scale: usize,
+) -> Box<dyn StreamingIterator<Item = [u8]> + 'a> {
+    Box::new(BufStreamingIterator::new(
+        array.iter(),
+        move |x, buf| {
+            if let Some(x) = x {
+                write_decimal(*x, scale, buf)
+            }
+        },
+        vec![],
+    ))
+}
+
+/// Serializes a dictionary array by writing the (serialized) value of each key
+fn dictionary_serializer<'a, K: DictionaryKey>(
+    array: &'a dyn Array,
+    options: &'a SerializeOptions,
+) -> Result<Box<dyn StreamingIterator<Item = [u8]> + 'a>> {
+    let array = array.as_any().downcast_ref::<DictionaryArray<K>>().unwrap();
+
+    // each value is serialized once, and then copied for every key that points to it
+    let mut values = new_serializer(array.values().as_ref(), options)?;
+    let mut serialized = vec![];
+    let mut offsets = vec![0];
+    while let Some(value) = values.next() {
+        serialized.extend_from_slice(value);
+        offsets.push(serialized.len());
+    }
+
+    Ok(Box::new(BufStreamingIterator::new(
+        array.keys_iter(),
+        move |x, buf| match x {
+            Some(key) => buf.extend_from_slice(&serialized[offsets[key]..offsets[key + 1]]),
+            None => buf.extend_from_slice(options.null.as_bytes()),
+        },
+        vec![],
+    )))
+}
+
+/// Returns a [`csv_core::Writer`] that writes fields according to `options` and `style`
+fn field_writer(options: &SerializeOptions, style: csv_core::QuoteStyle) -> csv_core::Writer {
+    csv_core::WriterBuilder::new()
+        .quote(options.quote)
+        .delimiter(options.delimiter)
+        .quote_style(style)
+        .build()
+}
+
+/// Writes `value` to `buf` as a CSV field quoted by `writer`. Empty values are always quoted
+/// so that a csv parser does not read them as missing.
+fn write_field(writer: &mut csv_core::Writer, quote: u8, value: &[u8], buf: &mut Vec<u8>) {
+    if value.is_empty() {
+        buf.extend_from_slice(&[quote, quote]);
+        return;
+    }
+    let start = buf.len();
+    // in the worst case, every byte is an escaped quote, and `field` and `delimiter` write a
+    // quote each plus the delimiter
+    buf.resize(start + value.len() * 2 + 3, 0);
+    match writer.field(value, &mut buf[start..]) {
+        (WriteResult::InputEmpty, _, n_out) => {
+            match writer.delimiter(&mut buf[start + n_out..]) {
+                // we subtract 1 because we never want to include the delimiter written by csv-core
+                (WriteResult::InputEmpty, n_out_delimiter) => {
+                    buf.truncate(start + n_out + n_out_delimiter - 1)
+                }
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// The current item of a [`FieldSerializer`]
+enum Field {
+    Null,
+    Value,
+    Quoted,
+    Finished,
+}
+
+/// A [`StreamingIterator`] of the CSV fields of an array: nulls are written as
+/// [`SerializeOptions::null`] and the values of `values` are quoted by `writer`, when any.
+struct FieldSerializer<'a> {
+    values: Box<dyn StreamingIterator<Item = [u8]> + 'a>,
+    validity: Option<BitmapIter<'a>>,
+    writer: Option<csv_core::Writer>,
+    options: &'a SerializeOptions,
+    buffer: Vec<u8>,
+    field: Field,
+}
+
+impl<'a> StreamingIterator for FieldSerializer<'a> {
+    type Item = [u8];
+
+    #[inline]
+    fn advance(&mut self) {
+        self.values.advance();
+        let is_valid = self
+            .validity
+            .as_mut()
+            .and_then(|validity| validity.next())
+            .unwrap_or(true);
+        self.field = match (self.values.get(), &mut self.writer) {
+            (None, _) => Field::Finished,
+            (Some(_), _) if !is_valid => Field::Null,
+            (Some(value), Some(writer)) => {
+                self.buffer.clear();
+                write_field(writer, self.options.quote, value, &mut self.buffer);
+                Field::Quoted
+            }
+            (Some(_), None) => Field::Value,
+        };
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&Self::Item> {
+        match self.field {
+            Field::Null => Some(self.options.null.as_bytes()),
+            Field::Value => self.values.get(),
+            Field::Quoted => Some(&self.buffer),
+            Field::Finished => None,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+/// Writes the CSV header of `names` to `buf` according to `options`.
+pub(super) fn write_names<T: AsRef<str>>(
+    names: &[T],
+    options: &SerializeOptions,
+    buf: &mut Vec<u8>,
+) {
+    let style = match options.quote_style {
+        QuoteStyle::Necessary => csv_core::QuoteStyle::Necessary,
+        QuoteStyle::Always | QuoteStyle::NonNumeric => csv_core::QuoteStyle::Always,
+    };
+    let mut writer = field_writer(options, style);
+    for (index, name) in names.iter().enumerate() {
+        if index > 0 {
+            buf.push(options.delimiter);
+        }
+        write_field(&mut writer, options.quote, name.as_ref().as_bytes(), buf);
+    }
+    buf.extend_from_slice(options.line_terminator.as_bytes());
+}
+
 /// Returns a [`StreamingIterator`] that yields `&[u8]` serialized from `array` according to `options`.
 /// For numeric types, this serializes as usual. For dates, times and timestamps, it uses `options` to
+/// format them. Nulls are serialized as `options.null` and the fields are quoted according to
+/// `options.quote_style`.
 /// Supported types:
-/// * boolean
-/// * numeric types (i.e. floats, int, uint)
-/// * times and dates
-/// * naive timestamps (timestamps without timezone information)
+/// * null and boolean
+/// * numeric types (i.e. floats, int, uint) and decimals
+/// * times, dates and timestamps
+/// * strings
+/// * binary, formatted according to `options.binary_format`
+/// * dictionaries of any supported type, serialized as their values
+/// * lists and structs, serialized as JSON (requires the `io_json` feature)
 /// # Error
 /// This function errors if any of the logical types in `batch` is not supported.
 pub fn new_serializer<'a>(
     array: &'a dyn Array,
     options: &'a SerializeOptions,
+) -> Result<Box<dyn StreamingIterator<Item = [u8]> + 'a>> {
+    match array.data_type() {
+        DataType::Null => {
+            return Ok(Box::new(BufStreamingIterator::new(
+                0..array.len(),
+                move |_, buf| buf.extend_from_slice(options.null.as_bytes()),
+                vec![],
+            )))
+        }
+        DataType::Dictionary(key_type, _, _) => {
+            return match_integer_type!(key_type, |$T| {
+                dictionary_serializer::<$T>(array, options)
+            })
+        }
+        _ => {}
+    }
+
+    let is_numeric = matches!(
+        array.data_type(),
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Decimal(_, _)
+    );
+    let style = match (options.quote_style, is_numeric) {
+        (QuoteStyle::Always, _) | (QuoteStyle::NonNumeric, false) => {
+            Some(csv_core::QuoteStyle::Always)
+        }
+        (QuoteStyle::Necessary, false) => Some(csv_core::QuoteStyle::Necessary),
+        (_, true) => None,
+    };
+
+    Ok(Box::new(FieldSerializer {
+        values: new_value_serializer(array, options)?,
+        validity: array.validity().map(|validity| validity.iter()),
+        writer: style.map(|style| field_writer(options, style)),
+        options,
+        buffer: vec![],
+        field: Field::Finished,
+    }))
+}
+
+/// Returns a [`StreamingIterator`] that yields the serialized values of `array`, which are
+/// unspecified for nulls.
+fn new_value_serializer<'a>(
+    array: &'a dyn Array,
+    options: &'a SerializeOptions,
 ) -> Result<Box<dyn StreamingIterator<Item = [u8]> + 'a>> {
     Ok(match array.data_type() {
         DataType::Boolean => {
@@ -413,79 +670,37 @@ pub fn new_serializer<'a>(
         DataType::Float64 => {
             dyn_primitive!(f64, array)
         }
+        DataType::Decimal(_, scale) => {
+            let array = array.as_any().downcast_ref().unwrap();
+            decimal_serializer(array, *scale)
+        }
         DataType::Utf8 => {
             let array = array.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
-            new_utf8_serializer(array, options)
+            utf8_serializer(array)
         }
         DataType::LargeUtf8 => {
             let array = array.as_any().downcast_ref::<Utf8Array<i64>>().unwrap();
-            new_utf8_serializer(array, options)
+            utf8_serializer(array)
         }
         DataType::Binary => {
             let array = array.as_any().downcast_ref::<BinaryArray<i32>>().unwrap();
-            Box::new(BufStreamingIterator::new(
-                array.iter(),
-                |x, buf| {
-                    if let Some(x) = x {
-                        buf.extend_from_slice(x);
-                    }
-                },
-                vec![],
-            ))
+            binary_serializer(array.iter(), options.binary_format)
         }
         DataType::LargeBinary => {
             let array = array.as_any().downcast_ref::<BinaryArray<i64>>().unwrap();
-            Box::new(BufStreamingIterator::new(
-                array.iter(),
-                |x, buf| {
-                    if let Some(x) = x {
-                        buf.extend_from_slice(x);
-                    }
-                },
-                vec![],
-            ))
+            binary_serializer(array.iter(), options.binary_format)
         }
-        DataType::Dictionary(keys_dt, values_dt, _) => match &**values_dt {
-            DataType::LargeUtf8 => match *keys_dt {
-                IntegerType::UInt32 => serialize_utf8_dict::<u32, i64>(array.as_any()),
-                IntegerType::UInt64 => serialize_utf8_dict::<u64, i64>(array.as_any()),
-                _ => todo!(),
-            },
-            DataType::Utf8 => match *keys_dt {
-                IntegerType::UInt32 => serialize_utf8_dict::<u32, i32>(array.as_any()),
-                IntegerType::UInt64 => serialize_utf8_dict::<u64, i32>(array.as_any()),
-                _ => todo!(),
-            },
-            _ => {
-                panic!("only dictionary with string values are supported by csv writer")
-            }
-        },
-        dt => panic!("data type: {:?} not supported by csv writer", dt),
+        DataType::FixedSizeBinary(_) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryArray>()
+                .unwrap();
+            binary_serializer(array.iter(), options.binary_format)
+        }
+        #[cfg(feature = "io_json")]
+        DataType::List(_) | DataType::LargeList(_) | DataType::Struct(_) => {
+            crate::io::json::write::new_serializer(array)
+        }
+        dt => return Err(Error::NotYetImplemented(format!("Writing {:?} to CSV", dt))),
     })
 }
-
-/// Helper for serializing a dictonary array. The generic parameters are:
-/// - `K` for the type of the keys of the dictionary
-/// - `O` for the type of the offsets in the Utf8Array: {i32, i64}
-fn serialize_utf8_dict<'a, K: DictionaryKey, O: Offset>(
-    array: &'a dyn Any,
-) -> Box<dyn StreamingIterator<Item = [u8]> + 'a> {
-    let array = array.downcast_ref::<DictionaryArray<K>>().unwrap();
-    let values = array
-        .values()
-        .as_any()
-        .downcast_ref::<Utf8Array<O>>()
-        .unwrap();
-    Box::new(BufStreamingIterator::new(
-        array.keys_iter(),
-        move |x, buf| {
-            if let Some(i) = x {
-                if !values.is_null(i) {
-                    let val = values.value(i);
-                    buf.extend_from_slice(val.as_bytes());
-                }
-            }
-        },
-        vec![],
-    ))
-}
@@ -20,6 +20,9 @@ use super::ipc::{IpcField, IpcSchema};
 pub use super::ipc::write::default_ipc_fields;
 pub use crate::io::ipc::write::common::WriteOptions;
 
+mod stream;
+pub use stream::*;
+
 /// Serializes [`Chunk`] to a vector of [`FlightData`] representing the serialized dictionaries
 /// and a [`FlightData`] representing the batch.
 /// # Errors
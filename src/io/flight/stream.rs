@@ -0,0 +1,162 @@
+//! Conversion between [`Stream`]s of [`FlightData`] and [`Stream`]s of [`Chunk`]s.
+//!
+//! These are independent of the gRPC transport: any client or server that can produce or consume
+//! a [`Stream`] of [`FlightData`] (e.g. the streams of the `DoGet`, `DoPut` and `DoExchange`
+//! calls) can use them.
+use arrow_format::flight::data::FlightData;
+use futures::{Stream, StreamExt, TryStreamExt};
+
+use crate::{
+    array::Array,
+    chunk::Chunk,
+    datatypes::Schema,
+    error::{Error, Result},
+    io::ipc::write::common::{encode_chunk, DictionaryTracker},
+    io::ipc::write::encode_schema,
+    io::ipc::{IpcField, IpcSchema},
+};
+
+use super::{default_ipc_fields, deserialize_message, deserialize_schemas};
+use super::{Dictionaries, WriteOptions};
+
+/// A [`Chunk`] and the application-defined metadata sent with it in
+/// [`FlightData::app_metadata`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlightChunk {
+    /// The chunk
+    pub chunk: Chunk<Box<dyn Array>>,
+    /// The application-defined metadata of the chunk. Empty when there is none.
+    pub app_metadata: Vec<u8>,
+}
+
+impl From<Chunk<Box<dyn Array>>> for FlightChunk {
+    fn from(chunk: Chunk<Box<dyn Array>>) -> Self {
+        Self {
+            chunk,
+            app_metadata: vec![],
+        }
+    }
+}
+
+/// Serializes a [`FlightChunk`] into the dictionary batches it requires followed by its record
+/// batch
+fn serialize_chunk(
+    chunk: FlightChunk,
+    ipc_fields: &[IpcField],
+    dictionary_tracker: &mut DictionaryTracker,
+    options: &WriteOptions,
+) -> Result<Vec<FlightData>> {
+    let FlightChunk {
+        chunk,
+        app_metadata,
+    } = chunk;
+    if ipc_fields.len() != chunk.arrays().len() {
+        return Err(Error::InvalidArgumentError(
+            "The argument `ipc_fields` must be consistent with the chunks' columns".to_string(),
+        ));
+    }
+    let (dictionaries, batch) = encode_chunk(&chunk, ipc_fields, dictionary_tracker, options)?;
+
+    let mut messages = dictionaries
+        .into_iter()
+        .map(FlightData::from)
+        .collect::<Vec<_>>();
+    messages.push(FlightData {
+        app_metadata,
+        ..batch.into()
+    });
+    Ok(messages)
+}
+
+/// Serializes a [`Stream`] of [`FlightChunk`]s into a [`Stream`] of [`FlightData`].
+///
+/// The first message is the schema. Each chunk is then preceded by the dictionary batches
+/// required to decode it: dictionaries are only sent when they are new or changed since the
+/// previous chunk, as a delta when they were only appended to. The `app_metadata` of each
+/// chunk is sent with its record batch message.
+pub fn serialize_stream<'a, S>(
+    chunks: S,
+    schema: &Schema,
+    ipc_fields: Option<&[IpcField]>,
+    options: WriteOptions,
+) -> impl Stream<Item = Result<FlightData>> + 'a
+where
+    S: Stream<Item = Result<FlightChunk>> + 'a,
+{
+    let ipc_fields = ipc_fields
+        .map(|fields| fields.to_vec())
+        .unwrap_or_else(|| default_ipc_fields(&schema.fields));
+    let schema = FlightData {
+        data_header: encode_schema(schema, &ipc_fields, options.endianness),
+        ..Default::default()
+    };
+
+    let mut dictionary_tracker = DictionaryTracker {
+        dictionaries: Default::default(),
+        cannot_replace: false,
+    };
+
+    let batches = chunks
+        .map(move |chunk| {
+            let messages = chunk.and_then(|chunk| {
+                serialize_chunk(chunk, &ipc_fields, &mut dictionary_tracker, &options)
+            });
+            match messages {
+                Ok(messages) => messages.into_iter().map(Ok).collect::<Vec<_>>(),
+                Err(error) => vec![Err(error)],
+            }
+        })
+        .flat_map(futures::stream::iter);
+
+    futures::stream::once(async { Ok(schema) }).chain(batches)
+}
+
+/// Deserializes a [`Stream`] of [`FlightData`] into its [`Schema`], [`IpcSchema`] and a
+/// [`Stream`] of [`FlightChunk`]s.
+///
+/// The first message of `stream` must be the schema. Dictionary batches (including deltas and
+/// replacements) may be interleaved with the record batches; they are used to decode the
+/// record batches that follow them. The `app_metadata` of each record batch message is
+/// returned with its chunk.
+/// # Errors
+/// This function errors when the first message is not a schema or `stream` is empty.
+pub async fn deserialize_stream<'a, S>(
+    mut stream: S,
+) -> Result<(
+    Schema,
+    IpcSchema,
+    impl Stream<Item = Result<FlightChunk>> + 'a,
+)>
+where
+    S: Stream<Item = Result<FlightData>> + Unpin + 'a,
+{
+    let message = stream
+        .try_next()
+        .await?
+        .ok_or_else(|| Error::oos("A flight stream must start with a schema message"))?;
+    let (schema, ipc_schema) = deserialize_schemas(&message.data_header)?;
+
+    let state = (
+        stream,
+        schema.fields.clone(),
+        ipc_schema.clone(),
+        Dictionaries::default(),
+    );
+    let chunks = futures::stream::try_unfold(
+        state,
+        |(mut stream, fields, ipc_schema, mut dictionaries)| async move {
+            while let Some(message) = stream.try_next().await? {
+                let chunk = deserialize_message(&message, &fields, &ipc_schema, &mut dictionaries)?;
+                if let Some(chunk) = chunk {
+                    let chunk = FlightChunk {
+                        chunk,
+                        app_metadata: message.app_metadata,
+                    };
+                    return Ok(Some((chunk, (stream, fields, ipc_schema, dictionaries))));
+                }
+            }
+            Ok(None)
+        },
+    );
+    Ok((schema, ipc_schema, chunks))
+}
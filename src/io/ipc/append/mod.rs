@@ -7,7 +7,6 @@ use std::io::{Read, Seek, SeekFrom, Write};
 
 use crate::error::{Error, Result};
 
-use super::endianess::is_native_little_endian;
 use super::read::{self, FileMetadata};
 use super::write::common::DictionaryTracker;
 use super::write::writer::*;
@@ -17,20 +16,19 @@ impl<R: Read + Seek + Write> FileWriter<R> {
     /// Creates a new [`FileWriter`] from an existing file, seeking to the last message
     /// and appending new messages afterwards. Users call `finish` to write the footer (with both)
     /// the existing and appended messages on it.
+    ///
+    /// The new messages are written in the byte order of the file, regardless of
+    /// `options.endianness`.
     /// # Error
     /// This function errors iff:
-    /// * the file's endianess is not the native endianess (not yet supported)
     /// * the file is not a valid Arrow IPC file
     pub fn try_from_file(
         mut writer: R,
         metadata: FileMetadata,
-        options: WriteOptions,
+        mut options: WriteOptions,
     ) -> Result<FileWriter<R>> {
-        if metadata.ipc_schema.is_little_endian != is_native_little_endian() {
-            return Err(Error::nyi(
-                "Appending to a file of a non-native endianess is still not supported",
-            ));
-        }
+        options.endianness =
+            Endianness::from_is_little_endian(metadata.ipc_schema.is_little_endian);
 
         let dictionaries =
             read::read_file_dictionaries(&mut writer, &metadata, &mut Default::default())?;
@@ -0,0 +1,380 @@
+//! Compaction and concatenation of Arrow IPC files
+use std::io::{Read, Seek, Write};
+
+use ahash::AHashMap;
+
+use crate::array::{
+    growable::make_growable, Array, BinaryArray, DictionaryArray, DictionaryKey,
+    FixedSizeBinaryArray, FixedSizeListArray, ListArray, MapArray, Offset, PrimitiveArray,
+    StructArray, Utf8Array,
+};
+use crate::chunk::Chunk;
+use crate::datatypes::PhysicalType;
+use crate::error::{Error, Result};
+
+use super::read::{read_batch, read_file_dictionaries, read_file_metadata, Dictionaries};
+use super::write::{FileWriter, WriteOptions};
+use super::IpcField;
+
+/// Options of [`compact_files`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CompactOptions {
+    /// The number of rows up to which consecutive record batches are coalesced into a single
+    /// record batch. Record batches with more rows are written as they are. When `None`,
+    /// record batches are not coalesced.
+    pub max_rows: Option<usize>,
+    /// The options of the written file, such as its compression and byte order
+    pub write_options: WriteOptions,
+}
+
+/// Concatenates the Arrow IPC files in `readers` into a single file written to `writer`,
+/// returning `writer`.
+///
+/// The written file has the schema, schema metadata and dictionary ids of the first file, and
+/// contains the record batches of all files in order, coalesced according to
+/// `options.max_rows`. The dictionaries of each dictionary id are unified across files: the
+/// written dictionary starts with the dictionary of the first file and is extended (via delta
+/// dictionaries) with the dictionaries of the other files that are not a prefix of it.
+/// # Errors
+/// This function errors when:
+/// * `readers` is empty
+/// * the fields of a file differ from the fields of the first file
+/// * the keys of a unified dictionary overflow its key type
+/// * a file is not a valid Arrow IPC file or `writer` errors
+pub fn compact_files<R: Read + Seek, W: Write>(
+    readers: Vec<R>,
+    writer: W,
+    options: CompactOptions,
+) -> Result<W> {
+    let files = readers
+        .into_iter()
+        .map(|mut reader| read_file_metadata(&mut reader).map(|metadata| (reader, metadata)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let (_, first) = files.first().ok_or_else(|| {
+        Error::InvalidArgumentError("Compacting IPC files requires at least one file".to_string())
+    })?;
+    if let Some(index) = files
+        .iter()
+        .position(|(_, metadata)| metadata.schema.fields != first.schema.fields)
+    {
+        return Err(Error::InvalidArgumentError(format!(
+            "The fields of the IPC file {} differ from the fields of the first file",
+            index
+        )));
+    }
+    let schema = first.schema.clone();
+    let ipc_fields = first.ipc_schema.fields.clone();
+
+    let mut writer = FileWriter::try_new(
+        writer,
+        &schema,
+        Some(ipc_fields.clone()),
+        options.write_options,
+    )?;
+
+    let mut dictionaries = Dictionaries::default();
+    let mut pending = vec![];
+    let mut pending_rows = 0;
+    let mut message_scratch = vec![];
+    let mut data_scratch = vec![];
+    for (mut reader, metadata) in files {
+        let file_dictionaries = read_file_dictionaries(&mut reader, &metadata, &mut data_scratch)?;
+        for index in 0..metadata.blocks.len() {
+            let chunk = read_batch(
+                &mut reader,
+                &file_dictionaries,
+                &metadata,
+                None,
+                None,
+                index,
+                &mut message_scratch,
+                &mut data_scratch,
+            )?;
+            let chunk = unify_chunk(&chunk, &ipc_fields, &mut dictionaries)?;
+
+            let max_rows = if let Some(max_rows) = options.max_rows {
+                max_rows
+            } else {
+                writer.write(&chunk, None)?;
+                continue;
+            };
+            if !pending.is_empty() && pending_rows + chunk.len() > max_rows {
+                let chunk = concatenate_chunks(&pending, &ipc_fields, &mut dictionaries)?;
+                writer.write(&chunk, None)?;
+                pending.clear();
+                pending_rows = 0;
+            }
+            pending_rows += chunk.len();
+            pending.push(chunk);
+        }
+    }
+    if !pending.is_empty() {
+        let chunk = concatenate_chunks(&pending, &ipc_fields, &mut dictionaries)?;
+        writer.write(&chunk, None)?;
+    }
+
+    writer.finish()?;
+    Ok(writer.into_inner())
+}
+
+/// Concatenates `chunks` into a single [`Chunk`]. The dictionaries of `chunks` must have been
+/// unified against `dictionaries`.
+fn concatenate_chunks(
+    chunks: &[Chunk<Box<dyn Array>>],
+    ipc_fields: &[IpcField],
+    dictionaries: &mut Dictionaries,
+) -> Result<Chunk<Box<dyn Array>>> {
+    if chunks.len() == 1 {
+        // the dictionaries may have been extended after the chunk was unified
+        return unify_chunk(&chunks[0], ipc_fields, dictionaries);
+    }
+
+    let length = chunks.iter().map(|chunk| chunk.len()).sum();
+    let arrays = (0..ipc_fields.len())
+        .map(|column| {
+            let arrays = chunks
+                .iter()
+                .map(|chunk| chunk.arrays()[column].as_ref())
+                .collect::<Vec<_>>();
+            let mut growable = make_growable(&arrays, false, length);
+            for (index, array) in arrays.iter().enumerate() {
+                growable.extend(index, 0, array.len());
+            }
+            growable.as_box()
+        })
+        .collect();
+    // the concatenated dictionaries repeat the unified values of each chunk: unifying them
+    // again deduplicates their values
+    unify_chunk(&Chunk::try_new(arrays)?, ipc_fields, dictionaries)
+}
+
+fn unify_chunk(
+    chunk: &Chunk<Box<dyn Array>>,
+    ipc_fields: &[IpcField],
+    dictionaries: &mut Dictionaries,
+) -> Result<Chunk<Box<dyn Array>>> {
+    let arrays = chunk
+        .arrays()
+        .iter()
+        .zip(ipc_fields.iter())
+        .map(|(array, field)| unify(array.as_ref(), field, dictionaries))
+        .collect::<Result<Vec<_>>>()?;
+    Chunk::try_new(arrays)
+}
+
+/// Returns `array` with the values of each of its dictionaries replaced by the unified values
+/// of its dictionary id in `dictionaries`.
+fn unify(
+    array: &dyn Array,
+    field: &IpcField,
+    dictionaries: &mut Dictionaries,
+) -> Result<Box<dyn Array>> {
+    use PhysicalType::*;
+    match array.data_type().to_physical_type() {
+        Dictionary(key_type) => match_integer_type!(key_type, |$T| {
+            let dict_id = field.dictionary_id.ok_or_else(|| {
+                Error::InvalidArgumentError("Dictionaries must have an associated id".to_string())
+            })?;
+            let array = array.as_any().downcast_ref::<DictionaryArray<$T>>().unwrap();
+            unify_dictionary(array, dict_id, dictionaries).map(|array| array.boxed())
+        }),
+        Struct => {
+            let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+            let values = array
+                .values()
+                .iter()
+                .zip(field.fields.iter())
+                .map(|(values, field)| unify(values.as_ref(), field, dictionaries))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(
+                StructArray::new(array.data_type().clone(), values, array.validity().cloned())
+                    .boxed(),
+            )
+        }
+        List => unify_list::<i32>(array, field, dictionaries),
+        LargeList => unify_list::<i64>(array, field, dictionaries),
+        FixedSizeList => {
+            let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+            let values = unify(array.values().as_ref(), &field.fields[0], dictionaries)?;
+            Ok(FixedSizeListArray::new(
+                array.data_type().clone(),
+                values,
+                array.validity().cloned(),
+            )
+            .boxed())
+        }
+        Map => {
+            let array = array.as_any().downcast_ref::<MapArray>().unwrap();
+            let values = unify(array.field().as_ref(), &field.fields[0], dictionaries)?;
+            Ok(MapArray::new(
+                array.data_type().clone(),
+                array.offsets().clone(),
+                values,
+                array.validity().cloned(),
+            )
+            .boxed())
+        }
+        _ => Ok(array.to_boxed()),
+    }
+}
+
+fn unify_list<O: Offset>(
+    array: &dyn Array,
+    field: &IpcField,
+    dictionaries: &mut Dictionaries,
+) -> Result<Box<dyn Array>> {
+    let array = array.as_any().downcast_ref::<ListArray<O>>().unwrap();
+    let values = unify(array.values().as_ref(), &field.fields[0], dictionaries)?;
+    Ok(ListArray::<O>::new(
+        array.data_type().clone(),
+        array.offsets().clone(),
+        values,
+        array.validity().cloned(),
+    )
+    .boxed())
+}
+
+/// Returns the bytes of each value of `array`, used to find equal dictionary values, or `None`
+/// when values of the type of `array` are not compared.
+fn value_bytes(array: &dyn Array) -> Option<Vec<Option<&[u8]>>> {
+    use PhysicalType::*;
+    Some(match array.data_type().to_physical_type() {
+        Primitive(primitive) => with_match_primitive_type!(primitive, |$T| {
+            let array = array.as_any().downcast_ref::<PrimitiveArray<$T>>().unwrap();
+            let values: &[u8] = bytemuck::cast_slice(array.values().as_slice());
+            values
+                .chunks_exact(std::mem::size_of::<$T>())
+                .zip(array.iter())
+                .map(|(bytes, value)| value.map(|_| bytes))
+                .collect()
+        }),
+        Utf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+            array.iter().map(|x| x.map(|x| x.as_bytes())).collect()
+        }
+        LargeUtf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i64>>().unwrap();
+            array.iter().map(|x| x.map(|x| x.as_bytes())).collect()
+        }
+        Binary => {
+            let array = array.as_any().downcast_ref::<BinaryArray<i32>>().unwrap();
+            array.iter().collect()
+        }
+        LargeBinary => {
+            let array = array.as_any().downcast_ref::<BinaryArray<i64>>().unwrap();
+            array.iter().collect()
+        }
+        FixedSizeBinary => {
+            let array = array
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryArray>()
+                .unwrap();
+            array.iter().collect()
+        }
+        _ => return None,
+    })
+}
+
+/// Unifies the values of `array` with the values of `dict_id` in `dictionaries`:
+/// * when the values of `array` are a prefix of the unified values, its keys are used as is
+/// * otherwise, the values of `array` that are not in the unified values are appended to them
+///   and the keys of `array` are remapped to the unified values.
+///
+/// Nested values are not compared: when the unified values are a prefix of the values of
+/// `array` they are replaced by them, and otherwise the values of `array` are appended.
+fn unify_dictionary<K: DictionaryKey>(
+    array: &DictionaryArray<K>,
+    dict_id: i64,
+    dictionaries: &mut Dictionaries,
+) -> Result<DictionaryArray<K>> {
+    let values = array.values();
+    let unified = if let Some(unified) = dictionaries.get(&dict_id) {
+        unified
+    } else {
+        dictionaries.insert(dict_id, values.clone());
+        return Ok(array.clone());
+    };
+
+    if values.len() <= unified.len() && values.as_ref() == unified.slice(0, values.len()).as_ref() {
+        return DictionaryArray::try_new(
+            array.data_type().clone(),
+            array.keys().clone(),
+            unified.clone(),
+        );
+    }
+
+    // the index in the unified values of each of the values of `array`, and the values of
+    // `array` appended to the unified values
+    let (positions, new_values) =
+        match (value_bytes(unified.as_ref()), value_bytes(values.as_ref())) {
+            (Some(unified_bytes), Some(values_bytes)) => {
+                let mut map = AHashMap::<Option<&[u8]>, usize>::with_capacity(unified_bytes.len());
+                for (index, bytes) in unified_bytes.into_iter().enumerate() {
+                    map.entry(bytes).or_insert(index);
+                }
+                let mut new_values = vec![];
+                let positions = values_bytes
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, bytes)| {
+                        let next = unified.len() + new_values.len();
+                        let position = *map.entry(bytes).or_insert(next);
+                        if position == next {
+                            new_values.push(index);
+                        }
+                        position
+                    })
+                    .collect::<Vec<_>>();
+                (positions, new_values)
+            }
+            _ if unified.len() < values.len()
+                && unified.as_ref() == values.slice(0, unified.len()).as_ref() =>
+            {
+                dictionaries.insert(dict_id, values.clone());
+                return Ok(array.clone());
+            }
+            _ => (
+                (unified.len()..unified.len() + values.len()).collect(),
+                (0..values.len()).collect(),
+            ),
+        };
+
+    let keys = array
+        .keys()
+        .iter()
+        .map(|key| {
+            key.map(|key| {
+                let position = (*key)
+                    .try_into()
+                    .ok()
+                    .and_then(|key: usize| positions.get(key))
+                    .ok_or_else(|| {
+                        Error::OutOfSpec(format!(
+                            "The dictionary key {:?} is out of bounds of the {} dictionary values",
+                            key,
+                            values.len()
+                        ))
+                    })?;
+                K::try_from(*position).map_err(|_| Error::Overflow)
+            })
+            .transpose()
+        })
+        .collect::<Result<PrimitiveArray<K>>>()?;
+
+    let unified = {
+        let mut growable = make_growable(
+            &[unified.as_ref(), values.as_ref()],
+            false,
+            unified.len() + new_values.len(),
+        );
+        growable.extend(0, 0, unified.len());
+        for index in new_values {
+            growable.extend(1, index, 1);
+        }
+        growable.as_box()
+    };
+    dictionaries.insert(dict_id, unified.clone());
+
+    DictionaryArray::try_new(array.data_type().clone(), keys, unified)
+}
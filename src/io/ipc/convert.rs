@@ -0,0 +1,35 @@
+//! Conversion of Arrow IPC files between byte orders
+use std::io::{Read, Seek, Write};
+
+use crate::error::Result;
+
+use super::read::{read_file_metadata, FileReader};
+use super::write::{FileWriter, WriteOptions};
+
+/// Rewrites the Arrow IPC file in `reader` into `writer` with the byte order and compression of
+/// `options`, returning `writer`.
+///
+/// The schema, its metadata and the dictionary ids of the file are preserved, and each record
+/// batch of the file is written as a record batch. Buffers are byte-swapped when the byte
+/// order of the file differs from `options.endianness`.
+/// # Errors
+/// This function errors when the file is not a valid Arrow IPC file or `writer` errors.
+pub fn convert_file<R: Read + Seek, W: Write>(
+    mut reader: R,
+    writer: W,
+    options: WriteOptions,
+) -> Result<W> {
+    let metadata = read_file_metadata(&mut reader)?;
+
+    let mut writer = FileWriter::try_new(
+        writer,
+        &metadata.schema,
+        Some(metadata.ipc_schema.fields.clone()),
+        options,
+    )?;
+    for chunk in FileReader::new(reader, metadata, None, None) {
+        writer.write(&chunk?, None)?;
+    }
+    writer.finish()?;
+    Ok(writer.into_inner())
+}
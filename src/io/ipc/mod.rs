@@ -41,7 +41,10 @@
 //! let x_coord = Field::new("x", DataType::Int32, false);
 //! let y_coord = Field::new("y", DataType::Int32, false);
 //! let schema = Schema::from(vec![x_coord, y_coord]);
-//! let options = WriteOptions {compression: None};
+//! let options = WriteOptions {
+//!     compression: None,
+//!     ..Default::default()
+//! };
 //! let mut writer = FileWriter::try_new(file, &schema, None, options)?;
 //!
 //! // Setup the data
@@ -73,10 +76,12 @@
 //! [2](https://github.com/jorgecarleitao/arrow2/blob/main/examples/ipc_file_write.rs),
 //! [3](https://github.com/jorgecarleitao/arrow2/tree/main/examples/ipc_pyarrow)).
 
-mod compression;
-mod endianess;
+pub(crate) mod compression;
+pub(crate) mod endianess;
 
 pub mod append;
+pub mod compact;
+pub mod convert;
 pub mod read;
 pub mod write;
 
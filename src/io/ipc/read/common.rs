@@ -4,6 +4,7 @@ use std::io::{Read, Seek};
 
 use arrow_format;
 
+use crate::array::growable::make_growable;
 use crate::array::*;
 use crate::chunk::Chunk;
 use crate::datatypes::{DataType, Field};
@@ -239,14 +240,9 @@ pub fn read_dictionary<R: Read + Seek>(
     file_size: u64,
     scratch: &mut Vec<u8>,
 ) -> Result<()> {
-    if batch
+    let is_delta = batch
         .is_delta()
-        .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferIsDelta(err)))?
-    {
-        return Err(Error::NotYetImplemented(
-            "delta dictionary batches not supported".to_string(),
-        ));
-    }
+        .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferIsDelta(err)))?;
 
     let id = batch
         .id()
@@ -287,8 +283,37 @@ pub fn read_dictionary<R: Read + Seek>(
         scratch,
     )?;
 
-    dictionaries.insert(id, chunk.into_arrays().pop().unwrap());
+    insert_dictionary(
+        dictionaries,
+        id,
+        chunk.into_arrays().pop().unwrap(),
+        is_delta,
+    )
+}
 
+/// Inserts the dictionary `values` of `id` in `dictionaries`. When `is_delta`, `values` are
+/// appended to the dictionary of `id` instead.
+pub(crate) fn insert_dictionary(
+    dictionaries: &mut Dictionaries,
+    id: i64,
+    values: Box<dyn Array>,
+    is_delta: bool,
+) -> Result<()> {
+    let values = if is_delta {
+        let previous = dictionaries.get(&id).ok_or_else(|| {
+            Error::from(OutOfSpecKind::MissingDictionaryForDelta { requested_id: id })
+        })?;
+        let previous = previous.as_ref();
+        let delta = values.as_ref();
+
+        let mut growable = make_growable(&[previous, delta], false, previous.len() + delta.len());
+        growable.extend(0, 0, previous.len());
+        growable.extend(1, 0, delta.len());
+        growable.as_box()
+    } else {
+        values
+    };
+    dictionaries.insert(id, values);
     Ok(())
 }
 
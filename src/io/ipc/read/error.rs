@@ -46,6 +46,8 @@ pub enum OutOfSpecKind {
     InvalidFlatbufferNodes(arrow_format::ipc::planus::Error),
     /// The message's body length is an invalid flatbuffer
     InvalidFlatbufferBodyLength(arrow_format::ipc::planus::Error),
+    /// The record batch's length is an invalid flatbuffer
+    InvalidFlatbufferLength(arrow_format::ipc::planus::Error),
     /// The message does not contain data
     MissingData,
     /// The message's data is an invalid flatbuffer
@@ -95,6 +97,11 @@ pub enum OutOfSpecKind {
         /// The requested dictionary id
         requested_id: i64,
     },
+    /// A delta dictionary batch whose dictionary was not read before
+    MissingDictionaryForDelta {
+        /// The requested dictionary id
+        requested_id: i64,
+    },
 }
 
 impl From<OutOfSpecKind> for Error {
@@ -1,8 +1,9 @@
 use ahash::AHashMap;
 use std::convert::TryInto;
 use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
 
-use crate::array::Array;
+use crate::array::{growable::make_growable, new_empty_array, Array};
 use crate::chunk::Chunk;
 use crate::datatypes::Schema;
 use crate::error::{Error, Result};
@@ -36,6 +37,145 @@ pub struct FileMetadata {
     pub size: u64,
 }
 
+/// The number of rows of each block of an Arrow IPC file, which [`FileMetadata::read_rows`]
+/// uses to find the blocks of a range of rows. It is created by [`FileMetadata::row_index`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowIndex {
+    row_counts: Vec<usize>,
+}
+
+impl RowIndex {
+    /// Returns the number of rows of each block of the file
+    pub fn row_counts(&self) -> &[usize] {
+        &self.row_counts
+    }
+
+    /// Returns the number of rows of the file
+    pub fn num_rows(&self) -> usize {
+        self.row_counts.iter().sum()
+    }
+}
+
+impl FileMetadata {
+    /// Returns the [`RowIndex`] of the file, reading the message header of every block from
+    /// `reader` (but none of their bodies).
+    pub fn row_index<R: Read + Seek>(&self, reader: &mut R) -> Result<RowIndex> {
+        let mut message_scratch = Default::default();
+        let row_counts = self
+            .blocks
+            .iter()
+            .map(|block| {
+                read_block_message(reader, block, &mut message_scratch)?;
+                let message = arrow_format::ipc::MessageRef::read_as_root(message_scratch.as_ref())
+                    .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferMessage(err)))?;
+                get_record_batch(message)?
+                    .length()
+                    .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferLength(err)))?
+                    .try_into()
+                    .map_err(|_| Error::from(OutOfSpecKind::UnexpectedNegativeInteger))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(RowIndex { row_counts })
+    }
+
+    /// Reads the rows `range` of the file, counted from its first row, into a single [`Chunk`].
+    ///
+    /// Only the blocks overlapping with `range` are read. Their rows outside of `range` are
+    /// sliced out and, when `range` spans more than one block, the blocks are concatenated.
+    /// `index` must be the [`RowIndex`] of the file.
+    /// # Errors
+    /// This function errors when `range` is out of bounds of the file or when the file is
+    /// invalid.
+    /// # Panics
+    /// This function panics iff the `projection` is not strictly increasing.
+    #[allow(clippy::too_many_arguments)]
+    pub fn read_rows<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        dictionaries: &Dictionaries,
+        index: &RowIndex,
+        projection: Option<&[usize]>,
+        range: Range<usize>,
+        message_scratch: &mut Vec<u8>,
+        data_scratch: &mut Vec<u8>,
+    ) -> Result<Chunk<Box<dyn Array>>> {
+        if index.row_counts.len() != self.blocks.len() {
+            return Err(Error::InvalidArgumentError(
+                "the row index is not the index of the file".to_string(),
+            ));
+        }
+        let num_rows = index.num_rows();
+        if range.start > range.end || range.end > num_rows {
+            return Err(Error::InvalidArgumentError(format!(
+                "the range of rows {:?} is out of bounds of a file with {} rows",
+                range, num_rows
+            )));
+        }
+
+        let mut chunks = vec![];
+        let mut block_start = 0;
+        for (block, &block_rows) in index.row_counts.iter().enumerate() {
+            let block_end = block_start + block_rows;
+            if block_start < range.end && range.start < block_end {
+                let offset = range.start.saturating_sub(block_start);
+                let limit = range.end.min(block_end) - block_start;
+                let chunk = read_batch(
+                    reader,
+                    dictionaries,
+                    self,
+                    projection,
+                    Some(limit),
+                    block,
+                    message_scratch,
+                    data_scratch,
+                )?;
+                let arrays = chunk
+                    .into_arrays()
+                    .into_iter()
+                    .map(|array| array.slice(offset, limit - offset))
+                    .collect();
+                chunks.push(Chunk::new(arrays));
+            }
+            block_start = block_end;
+        }
+
+        match chunks.len() {
+            0 => {
+                let fields = if let Some(projection) = projection {
+                    projection
+                        .iter()
+                        .map(|&index| &self.schema.fields[index])
+                        .collect::<Vec<_>>()
+                } else {
+                    self.schema.fields.iter().collect()
+                };
+                Chunk::try_new(
+                    fields
+                        .into_iter()
+                        .map(|field| new_empty_array(field.data_type.clone()))
+                        .collect(),
+                )
+            }
+            1 => Ok(chunks.pop().unwrap()),
+            _ => Chunk::try_new(
+                (0..chunks[0].arrays().len())
+                    .map(|column| {
+                        let arrays = chunks
+                            .iter()
+                            .map(|chunk| chunk.arrays()[column].as_ref())
+                            .collect::<Vec<_>>();
+                        let mut growable = make_growable(&arrays, false, range.len());
+                        for (index, array) in arrays.iter().enumerate() {
+                            growable.extend(index, 0, array.len());
+                        }
+                        growable.as_box()
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
 fn read_dictionary_message<R: Read + Seek>(
     reader: &mut R,
     offset: u64,
@@ -247,26 +387,12 @@ pub(crate) fn get_record_batch(
     }
 }
 
-/// Reads the record batch at position `index` from the reader.
-///
-/// This function is useful for random access to the file. For example, if
-/// you have indexed the file somewhere else, this allows pruning
-/// certain parts of the file.
-/// # Panics
-/// This function panics iff `index >= metadata.blocks.len()`
-#[allow(clippy::too_many_arguments)]
-pub fn read_batch<R: Read + Seek>(
+/// Reads the message of `block` into `message_scratch`, returning the offset of its body
+fn read_block_message<R: Read + Seek>(
     reader: &mut R,
-    dictionaries: &Dictionaries,
-    metadata: &FileMetadata,
-    projection: Option<&[usize]>,
-    limit: Option<usize>,
-    index: usize,
+    block: &arrow_format::ipc::Block,
     message_scratch: &mut Vec<u8>,
-    data_scratch: &mut Vec<u8>,
-) -> Result<Chunk<Box<dyn Array>>> {
-    let block = metadata.blocks[index];
-
+) -> Result<u64> {
     let offset: u64 = block
         .offset
         .try_into()
@@ -296,6 +422,30 @@ pub fn read_batch<R: Read + Seek>(
         .take(meta_len as u64)
         .read_to_end(message_scratch)?;
 
+    Ok(offset + length)
+}
+
+/// Reads the record batch at position `index` from the reader.
+///
+/// This function is useful for random access to the file. For example, if
+/// you have indexed the file somewhere else, this allows pruning
+/// certain parts of the file.
+/// # Panics
+/// This function panics iff `index >= metadata.blocks.len()`
+#[allow(clippy::too_many_arguments)]
+pub fn read_batch<R: Read + Seek>(
+    reader: &mut R,
+    dictionaries: &Dictionaries,
+    metadata: &FileMetadata,
+    projection: Option<&[usize]>,
+    limit: Option<usize>,
+    index: usize,
+    message_scratch: &mut Vec<u8>,
+    data_scratch: &mut Vec<u8>,
+) -> Result<Chunk<Box<dyn Array>>> {
+    let block = metadata.blocks[index];
+    let offset = read_block_message(reader, &block, message_scratch)?;
+
     let message = arrow_format::ipc::MessageRef::read_as_root(message_scratch.as_ref())
         .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferMessage(err)))?;
 
@@ -312,7 +462,7 @@ pub fn read_batch<R: Read + Seek>(
             .version()
             .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferVersion(err)))?,
         reader,
-        offset + length,
+        offset,
         metadata.size,
         data_scratch,
     )
@@ -1,10 +1,12 @@
 //! Async reader for Arrow IPC files
 use ahash::AHashMap;
 use std::io::SeekFrom;
+use std::ops::Range;
 
 use arrow_format::ipc::{planus::ReadAsRoot, Block, MessageHeaderRef};
 use futures::{
-    stream::BoxStream, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, Stream, StreamExt,
+    future::BoxFuture, stream::BoxStream, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, Stream,
+    StreamExt,
 };
 
 use crate::array::*;
@@ -14,7 +16,7 @@ use crate::error::{Error, Result};
 use crate::io::ipc::{IpcSchema, ARROW_MAGIC, CONTINUATION_MARKER};
 
 use super::common::{apply_projection, prepare_projection, read_dictionary, read_record_batch};
-use super::file::{deserialize_footer, get_record_batch};
+use super::file::{deserialize_footer, get_dictionary_batch, get_record_batch};
 use super::Dictionaries;
 use super::FileMetadata;
 use super::OutOfSpecKind;
@@ -40,7 +42,46 @@ impl<'a> FileStream<'a> {
     where
         R: AsyncRead + AsyncSeek + Unpin + Send + 'a,
     {
-        let (projection, schema) = if let Some(projection) = projection {
+        let (projection, schema) = Self::prepare_projection(&metadata, projection);
+
+        let stream = Self::stream(reader, None, metadata.clone(), projection, limit);
+        Self {
+            stream,
+            metadata,
+            schema,
+        }
+    }
+
+    /// Create a new IPC file reader that reads each block of the file (with its message) with
+    /// a single range read from `reader`.
+    ///
+    /// Together with [`read_file_metadata_ranged`], this allows reading (projected) record
+    /// batches from a file in an object store without fetching the whole file.
+    pub fn new_ranged<R>(
+        reader: R,
+        metadata: FileMetadata,
+        projection: Option<Vec<usize>>,
+        limit: Option<usize>,
+    ) -> Self
+    where
+        R: RangeRead + 'a,
+    {
+        let (projection, schema) = Self::prepare_projection(&metadata, projection);
+
+        let stream = Self::ranged_stream(reader, metadata.clone(), projection, limit);
+        Self {
+            stream,
+            metadata,
+            schema,
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn prepare_projection(
+        metadata: &FileMetadata,
+        projection: Option<Vec<usize>>,
+    ) -> (Option<(Vec<usize>, AHashMap<usize, usize>)>, Option<Schema>) {
+        if let Some(projection) = projection {
             let (p, h, fields) = prepare_projection(&metadata.schema.fields, projection);
             let schema = Schema {
                 fields,
@@ -49,13 +90,6 @@ impl<'a> FileStream<'a> {
             (Some((p, h)), Some(schema))
         } else {
             (None, None)
-        };
-
-        let stream = Self::stream(reader, None, metadata.clone(), projection, limit);
-        Self {
-            stream,
-            metadata,
-            schema,
         }
     }
 
@@ -113,6 +147,48 @@ impl<'a> FileStream<'a> {
         }
         .boxed()
     }
+
+    fn ranged_stream<R>(
+        reader: R,
+        metadata: FileMetadata,
+        projection: Option<(Vec<usize>, AHashMap<usize, usize>)>,
+        limit: Option<usize>,
+    ) -> BoxStream<'a, Result<Chunk<Box<dyn Array>>>>
+    where
+        R: RangeRead + 'a,
+    {
+        async_stream::try_stream! {
+            let mut scratch = Default::default();
+            let dictionaries = read_file_dictionaries_ranged(&reader, &metadata, &mut scratch).await?;
+
+            let mut remaining = limit.unwrap_or(usize::MAX);
+            for block in 0..metadata.blocks.len() {
+                if remaining == 0 {
+                    break;
+                }
+                let chunk = read_batch_ranged(
+                    &reader,
+                    &dictionaries,
+                    &metadata,
+                    projection.as_ref().map(|x| x.0.as_ref()),
+                    Some(remaining),
+                    block,
+                    &mut scratch,
+                ).await?;
+                remaining -= chunk.len();
+
+                let chunk = if let Some((_, map)) = &projection {
+                    // re-order according to projection
+                    apply_projection(chunk, map)
+                } else {
+                    chunk
+                };
+
+                yield chunk;
+            }
+        }
+        .boxed()
+    }
 }
 
 impl<'a> Stream for FileStream<'a> {
@@ -350,3 +426,187 @@ async fn cached_read_dictionaries<R: AsyncRead + AsyncSeek + Unpin>(
     };
     Ok(())
 }
+
+/// A source of bytes that is read by ranges, such as an object in an object store.
+pub trait RangeRead: Send + Sync {
+    /// Returns the size of the source in bytes
+    fn size(&self) -> BoxFuture<'_, Result<u64>>;
+
+    /// Returns the bytes of the source in `range`
+    fn read_range(&self, range: Range<u64>) -> BoxFuture<'_, Result<Vec<u8>>>;
+}
+
+impl<T: RangeRead + ?Sized> RangeRead for &T {
+    fn size(&self) -> BoxFuture<'_, Result<u64>> {
+        (**self).size()
+    }
+
+    fn read_range(&self, range: Range<u64>) -> BoxFuture<'_, Result<Vec<u8>>> {
+        (**self).read_range(range)
+    }
+}
+
+/// The number of bytes read from the end of the file to get its footer. Footers that are
+/// larger are read with a second range read.
+const FOOTER_PREFETCH: u64 = 64 * 1024;
+
+async fn read_exact_range<R: RangeRead + ?Sized>(reader: &R, range: Range<u64>) -> Result<Vec<u8>> {
+    let length = range.end - range.start;
+    let bytes = reader.read_range(range).await?;
+    if bytes.len() as u64 != length {
+        return Err(Error::oos(format!(
+            "A range read of {} bytes returned {} bytes",
+            length,
+            bytes.len()
+        )));
+    }
+    Ok(bytes)
+}
+
+/// Read the metadata from an IPC file starting from its footer: the last bytes of the file
+/// are read with a single range read, followed by a second one when the footer is larger.
+pub async fn read_file_metadata_ranged<R: RangeRead + ?Sized>(reader: &R) -> Result<FileMetadata> {
+    let size = reader.size().await?;
+    if size < 10 {
+        return Err(Error::from(OutOfSpecKind::InvalidFooter));
+    }
+
+    let tail_start = size.saturating_sub(FOOTER_PREFETCH);
+    let tail = read_exact_range(reader, tail_start..size).await?;
+
+    let footer = &tail[tail.len() - 10..];
+    if footer[4..] != ARROW_MAGIC {
+        return Err(Error::from(OutOfSpecKind::InvalidFooter));
+    }
+    let footer_len: u64 = i32::from_le_bytes(footer[..4].try_into().unwrap())
+        .try_into()
+        .map_err(|_| Error::from(OutOfSpecKind::NegativeFooterLength))?;
+
+    let footer_start = (size - 10)
+        .checked_sub(footer_len)
+        .ok_or_else(|| Error::from(OutOfSpecKind::InvalidFooter))?;
+    if footer_start >= tail_start {
+        let footer_start = (footer_start - tail_start) as usize;
+        deserialize_footer(&tail[footer_start..tail.len() - 10], size)
+    } else {
+        let footer = read_exact_range(reader, footer_start..size - 10).await?;
+        deserialize_footer(&footer, size)
+    }
+}
+
+/// Reads the message and body of `block` with a single range read, returning the bytes of
+/// the block and the ranges of its message and body in them
+async fn read_block_ranged<R: RangeRead + ?Sized>(
+    reader: &R,
+    block: &Block,
+) -> Result<(Vec<u8>, Range<usize>, Range<usize>)> {
+    let offset: u64 = block
+        .offset
+        .try_into()
+        .map_err(|_| Error::from(OutOfSpecKind::UnexpectedNegativeInteger))?;
+    let meta_data_length: usize = block
+        .meta_data_length
+        .try_into()
+        .map_err(|_| Error::from(OutOfSpecKind::UnexpectedNegativeInteger))?;
+    let body_length: usize = block
+        .body_length
+        .try_into()
+        .map_err(|_| Error::from(OutOfSpecKind::UnexpectedNegativeInteger))?;
+
+    let end = offset + (meta_data_length + body_length) as u64;
+    let bytes = read_exact_range(reader, offset..end).await?;
+
+    let start = if bytes.get(..4) == Some(&CONTINUATION_MARKER) {
+        4
+    } else {
+        0
+    };
+    let message_fits = |end: usize| {
+        if end > meta_data_length {
+            Err(Error::oos(
+                "The message of a block must fit in the block's metadata length",
+            ))
+        } else {
+            Ok(())
+        }
+    };
+    message_fits(start + 4)?;
+    let meta_len: usize = i32::from_le_bytes(bytes[start..start + 4].try_into().unwrap())
+        .try_into()
+        .map_err(|_| Error::from(OutOfSpecKind::UnexpectedNegativeInteger))?;
+    let message = start + 4..start + 4 + meta_len;
+    message_fits(message.end)?;
+    Ok((
+        bytes,
+        message,
+        meta_data_length..meta_data_length + body_length,
+    ))
+}
+
+/// Reads all the dictionaries of an IPC file, with one range read per dictionary batch.
+pub async fn read_file_dictionaries_ranged<R: RangeRead + ?Sized>(
+    reader: &R,
+    metadata: &FileMetadata,
+    scratch: &mut Vec<u8>,
+) -> Result<Dictionaries> {
+    let mut dictionaries = Default::default();
+    for block in metadata.dictionaries.as_deref().unwrap_or_default() {
+        let (bytes, message, body) = read_block_ranged(reader, block).await?;
+
+        let message = arrow_format::ipc::MessageRef::read_as_root(&bytes[message])
+            .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferMessage(err)))?;
+        let batch = get_dictionary_batch(&message)?;
+
+        let body = &bytes[body];
+        read_dictionary(
+            batch,
+            &metadata.schema.fields,
+            &metadata.ipc_schema,
+            &mut dictionaries,
+            &mut std::io::Cursor::new(body),
+            0,
+            body.len() as u64,
+            scratch,
+        )?;
+    }
+    Ok(dictionaries)
+}
+
+/// Reads the record batch at position `index` of an IPC file with a single range read.
+///
+/// This is the ranged counterpart of [`read_batch`](super::read_batch): only the block of the
+/// record batch is read from `reader`.
+/// # Panics
+/// This function panics iff `index >= metadata.blocks.len()`
+pub async fn read_batch_ranged<R: RangeRead + ?Sized>(
+    reader: &R,
+    dictionaries: &Dictionaries,
+    metadata: &FileMetadata,
+    projection: Option<&[usize]>,
+    limit: Option<usize>,
+    index: usize,
+    scratch: &mut Vec<u8>,
+) -> Result<Chunk<Box<dyn Array>>> {
+    let (bytes, message, body) = read_block_ranged(reader, &metadata.blocks[index]).await?;
+
+    let message = arrow_format::ipc::MessageRef::read_as_root(&bytes[message])
+        .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferMessage(err)))?;
+    let batch = get_record_batch(message)?;
+
+    let body = &bytes[body];
+    read_record_batch(
+        batch,
+        &metadata.schema.fields,
+        &metadata.ipc_schema,
+        projection,
+        limit,
+        dictionaries,
+        message
+            .version()
+            .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferVersion(err)))?,
+        &mut std::io::Cursor::new(body),
+        0,
+        body.len() as u64,
+        scratch,
+    )
+}
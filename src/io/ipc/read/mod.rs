@@ -28,10 +28,11 @@ pub mod stream_async;
 #[cfg_attr(docsrs, doc(cfg(feature = "io_ipc_read_async")))]
 pub mod file_async;
 
-pub(crate) use common::first_dict_field;
+pub(crate) use common::{first_dict_field, insert_dictionary};
 #[cfg(feature = "io_flight")]
 pub(crate) use common::{read_dictionary, read_record_batch};
-pub use file::{read_batch, read_file_dictionaries, read_file_metadata, FileMetadata};
+pub(crate) use deserialize::skip;
+pub use file::{read_batch, read_file_dictionaries, read_file_metadata, FileMetadata, RowIndex};
 pub use reader::FileReader;
 pub(crate) use schema::deserialize_schema;
 pub use stream::{read_stream_metadata, StreamMetadata, StreamReader, StreamState};
@@ -9,37 +9,33 @@ use super::super::compression;
 use super::super::endianess::is_native_little_endian;
 use super::{Compression, IpcBuffer, Node, OutOfSpecKind};
 
+/// Fills `buffer` from `bytes`, whose values are in the byte order of `is_little_endian`.
+fn swap_into<T: NativeType>(bytes: &[u8], buffer: &mut [T], is_little_endian: bool) {
+    let chunks = bytes.chunks_exact(std::mem::size_of::<T>());
+    buffer.iter_mut().zip(chunks).for_each(|(slot, chunk)| {
+        let a: T::Bytes = match chunk.try_into() {
+            Ok(a) => a,
+            Err(_) => unreachable!(),
+        };
+        *slot = if is_little_endian {
+            T::from_le_bytes(a)
+        } else {
+            T::from_be_bytes(a)
+        };
+    });
+}
+
 fn read_swapped<T: NativeType, R: Read + Seek>(
     reader: &mut R,
     length: usize,
-    buffer: &mut Vec<T>,
+    buffer: &mut [T],
     is_little_endian: bool,
 ) -> Result<()> {
     // slow case where we must reverse bits
     let mut slice = vec![0u8; length * std::mem::size_of::<T>()];
     reader.read_exact(&mut slice)?;
 
-    let chunks = slice.chunks_exact(std::mem::size_of::<T>());
-    if !is_little_endian {
-        // machine is little endian, file is big endian
-        buffer
-            .as_mut_slice()
-            .iter_mut()
-            .zip(chunks)
-            .try_for_each(|(slot, chunk)| {
-                let a: T::Bytes = match chunk.try_into() {
-                    Ok(a) => a,
-                    Err(_) => unreachable!(),
-                };
-                *slot = T::from_be_bytes(a);
-                Result::Ok(())
-            })?;
-    } else {
-        // machine is big endian, file is little endian
-        return Err(Error::NotYetImplemented(
-            "Reading little endian files from big endian machines".to_string(),
-        ));
-    }
+    swap_into(&slice, buffer, is_little_endian);
     Ok(())
 }
 
@@ -95,12 +91,6 @@ fn read_compressed_buffer<T: NativeType, R: Read + Seek>(
     compression: Compression,
     scratch: &mut Vec<u8>,
 ) -> Result<Vec<T>> {
-    if is_little_endian != is_native_little_endian() {
-        return Err(Error::NotYetImplemented(
-            "Reading compressed and big endian IPC".to_string(),
-        ));
-    }
-
     // it is undefined behavior to call read_exact on un-initialized, https://doc.rust-lang.org/std/io/trait.Read.html#tymethod.read
     // see also https://github.com/MaikKlein/ash/issues/354#issue-781730580
     let mut buffer = vec![T::default(); length];
@@ -113,19 +103,25 @@ fn read_compressed_buffer<T: NativeType, R: Read + Seek>(
         .take(buffer_length as u64)
         .read_to_end(scratch)?;
 
-    let out_slice = bytemuck::cast_slice_mut(&mut buffer);
-
     let compression = compression
         .codec()
         .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferCompression(err)))?;
 
-    match compression {
+    let decompress = |out_slice: &mut [u8]| match compression {
         arrow_format::ipc::CompressionType::Lz4Frame => {
-            compression::decompress_lz4(&scratch[8..], out_slice)?;
+            compression::decompress_lz4(&scratch[8..], out_slice)
         }
         arrow_format::ipc::CompressionType::Zstd => {
-            compression::decompress_zstd(&scratch[8..], out_slice)?;
+            compression::decompress_zstd(&scratch[8..], out_slice)
         }
+    };
+
+    if is_native_little_endian() == is_little_endian {
+        decompress(bytemuck::cast_slice_mut(&mut buffer))?;
+    } else {
+        let mut slice = vec![0u8; length * std::mem::size_of::<T>()];
+        decompress(&mut slice)?;
+        swap_into(&slice, &mut buffer, is_little_endian);
     }
     Ok(buffer)
 }
@@ -230,7 +230,12 @@ impl<'a, R: AsyncRead + Unpin + Send> Stream for AsyncStreamReader<'a, R> {
                     me.future = Some(Box::pin(maybe_next(state)));
                     Poll::Ready(Some(Ok(batch)))
                 }
-                Poll::Ready(Ok(Some(StreamState::Waiting(_)))) => Poll::Pending,
+                Poll::Ready(Ok(Some(StreamState::Waiting(state)))) => {
+                    // a dictionary batch was read: read the next message
+                    me.future = Some(Box::pin(maybe_next(state)));
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
                 Poll::Ready(Err(err)) => {
                     me.future = None;
                     Poll::Ready(Some(Err(err)))
@@ -10,7 +10,7 @@ use crate::io::ipc::endianess::is_native_little_endian;
 use crate::io::ipc::read::Dictionaries;
 
 use super::super::IpcField;
-use super::{write, write_dictionary};
+use super::write;
 
 /// Compression codec
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -21,12 +21,49 @@ pub enum Compression {
     ZSTD,
 }
 
+/// The byte order of the buffers of an IPC file or stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// Little endian
+    Little,
+    /// Big endian
+    Big,
+}
+
+impl Endianness {
+    /// The byte order of the machine this code is running on
+    pub fn native() -> Self {
+        Self::from_is_little_endian(is_native_little_endian())
+    }
+
+    pub(crate) fn from_is_little_endian(is_little_endian: bool) -> Self {
+        if is_little_endian {
+            Self::Little
+        } else {
+            Self::Big
+        }
+    }
+
+    pub(crate) fn is_little_endian(&self) -> bool {
+        *self == Self::Little
+    }
+}
+
+impl Default for Endianness {
+    fn default() -> Self {
+        Self::native()
+    }
+}
+
 /// Options declaring the behaviour of writing to IPC
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct WriteOptions {
     /// Whether the buffers should be compressed and which codec to use.
     /// Note: to use compression the crate must be compiled with feature `io_ipc_compression`.
     pub compression: Option<Compression>,
+    /// The byte order of the written buffers. Buffers are byte-swapped when it differs from
+    /// the native byte order. Defaults to the native byte order.
+    pub endianness: Endianness,
 }
 
 fn encode_dictionary(
@@ -44,7 +81,7 @@ fn encode_dictionary(
             let dict_id = field.dictionary_id
                 .ok_or_else(|| Error::InvalidArgumentError("Dictionaries must have an associated id".to_string()))?;
 
-            let emit = dictionary_tracker.insert(dict_id, array)?;
+            let update = dictionary_tracker.insert(dict_id, array)?;
 
             let array = array.as_any().downcast_ref::<DictionaryArray<$T>>().unwrap();
             let values = array.values();
@@ -55,13 +92,25 @@ fn encode_dictionary(
                 encoded_dictionaries
             )?;
 
-            if emit {
-                encoded_dictionaries.push(dictionary_batch_to_bytes::<$T>(
-                    dict_id,
-                    array,
-                    options,
-                    is_native_little_endian(),
-                ));
+            match update {
+                DictionaryUpdate::Unchanged => {}
+                DictionaryUpdate::Full => {
+                    encoded_dictionaries.push(dictionary_batch_to_bytes(
+                        dict_id,
+                        values.as_ref(),
+                        false,
+                        options,
+                    ));
+                }
+                DictionaryUpdate::Delta(offset) => {
+                    let delta = values.slice(offset, values.len() - offset);
+                    encoded_dictionaries.push(dictionary_batch_to_bytes(
+                        dict_id,
+                        delta.as_ref(),
+                        true,
+                        options,
+                    ));
+                }
             };
             Ok(())
         }),
@@ -225,7 +274,7 @@ fn columns_to_bytes(columns: &Chunk<Box<dyn Array>>, options: &WriteOptions) ->
             &mut arrow_data,
             &mut nodes,
             &mut offset,
-            is_native_little_endian(),
+            options.endianness.is_little_endian(),
             options.compression,
         )
     }
@@ -256,27 +305,27 @@ fn columns_to_bytes(columns: &Chunk<Box<dyn Array>>, options: &WriteOptions) ->
 }
 
 /// Write dictionary values into two sets of bytes, one for the header (ipc::Schema::Message) and the
-/// other for the data
-fn dictionary_batch_to_bytes<K: DictionaryKey>(
+/// other for the data. When `is_delta`, `values` are appended to the dictionary `dict_id` by readers.
+fn dictionary_batch_to_bytes(
     dict_id: i64,
-    array: &DictionaryArray<K>,
+    values: &dyn Array,
+    is_delta: bool,
     options: &WriteOptions,
-    is_little_endian: bool,
 ) -> EncodedData {
     let mut nodes: Vec<arrow_format::ipc::FieldNode> = vec![];
     let mut buffers: Vec<arrow_format::ipc::Buffer> = vec![];
     let mut arrow_data: Vec<u8> = vec![];
 
-    let length = write_dictionary(
-        array,
+    write(
+        values,
         &mut buffers,
         &mut arrow_data,
         &mut nodes,
         &mut 0,
-        is_little_endian,
+        options.endianness.is_little_endian(),
         options.compression,
-        false,
     );
+    let length = values.len();
 
     let compression = serialize_compression(options.compression);
 
@@ -291,7 +340,7 @@ fn dictionary_batch_to_bytes<K: DictionaryKey>(
                     buffers: Some(buffers),
                     compression,
                 })),
-                is_delta: false,
+                is_delta,
             },
         ))),
         body_length: arrow_data.len() as i64,
@@ -307,9 +356,22 @@ fn dictionary_batch_to_bytes<K: DictionaryKey>(
     }
 }
 
+/// The dictionary batch to emit for a dictionary, as returned by [`DictionaryTracker::insert`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictionaryUpdate {
+    /// The dictionary was already emitted: nothing needs to be emitted
+    Unchanged,
+    /// The dictionary was never emitted or replaces the previous one: all its values are emitted
+    Full,
+    /// The dictionary appends values to the previous one: its values from this offset are
+    /// emitted as a delta dictionary batch
+    Delta(usize),
+}
+
 /// Keeps track of dictionaries that have been written, to avoid emitting the same dictionary
-/// multiple times. Can optionally error if an update to an existing dictionary is attempted, which
-/// isn't allowed in the `FileWriter`.
+/// multiple times. Dictionaries whose values extend the values previously written are emitted
+/// as deltas. Can optionally error if any other update to an existing dictionary is attempted,
+/// which isn't allowed in the `FileWriter`.
 pub struct DictionaryTracker {
     pub dictionaries: Dictionaries,
     pub cannot_replace: bool,
@@ -318,14 +380,15 @@ pub struct DictionaryTracker {
 impl DictionaryTracker {
     /// Keep track of the dictionary with the given ID and values. Behavior:
     ///
-    /// * If this ID has been written already and has the same data, return `Ok(false)` to indicate
-    ///   that the dictionary was not actually inserted (because it's already been seen).
+    /// * If this ID has been written already and has the same data, return
+    ///   [`DictionaryUpdate::Unchanged`].
+    /// * If this ID has been written already and its values are a prefix of the new values,
+    ///   return [`DictionaryUpdate::Delta`] with the number of values already written.
     /// * If this ID has been written already but with different data, and this tracker is
     ///   configured to return an error, return an error.
     /// * If the tracker has not been configured to error on replacement or this dictionary
-    ///   has never been seen before, return `Ok(true)` to indicate that the dictionary was just
-    ///   inserted.
-    pub fn insert(&mut self, dict_id: i64, array: &dyn Array) -> Result<bool> {
+    ///   has never been seen before, return [`DictionaryUpdate::Full`].
+    pub fn insert(&mut self, dict_id: i64, array: &dyn Array) -> Result<DictionaryUpdate> {
         let values = match array.data_type() {
             DataType::Dictionary(key_type, _, _) => {
                 match_integer_type!(key_type, |$T| {
@@ -340,22 +403,30 @@ impl DictionaryTracker {
         };
 
         // If a dictionary with this id was already emitted, check if it was the same.
-        if let Some(last) = self.dictionaries.get(&dict_id) {
+        let update = if let Some(last) = self.dictionaries.get(&dict_id) {
             if last.as_ref() == values.as_ref() {
                 // Same dictionary values => no need to emit it again
-                return Ok(false);
+                return Ok(DictionaryUpdate::Unchanged);
+            } else if last.len() < values.len()
+                && last.as_ref() == values.slice(0, last.len()).as_ref()
+            {
+                DictionaryUpdate::Delta(last.len())
             } else if self.cannot_replace {
                 return Err(Error::InvalidArgumentError(
                     "Dictionary replacement detected when writing IPC file format. \
                      Arrow IPC files only support a single dictionary for a given field \
-                     across all batches."
+                     across all batches, extended by delta dictionaries."
                         .to_string(),
                 ));
+            } else {
+                DictionaryUpdate::Full
             }
+        } else {
+            DictionaryUpdate::Full
         };
 
         self.dictionaries.insert(dict_id, values.clone());
-        Ok(true)
+        Ok(update)
     }
 }
 
@@ -7,8 +7,8 @@ use futures::{future::BoxFuture, AsyncWrite, AsyncWriteExt, FutureExt, Sink};
 
 use super::common::{encode_chunk, DictionaryTracker, EncodedData, WriteOptions};
 use super::common_async::{write_continuation, write_message};
-use super::schema::serialize_schema;
-use super::{default_ipc_fields, schema_to_bytes, Record};
+use super::schema::{encode_schema, serialize_schema};
+use super::{default_ipc_fields, Record};
 use crate::datatypes::*;
 use crate::error::{Error, Result};
 use crate::io::ipc::{IpcField, ARROW_MAGIC};
@@ -84,7 +84,7 @@ where
     ) -> Self {
         let fields = ipc_fields.unwrap_or_else(|| default_ipc_fields(&schema.fields));
         let encoded = EncodedData {
-            ipc_message: schema_to_bytes(schema, &fields),
+            ipc_message: encode_schema(schema, &fields, options.endianness),
             arrow_data: vec![],
         };
         let task = Some(Self::start(writer, encoded).boxed());
@@ -230,7 +230,8 @@ where
         match futures::ready!(this.poll_write(cx)) {
             Ok(()) => {
                 if let Some(writer) = this.writer.take() {
-                    let schema = serialize_schema(&this.schema, &this.fields);
+                    let schema =
+                        serialize_schema(&this.schema, &this.fields, this.options.endianness);
                     let footer = Footer {
                         version: MetadataVersion::V5,
                         schema: Some(Box::new(schema)),
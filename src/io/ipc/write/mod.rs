@@ -5,10 +5,10 @@ mod serialize;
 mod stream;
 pub(crate) mod writer;
 
-pub use common::{Compression, Record, WriteOptions};
+pub use common::{Compression, Endianness, Record, WriteOptions};
+pub(crate) use schema::encode_schema;
 pub use schema::schema_to_bytes;
 pub use serialize::write;
-pub(self) use serialize::write_dictionary;
 pub use stream::StreamWriter;
 pub use writer::FileWriter;
 
@@ -3,13 +3,24 @@ use arrow_format::ipc::planus::Builder;
 use crate::datatypes::{
     DataType, Field, IntegerType, IntervalUnit, Metadata, Schema, TimeUnit, UnionMode,
 };
-use crate::io::ipc::endianess::is_native_little_endian;
 
 use super::super::IpcField;
+use super::Endianness;
 
 /// Converts a [Schema] and [IpcField]s to a flatbuffers-encoded [arrow_format::ipc::Message].
+/// The message declares the native byte order.
 pub fn schema_to_bytes(schema: &Schema, ipc_fields: &[IpcField]) -> Vec<u8> {
-    let schema = serialize_schema(schema, ipc_fields);
+    encode_schema(schema, ipc_fields, Endianness::native())
+}
+
+/// Converts a [Schema] and [IpcField]s to a flatbuffers-encoded [arrow_format::ipc::Message]
+/// declaring the byte order `endianness`.
+pub(crate) fn encode_schema(
+    schema: &Schema,
+    ipc_fields: &[IpcField],
+    endianness: Endianness,
+) -> Vec<u8> {
+    let schema = serialize_schema(schema, ipc_fields, endianness);
 
     let message = arrow_format::ipc::Message {
         version: arrow_format::ipc::MetadataVersion::V5,
@@ -22,11 +33,14 @@ pub fn schema_to_bytes(schema: &Schema, ipc_fields: &[IpcField]) -> Vec<u8> {
     footer_data.to_vec()
 }
 
-pub fn serialize_schema(schema: &Schema, ipc_fields: &[IpcField]) -> arrow_format::ipc::Schema {
-    let endianness = if is_native_little_endian() {
-        arrow_format::ipc::Endianness::Little
-    } else {
-        arrow_format::ipc::Endianness::Big
+pub fn serialize_schema(
+    schema: &Schema,
+    ipc_fields: &[IpcField],
+    endianness: Endianness,
+) -> arrow_format::ipc::Schema {
+    let endianness = match endianness {
+        Endianness::Little => arrow_format::ipc::Endianness::Little,
+        Endianness::Big => arrow_format::ipc::Endianness::Big,
     };
 
     let fields = schema
@@ -720,7 +720,12 @@ fn _write_compressed_buffer<T: NativeType>(
             }
         }
     } else {
-        todo!()
+        _write_compressed_buffer_from_iter(
+            buffer.iter().copied(),
+            arrow_data,
+            is_little_endian,
+            compression,
+        )
     }
 }
 
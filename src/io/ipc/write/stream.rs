@@ -8,7 +8,8 @@ use std::io::Write;
 use super::super::IpcField;
 use super::common::{encode_chunk, DictionaryTracker, EncodedData, WriteOptions};
 use super::common_sync::{write_continuation, write_message};
-use super::{default_ipc_fields, schema_to_bytes};
+use super::default_ipc_fields;
+use super::schema::encode_schema;
 
 use crate::array::Array;
 use crate::chunk::Chunk;
@@ -20,6 +21,11 @@ use crate::error::{Error, Result};
 /// The data written by this writer must be read in order. To signal that no more
 /// data is arriving through the stream call [`self.finish()`](StreamWriter::finish);
 ///
+/// The dictionaries of dictionary-encoded columns are written before the first chunk that
+/// uses them. When the dictionary of a column changes, it is written again: as a delta
+/// when its values extend the previous values, and as a replacement otherwise. Readers
+/// use the latest dictionary for all later chunks.
+///
 /// For a usage walkthrough consult [this example](https://github.com/jorgecarleitao/arrow2/tree/main/examples/ipc_pyarrow).
 pub struct StreamWriter<W: Write> {
     /// The object to write to
@@ -59,7 +65,11 @@ impl<W: Write> StreamWriter<W> {
         });
 
         let encoded_message = EncodedData {
-            ipc_message: schema_to_bytes(schema, self.ipc_fields.as_ref().unwrap()),
+            ipc_message: encode_schema(
+                schema,
+                self.ipc_fields.as_ref().unwrap(),
+                self.write_options.endianness,
+            ),
             arrow_data: vec![],
         };
         write_message(&mut self.writer, encoded_message)?;
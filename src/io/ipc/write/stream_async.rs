@@ -5,10 +5,11 @@ use std::{pin::Pin, task::Poll};
 use futures::{future::BoxFuture, AsyncWrite, FutureExt, Sink};
 
 use super::super::IpcField;
-pub use super::common::WriteOptions;
 use super::common::{encode_chunk, DictionaryTracker, EncodedData};
+pub use super::common::{Endianness, WriteOptions};
 use super::common_async::{write_continuation, write_message};
-use super::{default_ipc_fields, schema_to_bytes, Record};
+use super::schema::encode_schema;
+use super::{default_ipc_fields, Record};
 
 use crate::datatypes::*;
 use crate::error::{Error, Result};
@@ -16,6 +17,8 @@ use crate::error::{Error, Result};
 /// A sink that writes array [`chunks`](crate::chunk::Chunk) as an IPC stream.
 ///
 /// The stream header is automatically written before writing the first chunk.
+/// Dictionaries are written as in [`StreamWriter`](super::StreamWriter): whenever
+/// they change, as a delta or as a replacement.
 ///
 /// # Examples
 ///
@@ -67,7 +70,12 @@ where
         write_options: WriteOptions,
     ) -> Self {
         let fields = ipc_fields.unwrap_or_else(|| default_ipc_fields(&schema.fields));
-        let task = Some(Self::start(writer, schema, &fields[..]));
+        let task = Some(Self::start(
+            writer,
+            schema,
+            &fields[..],
+            write_options.endianness,
+        ));
         Self {
             writer: None,
             task,
@@ -84,9 +92,10 @@ where
         mut writer: W,
         schema: &Schema,
         ipc_fields: &[IpcField],
+        endianness: Endianness,
     ) -> BoxFuture<'a, Result<Option<W>>> {
         let message = EncodedData {
-            ipc_message: schema_to_bytes(schema, ipc_fields),
+            ipc_message: encode_schema(schema, ipc_fields, endianness),
             arrow_data: vec![],
         };
         async move {
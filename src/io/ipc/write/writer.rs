@@ -7,7 +7,7 @@ use super::{
     super::ARROW_MAGIC,
     common::{encode_chunk, DictionaryTracker, EncodedData, WriteOptions},
     common_sync::{write_continuation, write_message},
-    default_ipc_fields, schema, schema_to_bytes,
+    default_ipc_fields, schema,
 };
 
 use crate::array::Array;
@@ -105,7 +105,11 @@ impl<W: Write> FileWriter<W> {
         // write the schema, set the written bytes to the schema
 
         let encoded_message = EncodedData {
-            ipc_message: schema_to_bytes(&self.schema, &self.ipc_fields),
+            ipc_message: schema::encode_schema(
+                &self.schema,
+                &self.ipc_fields,
+                self.options.endianness,
+            ),
             arrow_data: vec![],
         };
 
@@ -176,7 +180,8 @@ impl<W: Write> FileWriter<W> {
         // write EOS
         write_continuation(&mut self.writer, 0)?;
 
-        let schema = schema::serialize_schema(&self.schema, &self.ipc_fields);
+        let schema =
+            schema::serialize_schema(&self.schema, &self.ipc_fields, self.options.endianness);
 
         let root = arrow_format::ipc::Footer {
             version: arrow_format::ipc::MetadataVersion::V5,
@@ -1,5 +1,6 @@
-//! APIs to read from [ORC format](https://orc.apache.org).
+//! APIs to read from and write to [ORC format](https://orc.apache.org).
 pub mod read;
+pub mod write;
 
 pub use orc_format as format;
 
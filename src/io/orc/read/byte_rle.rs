@@ -0,0 +1,44 @@
+//! Decoders of ORC's byte and boolean run-length encodings.
+//!
+//! `orc_format::read::decode::BooleanIter` is not used because, as of `orc-format` 0.3, it
+//! decodes a run of `n` bytes into `n + 1` bytes and does not advance past a run of
+//! literals, and `BooleanRleRunIter` does not expose the number of literals of a run.
+use std::io::Read;
+
+use crate::bitmap::MutableBitmap;
+use crate::error::Error;
+
+/// Decodes `length` bytes encoded with ORC's byte run-length encoding
+pub fn read_byte_rle<R: Read>(reader: &mut R, length: usize) -> Result<Vec<u8>, Error> {
+    let mut values = Vec::with_capacity(length);
+    while values.len() < length {
+        let mut header = [0u8];
+        reader.read_exact(&mut header)?;
+        let header = header[0] as i8;
+        if header < 0 {
+            // a sequence of `-header` literals
+            let start = values.len();
+            values.resize(start + (-(header as i16)) as usize, 0);
+            reader.read_exact(&mut values[start..])?;
+        } else {
+            // a run of `header + 3` repeated values
+            let mut value = [0u8];
+            reader.read_exact(&mut value)?;
+            values.extend(std::iter::repeat_n(value[0], header as usize + 3));
+        }
+    }
+    // the last run may contain more values than requested
+    values.truncate(length);
+    Ok(values)
+}
+
+/// Decodes `length` booleans encoded with ORC's boolean run-length encoding, where bits
+/// are packed from the most significant bit of each byte.
+pub fn read_boolean_rle<R: Read>(reader: &mut R, length: usize) -> Result<MutableBitmap, Error> {
+    let bytes = read_byte_rle(reader, length.div_ceil(8))?;
+    let mut values = MutableBitmap::with_capacity(length);
+    for i in 0..length {
+        values.push(bytes[i / 8] & (128 >> (i % 8)) != 0);
+    }
+    Ok(values)
+}
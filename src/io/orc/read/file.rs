@@ -0,0 +1,218 @@
+use std::io::{Read, Seek};
+
+use orc_format::proto::ColumnStatistics;
+use orc_format::read::{read_stripe_column, read_stripe_footer, FileMetadata};
+
+use crate::array::Array;
+use crate::chunk::Chunk;
+use crate::datatypes::Schema;
+use crate::error::{Error, Result};
+
+use super::{deserialize_nested, n_columns};
+
+/// Returns the id of the first ORC column of each field of `schema`, matched by name
+/// against the fields of the root struct of the file.
+fn field_columns(metadata: &FileMetadata, schema: &Schema) -> Result<Vec<u32>> {
+    let root = metadata
+        .footer
+        .types
+        .first()
+        .ok_or_else(|| Error::ExternalFormat("ORC file without types".to_string()))?;
+    schema
+        .fields
+        .iter()
+        .map(|field| {
+            root.field_names
+                .iter()
+                .position(|name| name == &field.name)
+                .and_then(|i| root.subtypes.get(i).copied())
+                .ok_or_else(|| {
+                    Error::InvalidArgumentError(format!(
+                        "Field \"{}\" is not part of the ORC file",
+                        field.name
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Returns the indices of the stripes of the file for which `predicate` returns `true`.
+///
+/// `predicate` is called for every stripe with the statistics of each field of `schema` in
+/// that stripe, in the order of the fields. For nested fields, these are the statistics of its
+/// outermost column. Stripes are never skipped when the file does not contain stripe
+/// statistics.
+/// # Errors
+/// Errors if a field of `schema` is not part of the file.
+pub fn filter_stripes<F>(
+    metadata: &FileMetadata,
+    schema: &Schema,
+    mut predicate: F,
+) -> Result<Vec<usize>>
+where
+    F: FnMut(&[&ColumnStatistics]) -> bool,
+{
+    let num_stripes = metadata.footer.stripes.len();
+    if metadata.metadata.stripe_stats.len() != num_stripes {
+        return Ok((0..num_stripes).collect());
+    }
+    let columns = field_columns(metadata, schema)?;
+
+    let mut statistics = vec![];
+    Ok(metadata
+        .metadata
+        .stripe_stats
+        .iter()
+        .enumerate()
+        .filter_map(|(stripe, stripe_stats)| {
+            statistics.clear();
+            statistics.extend(
+                columns
+                    .iter()
+                    .filter_map(|column| stripe_stats.col_stats.get(*column as usize)),
+            );
+            // keep stripes with incomplete statistics
+            if statistics.len() != columns.len() || predicate(&statistics) {
+                Some(stripe)
+            } else {
+                None
+            }
+        })
+        .collect())
+}
+
+/// An iterator of [`Chunk`]s, one per stripe of an ORC file.
+///
+/// Only the columns of the fields of the schema are read, and stripes can be selected upfront,
+/// e.g. via [`filter_stripes`].
+/// # Implementation
+/// This iterator is single threaded on both IO-bounded and CPU-bounded tasks, and mixes them.
+pub struct FileReader<R: Read + Seek> {
+    reader: R,
+    metadata: FileMetadata,
+    schema: Schema,
+    /// the id of the first column of each field of `schema`
+    columns: Vec<u32>,
+    stripes: std::vec::IntoIter<usize>,
+    remaining_rows: usize,
+    scratch: Vec<u8>,
+}
+
+impl<R: Read + Seek> FileReader<R> {
+    /// Returns a new [`FileReader`] reading the fields of `schema` from `stripes` (all stripes
+    /// when `None`) of the file, up to `limit` rows.
+    ///
+    /// `schema` is usually the schema inferred via [`super::infer_schema`] with some fields
+    /// removed, to read a projection of the file.
+    /// # Errors
+    /// Errors if a field of `schema` is not part of the file or if a stripe does not exist.
+    pub fn try_new(
+        reader: R,
+        metadata: FileMetadata,
+        schema: Schema,
+        stripes: Option<Vec<usize>>,
+        limit: Option<usize>,
+    ) -> Result<Self> {
+        let columns = field_columns(&metadata, &schema)?;
+
+        let num_stripes = metadata.footer.stripes.len();
+        let stripes = stripes.unwrap_or_else(|| (0..num_stripes).collect());
+        if let Some(stripe) = stripes.iter().find(|stripe| **stripe >= num_stripes) {
+            return Err(Error::InvalidArgumentError(format!(
+                "Stripe {stripe} does not exist; the file has {num_stripes} stripes"
+            )));
+        }
+
+        Ok(Self {
+            reader,
+            metadata,
+            schema,
+            columns,
+            stripes: stripes.into_iter(),
+            remaining_rows: limit.unwrap_or(usize::MAX),
+            scratch: vec![],
+        })
+    }
+
+    /// Returns the [`Schema`] of the chunks of this reader
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// Returns the [`FileMetadata`] of the file
+    pub fn metadata(&self) -> &FileMetadata {
+        &self.metadata
+    }
+
+    /// Consumes itself into the inner reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn read_stripe(&mut self, stripe: usize) -> Result<Chunk<Box<dyn Array>>> {
+        let footer =
+            read_stripe_footer(&mut self.reader, &self.metadata, stripe, &mut self.scratch)?;
+
+        let arrays = self
+            .schema
+            .fields
+            .iter()
+            .zip(self.columns.iter())
+            .map(|(field, start)| {
+                let n = n_columns(&field.data_type) as u32;
+                let columns = (*start..*start + n)
+                    .map(|column| {
+                        read_stripe_column(
+                            &mut self.reader,
+                            &self.metadata,
+                            stripe,
+                            footer.clone(),
+                            column,
+                            vec![],
+                        )
+                    })
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                deserialize_nested(field.data_type.clone(), &columns)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Chunk::try_new(arrays)
+    }
+
+    fn _next(&mut self) -> Result<Option<Chunk<Box<dyn Array>>>> {
+        if self.schema.fields.is_empty() || self.remaining_rows == 0 {
+            return Ok(None);
+        }
+        let stripe = if let Some(stripe) = self.stripes.next() {
+            stripe
+        } else {
+            return Ok(None);
+        };
+
+        let chunk = self.read_stripe(stripe)?;
+        let chunk = if chunk.len() > self.remaining_rows {
+            let arrays = chunk
+                .into_arrays()
+                .into_iter()
+                .map(|array| array.slice(0, self.remaining_rows))
+                .collect();
+            Chunk::new(arrays)
+        } else {
+            chunk
+        };
+        self.remaining_rows -= chunk.len();
+        Ok(Some(chunk))
+    }
+}
+
+impl<R: Read + Seek> Iterator for FileReader<R> {
+    type Item = Result<Chunk<Box<dyn Array>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self._next().transpose()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.stripes.size_hint().1)
+    }
+}
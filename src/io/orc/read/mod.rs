@@ -2,18 +2,26 @@
 use std::io::Read;
 
 use crate::array::{
-    Array, BinaryArray, BooleanArray, Int64Array, Offset, PrimitiveArray, Utf8Array,
+    growable::make_growable, Array, BinaryArray, BooleanArray, Int64Array, ListArray, MapArray,
+    Offset, PrimitiveArray, StructArray, Utf8Array,
 };
-use crate::bitmap::{Bitmap, MutableBitmap};
-use crate::datatypes::{DataType, Field, Schema};
+use crate::bitmap::Bitmap;
+use crate::datatypes::{DataType, Field, Schema, TimeUnit};
 use crate::error::Error;
 use crate::types::NativeType;
 
+use orc_format::proto::column_encoding::Kind as EncodingKind;
 use orc_format::proto::stream::Kind;
 use orc_format::proto::{Footer, Type};
 use orc_format::read::decode;
 use orc_format::read::Column;
 
+mod byte_rle;
+mod file;
+
+use byte_rle::{read_boolean_rle, read_byte_rle};
+pub use file::{filter_stripes, FileReader};
+
 /// Infers a [`Schema`] from the files' [`Footer`].
 /// # Errors
 /// This function errors if the type is not yet supported.
@@ -30,6 +38,24 @@ pub fn infer_schema(footer: &Footer) -> Result<Schema, Error> {
     }
 }
 
+/// The precision of decimals of files written before ORC stored it
+const DEFAULT_PRECISION: u32 = 38;
+/// The scale of decimals of files written before ORC stored it
+const DEFAULT_SCALE: u32 = 10;
+
+/// Infers the [`DataType`] of the `index`th subtype of `type_`
+fn infer_child(type_: &Type, index: usize, types: &[Type]) -> Result<DataType, Error> {
+    let i = *type_.subtypes.get(index).ok_or_else(|| {
+        Error::ExternalFormat(format!("ORC {:?} is missing a subtype", type_.kind()))
+    })?;
+    infer_dt(
+        types
+            .get(i as usize)
+            .ok_or_else(|| Error::ExternalFormat(format!("ORC field {i} not found")))?,
+        types,
+    )
+}
+
 fn infer_dt(type_: &Type, types: &[Type]) -> Result<DataType, Error> {
     use orc_format::proto::r#type::Kind::*;
     let dt = match type_.kind() {
@@ -40,8 +66,27 @@ fn infer_dt(type_: &Type, types: &[Type]) -> Result<DataType, Error> {
         Long => DataType::Int64,
         Float => DataType::Float32,
         Double => DataType::Float64,
-        String => DataType::Utf8,
+        String | Char | Varchar => DataType::Utf8,
         Binary => DataType::Binary,
+        Timestamp => DataType::Timestamp(TimeUnit::Nanosecond, None),
+        Date => DataType::Date32,
+        Decimal => DataType::Decimal(
+            type_.precision.unwrap_or(DEFAULT_PRECISION) as usize,
+            type_.scale.unwrap_or(DEFAULT_SCALE) as usize,
+        ),
+        List => {
+            let inner = infer_child(type_, 0, types)?;
+            DataType::List(Box::new(Field::new("item", inner, true)))
+        }
+        Map => {
+            let key = infer_child(type_, 0, types)?;
+            let value = infer_child(type_, 1, types)?;
+            let entries = DataType::Struct(vec![
+                Field::new("key", key, false),
+                Field::new("value", value, true),
+            ]);
+            DataType::Map(Box::new(Field::new("entries", entries, false)), false)
+        }
         Struct => {
             let sub_types = type_
                 .subtypes
@@ -65,17 +110,22 @@ fn infer_dt(type_: &Type, types: &[Type]) -> Result<DataType, Error> {
     Ok(dt)
 }
 
-fn deserialize_validity(column: &Column, scratch: &mut Vec<u8>) -> Result<Option<Bitmap>, Error> {
-    let stream = column.get_stream(Kind::Present, std::mem::take(scratch))?;
-
-    let mut stream = decode::BooleanIter::new(stream, column.number_of_rows());
+fn deserialize_validity(
+    column: &Column,
+    num_rows: usize,
+    scratch: &mut Vec<u8>,
+) -> Result<Option<Bitmap>, Error> {
+    // columns without nulls have no present stream
+    let stream = match column.get_stream(Kind::Present, std::mem::take(scratch)) {
+        Ok(stream) => stream,
+        Err(orc_format::error::Error::InvalidKind(_, Kind::Present)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
 
-    let mut validity = MutableBitmap::with_capacity(column.number_of_rows());
-    for item in stream.by_ref() {
-        validity.push(item?)
-    }
+    let mut stream = stream;
+    let validity = read_boolean_rle(&mut stream, num_rows)?;
 
-    *scratch = std::mem::take(&mut stream.into_inner().into_inner());
+    *scratch = stream.into_inner();
 
     Ok(validity.into())
 }
@@ -84,11 +134,11 @@ fn deserialize_validity(column: &Column, scratch: &mut Vec<u8>) -> Result<Option
 fn deserialize_float<T: NativeType + decode::Float>(
     data_type: DataType,
     column: &Column,
+    num_rows: usize,
 ) -> Result<PrimitiveArray<T>, Error> {
     let mut scratch = vec![];
-    let num_rows = column.number_of_rows();
 
-    let validity = deserialize_validity(column, &mut scratch)?;
+    let validity = deserialize_validity(column, num_rows, &mut scratch)?;
 
     let mut chunks = column.get_stream(Kind::Data, scratch)?;
 
@@ -115,42 +165,41 @@ fn deserialize_float<T: NativeType + decode::Float>(
 }
 
 /// Deserializes column `column` from `stripe`, assumed to represent a boolean array
-fn deserialize_bool(data_type: DataType, column: &Column) -> Result<BooleanArray, Error> {
-    let num_rows = column.number_of_rows();
+fn deserialize_bool(
+    data_type: DataType,
+    column: &Column,
+    num_rows: usize,
+) -> Result<BooleanArray, Error> {
     let mut scratch = vec![];
 
-    let validity = deserialize_validity(column, &mut scratch)?;
-
-    let mut chunks = column.get_stream(Kind::Data, std::mem::take(&mut scratch))?;
+    let validity = deserialize_validity(column, num_rows, &mut scratch)?;
 
-    let mut values = MutableBitmap::with_capacity(num_rows);
-    if let Some(validity) = &validity {
-        let mut items =
-            decode::BooleanIter::new(&mut chunks, validity.len() - validity.unset_bits());
+    let length = num_values(validity.as_ref(), num_rows);
 
-        for is_valid in validity {
-            values.push(if is_valid {
-                items.next().transpose()?.unwrap_or_default()
-            } else {
-                false
-            });
-        }
+    let mut chunks = column.get_stream(Kind::Data, scratch)?;
+    let values = read_boolean_rle(&mut chunks, length)?;
+    let values = if let Some(validity) = &validity {
+        let mut values = values.iter();
+        validity
+            .iter()
+            .map(|is_valid| is_valid && values.next().unwrap_or_default())
+            .collect()
     } else {
-        let valid_iter = decode::BooleanIter::new(&mut chunks, num_rows);
-        for v in valid_iter {
-            values.push(v?)
-        }
-    }
+        values
+    };
 
     BooleanArray::try_new(data_type, values.into(), validity)
 }
 
 /// Deserializes column `column` from `stripe`, assumed to represent a boolean array
-fn deserialize_i64(data_type: DataType, column: &Column) -> Result<Int64Array, Error> {
-    let num_rows = column.number_of_rows();
+fn deserialize_i64(
+    data_type: DataType,
+    column: &Column,
+    num_rows: usize,
+) -> Result<Int64Array, Error> {
     let mut scratch = vec![];
 
-    let validity = deserialize_validity(column, &mut scratch)?;
+    let validity = deserialize_validity(column, num_rows, &mut scratch)?;
 
     let chunks = column.get_stream(Kind::Data, std::mem::take(&mut scratch))?;
 
@@ -181,15 +230,40 @@ fn deserialize_i64(data_type: DataType, column: &Column) -> Result<Int64Array, E
     Int64Array::try_new(data_type, values.into(), validity)
 }
 
+/// Deserializes column `column` from `stripe`, assumed to represent an i8 array.
+/// ORC encodes bytes with its byte run-length encoding.
+fn deserialize_i8(
+    data_type: DataType,
+    column: &Column,
+    num_rows: usize,
+) -> Result<PrimitiveArray<i8>, Error> {
+    let mut scratch = vec![];
+
+    let validity = deserialize_validity(column, num_rows, &mut scratch)?;
+    let length = num_values(validity.as_ref(), num_rows);
+
+    let mut chunks = column.get_stream(Kind::Data, scratch)?;
+    let values = read_byte_rle(&mut chunks, length)?
+        .into_iter()
+        .map(|x| x as i8)
+        .collect();
+    let values = expand(values, validity.as_ref(), num_rows);
+
+    PrimitiveArray::try_new(data_type, values.into(), validity)
+}
+
 /// Deserializes column `column` from `stripe`, assumed to represent a boolean array
-fn deserialize_int<T>(data_type: DataType, column: &Column) -> Result<PrimitiveArray<T>, Error>
+fn deserialize_int<T>(
+    data_type: DataType,
+    column: &Column,
+    num_rows: usize,
+) -> Result<PrimitiveArray<T>, Error>
 where
     T: NativeType + TryFrom<i64>,
 {
-    let num_rows = column.number_of_rows();
     let mut scratch = vec![];
 
-    let validity = deserialize_validity(column, &mut scratch)?;
+    let validity = deserialize_validity(column, num_rows, &mut scratch)?;
 
     let chunks = column.get_stream(Kind::Data, std::mem::take(&mut scratch))?;
 
@@ -265,13 +339,65 @@ fn try_extend<O: Offset + TryFrom<u64>, I: Iterator<Item = u64>>(
     Ok(())
 }
 
+/// Deserializes a dictionary-encoded column of variable-length values: its data stream
+/// contains the indices of each value on the dictionary.
+fn deserialize_dictionary_generic<O: Offset>(
+    column: &Column,
+    num_rows: usize,
+    validity: Option<&Bitmap>,
+    scratch: Vec<u8>,
+) -> Result<(Vec<O>, Vec<u8>), Error> {
+    let size = column
+        .dictionary_size()
+        .ok_or(orc_format::error::Error::OutOfSpec)?;
+    let length = num_values(validity, num_rows);
+
+    let (indices, scratch) = read_unsigned(column, Kind::Data, length, scratch)?;
+    let (lengths, scratch) = read_unsigned(column, Kind::Length, size, scratch)?;
+
+    let mut dictionary_offsets = Vec::with_capacity(size + 1);
+    dictionary_offsets.push(0usize);
+    for length in lengths {
+        dictionary_offsets.push(dictionary_offsets.last().unwrap() + length as usize);
+    }
+    let mut dictionary = vec![0; *dictionary_offsets.last().unwrap()];
+    let mut data = column.get_stream(Kind::DictionaryData, scratch)?;
+    data.read_exact(&mut dictionary)?;
+
+    let mut offsets = Vec::with_capacity(num_rows + 1);
+    offsets.push(O::default());
+    let mut values = vec![];
+    let mut indices = indices.into_iter();
+    for is_valid in (0..num_rows).map(|i| validity.map(|x| x.get_bit(i)).unwrap_or(true)) {
+        if is_valid {
+            let index = indices.next().ok_or(orc_format::error::Error::OutOfSpec)? as usize;
+            if index >= size {
+                return Err(Error::ExternalFormat(
+                    "ORC dictionary index out of bounds".to_string(),
+                ));
+            }
+            values.extend_from_slice(
+                &dictionary[dictionary_offsets[index]..dictionary_offsets[index + 1]],
+            );
+        }
+        offsets.push(O::from_usize(values.len()).ok_or(Error::Overflow)?);
+    }
+    Ok((offsets, values))
+}
+
 fn deserialize_binary_generic<O: Offset + TryFrom<u64>>(
     column: &Column,
+    num_rows: usize,
 ) -> Result<(Vec<O>, Vec<u8>, Option<Bitmap>), Error> {
-    let num_rows = column.number_of_rows();
     let mut scratch = vec![];
 
-    let validity = deserialize_validity(column, &mut scratch)?;
+    let validity = deserialize_validity(column, num_rows, &mut scratch)?;
+
+    if column.encoding().kind() == EncodingKind::DictionaryV2 {
+        let (offsets, values) =
+            deserialize_dictionary_generic(column, num_rows, validity.as_ref(), scratch)?;
+        return Ok((offsets, values, validity));
+    }
 
     let lengths = column.get_stream(Kind::Length, scratch)?;
 
@@ -326,34 +452,411 @@ fn deserialize_binary_generic<O: Offset + TryFrom<u64>>(
 fn deserialize_utf8<O: Offset + TryFrom<u64>>(
     data_type: DataType,
     column: &Column,
+    num_rows: usize,
 ) -> Result<Utf8Array<O>, Error> {
-    let (offsets, values, validity) = deserialize_binary_generic::<O>(column)?;
+    let (offsets, values, validity) = deserialize_binary_generic::<O>(column, num_rows)?;
     Utf8Array::try_new(data_type, offsets.into(), values.into(), validity)
 }
 
 fn deserialize_binary<O: Offset + TryFrom<u64>>(
     data_type: DataType,
     column: &Column,
+    num_rows: usize,
 ) -> Result<BinaryArray<O>, Error> {
-    let (offsets, values, validity) = deserialize_binary_generic::<O>(column)?;
+    let (offsets, values, validity) = deserialize_binary_generic::<O>(column, num_rows)?;
     BinaryArray::try_new(data_type, offsets.into(), values.into(), validity)
 }
 
+/// The number of non-null values of a column with `num_rows` rows
+fn num_values(validity: Option<&Bitmap>, num_rows: usize) -> usize {
+    validity
+        .map(|validity| validity.len() - validity.unset_bits())
+        .unwrap_or(num_rows)
+}
+
+/// Returns `values`, one per valid slot of `validity`, expanded to `num_rows` slots
+fn expand<T: Copy + Default>(values: Vec<T>, validity: Option<&Bitmap>, num_rows: usize) -> Vec<T> {
+    if let Some(validity) = validity {
+        let mut values = values.into_iter();
+        validity
+            .iter()
+            .map(|is_valid| {
+                if is_valid {
+                    values.next().unwrap_or_default()
+                } else {
+                    T::default()
+                }
+            })
+            .collect()
+    } else {
+        debug_assert_eq!(values.len(), num_rows);
+        values
+    }
+}
+
+/// Reads `length` signed integers from the stream `kind` of `column`
+fn read_signed(
+    column: &Column,
+    kind: Kind,
+    length: usize,
+    scratch: Vec<u8>,
+) -> Result<(Vec<i64>, Vec<u8>), Error> {
+    let stream = column.get_stream(kind, scratch)?;
+    let mut iter = decode::SignedRleV2Iter::new(stream, length, vec![]);
+    let values = iter.by_ref().collect::<Result<Vec<_>, _>>()?;
+    let (stream, _) = iter.into_inner();
+    Ok((values, stream.into_inner()))
+}
+
+/// Reads `length` unsigned integers from the stream `kind` of `column`
+fn read_unsigned(
+    column: &Column,
+    kind: Kind,
+    length: usize,
+    scratch: Vec<u8>,
+) -> Result<(Vec<u64>, Vec<u8>), Error> {
+    let stream = column.get_stream(kind, scratch)?;
+    let mut iter = decode::UnsignedRleV2Iter::new(stream, length, vec![]);
+    let values = iter.by_ref().collect::<Result<Vec<_>, _>>()?;
+    let (stream, _) = iter.into_inner();
+    Ok((values, stream.into_inner()))
+}
+
+/// The number of seconds between the unix epoch and ORC's epoch, 2015-01-01
+pub(super) const ORC_EPOCH: i64 = 1_420_070_400;
+
+/// Decodes nanoseconds as stored by ORC: the 3 least significant bits
+/// contain the number of trailing decimal zeros that were removed, minus one.
+fn decode_nanos(value: u64) -> i64 {
+    let zeros = value & 0b111;
+    let nanos = (value >> 3) as i64;
+    if zeros == 0 {
+        nanos
+    } else {
+        nanos * 10i64.pow(zeros as u32 + 1)
+    }
+}
+
+/// Deserializes column `column` from `stripe`, assumed to represent a timestamp array.
+/// ORC stores timestamps as seconds since [`ORC_EPOCH`] and nanoseconds in two streams.
+fn deserialize_timestamp(
+    data_type: DataType,
+    column: &Column,
+    num_rows: usize,
+) -> Result<PrimitiveArray<i64>, Error> {
+    let mut scratch = vec![];
+
+    let validity = deserialize_validity(column, num_rows, &mut scratch)?;
+    let length = num_values(validity.as_ref(), num_rows);
+
+    let (seconds, scratch) = read_signed(column, Kind::Data, length, scratch)?;
+    let (nanos, _) = read_unsigned(column, Kind::Secondary, length, scratch)?;
+
+    let values = seconds
+        .into_iter()
+        .zip(nanos)
+        .map(|(seconds, nanos)| {
+            let mut seconds = seconds + ORC_EPOCH;
+            let nanos = decode_nanos(nanos);
+            // writers store the seconds of negative timestamps rounded towards zero
+            if seconds < 0 && nanos > 999_999 {
+                seconds -= 1;
+            }
+            seconds
+                .checked_mul(1_000_000_000)
+                .and_then(|x| x.checked_add(nanos))
+                .ok_or_else(|| {
+                    Error::ExternalFormat("ORC timestamp out of range of i64 nanoseconds".into())
+                })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let values = expand(values, validity.as_ref(), num_rows);
+
+    PrimitiveArray::try_new(data_type, values.into(), validity)
+}
+
+/// Reads a zigzag-encoded unbounded base-128 varint
+fn read_varint<R: Read>(reader: &mut R) -> Result<i128, Error> {
+    let mut value = 0u128;
+    let mut shift = 0;
+    loop {
+        let mut byte = [0u8];
+        reader.read_exact(&mut byte)?;
+        if shift >= 128 {
+            return Err(Error::ExternalFormat(
+                "ORC decimal does not fit in 128 bits".to_string(),
+            ));
+        }
+        value |= ((byte[0] & 0x7f) as u128) << shift;
+        if byte[0] & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    Ok((value >> 1) as i128 ^ -((value & 1) as i128))
+}
+
+/// Deserializes column `column` from `stripe`, assumed to represent a decimal array.
+/// ORC stores the scale of each value, which is rescaled to the scale of `data_type`.
+fn deserialize_decimal(
+    data_type: DataType,
+    column: &Column,
+    num_rows: usize,
+) -> Result<PrimitiveArray<i128>, Error> {
+    let scale = if let DataType::Decimal(_, scale) = data_type.to_logical_type() {
+        *scale as i64
+    } else {
+        unreachable!()
+    };
+    let mut scratch = vec![];
+
+    let validity = deserialize_validity(column, num_rows, &mut scratch)?;
+    let length = num_values(validity.as_ref(), num_rows);
+
+    let mut data = column.get_stream(Kind::Data, scratch)?;
+    let values = (0..length)
+        .map(|_| read_varint(&mut data))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let (scales, _) = read_signed(column, Kind::Secondary, length, data.into_inner())?;
+
+    let values = values
+        .into_iter()
+        .zip(scales)
+        .map(|(value, value_scale)| {
+            let value = if value_scale <= scale {
+                10i128
+                    .checked_pow((scale - value_scale) as u32)
+                    .and_then(|x| value.checked_mul(x))
+            } else {
+                10i128
+                    .checked_pow((value_scale - scale) as u32)
+                    .map(|x| value / x)
+            };
+            value.ok_or_else(|| {
+                Error::ExternalFormat("ORC decimal out of range of its precision".to_string())
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let values = expand(values, validity.as_ref(), num_rows);
+
+    PrimitiveArray::try_new(data_type, values.into(), validity)
+}
+
+/// Deserializes the lengths of `column` into offsets
+fn deserialize_offsets(
+    column: &Column,
+    num_rows: usize,
+) -> Result<(Vec<i32>, Option<Bitmap>), Error> {
+    let mut scratch = vec![];
+
+    let validity = deserialize_validity(column, num_rows, &mut scratch)?;
+    let length = num_values(validity.as_ref(), num_rows);
+
+    let (lengths, _) = read_unsigned(column, Kind::Length, length, scratch)?;
+    let lengths = expand(lengths, validity.as_ref(), num_rows);
+
+    let mut offsets = Vec::with_capacity(num_rows + 1);
+    let mut length = 0i32;
+    offsets.push(length);
+    for item in lengths {
+        let item: i32 = item
+            .try_into()
+            .map_err(|_| Error::ExternalFormat("value uncastable".to_string()))?;
+        length = length
+            .checked_add(item)
+            .ok_or_else(|| Error::ExternalFormat("ORC list too long for i32 offsets".into()))?;
+        offsets.push(length);
+    }
+    Ok((offsets, validity))
+}
+
+fn deserialize_list(
+    data_type: DataType,
+    columns: &[Column],
+    num_rows: usize,
+) -> Result<ListArray<i32>, Error> {
+    let (offsets, validity) = deserialize_offsets(&columns[0], num_rows)?;
+
+    let field = ListArray::<i32>::get_child_field(&data_type);
+    let values = deserialize_columns(
+        field.data_type.clone(),
+        &columns[1..],
+        *offsets.last().unwrap() as usize,
+    )?;
+
+    ListArray::try_new(data_type, offsets.into(), values, validity)
+}
+
+fn deserialize_map(
+    data_type: DataType,
+    columns: &[Column],
+    num_rows: usize,
+) -> Result<MapArray, Error> {
+    let (offsets, validity) = deserialize_offsets(&columns[0], num_rows)?;
+
+    let field = if let DataType::Map(field, _) = data_type.to_logical_type() {
+        field
+    } else {
+        unreachable!()
+    };
+    let entries = deserialize_fields(
+        field.data_type.clone(),
+        &columns[1..],
+        *offsets.last().unwrap() as usize,
+        None,
+    )?;
+
+    MapArray::try_new(data_type, offsets.into(), entries.boxed(), validity)
+}
+
+/// Returns `array`, with one value per valid slot of `validity`, expanded to one value per slot
+fn expand_array(array: Box<dyn Array>, validity: Option<&Bitmap>) -> Box<dyn Array> {
+    let validity = match validity {
+        Some(validity) if validity.unset_bits() > 0 => validity,
+        _ => return array,
+    };
+    let mut growable = make_growable(&[array.as_ref()], true, validity.len());
+    let mut offset = 0;
+    for is_valid in validity.iter() {
+        if is_valid {
+            growable.extend(0, offset, 1);
+            offset += 1;
+        } else {
+            growable.extend_validity(1);
+        }
+    }
+    growable.as_box()
+}
+
+/// Deserializes `columns` into the fields of the struct `data_type`.
+/// Like in ORC, the fields only contain values for the valid slots of `validity`.
+fn deserialize_fields(
+    data_type: DataType,
+    mut columns: &[Column],
+    num_rows: usize,
+    validity: Option<Bitmap>,
+) -> Result<StructArray, Error> {
+    let length = num_values(validity.as_ref(), num_rows);
+    let values = StructArray::get_fields(&data_type)
+        .iter()
+        .map(|field| {
+            let n = n_columns(&field.data_type);
+            if columns.len() < n {
+                return Err(Error::ExternalFormat(
+                    "ORC struct has fewer columns than its fields".to_string(),
+                ));
+            }
+            let (field_columns, remaining) = columns.split_at(n);
+            columns = remaining;
+            deserialize_columns(field.data_type.clone(), field_columns, length)
+                .map(|array| expand_array(array, validity.as_ref()))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    StructArray::try_new(data_type, values, validity)
+}
+
+fn deserialize_struct(
+    data_type: DataType,
+    columns: &[Column],
+    num_rows: usize,
+) -> Result<StructArray, Error> {
+    let validity = deserialize_validity(&columns[0], num_rows, &mut vec![])?;
+    deserialize_fields(data_type, &columns[1..], num_rows, validity)
+}
+
+/// Returns the number of ORC columns that an array of `data_type` spans, including its own.
+///
+/// ORC stores nested types in multiple columns, numbered in pre-order: the column of a
+/// [`DataType::List`] is followed by the columns of its values, and the column of a
+/// [`DataType::Map`] by the columns of its keys and values.
+pub fn n_columns(data_type: &DataType) -> usize {
+    match data_type.to_logical_type() {
+        DataType::List(inner) | DataType::LargeList(inner) => 1 + n_columns(&inner.data_type),
+        DataType::Map(inner, _) => n_columns(&inner.data_type),
+        DataType::Struct(fields) => {
+            1 + fields
+                .iter()
+                .map(|field| n_columns(&field.data_type))
+                .sum::<usize>()
+        }
+        _ => 1,
+    }
+}
+
+fn deserialize_columns(
+    data_type: DataType,
+    columns: &[Column],
+    num_rows: usize,
+) -> Result<Box<dyn Array>, Error> {
+    let n = n_columns(&data_type);
+    if columns.len() < n {
+        return Err(Error::InvalidArgumentError(format!(
+            "Deserializing {data_type:?} from ORC requires {n} columns but {} were provided",
+            columns.len()
+        )));
+    }
+    let column = &columns[0];
+    match data_type.to_logical_type() {
+        DataType::Boolean => deserialize_bool(data_type, column, num_rows).map(|x| x.boxed()),
+        DataType::Int8 => deserialize_i8(data_type, column, num_rows).map(|x| x.boxed()),
+        DataType::Int16 => deserialize_int::<i16>(data_type, column, num_rows).map(|x| x.boxed()),
+        DataType::Int32 | DataType::Date32 => {
+            deserialize_int::<i32>(data_type, column, num_rows).map(|x| x.boxed())
+        }
+        DataType::Int64 => deserialize_i64(data_type, column, num_rows).map(|x| x.boxed()),
+        DataType::Timestamp(TimeUnit::Nanosecond, None) => {
+            deserialize_timestamp(data_type, column, num_rows).map(|x| x.boxed())
+        }
+        DataType::Decimal(_, _) => {
+            deserialize_decimal(data_type, column, num_rows).map(|x| x.boxed())
+        }
+        DataType::Float32 => {
+            deserialize_float::<f32>(data_type, column, num_rows).map(|x| x.boxed())
+        }
+        DataType::Float64 => {
+            deserialize_float::<f64>(data_type, column, num_rows).map(|x| x.boxed())
+        }
+        DataType::Utf8 => deserialize_utf8::<i32>(data_type, column, num_rows).map(|x| x.boxed()),
+        DataType::LargeUtf8 => {
+            deserialize_utf8::<i64>(data_type, column, num_rows).map(|x| x.boxed())
+        }
+        DataType::Binary => {
+            deserialize_binary::<i32>(data_type, column, num_rows).map(|x| x.boxed())
+        }
+        DataType::LargeBinary => {
+            deserialize_binary::<i64>(data_type, column, num_rows).map(|x| x.boxed())
+        }
+        DataType::List(_) => deserialize_list(data_type, columns, num_rows).map(|x| x.boxed()),
+        DataType::Map(_, _) => deserialize_map(data_type, columns, num_rows).map(|x| x.boxed()),
+        DataType::Struct(_) => deserialize_struct(data_type, columns, num_rows).map(|x| x.boxed()),
+        dt => Err(Error::nyi(format!("Deserializing {dt:?} from ORC"))),
+    }
+}
+
 /// Deserializes column `column` from `stripe`, assumed
 /// to represent an array of `data_type`.
+///
+/// Types spanning multiple ORC columns (see [`n_columns`]) must be deserialized
+/// via [`deserialize_nested`].
 pub fn deserialize(data_type: DataType, column: &Column) -> Result<Box<dyn Array>, Error> {
-    match data_type {
-        DataType::Boolean => deserialize_bool(data_type, column).map(|x| x.boxed()),
-        DataType::Int8 => deserialize_int::<i8>(data_type, column).map(|x| x.boxed()),
-        DataType::Int16 => deserialize_int::<i16>(data_type, column).map(|x| x.boxed()),
-        DataType::Int32 => deserialize_int::<i32>(data_type, column).map(|x| x.boxed()),
-        DataType::Int64 => deserialize_i64(data_type, column).map(|x| x.boxed()),
-        DataType::Float32 => deserialize_float::<f32>(data_type, column).map(|x| x.boxed()),
-        DataType::Float64 => deserialize_float::<f64>(data_type, column).map(|x| x.boxed()),
-        DataType::Utf8 => deserialize_utf8::<i32>(data_type, column).map(|x| x.boxed()),
-        DataType::LargeUtf8 => deserialize_utf8::<i64>(data_type, column).map(|x| x.boxed()),
-        DataType::Binary => deserialize_binary::<i32>(data_type, column).map(|x| x.boxed()),
-        DataType::LargeBinary => deserialize_binary::<i64>(data_type, column).map(|x| x.boxed()),
-        dt => Err(Error::nyi(format!("Deserializing {dt:?} from ORC"))),
-    }
+    deserialize_nested(data_type, std::slice::from_ref(column))
+}
+
+/// Deserializes `columns` from `stripe`, assumed to represent an array of `data_type`.
+///
+/// `columns` must contain the [`n_columns`] consecutive columns of the stripe starting at the
+/// column of `data_type`, i.e. its own column followed by the columns of its children.
+/// # Errors
+/// This function errors if the type is not yet supported or if the number of columns is
+/// smaller than required.
+pub fn deserialize_nested(
+    data_type: DataType,
+    columns: &[Column],
+) -> Result<Box<dyn Array>, Error> {
+    let num_rows = columns
+        .first()
+        .map(|column| column.number_of_rows())
+        .unwrap_or_default();
+    deserialize_columns(data_type, columns, num_rows)
 }
@@ -0,0 +1,244 @@
+//! Encoders of ORC's run-length encodings.
+//! See <https://orc.apache.org/specification/ORCv1/> for their specification.
+
+/// The maximum number of literals of a byte run
+const MAX_BYTE_LITERALS: usize = 128;
+/// The minimum number of repeated values of a byte run
+const MIN_BYTE_REPEAT: usize = 3;
+/// The maximum number of repeated values of a byte run
+const MAX_BYTE_REPEAT: usize = 127 + MIN_BYTE_REPEAT;
+
+/// The maximum number of values of a RLEv2 run
+const MAX_RLE_V2_LENGTH: usize = 512;
+/// The minimum number of repeated values of a RLEv2 run
+const MIN_REPEAT: usize = 3;
+/// The maximum number of repeated values of a RLEv2 short repeat run
+const MAX_SHORT_REPEAT: usize = 10;
+
+/// Returns the number of times `values[0]` is repeated at the start of `values`, up to `max`
+#[inline]
+fn repeated<T: PartialEq>(values: &[T], max: usize) -> usize {
+    values
+        .iter()
+        .take(max)
+        .take_while(|x| **x == values[0])
+        .count()
+}
+
+fn write_byte_literals(literals: &[u8], buffer: &mut Vec<u8>) {
+    for chunk in literals.chunks(MAX_BYTE_LITERALS) {
+        buffer.push(-(chunk.len() as i16) as u8);
+        buffer.extend_from_slice(chunk);
+    }
+}
+
+/// Encodes `values` with ORC's byte run-length encoding into `buffer`
+pub fn write_byte_rle(values: &[u8], buffer: &mut Vec<u8>) {
+    let mut start = 0;
+    let mut i = 0;
+    while i < values.len() {
+        let run = repeated(&values[i..], MAX_BYTE_REPEAT);
+        if run >= MIN_BYTE_REPEAT {
+            write_byte_literals(&values[start..i], buffer);
+            buffer.push((run - MIN_BYTE_REPEAT) as u8);
+            buffer.push(values[i]);
+            i += run;
+            start = i;
+        } else {
+            i += 1;
+        }
+    }
+    write_byte_literals(&values[start..], buffer);
+}
+
+/// Encodes `values` with ORC's boolean run-length encoding into `buffer`:
+/// bits are packed from the most significant bit of each byte and the bytes are
+/// encoded with ORC's byte run-length encoding.
+pub fn write_boolean_rle<I: Iterator<Item = bool>>(values: I, buffer: &mut Vec<u8>) {
+    let mut bytes = vec![];
+    let mut byte = 0u8;
+    let mut position = 0;
+    for value in values {
+        if value {
+            byte |= 128 >> position;
+        }
+        position += 1;
+        if position == 8 {
+            bytes.push(byte);
+            byte = 0;
+            position = 0;
+        }
+    }
+    if position > 0 {
+        bytes.push(byte);
+    }
+    write_byte_rle(&bytes, buffer)
+}
+
+/// Writes `value` as an unsigned base-128 varint
+#[inline]
+pub fn write_varint(mut value: u128, buffer: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buffer.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+#[inline]
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Writes `value` as a zigzag-encoded base-128 varint
+#[inline]
+pub fn write_signed_varint(value: i128, buffer: &mut Vec<u8>) {
+    write_varint(((value << 1) ^ (value >> 127)) as u128, buffer)
+}
+
+/// Returns the smallest bit width supported by RLEv2's direct encoding that fits `value`,
+/// alongside its 5-bit encoded representation
+fn direct_width(value: u64) -> (usize, u8) {
+    match 64 - value.leading_zeros() {
+        0..=1 => (1, 0),
+        2 => (2, 1),
+        3..=4 => (4, 3),
+        5..=8 => (8, 7),
+        9..=16 => (16, 15),
+        17..=24 => (24, 23),
+        25..=32 => (32, 27),
+        33..=40 => (40, 28),
+        41..=48 => (48, 29),
+        49..=56 => (56, 30),
+        _ => (64, 31),
+    }
+}
+
+fn write_short_repeat(value: u64, count: usize, buffer: &mut Vec<u8>) {
+    let width = (64 - value.leading_zeros() as usize).div_ceil(8).max(1);
+    buffer.push((((width - 1) << 3) | (count - MIN_REPEAT)) as u8);
+    buffer.extend_from_slice(&value.to_be_bytes()[8 - width..]);
+}
+
+/// Writes a delta run of `count` values equal to `value`, i.e. with a fixed delta of zero
+fn write_fixed_delta(value: u64, count: usize, buffer: &mut Vec<u8>) {
+    let length = count - 1;
+    buffer.push(0b1100_0000 | (length >> 8) as u8);
+    buffer.push(length as u8);
+    write_varint(value as u128, buffer);
+    // the delta base
+    write_varint(0, buffer);
+}
+
+fn write_direct(values: &[u64], buffer: &mut Vec<u8>) {
+    let max = values.iter().copied().max().unwrap_or_default();
+    let (width, encoded_width) = direct_width(max);
+
+    let length = values.len() - 1;
+    buffer.push(0b0100_0000 | (encoded_width << 1) | (length >> 8) as u8);
+    buffer.push(length as u8);
+
+    if width >= 8 {
+        let bytes = width / 8;
+        for value in values {
+            buffer.extend_from_slice(&value.to_be_bytes()[8 - bytes..]);
+        }
+    } else {
+        // values are packed from the most significant bit of each byte
+        let mut byte = 0u8;
+        let mut position = 0;
+        for value in values {
+            byte |= (*value as u8) << (8 - position - width);
+            position += width;
+            if position == 8 {
+                buffer.push(byte);
+                byte = 0;
+                position = 0;
+            }
+        }
+        if position > 0 {
+            buffer.push(byte);
+        }
+    }
+}
+
+/// Encodes `values` with ORC's run-length encoding version 2 into `buffer`.
+///
+/// Repeated values are encoded as short repeat or (fixed) delta runs and all other
+/// values as direct runs.
+pub fn write_unsigned_rle_v2(values: &[u64], buffer: &mut Vec<u8>) {
+    let mut i = 0;
+    while i < values.len() {
+        let run = repeated(&values[i..], MAX_RLE_V2_LENGTH);
+        if run >= MIN_REPEAT {
+            if run <= MAX_SHORT_REPEAT {
+                write_short_repeat(values[i], run, buffer);
+            } else {
+                write_fixed_delta(values[i], run, buffer);
+            }
+            i += run;
+            continue;
+        }
+
+        let start = i;
+        while i < values.len()
+            && i - start < MAX_RLE_V2_LENGTH
+            && repeated(&values[i..], MIN_REPEAT) < MIN_REPEAT
+        {
+            i += 1;
+        }
+        write_direct(&values[start..i], buffer);
+    }
+}
+
+/// Encodes `values` with ORC's signed run-length encoding version 2 into `buffer`.
+pub fn write_signed_rle_v2<I: Iterator<Item = i64>>(values: I, buffer: &mut Vec<u8>) {
+    let values = values.map(zigzag).collect::<Vec<_>>();
+    write_unsigned_rle_v2(&values, buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_rle() {
+        let mut buffer = vec![];
+        write_byte_rle(&[0; 100], &mut buffer);
+        assert_eq!(buffer, vec![0x61, 0x00]);
+
+        let mut buffer = vec![];
+        write_byte_rle(&[0x44, 0x45], &mut buffer);
+        assert_eq!(buffer, vec![0xfe, 0x44, 0x45]);
+    }
+
+    #[test]
+    fn boolean_rle() {
+        // one true followed by seven false
+        let mut buffer = vec![];
+        write_boolean_rle(
+            [true].into_iter().chain(std::iter::repeat_n(false, 7)),
+            &mut buffer,
+        );
+        assert_eq!(buffer, vec![0xff, 0x80]);
+    }
+
+    #[test]
+    fn short_repeat() {
+        // example from the specification: [10000, 10000, 10000, 10000, 10000]
+        let mut buffer = vec![];
+        write_unsigned_rle_v2(&[10000; 5], &mut buffer);
+        assert_eq!(buffer, vec![0x0a, 0x27, 0x10]);
+    }
+
+    #[test]
+    fn direct() {
+        // example from the specification: [23713, 43806, 57005, 48879]
+        let mut buffer = vec![];
+        write_unsigned_rle_v2(&[23713, 43806, 57005, 48879], &mut buffer);
+        assert_eq!(
+            buffer,
+            vec![0x5e, 0x03, 0x5c, 0xa1, 0xab, 0x1e, 0xde, 0xad, 0xbe, 0xef]
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! APIs to write to [ORC format](https://orc.apache.org).
+//!
+//! Each [`Chunk`] is written as a stripe, without compression. Integers are encoded with
+//! RLE version 2, strings with either dictionary or direct encoding, and the file's footer
+//! contains the statistics of every column per stripe and of the whole file.
+use std::io::Write;
+
+use orc_format::proto::column_encoding::Kind as EncodingKind;
+use orc_format::proto::{
+    CompressionKind, Footer, Metadata, PostScript, Stream, StripeFooter, StripeInformation,
+    StripeStatistics, Type,
+};
+use prost::Message;
+
+use crate::array::{new_empty_array, Array};
+use crate::chunk::Chunk;
+use crate::datatypes::Schema;
+use crate::error::{Error, Result};
+
+mod encode;
+mod schema;
+mod serialize;
+mod statistics;
+
+pub use schema::to_orc_types;
+
+use serialize::{serialize, EncodedColumn};
+use statistics::{Statistics, Summary};
+
+/// ORC's magic bytes, at the start of every file and in its postscript
+const MAGIC: &[u8; 3] = b"ORC";
+
+/// Options to write to ORC
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WriteOptions {
+    /// The maximum ratio between the number of distinct and the number of non-null values
+    /// of a string column of a stripe for it to be dictionary-encoded.
+    /// `0.0` disables dictionary encoding.
+    pub dictionary_key_size_threshold: f64,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            dictionary_key_size_threshold: 0.8,
+        }
+    }
+}
+
+/// Serializes `columns` of a stripe, including its root struct column
+fn encode_columns<A: AsRef<dyn Array>>(
+    schema: &Schema,
+    columns: &[A],
+    options: &WriteOptions,
+) -> Result<Vec<EncodedColumn>> {
+    if columns.len() != schema.fields.len() {
+        return Err(Error::InvalidArgumentError(format!(
+            "The chunk has {} arrays but the schema has {} fields",
+            columns.len(),
+            schema.fields.len()
+        )));
+    }
+    let len = columns
+        .first()
+        .map(|x| x.as_ref().len())
+        .unwrap_or_default();
+
+    let mut encoded = vec![EncodedColumn {
+        streams: vec![],
+        encoding: orc_format::proto::ColumnEncoding {
+            kind: Some(EncodingKind::Direct as i32),
+            dictionary_size: None,
+        },
+        statistics: Statistics {
+            number_of_values: len as u64,
+            has_null: false,
+            summary: Summary::None,
+        },
+    }];
+    for (array, field) in columns.iter().zip(schema.fields.iter()) {
+        let array = array.as_ref();
+        if array.data_type() != &field.data_type {
+            return Err(Error::InvalidArgumentError(format!(
+                "The array of field \"{}\" has data type {:?} but the schema declares {:?}",
+                field.name,
+                array.data_type(),
+                field.data_type
+            )));
+        }
+        serialize(array, options, &mut encoded)?;
+    }
+    Ok(encoded)
+}
+
+/// An ORC file writer that writes each [`Chunk`] as a stripe.
+///
+/// The file is only valid after [`FileWriter::finish`] is called.
+pub struct FileWriter<W: Write> {
+    writer: W,
+    schema: Schema,
+    options: WriteOptions,
+    types: Vec<Type>,
+    /// the number of bytes written so far
+    offset: u64,
+    stripes: Vec<StripeInformation>,
+    stripe_statistics: Vec<StripeStatistics>,
+    /// the statistics of the whole file, merged from the statistics of each stripe
+    statistics: Vec<Statistics>,
+    number_of_rows: u64,
+    finished: bool,
+}
+
+impl<W: Write> FileWriter<W> {
+    /// Creates a new [`FileWriter`] and writes the header to `writer`
+    /// # Errors
+    /// Errors if any of the data types of `schema` is not yet supported.
+    pub fn try_new(mut writer: W, schema: Schema, options: WriteOptions) -> Result<Self> {
+        let types = to_orc_types(&schema)?;
+
+        // the statistics of a file without rows
+        let empty = schema
+            .fields
+            .iter()
+            .map(|field| new_empty_array(field.data_type.clone()))
+            .collect::<Vec<_>>();
+        let statistics = encode_columns(&schema, &empty, &options)?
+            .into_iter()
+            .map(|column| column.statistics)
+            .collect();
+
+        writer.write_all(MAGIC)?;
+
+        Ok(Self {
+            writer,
+            schema,
+            options,
+            types,
+            offset: MAGIC.len() as u64,
+            stripes: vec![],
+            stripe_statistics: vec![],
+            statistics,
+            number_of_rows: 0,
+            finished: false,
+        })
+    }
+
+    /// Returns the [`Schema`] of this writer
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// Writes `chunk` as a new stripe
+    /// # Errors
+    /// Errors if the file is finished or if the arrays of `chunk` do not match the schema.
+    pub fn write<A: AsRef<dyn Array>>(&mut self, chunk: &Chunk<A>) -> Result<()> {
+        if self.finished {
+            return Err(Error::InvalidArgumentError(
+                "The ORC file is finished and can no longer be written to".to_string(),
+            ));
+        }
+        let columns = encode_columns(&self.schema, chunk.arrays(), &self.options)?;
+
+        let mut streams = vec![];
+        let mut encodings = vec![];
+        let mut col_stats = vec![];
+        let mut data_length = 0;
+        for (id, (column, statistics)) in columns
+            .into_iter()
+            .zip(self.statistics.iter_mut())
+            .enumerate()
+        {
+            // the streams of each column are contiguous
+            for (kind, data) in column.streams {
+                self.writer.write_all(&data)?;
+                data_length += data.len() as u64;
+                streams.push(Stream {
+                    kind: Some(kind as i32),
+                    column: Some(id as u32),
+                    length: Some(data.len() as u64),
+                });
+            }
+            encodings.push(column.encoding);
+            col_stats.push(column.statistics.to_proto());
+            statistics.merge(&column.statistics);
+        }
+
+        let footer = StripeFooter {
+            streams,
+            columns: encodings,
+            writer_timezone: None,
+        }
+        .encode_to_vec();
+        self.writer.write_all(&footer)?;
+
+        self.stripes.push(StripeInformation {
+            offset: Some(self.offset),
+            index_length: Some(0),
+            data_length: Some(data_length),
+            footer_length: Some(footer.len() as u64),
+            number_of_rows: Some(chunk.len() as u64),
+        });
+        self.stripe_statistics.push(StripeStatistics { col_stats });
+        self.offset += data_length + footer.len() as u64;
+        self.number_of_rows += chunk.len() as u64;
+        Ok(())
+    }
+
+    /// Writes the metadata, footer and postscript of the file, finishing it.
+    /// # Errors
+    /// Errors if the file is already finished.
+    pub fn finish(&mut self) -> Result<()> {
+        if self.finished {
+            return Err(Error::InvalidArgumentError(
+                "The ORC file can only be finished once".to_string(),
+            ));
+        }
+
+        let metadata = Metadata {
+            stripe_stats: std::mem::take(&mut self.stripe_statistics),
+        }
+        .encode_to_vec();
+        self.writer.write_all(&metadata)?;
+
+        let footer = Footer {
+            header_length: Some(MAGIC.len() as u64),
+            content_length: Some(self.offset),
+            stripes: std::mem::take(&mut self.stripes),
+            types: self.types.clone(),
+            metadata: vec![],
+            number_of_rows: Some(self.number_of_rows),
+            statistics: self.statistics.iter().map(|x| x.to_proto()).collect(),
+            row_index_stride: Some(0),
+        }
+        .encode_to_vec();
+        self.writer.write_all(&footer)?;
+
+        let postscript = PostScript {
+            footer_length: Some(footer.len() as u64),
+            compression: Some(CompressionKind::None as i32),
+            compression_block_size: None,
+            version: vec![0, 12],
+            metadata_length: Some(metadata.len() as u64),
+            writer_version: None,
+            magic: Some(String::from_utf8(MAGIC.to_vec()).unwrap()),
+        }
+        .encode_to_vec();
+        self.writer.write_all(&postscript)?;
+        // the postscript is small, well within the 255 bytes allowed
+        self.writer.write_all(&[postscript.len() as u8])?;
+
+        self.finished = true;
+        Ok(())
+    }
+
+    /// Consumes itself into the inner writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
@@ -0,0 +1,85 @@
+use orc_format::proto::r#type::Kind;
+use orc_format::proto::Type;
+
+use crate::datatypes::{DataType, Field, Schema};
+use crate::error::{Error, Result};
+
+fn new_type(kind: Kind) -> Type {
+    Type {
+        kind: Some(kind as i32),
+        ..Default::default()
+    }
+}
+
+fn to_struct_type(fields: &[Field], types: &mut Vec<Type>) -> Result<()> {
+    let index = types.len();
+    types.push(new_type(Kind::Struct));
+    for field in fields {
+        let id = types.len() as u32;
+        to_types(&field.data_type, types)?;
+        types[index].subtypes.push(id);
+        types[index].field_names.push(field.name.clone());
+    }
+    Ok(())
+}
+
+/// Appends the ORC [`Type`]s of `data_type` to `types`, in pre-order.
+fn to_types(data_type: &DataType, types: &mut Vec<Type>) -> Result<()> {
+    let kind = match data_type.to_logical_type() {
+        DataType::Boolean => Kind::Boolean,
+        DataType::Int8 => Kind::Byte,
+        DataType::Int16 => Kind::Short,
+        DataType::Int32 => Kind::Int,
+        DataType::Int64 => Kind::Long,
+        DataType::Float32 => Kind::Float,
+        DataType::Float64 => Kind::Double,
+        DataType::Utf8 | DataType::LargeUtf8 => Kind::String,
+        DataType::Binary | DataType::LargeBinary => Kind::Binary,
+        DataType::Timestamp(_, None) => Kind::Timestamp,
+        DataType::Date32 => Kind::Date,
+        DataType::Decimal(precision, scale) => {
+            types.push(Type {
+                precision: Some(*precision as u32),
+                scale: Some(*scale as u32),
+                ..new_type(Kind::Decimal)
+            });
+            return Ok(());
+        }
+        DataType::List(inner) | DataType::LargeList(inner) => {
+            let id = types.len() as u32 + 1;
+            types.push(Type {
+                subtypes: vec![id],
+                ..new_type(Kind::List)
+            });
+            return to_types(&inner.data_type, types);
+        }
+        DataType::Map(inner, _) => {
+            let index = types.len();
+            types.push(new_type(Kind::Map));
+            let fields = if let DataType::Struct(fields) = inner.data_type.to_logical_type() {
+                fields
+            } else {
+                return Err(Error::oos("The entries of a map must be a struct"));
+            };
+            for field in fields {
+                let id = types.len() as u32;
+                to_types(&field.data_type, types)?;
+                types[index].subtypes.push(id);
+            }
+            return Ok(());
+        }
+        DataType::Struct(fields) => return to_struct_type(fields, types),
+        other => return Err(Error::nyi(format!("Writing {other:?} to ORC"))),
+    };
+    types.push(new_type(kind));
+    Ok(())
+}
+
+/// Converts a [`Schema`] to ORC's list of [`Type`]s, whose first type is the root struct.
+/// # Errors
+/// This function errors if any of the data types is not yet supported.
+pub fn to_orc_types(schema: &Schema) -> Result<Vec<Type>> {
+    let mut types = vec![];
+    to_struct_type(&schema.fields, &mut types)?;
+    Ok(types)
+}
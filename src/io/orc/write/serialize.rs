@@ -0,0 +1,515 @@
+use std::collections::HashMap;
+
+use orc_format::proto::column_encoding::Kind as EncodingKind;
+use orc_format::proto::stream::Kind;
+use orc_format::proto::ColumnEncoding;
+
+use crate::array::{
+    growable::make_growable, Array, BinaryArray, BooleanArray, ListArray, MapArray, Offset,
+    PrimitiveArray, StructArray, Utf8Array,
+};
+use crate::bitmap::Bitmap;
+use crate::datatypes::{DataType, TimeUnit};
+use crate::error::{Error, Result};
+use crate::types::NativeType;
+
+use super::super::read::ORC_EPOCH;
+use super::encode::{
+    write_boolean_rle, write_byte_rle, write_signed_rle_v2, write_signed_varint,
+    write_unsigned_rle_v2,
+};
+use super::statistics::{Statistics, Summary};
+use super::WriteOptions;
+
+/// The streams, encoding and statistics of an ORC column of a stripe
+#[derive(Debug)]
+pub struct EncodedColumn {
+    pub streams: Vec<(Kind, Vec<u8>)>,
+    pub encoding: ColumnEncoding,
+    pub statistics: Statistics,
+}
+
+fn encoding(kind: EncodingKind) -> ColumnEncoding {
+    ColumnEncoding {
+        kind: Some(kind as i32),
+        dictionary_size: None,
+    }
+}
+
+/// Returns the ranges `(start, length)` of consecutive valid slots of `validity`
+fn valid_ranges(validity: Option<&Bitmap>, length: usize) -> Vec<(usize, usize)> {
+    let validity = if let Some(validity) = validity {
+        validity
+    } else {
+        return vec![(0, length)];
+    };
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for (i, is_valid) in validity.iter().enumerate() {
+        if !is_valid {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some((start, length)) if *start + *length == i => *length += 1,
+            _ => ranges.push((i, 1)),
+        }
+    }
+    ranges
+}
+
+/// Returns the concatenation of `ranges` of `array`
+fn take_ranges(array: &dyn Array, ranges: &[(usize, usize)]) -> Box<dyn Array> {
+    if let [(start, length)] = ranges {
+        return array.slice(*start, *length);
+    }
+    let capacity = ranges.iter().map(|(_, length)| length).sum();
+    let mut growable = make_growable(&[array], false, capacity);
+    for (start, length) in ranges {
+        growable.extend(0, *start, *length);
+    }
+    growable.as_box()
+}
+
+/// Returns the ranges of the values of the valid slots of a list-like array
+fn list_ranges<O: Offset>(offsets: &[O], validity: Option<&Bitmap>) -> Vec<(usize, usize)> {
+    valid_ranges(validity, offsets.len() - 1)
+        .into_iter()
+        .map(|(start, length)| {
+            let first = offsets[start].to_usize();
+            (first, offsets[start + length].to_usize() - first)
+        })
+        .collect()
+}
+
+fn serialize_bool(array: &BooleanArray, streams: &mut Vec<(Kind, Vec<u8>)>) -> Summary {
+    let trues = array.iter().flatten().filter(|x| *x).count() as u64;
+    let mut data = vec![];
+    write_boolean_rle(array.iter().flatten(), &mut data);
+    streams.push((Kind::Data, data));
+    Summary::Boolean { trues }
+}
+
+fn integer_summary(values: &[i64]) -> Summary {
+    Summary::Integer {
+        min: values.iter().copied().min(),
+        max: values.iter().copied().max(),
+        sum: values.iter().try_fold(0i64, |acc, x| acc.checked_add(*x)),
+    }
+}
+
+fn serialize_i8(array: &PrimitiveArray<i8>, streams: &mut Vec<(Kind, Vec<u8>)>) -> Summary {
+    let values = array.iter().flatten().copied().collect::<Vec<_>>();
+    let mut data = vec![];
+    write_byte_rle(
+        &values.iter().map(|x| *x as u8).collect::<Vec<_>>(),
+        &mut data,
+    );
+    streams.push((Kind::Data, data));
+    integer_summary(&values.into_iter().map(|x| x as i64).collect::<Vec<_>>())
+}
+
+fn serialize_integer<T: NativeType + Into<i64>>(
+    array: &PrimitiveArray<T>,
+    streams: &mut Vec<(Kind, Vec<u8>)>,
+) -> Vec<i64> {
+    let values = array
+        .iter()
+        .flatten()
+        .map(|x| (*x).into())
+        .collect::<Vec<_>>();
+    let mut data = vec![];
+    write_signed_rle_v2(values.iter().copied(), &mut data);
+    streams.push((Kind::Data, data));
+    values
+}
+
+fn serialize_float<T: NativeType + Into<f64>>(
+    array: &PrimitiveArray<T>,
+    streams: &mut Vec<(Kind, Vec<u8>)>,
+) -> Summary {
+    let mut data = vec![];
+    let mut min: Option<f64> = None;
+    let mut max: Option<f64> = None;
+    let mut sum = 0.0;
+    for value in array.iter().flatten() {
+        data.extend_from_slice(value.to_le_bytes().as_ref());
+        let value: f64 = (*value).into();
+        if !value.is_nan() {
+            min = Some(min.map(|x| x.min(value)).unwrap_or(value));
+            max = Some(max.map(|x| x.max(value)).unwrap_or(value));
+        }
+        sum += value;
+    }
+    streams.push((Kind::Data, data));
+    Summary::Double { min, max, sum }
+}
+
+/// Serializes variable-length `values` with either direct or dictionary encoding
+fn serialize_binary_generic(
+    values: &[&[u8]],
+    options: &WriteOptions,
+    streams: &mut Vec<(Kind, Vec<u8>)>,
+) -> ColumnEncoding {
+    let mut distinct = HashMap::<&[u8], u32>::new();
+    for value in values {
+        distinct.entry(*value).or_default();
+    }
+
+    if values.is_empty()
+        || distinct.len() as f64 > options.dictionary_key_size_threshold * values.len() as f64
+    {
+        let mut data = Vec::with_capacity(values.iter().map(|x| x.len()).sum());
+        values.iter().for_each(|x| data.extend_from_slice(x));
+        let lengths = values.iter().map(|x| x.len() as u64).collect::<Vec<_>>();
+        let mut length = vec![];
+        write_unsigned_rle_v2(&lengths, &mut length);
+
+        streams.push((Kind::Data, data));
+        streams.push((Kind::Length, length));
+        return encoding(EncodingKind::DirectV2);
+    }
+
+    // the dictionary is sorted
+    let mut dictionary = distinct.keys().copied().collect::<Vec<_>>();
+    dictionary.sort_unstable();
+    for (i, key) in dictionary.iter().enumerate() {
+        distinct.insert(key, i as u32);
+    }
+
+    let indices = values
+        .iter()
+        .map(|x| distinct[x] as u64)
+        .collect::<Vec<_>>();
+    let mut data = vec![];
+    write_unsigned_rle_v2(&indices, &mut data);
+
+    let mut dictionary_data = vec![];
+    dictionary
+        .iter()
+        .for_each(|x| dictionary_data.extend_from_slice(x));
+    let lengths = dictionary
+        .iter()
+        .map(|x| x.len() as u64)
+        .collect::<Vec<_>>();
+    let mut length = vec![];
+    write_unsigned_rle_v2(&lengths, &mut length);
+
+    streams.push((Kind::Data, data));
+    streams.push((Kind::DictionaryData, dictionary_data));
+    streams.push((Kind::Length, length));
+    ColumnEncoding {
+        kind: Some(EncodingKind::DictionaryV2 as i32),
+        dictionary_size: Some(dictionary.len() as u32),
+    }
+}
+
+fn serialize_utf8<O: Offset>(
+    array: &Utf8Array<O>,
+    options: &WriteOptions,
+    streams: &mut Vec<(Kind, Vec<u8>)>,
+) -> (ColumnEncoding, Summary) {
+    let values = array.iter().flatten().collect::<Vec<_>>();
+    let summary = Summary::String {
+        min: values.iter().min().map(|x| x.to_string()),
+        max: values.iter().max().map(|x| x.to_string()),
+        sum: values.iter().map(|x| x.len() as i64).sum(),
+    };
+    let values = values.into_iter().map(|x| x.as_bytes()).collect::<Vec<_>>();
+    let encoding = serialize_binary_generic(&values, options, streams);
+    (encoding, summary)
+}
+
+fn serialize_binary<O: Offset>(
+    array: &BinaryArray<O>,
+    streams: &mut Vec<(Kind, Vec<u8>)>,
+) -> (ColumnEncoding, Summary) {
+    let values = array.iter().flatten().collect::<Vec<_>>();
+    let summary = Summary::Binary {
+        sum: values.iter().map(|x| x.len() as i64).sum(),
+    };
+    // ORC only supports dictionary encoding for strings
+    let options = WriteOptions {
+        dictionary_key_size_threshold: 0.0,
+    };
+    let encoding = serialize_binary_generic(&values, &options, streams);
+    (encoding, summary)
+}
+
+/// Encodes nanoseconds as ORC does: trailing decimal zeros are removed and their
+/// number, minus one, is stored in the 3 least significant bits.
+fn encode_nanos(nanos: i64) -> u64 {
+    let mut value = nanos as u64;
+    let mut zeros = 0;
+    while value != 0 && value.is_multiple_of(10) && zeros < 8 {
+        value /= 10;
+        zeros += 1;
+    }
+    if zeros >= 2 {
+        (value << 3) | (zeros - 1)
+    } else {
+        (nanos as u64) << 3
+    }
+}
+
+/// Serializes a timestamp array as seconds since [`ORC_EPOCH`] and nanoseconds.
+///
+/// Like other ORC writers, the seconds of timestamps before the unix epoch with a
+/// sub-millisecond component are rounded towards zero.
+fn serialize_timestamp(
+    array: &PrimitiveArray<i64>,
+    unit: TimeUnit,
+    streams: &mut Vec<(Kind, Vec<u8>)>,
+) -> Result<Summary> {
+    let (factor, nanos_factor) = match unit {
+        TimeUnit::Second => (1, 1_000_000_000),
+        TimeUnit::Millisecond => (1_000, 1_000_000),
+        TimeUnit::Microsecond => (1_000_000, 1_000),
+        TimeUnit::Nanosecond => (1_000_000_000, 1),
+    };
+
+    let mut seconds = vec![];
+    let mut nanos = vec![];
+    let mut min = None;
+    let mut max = None;
+    for value in array.iter().flatten() {
+        let mut second = value.div_euclid(factor);
+        let nano = value.rem_euclid(factor) * nanos_factor;
+        let millis = second.saturating_mul(1_000) + nano / 1_000_000;
+        min = Some(min.map(|x: i64| x.min(millis)).unwrap_or(millis));
+        max = Some(max.map(|x: i64| x.max(millis)).unwrap_or(millis));
+
+        if second < 0 && nano > 999_999 {
+            second += 1;
+        }
+        seconds.push(second.checked_sub(ORC_EPOCH).ok_or_else(|| {
+            Error::InvalidArgumentError("Timestamp out of range of ORC".to_string())
+        })?);
+        nanos.push(encode_nanos(nano));
+    }
+
+    let mut data = vec![];
+    write_signed_rle_v2(seconds.into_iter(), &mut data);
+    let mut secondary = vec![];
+    write_unsigned_rle_v2(&nanos, &mut secondary);
+    streams.push((Kind::Data, data));
+    streams.push((Kind::Secondary, secondary));
+    Ok(Summary::Timestamp { min, max })
+}
+
+fn serialize_decimal(
+    array: &PrimitiveArray<i128>,
+    scale: usize,
+    streams: &mut Vec<(Kind, Vec<u8>)>,
+) -> Summary {
+    let values = array.iter().flatten().copied().collect::<Vec<_>>();
+
+    let mut data = vec![];
+    values
+        .iter()
+        .for_each(|x| write_signed_varint(*x, &mut data));
+    let mut secondary = vec![];
+    write_signed_rle_v2(
+        std::iter::repeat_n(scale as i64, values.len()),
+        &mut secondary,
+    );
+    streams.push((Kind::Data, data));
+    streams.push((Kind::Secondary, secondary));
+
+    Summary::Decimal {
+        min: values.iter().copied().min(),
+        max: values.iter().copied().max(),
+        sum: values.iter().try_fold(0i128, |acc, x| acc.checked_add(*x)),
+        scale,
+    }
+}
+
+fn serialize_lengths<O: Offset>(
+    offsets: &[O],
+    validity: Option<&Bitmap>,
+    streams: &mut Vec<(Kind, Vec<u8>)>,
+) {
+    let lengths = offsets
+        .windows(2)
+        .enumerate()
+        .filter(|(i, _)| validity.map(|x| x.get_bit(*i)).unwrap_or(true))
+        .map(|(_, w)| (w[1] - w[0]).to_usize() as u64)
+        .collect::<Vec<_>>();
+    let mut length = vec![];
+    write_unsigned_rle_v2(&lengths, &mut length);
+    streams.push((Kind::Length, length));
+}
+
+/// Serializes `array` into one or more [`EncodedColumn`]s (one per ORC column that it spans,
+/// in pre-order), appending them to `columns`.
+///
+/// Only the valid slots of `array` are serialized, and the children of nested arrays
+/// only contain the values of its valid slots.
+pub fn serialize(
+    array: &dyn Array,
+    options: &WriteOptions,
+    columns: &mut Vec<EncodedColumn>,
+) -> Result<()> {
+    let validity = array.validity().filter(|_| array.null_count() > 0);
+
+    let mut streams = vec![];
+    if let Some(validity) = validity {
+        let mut present = vec![];
+        write_boolean_rle(validity.iter(), &mut present);
+        streams.push((Kind::Present, present));
+    }
+
+    let mut children = vec![];
+    let (encoding, summary) = match array.data_type().to_logical_type() {
+        DataType::Boolean => {
+            let array = array.as_any().downcast_ref().unwrap();
+            let summary = serialize_bool(array, &mut streams);
+            (encoding(EncodingKind::Direct), summary)
+        }
+        DataType::Int8 => {
+            let array = array.as_any().downcast_ref().unwrap();
+            let summary = serialize_i8(array, &mut streams);
+            (encoding(EncodingKind::Direct), summary)
+        }
+        DataType::Int16 => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<i16>>()
+                .unwrap();
+            let values = serialize_integer(array, &mut streams);
+            (encoding(EncodingKind::DirectV2), integer_summary(&values))
+        }
+        DataType::Int32 => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<i32>>()
+                .unwrap();
+            let values = serialize_integer(array, &mut streams);
+            (encoding(EncodingKind::DirectV2), integer_summary(&values))
+        }
+        DataType::Int64 => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<i64>>()
+                .unwrap();
+            let values = serialize_integer(array, &mut streams);
+            (encoding(EncodingKind::DirectV2), integer_summary(&values))
+        }
+        DataType::Date32 => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<i32>>()
+                .unwrap();
+            let values = serialize_integer(array, &mut streams);
+            let summary = Summary::Date {
+                min: values.iter().min().map(|x| *x as i32),
+                max: values.iter().max().map(|x| *x as i32),
+            };
+            (encoding(EncodingKind::DirectV2), summary)
+        }
+        DataType::Float32 => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<f32>>()
+                .unwrap();
+            let summary = serialize_float(array, &mut streams);
+            (encoding(EncodingKind::Direct), summary)
+        }
+        DataType::Float64 => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<f64>>()
+                .unwrap();
+            let summary = serialize_float(array, &mut streams);
+            (encoding(EncodingKind::Direct), summary)
+        }
+        DataType::Timestamp(unit, None) => {
+            let array = array.as_any().downcast_ref().unwrap();
+            let summary = serialize_timestamp(array, *unit, &mut streams)?;
+            (encoding(EncodingKind::DirectV2), summary)
+        }
+        DataType::Decimal(_, scale) => {
+            let array = array.as_any().downcast_ref().unwrap();
+            let summary = serialize_decimal(array, *scale, &mut streams);
+            (encoding(EncodingKind::DirectV2), summary)
+        }
+        DataType::Utf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+            serialize_utf8(array, options, &mut streams)
+        }
+        DataType::LargeUtf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i64>>().unwrap();
+            serialize_utf8(array, options, &mut streams)
+        }
+        DataType::Binary => {
+            let array = array.as_any().downcast_ref::<BinaryArray<i32>>().unwrap();
+            serialize_binary(array, &mut streams)
+        }
+        DataType::LargeBinary => {
+            let array = array.as_any().downcast_ref::<BinaryArray<i64>>().unwrap();
+            serialize_binary(array, &mut streams)
+        }
+        DataType::List(_) => {
+            let array = array.as_any().downcast_ref::<ListArray<i32>>().unwrap();
+            serialize_lengths(array.offsets(), validity, &mut streams);
+            let ranges = list_ranges(array.offsets(), validity);
+            children.push(take_ranges(array.values().as_ref(), &ranges));
+            (encoding(EncodingKind::DirectV2), Summary::None)
+        }
+        DataType::LargeList(_) => {
+            let array = array.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+            serialize_lengths(array.offsets(), validity, &mut streams);
+            let ranges = list_ranges(array.offsets(), validity);
+            children.push(take_ranges(array.values().as_ref(), &ranges));
+            (encoding(EncodingKind::DirectV2), Summary::None)
+        }
+        DataType::Map(_, _) => {
+            let array = array.as_any().downcast_ref::<MapArray>().unwrap();
+            serialize_lengths(array.offsets(), validity, &mut streams);
+            let ranges = list_ranges(array.offsets(), validity);
+            let entries = take_ranges(array.field().as_ref(), &ranges);
+            let entries = entries.as_any().downcast_ref::<StructArray>().unwrap();
+            // the keys and values are the children of the map
+            children.extend(entries.values().iter().cloned());
+            (encoding(EncodingKind::DirectV2), Summary::None)
+        }
+        DataType::Struct(_) => {
+            let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+            let ranges = valid_ranges(validity, array.len());
+            children.extend(
+                array
+                    .values()
+                    .iter()
+                    .map(|x| take_ranges(x.as_ref(), &ranges)),
+            );
+            (encoding(EncodingKind::Direct), Summary::None)
+        }
+        other => return Err(Error::nyi(format!("Writing {other:?} to ORC"))),
+    };
+
+    columns.push(EncodedColumn {
+        streams,
+        encoding,
+        statistics: Statistics {
+            number_of_values: (array.len() - array.null_count()) as u64,
+            has_null: array.null_count() > 0,
+            summary,
+        },
+    });
+
+    children
+        .iter()
+        .try_for_each(|child| serialize(child.as_ref(), options, columns))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nanos() {
+        assert_eq!(encode_nanos(0), 0);
+        assert_eq!(encode_nanos(1), 1 << 3);
+        assert_eq!(encode_nanos(10), 10 << 3);
+        assert_eq!(encode_nanos(500_000_000), (5 << 3) | 7);
+        assert_eq!(encode_nanos(1_000), (1 << 3) | 2);
+    }
+}
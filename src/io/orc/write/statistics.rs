@@ -0,0 +1,242 @@
+use orc_format::proto::{
+    BinaryStatistics, BucketStatistics, ColumnStatistics, DateStatistics, DecimalStatistics,
+    DoubleStatistics, IntegerStatistics, StringStatistics, TimestampStatistics,
+};
+
+/// Type-specific statistics of an ORC column
+#[derive(Debug, Clone, PartialEq)]
+pub enum Summary {
+    /// Types without type-specific statistics, e.g. lists and structs
+    None,
+    Boolean {
+        trues: u64,
+    },
+    Integer {
+        min: Option<i64>,
+        max: Option<i64>,
+        sum: Option<i64>,
+    },
+    Double {
+        min: Option<f64>,
+        max: Option<f64>,
+        sum: f64,
+    },
+    String {
+        min: Option<String>,
+        max: Option<String>,
+        sum: i64,
+    },
+    Binary {
+        sum: i64,
+    },
+    Date {
+        min: Option<i32>,
+        max: Option<i32>,
+    },
+    /// In milliseconds since the unix epoch
+    Timestamp {
+        min: Option<i64>,
+        max: Option<i64>,
+    },
+    Decimal {
+        min: Option<i128>,
+        max: Option<i128>,
+        sum: Option<i128>,
+        scale: usize,
+    },
+}
+
+/// Statistics of an ORC column, that can be merged across stripes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics {
+    pub number_of_values: u64,
+    pub has_null: bool,
+    pub summary: Summary,
+}
+
+fn merge_min<T: PartialOrd>(lhs: Option<T>, rhs: Option<T>) -> Option<T> {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => Some(if rhs < lhs { rhs } else { lhs }),
+        (lhs, rhs) => lhs.or(rhs),
+    }
+}
+
+fn merge_max<T: PartialOrd>(lhs: Option<T>, rhs: Option<T>) -> Option<T> {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => Some(if rhs > lhs { rhs } else { lhs }),
+        (lhs, rhs) => lhs.or(rhs),
+    }
+}
+
+impl Statistics {
+    /// Merges `other`, the statistics of the same column in another stripe, into `self`
+    pub fn merge(&mut self, other: &Self) {
+        self.number_of_values += other.number_of_values;
+        self.has_null |= other.has_null;
+        let summary = std::mem::replace(&mut self.summary, Summary::None);
+        self.summary = match (summary, &other.summary) {
+            (Summary::Boolean { trues }, Summary::Boolean { trues: other }) => Summary::Boolean {
+                trues: trues + other,
+            },
+            (
+                Summary::Integer { min, max, sum },
+                Summary::Integer {
+                    min: a,
+                    max: b,
+                    sum: c,
+                },
+            ) => Summary::Integer {
+                min: merge_min(min, *a),
+                max: merge_max(max, *b),
+                sum: sum.zip(*c).and_then(|(x, y)| x.checked_add(y)),
+            },
+            (
+                Summary::Double { min, max, sum },
+                Summary::Double {
+                    min: a,
+                    max: b,
+                    sum: c,
+                },
+            ) => Summary::Double {
+                min: merge_min(min, *a),
+                max: merge_max(max, *b),
+                sum: sum + c,
+            },
+            (
+                Summary::String { min, max, sum },
+                Summary::String {
+                    min: a,
+                    max: b,
+                    sum: c,
+                },
+            ) => Summary::String {
+                min: merge_min(min, a.clone()),
+                max: merge_max(max, b.clone()),
+                sum: sum + c,
+            },
+            (Summary::Binary { sum }, Summary::Binary { sum: other }) => {
+                Summary::Binary { sum: sum + other }
+            }
+            (Summary::Date { min, max }, Summary::Date { min: a, max: b }) => Summary::Date {
+                min: merge_min(min, *a),
+                max: merge_max(max, *b),
+            },
+            (Summary::Timestamp { min, max }, Summary::Timestamp { min: a, max: b }) => {
+                Summary::Timestamp {
+                    min: merge_min(min, *a),
+                    max: merge_max(max, *b),
+                }
+            }
+            (
+                Summary::Decimal {
+                    min,
+                    max,
+                    sum,
+                    scale,
+                },
+                Summary::Decimal {
+                    min: a,
+                    max: b,
+                    sum: c,
+                    ..
+                },
+            ) => Summary::Decimal {
+                min: merge_min(min, *a),
+                max: merge_max(max, *b),
+                sum: sum.zip(*c).and_then(|(x, y)| x.checked_add(y)),
+                scale,
+            },
+            (summary, _) => summary,
+        };
+    }
+
+    /// Converts itself to ORC's representation
+    pub fn to_proto(&self) -> ColumnStatistics {
+        let mut statistics = ColumnStatistics {
+            number_of_values: Some(self.number_of_values),
+            has_null: Some(self.has_null),
+            ..Default::default()
+        };
+        match &self.summary {
+            Summary::None => {}
+            Summary::Boolean { trues } => {
+                statistics.bucket_statistics = Some(BucketStatistics {
+                    count: vec![*trues],
+                })
+            }
+            Summary::Integer { min, max, sum } => {
+                statistics.int_statistics = Some(IntegerStatistics {
+                    minimum: *min,
+                    maximum: *max,
+                    sum: *sum,
+                })
+            }
+            Summary::Double { min, max, sum } => {
+                statistics.double_statistics = Some(DoubleStatistics {
+                    minimum: *min,
+                    maximum: *max,
+                    sum: Some(*sum),
+                })
+            }
+            Summary::String { min, max, sum } => {
+                statistics.string_statistics = Some(StringStatistics {
+                    minimum: min.clone(),
+                    maximum: max.clone(),
+                    sum: Some(*sum),
+                })
+            }
+            Summary::Binary { sum } => {
+                statistics.binary_statistics = Some(BinaryStatistics { sum: Some(*sum) })
+            }
+            Summary::Date { min, max } => {
+                statistics.date_statistics = Some(DateStatistics {
+                    minimum: *min,
+                    maximum: *max,
+                })
+            }
+            Summary::Timestamp { min, max } => {
+                statistics.timestamp_statistics = Some(TimestampStatistics {
+                    minimum: *min,
+                    maximum: *max,
+                })
+            }
+            Summary::Decimal {
+                min,
+                max,
+                sum,
+                scale,
+            } => {
+                statistics.decimal_statistics = Some(DecimalStatistics {
+                    minimum: min.map(|x| format_decimal(x, *scale)),
+                    maximum: max.map(|x| format_decimal(x, *scale)),
+                    sum: sum.map(|x| format_decimal(x, *scale)),
+                })
+            }
+        };
+        statistics
+    }
+}
+
+/// Formats a decimal `value` with `scale` as a string, e.g. `-1.05`.
+fn format_decimal(value: i128, scale: usize) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let sign = if value < 0 { "-" } else { "" };
+    if scale == 0 {
+        return format!("{sign}{digits}");
+    }
+    let digits = format!("{digits:0>width$}", width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    format!("{sign}{integer}.{fraction}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimal() {
+        assert_eq!(format_decimal(-105, 2), "-1.05");
+        assert_eq!(format_decimal(5, 3), "0.005");
+        assert_eq!(format_decimal(123, 0), "123");
+    }
+}
@@ -0,0 +1,163 @@
+//! APIs to read and probe parquet's split-block bloom filters.
+//!
+//! Bloom filters complement statistics for point lookups: a row group whose bloom filter
+//! does not contain a value does not contain it, even if the value is within its min and max.
+use std::io::{Read, Seek};
+
+use parquet2::types::NativeType as ParquetNativeType;
+
+pub use parquet2::bloom_filter::{hash_byte, hash_native, insert, is_in_set};
+
+use crate::datatypes::{DataType, Field};
+use crate::error::{Error, Result};
+use crate::scalar::*;
+use crate::types::NativeType;
+
+use super::{get_field_columns, ColumnChunkMetaData, RowGroupMetaData};
+use crate::io::parquet::write::decimal_length_from_precision;
+
+/// Reads the bloom filter of `column` into `bitset`.
+/// `bitset` is left empty when the column chunk has no bloom filter or its algorithm,
+/// hash or compression is not supported.
+/// # Errors
+/// Errors if the filter can't be read or its header can't be deserialized.
+pub fn read<R: Read + Seek>(
+    column: &ColumnChunkMetaData,
+    reader: &mut R,
+    bitset: &mut Vec<u8>,
+) -> Result<()> {
+    Ok(parquet2::bloom_filter::read(column, reader, bitset)?)
+}
+
+fn hash_primitive<T: NativeType, P: ParquetNativeType>(
+    value: &dyn Scalar,
+    f: impl Fn(T) -> P,
+) -> Option<u64> {
+    let value = value.as_any().downcast_ref::<PrimitiveScalar<T>>().unwrap();
+    value.value().map(|x| hash_native(f(x)))
+}
+
+/// Returns the hash of `value` as inserted in the bloom filter of a column of its data type,
+/// or `None` if `value` is null.
+/// # Errors
+/// Errors if bloom filters are not written for columns of the data type of `value`.
+pub fn hash_scalar(value: &dyn Scalar) -> Result<Option<u64>> {
+    // casts below MUST match the casts done when writing pages.
+    Ok(match value.data_type().to_logical_type() {
+        DataType::UInt8 => hash_primitive(value, |x: u8| x as i32),
+        DataType::UInt16 => hash_primitive(value, |x: u16| x as i32),
+        DataType::UInt32 => hash_primitive(value, |x: u32| x as i32),
+        DataType::UInt64 => hash_primitive(value, |x: u64| x as i64),
+        DataType::Int8 => hash_primitive(value, |x: i8| x as i32),
+        DataType::Int16 => hash_primitive(value, |x: i16| x as i32),
+        DataType::Int32 | DataType::Date32 | DataType::Time32(_) => {
+            hash_primitive(value, |x: i32| x)
+        }
+        DataType::Int64
+        | DataType::Date64
+        | DataType::Time64(_)
+        | DataType::Timestamp(_, _)
+        | DataType::Duration(_) => hash_primitive(value, |x: i64| x),
+        DataType::Float32 => hash_primitive(value, |x: f32| x),
+        DataType::Float64 => hash_primitive(value, |x: f64| x),
+        DataType::Decimal(precision, _) => {
+            let precision = *precision;
+            if precision <= 9 {
+                hash_primitive(value, |x: i128| x as i32)
+            } else if precision <= 18 {
+                hash_primitive(value, |x: i128| x as i64)
+            } else {
+                let size = decimal_length_from_precision(precision);
+                let value = value
+                    .as_any()
+                    .downcast_ref::<PrimitiveScalar<i128>>()
+                    .unwrap();
+                value
+                    .value()
+                    .map(|x| hash_byte(&x.to_be_bytes()[16 - size..]))
+            }
+        }
+        DataType::Utf8 => {
+            let value = value.as_any().downcast_ref::<Utf8Scalar<i32>>().unwrap();
+            value.value().map(hash_byte)
+        }
+        DataType::LargeUtf8 => {
+            let value = value.as_any().downcast_ref::<Utf8Scalar<i64>>().unwrap();
+            value.value().map(hash_byte)
+        }
+        DataType::Binary => {
+            let value = value.as_any().downcast_ref::<BinaryScalar<i32>>().unwrap();
+            value.value().map(hash_byte)
+        }
+        DataType::LargeBinary => {
+            let value = value.as_any().downcast_ref::<BinaryScalar<i64>>().unwrap();
+            value.value().map(hash_byte)
+        }
+        DataType::FixedSizeBinary(_) => {
+            let value = value
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryScalar>()
+                .unwrap();
+            value.value().map(hash_byte)
+        }
+        other => {
+            return Err(Error::nyi(format!(
+                "Bloom filters of parquet columns of type {other:?}"
+            )))
+        }
+    })
+}
+
+/// Returns whether a column chunk whose bloom filter is `bitset` may contain `value`.
+/// This is always `true` when `bitset` is empty (i.e. the column chunk has no bloom filter)
+/// or when `value` is null, since bloom filters only contain non-null values.
+/// # Errors
+/// Errors if bloom filters are not written for columns of the data type of `value`.
+pub fn may_contain(bitset: &[u8], value: &dyn Scalar) -> Result<bool> {
+    if bitset.is_empty() {
+        return Ok(true);
+    }
+    Ok(match hash_scalar(value)? {
+        Some(hash) => is_in_set(bitset, hash),
+        None => true,
+    })
+}
+
+/// Returns the row groups of `row_groups` whose bloom filter of the column `field`
+/// may contain `value`. Row groups without a bloom filter for the column are always kept.
+/// # Errors
+/// Errors if `field` is nested, if `value` is not of the data type of `field`, or if the
+/// bloom filters can't be read.
+pub fn prune_row_groups<R: Read + Seek>(
+    reader: &mut R,
+    field: &Field,
+    row_groups: Vec<RowGroupMetaData>,
+    value: &dyn Scalar,
+) -> Result<Vec<RowGroupMetaData>> {
+    let data_type = match field.data_type.to_logical_type() {
+        DataType::Dictionary(_, values, _) => values.as_ref(),
+        other => other,
+    };
+    if value.data_type().to_logical_type() != data_type.to_logical_type() {
+        return Err(Error::InvalidArgumentError(format!(
+            "The value {value:?} must be of the type of the field \"{}\", {data_type:?}",
+            field.name
+        )));
+    }
+
+    let mut bitset = vec![];
+    let mut selected = Vec::with_capacity(row_groups.len());
+    for row_group in row_groups {
+        let columns = get_field_columns(row_group.columns(), &field.name);
+        if columns.len() != 1 {
+            return Err(Error::nyi(
+                "Bloom filters of nested parquet columns".to_string(),
+            ));
+        }
+        read(columns[0], reader, &mut bitset)?;
+        if may_contain(&bitset, value)? {
+            selected.push(row_group);
+        }
+    }
+    Ok(selected)
+}
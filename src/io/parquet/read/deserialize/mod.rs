@@ -15,7 +15,9 @@ use parquet2::read::get_page_iterator as _get_page_iterator;
 use parquet2::schema::types::PrimitiveType;
 
 use crate::{
-    array::{Array, DictionaryKey, FixedSizeListArray, ListArray},
+    array::{
+        growable::make_growable, Array, DictionaryKey, FixedSizeListArray, ListArray, MapArray,
+    },
     datatypes::{DataType, Field, IntervalUnit},
     error::Result,
 };
@@ -70,11 +72,43 @@ fn create_list(
                 validity.and_then(|x| x.into()),
             ))
         }
-        DataType::FixedSizeList(_, _) => Box::new(FixedSizeListArray::new(
-            data_type,
-            values,
-            validity.and_then(|x| x.into()),
-        )),
+        DataType::FixedSizeList(_, size) => {
+            offsets.push(values.len() as i64);
+            let size = *size;
+
+            // null entries are stored without values in parquet, but take `size` slots in arrow
+            let values = if offsets.windows(2).all(|x| (x[1] - x[0]) as usize == size) {
+                values
+            } else {
+                let mut growable = make_growable(&[values.as_ref()], true, offsets.len() * size);
+                for window in offsets.windows(2) {
+                    let length = (window[1] - window[0]) as usize;
+                    if length == size {
+                        growable.extend(0, window[0] as usize, length);
+                    } else {
+                        growable.extend_validity(size);
+                    }
+                }
+                growable.as_box()
+            };
+
+            Box::new(FixedSizeListArray::new(
+                data_type,
+                values,
+                validity.and_then(|x| x.into()),
+            ))
+        }
+        DataType::Map(_, _) => {
+            offsets.push(values.len() as i64);
+
+            let offsets = offsets.iter().map(|x| *x as i32).collect::<Vec<_>>();
+            Box::new(MapArray::new(
+                data_type,
+                offsets.into(),
+                values,
+                validity.and_then(|x| x.into()),
+            ))
+        }
         _ => unreachable!(),
     }
 }
@@ -146,6 +180,13 @@ fn n_columns(data_type: &DataType) -> usize {
                 unreachable!()
             }
         }
+        Map => {
+            if let DataType::Map(inner, _) = data_type.to_logical_type() {
+                n_columns(&inner.data_type)
+            } else {
+                unreachable!()
+            }
+        }
         _ => todo!(),
     }
 }
@@ -1,7 +1,7 @@
 use parquet2::schema::types::PrimitiveType;
 
 use crate::{
-    array::{BinaryArray, MapArray, Utf8Array},
+    array::{BinaryArray, Utf8Array},
     datatypes::{DataType, Field},
     error::{Error, Result},
 };
@@ -292,14 +292,9 @@ where
                     chunk_size,
                 )?;
                 Box::new(iter.map(move |x| {
-                    let (nested, inner) = x?;
-                    let array = MapArray::new(
-                        field.data_type().clone(),
-                        vec![0, inner.len() as i32].into(),
-                        inner,
-                        None,
-                    );
-                    Ok((nested, array.boxed()))
+                    let (mut nested, array) = x?;
+                    let array = create_list(field.data_type().clone(), &mut nested, array);
+                    Ok((nested, array))
                 }))
             }
             other => {
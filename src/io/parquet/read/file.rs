@@ -43,31 +43,27 @@ impl<R: Read + Seek> FileReader<R> {
         }
     }
 
-    fn next_row_group(&mut self) -> Result<Option<RowGroupDeserializer>> {
-        let result = self.row_groups.next().transpose()?;
-
-        // If current_row_group is None, then there will be no elements to remove.
-        if self.current_row_group.is_some() {
-            self.remaining_rows = self.remaining_rows.saturating_sub(
-                result
-                    .as_ref()
-                    .map(|x| x.num_rows())
-                    .unwrap_or(self.remaining_rows),
-            );
-        }
-        Ok(result)
+    /// Returns this [`FileReader`] yielding only the rows that match `predicate`.
+    ///
+    /// Row groups and pages (when the file has a page index and no `page_indexes` were
+    /// declared) that can't contain matching rows are not read, and the deserialized rows are
+    /// filtered by `predicate`. `limit` applies to the filtered rows.
+    ///
+    /// The columns of `predicate` must be part of the schema.
+    /// # Errors
+    /// Errors if the predicate is not valid for the schema.
+    #[cfg(feature = "io_parquet_predicate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "io_parquet_predicate")))]
+    pub fn with_predicate(mut self, predicate: super::predicate::Predicate) -> Result<Self> {
+        self.row_groups = self.row_groups.with_predicate(predicate)?;
+        Ok(self)
     }
-}
 
-impl<R: Read + Seek> Iterator for FileReader<R> {
-    type Item = Result<Chunk<Box<dyn Array>>>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.remaining_rows == 0 {
-            // reached the limit
-            return None;
-        }
+    fn next_row_group(&mut self) -> Result<Option<RowGroupDeserializer>> {
+        self.row_groups.next().transpose()
+    }
 
+    fn next_chunk(&mut self) -> Option<Result<Chunk<Box<dyn Array>>>> {
         if let Some(row_group) = &mut self.current_row_group {
             match row_group.next() {
                 // no more chunks in the current row group => try a new one
@@ -75,7 +71,7 @@ impl<R: Read + Seek> Iterator for FileReader<R> {
                     Ok(Some(row_group)) => {
                         self.current_row_group = Some(row_group);
                         // new found => pull again
-                        self.next()
+                        self.next_chunk()
                     }
                     Ok(None) => {
                         self.current_row_group = None;
@@ -89,7 +85,7 @@ impl<R: Read + Seek> Iterator for FileReader<R> {
             match self.next_row_group() {
                 Ok(Some(row_group)) => {
                     self.current_row_group = Some(row_group);
-                    self.next()
+                    self.next_chunk()
                 }
                 Ok(None) => {
                     self.current_row_group = None;
@@ -101,6 +97,35 @@ impl<R: Read + Seek> Iterator for FileReader<R> {
     }
 }
 
+impl<R: Read + Seek> Iterator for FileReader<R> {
+    type Item = Result<Chunk<Box<dyn Array>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining_rows == 0 {
+            // reached the limit
+            return None;
+        }
+
+        let chunk = match self.next_chunk()? {
+            Ok(chunk) => chunk,
+            Err(e) => return Some(Err(e)),
+        };
+        // row groups are limited before deserialization unless rows are filtered
+        let chunk = if chunk.len() > self.remaining_rows {
+            let arrays = chunk
+                .into_arrays()
+                .into_iter()
+                .map(|array| array.slice(0, self.remaining_rows))
+                .collect();
+            Chunk::new(arrays)
+        } else {
+            chunk
+        };
+        self.remaining_rows -= chunk.len();
+        Some(Ok(chunk))
+    }
+}
+
 /// An [`Iterator<Item=RowGroupDeserializer>`] from row groups of a parquet file.
 ///
 /// # Implementation
@@ -113,6 +138,8 @@ pub struct RowGroupReader<R: Read + Seek> {
     chunk_size: Option<usize>,
     remaining_rows: usize,
     page_indexes: Option<std::vec::IntoIter<Vec<Vec<Vec<FilteredPage>>>>>,
+    #[cfg(feature = "io_parquet_predicate")]
+    predicate: Option<super::predicate::Predicate>,
 }
 
 impl<R: Read + Seek> RowGroupReader<R> {
@@ -135,9 +162,27 @@ impl<R: Read + Seek> RowGroupReader<R> {
             chunk_size,
             remaining_rows: limit.unwrap_or(usize::MAX),
             page_indexes: page_indexes.map(|pages| pages.into_iter()),
+            #[cfg(feature = "io_parquet_predicate")]
+            predicate: None,
         }
     }
 
+    /// Returns this [`RowGroupReader`] skipping the row groups and pages that can't contain
+    /// rows matching `predicate`, and whose [`RowGroupDeserializer`]s only yield matching rows.
+    ///
+    /// Since the number of matching rows is only known once they are deserialized, `limit`
+    /// no longer applies; use [`FileReader::with_predicate`] to limit the filtered rows.
+    /// # Errors
+    /// Errors if the predicate is not valid for the schema.
+    #[cfg(feature = "io_parquet_predicate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "io_parquet_predicate")))]
+    pub fn with_predicate(mut self, predicate: super::predicate::Predicate) -> Result<Self> {
+        predicate.validate(&self.schema.fields)?;
+        self.remaining_rows = usize::MAX;
+        self.predicate = Some(predicate);
+        Ok(self)
+    }
+
     #[inline]
     fn _next(&mut self) -> Result<Option<RowGroupDeserializer>> {
         if self.schema.fields.is_empty() {
@@ -156,21 +201,16 @@ impl<R: Read + Seek> RowGroupReader<R> {
 
         let pages = self.page_indexes.as_mut().and_then(|iter| iter.next());
 
+        #[cfg(feature = "io_parquet_predicate")]
+        if let Some(predicate) = self.predicate.clone() {
+            return self.next_filtered(row_group, pages, predicate);
+        }
+
         // the number of rows depends on whether indexes are selected or not.
+        // first field, first column within that field
         let num_rows = pages
             .as_ref()
-            .map(|x| {
-                // first field, first column within that field
-                x[0][0]
-                    .iter()
-                    .map(|page| {
-                        page.selected_rows
-                            .iter()
-                            .map(|interval| interval.length)
-                            .sum::<usize>()
-                    })
-                    .sum()
-            })
+            .map(|x| selected_rows(&x[0][0]))
             .unwrap_or_else(|| row_group.num_rows());
 
         let column_chunks = read_columns_many(
@@ -186,6 +226,47 @@ impl<R: Read + Seek> RowGroupReader<R> {
         self.remaining_rows = self.remaining_rows.saturating_sub(num_rows);
         Ok(Some(result))
     }
+
+    #[cfg(feature = "io_parquet_predicate")]
+    fn next_filtered(
+        &mut self,
+        row_group: RowGroupMetaData,
+        pages: Option<Vec<Vec<Vec<FilteredPage>>>>,
+        predicate: super::predicate::Predicate,
+    ) -> Result<Option<RowGroupDeserializer>> {
+        let fields = &self.schema.fields;
+        if predicate
+            .prune_row_groups(fields, vec![row_group.clone()])?
+            .is_empty()
+        {
+            return self._next();
+        }
+
+        let pages = match pages {
+            Some(pages) => Some(pages),
+            None => predicate.read_pages(&mut self.reader, &row_group, fields)?,
+        };
+        let num_rows = pages
+            .as_ref()
+            .map(|x| selected_rows(&x[0][0]))
+            .unwrap_or_else(|| row_group.num_rows());
+        if num_rows == 0 {
+            return self._next();
+        }
+
+        let column_chunks = read_columns_many(
+            &mut self.reader,
+            &row_group,
+            fields.clone(),
+            self.chunk_size,
+            None,
+            pages,
+        )?;
+
+        RowGroupDeserializer::new(column_chunks, num_rows, None)
+            .with_predicate(predicate, fields.clone())
+            .map(Some)
+    }
 }
 
 impl<R: Read + Seek> Iterator for RowGroupReader<R> {
@@ -199,3 +280,16 @@ impl<R: Read + Seek> Iterator for RowGroupReader<R> {
         self.row_groups.size_hint()
     }
 }
+
+/// Returns the number of rows selected by `pages`
+fn selected_rows(pages: &[FilteredPage]) -> usize {
+    pages
+        .iter()
+        .map(|page| {
+            page.selected_rows
+                .iter()
+                .map(|interval| interval.length)
+                .sum::<usize>()
+        })
+        .sum()
+}
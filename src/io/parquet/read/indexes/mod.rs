@@ -349,3 +349,51 @@ pub fn read_filtered_pages<
         })
         .collect()
 }
+
+/// Returns the page statistics of each field in `fields` together with the (row) intervals
+/// of its pages, one [`Vec<Interval>`] per parquet column of the field.
+#[cfg(feature = "io_parquet_predicate")]
+pub(super) fn read_page_statistics<R: Read + Seek>(
+    reader: &mut R,
+    row_group: &RowGroupMetaData,
+    fields: &[Field],
+) -> Result<(Vec<FieldPageStatistics>, Vec<Vec<Vec<Interval>>>), Error> {
+    let num_rows = row_group.num_rows();
+
+    let locations = read_pages_locations(reader, row_group.columns())?;
+    let intervals = fields
+        .iter()
+        .map(|field| {
+            get_field_pages(row_group.columns(), &locations, &field.name)
+                .into_iter()
+                .map(|locations| Ok(compute_page_row_intervals(locations, num_rows)?))
+                .collect::<Result<Vec<_>, Error>>()
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let indexes = read_columns_indexes(reader, row_group.columns(), fields)?;
+    Ok((indexes, intervals))
+}
+
+/// Returns the set of [`FilteredPage`] of each field in `fields` that overlap with `intervals`,
+/// in the same format as [`read_filtered_pages`].
+#[cfg(feature = "io_parquet_predicate")]
+pub(super) fn select_field_pages<R: Read + Seek>(
+    reader: &mut R,
+    row_group: &RowGroupMetaData,
+    fields: &[Field],
+    intervals: &[Interval],
+) -> Result<Vec<Vec<Vec<FilteredPage>>>, Error> {
+    let num_rows = row_group.num_rows();
+
+    let locations = read_pages_locations(reader, row_group.columns())?;
+    fields
+        .iter()
+        .map(|field| {
+            get_field_pages(row_group.columns(), &locations, &field.name)
+                .into_iter()
+                .map(|locations| Ok(select_pages(intervals, locations, num_rows)?))
+                .collect::<Result<Vec<_>, Error>>()
+        })
+        .collect()
+}
@@ -1,9 +1,15 @@
 //! APIs to read from Parquet format.
 #![allow(clippy::type_complexity)]
 
+#[cfg(feature = "io_parquet_bloom_filter")]
+#[cfg_attr(docsrs, doc(cfg(feature = "io_parquet_bloom_filter")))]
+pub mod bloom_filter;
 mod deserialize;
 mod file;
 pub mod indexes;
+#[cfg(feature = "io_parquet_predicate")]
+#[cfg_attr(docsrs, doc(cfg(feature = "io_parquet_predicate")))]
+pub mod predicate;
 mod row_group;
 pub mod schema;
 pub mod statistics;
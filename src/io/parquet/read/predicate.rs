@@ -0,0 +1,636 @@
+//! APIs to push predicates down to the parquet reader.
+//!
+//! A [`Predicate`] is used at three levels:
+//! * row groups whose statistics show that no row can match are skipped ([`Predicate::prune_row_groups`])
+//! * pages whose statistics (page index) show that no row can match are not read ([`Predicate::read_pages`])
+//! * the remaining rows are filtered exactly once deserialized ([`Predicate::filter`])
+use std::io::{Read, Seek};
+
+use parquet2::indexes::{FilteredPage, Interval};
+
+use crate::array::{Array, BooleanArray, UInt64Array};
+use crate::bitmap::Bitmap;
+use crate::chunk::Chunk;
+use crate::compute::boolean::{is_not_null, is_null, not};
+use crate::compute::boolean_kleene::{and, or};
+use crate::compute::comparison;
+use crate::compute::filter::filter_chunk;
+use crate::datatypes::{DataType, Field, PhysicalType};
+use crate::error::{Error, Result};
+use crate::scalar::{PrimitiveScalar, Scalar};
+
+use super::indexes::{
+    has_indexes, read_page_statistics, select_field_pages, ColumnPageStatistics,
+    FieldPageStatistics,
+};
+use super::{statistics, RowGroupMetaData};
+
+/// A comparison between a column and a literal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    /// `==`
+    Eq,
+    /// `!=`
+    NotEq,
+    /// `<`
+    Lt,
+    /// `<=`
+    LtEq,
+    /// `>`
+    Gt,
+    /// `>=`
+    GtEq,
+}
+
+impl Comparison {
+    fn negate(self) -> Self {
+        match self {
+            Self::Eq => Self::NotEq,
+            Self::NotEq => Self::Eq,
+            Self::Lt => Self::GtEq,
+            Self::LtEq => Self::Gt,
+            Self::Gt => Self::LtEq,
+            Self::GtEq => Self::Lt,
+        }
+    }
+}
+
+/// A boolean expression over the (top-level) columns of a parquet file.
+///
+/// A row matches the predicate when the expression evaluates to `true`; rows for which it
+/// evaluates to `false` or null (e.g. comparisons against null values) are filtered out.
+#[derive(Debug, Clone)]
+pub enum Predicate {
+    /// `column <op> value`
+    Compare(String, Comparison, Box<dyn Scalar>),
+    /// `column IS NULL`
+    IsNull(String),
+    /// `column IS NOT NULL`
+    IsNotNull(String),
+    /// `column IN (values)`
+    In(String, Vec<Box<dyn Scalar>>),
+    /// Both predicates
+    And(Box<Predicate>, Box<Predicate>),
+    /// Either predicate
+    Or(Box<Predicate>, Box<Predicate>),
+    /// The negation of the predicate
+    Not(Box<Predicate>),
+}
+
+impl Predicate {
+    /// Returns the predicate `column <op> value`
+    pub fn compare<S: Scalar>(column: impl Into<String>, op: Comparison, value: S) -> Self {
+        Self::Compare(column.into(), op, Box::new(value))
+    }
+
+    /// Returns the predicate `column IS NULL`
+    pub fn is_null(column: impl Into<String>) -> Self {
+        Self::IsNull(column.into())
+    }
+
+    /// Returns the predicate `column IS NOT NULL`
+    pub fn is_not_null(column: impl Into<String>) -> Self {
+        Self::IsNotNull(column.into())
+    }
+
+    /// Returns the predicate `column IN (values)`
+    pub fn is_in(column: impl Into<String>, values: Vec<Box<dyn Scalar>>) -> Self {
+        Self::In(column.into(), values)
+    }
+
+    /// Returns the predicate `self AND other`
+    pub fn and(self, other: Self) -> Self {
+        Self::And(Box::new(self), Box::new(other))
+    }
+
+    /// Returns the predicate `self OR other`
+    pub fn or(self, other: Self) -> Self {
+        Self::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Returns the names of the columns used by this predicate, without repetitions
+    pub fn columns(&self) -> Vec<&str> {
+        let mut columns = vec![];
+        self.collect_columns(&mut columns);
+        columns
+    }
+
+    fn collect_columns<'a>(&'a self, columns: &mut Vec<&'a str>) {
+        match self {
+            Self::Compare(column, _, _)
+            | Self::IsNull(column)
+            | Self::IsNotNull(column)
+            | Self::In(column, _) => {
+                if !columns.contains(&column.as_str()) {
+                    columns.push(column)
+                }
+            }
+            Self::And(lhs, rhs) | Self::Or(lhs, rhs) => {
+                lhs.collect_columns(columns);
+                rhs.collect_columns(columns);
+            }
+            Self::Not(inner) => inner.collect_columns(columns),
+        }
+    }
+
+    /// Checks that every column of this predicate is a field of `fields` whose data type
+    /// supports the comparisons made against it.
+    /// # Errors
+    /// Errors if a column is not part of `fields`, if it is nested or a dictionary, or if a
+    /// literal has a different data type than its column.
+    pub fn validate(&self, fields: &[Field]) -> Result<()> {
+        match self {
+            Self::Compare(column, op, value) => {
+                let data_type = field_data_type(fields, column)?;
+                check_literal(column, data_type, value.as_ref())?;
+                let supported = match op {
+                    Comparison::Eq => comparison::can_eq_scalar(data_type),
+                    Comparison::NotEq => comparison::can_neq_scalar(data_type),
+                    Comparison::Lt => comparison::can_lt_scalar(data_type),
+                    Comparison::LtEq => comparison::can_lt_eq_scalar(data_type),
+                    Comparison::Gt => comparison::can_gt_scalar(data_type),
+                    Comparison::GtEq => comparison::can_gt_eq_scalar(data_type),
+                };
+                if !supported {
+                    return Err(Error::nyi(format!(
+                        "Comparison {op:?} of columns of type {data_type:?}"
+                    )));
+                }
+                Ok(())
+            }
+            Self::IsNull(column) | Self::IsNotNull(column) => {
+                field_data_type(fields, column).map(|_| ())
+            }
+            Self::In(column, values) => {
+                let data_type = field_data_type(fields, column)?;
+                values
+                    .iter()
+                    .try_for_each(|value| check_literal(column, data_type, value.as_ref()))?;
+                if !comparison::can_eq_scalar(data_type) {
+                    return Err(Error::nyi(format!("IN of columns of type {data_type:?}")));
+                }
+                Ok(())
+            }
+            Self::And(lhs, rhs) | Self::Or(lhs, rhs) => {
+                lhs.validate(fields)?;
+                rhs.validate(fields)
+            }
+            Self::Not(inner) => inner.validate(fields),
+        }
+    }
+
+    /// Returns an equivalent predicate without [`Predicate::Not`], pushing negations down
+    /// to the comparisons.
+    fn normalize(&self) -> Self {
+        match self {
+            Self::And(lhs, rhs) => lhs.normalize().and(rhs.normalize()),
+            Self::Or(lhs, rhs) => lhs.normalize().or(rhs.normalize()),
+            Self::Not(inner) => inner.negate(),
+            other => other.clone(),
+        }
+    }
+
+    /// Returns the normalized negation of this predicate.
+    ///
+    /// Under three-valued logic, `NOT (x < 1)` is `x >= 1` and `NOT (a AND b)` is
+    /// `NOT a OR NOT b`, which keeps null values filtered out.
+    fn negate(&self) -> Self {
+        match self {
+            Self::Compare(column, op, value) => {
+                Self::Compare(column.clone(), op.negate(), value.clone())
+            }
+            Self::IsNull(column) => Self::IsNotNull(column.clone()),
+            Self::IsNotNull(column) => Self::IsNull(column.clone()),
+            Self::In(column, values) => values
+                .iter()
+                .map(|value| Self::Compare(column.clone(), Comparison::NotEq, value.clone()))
+                .reduce(Self::and)
+                // `NOT IN ()` matches every row
+                .unwrap_or_else(|| {
+                    Self::is_null(column.clone()).or(Self::is_not_null(column.clone()))
+                }),
+            Self::And(lhs, rhs) => lhs.negate().or(rhs.negate()),
+            Self::Or(lhs, rhs) => lhs.negate().and(rhs.negate()),
+            Self::Not(inner) => inner.normalize(),
+        }
+    }
+
+    /// Evaluates whether each item (a row group or a page) summarized by statistics may
+    /// contain rows matching this (normalized) predicate. `statistics` returns the statistics of
+    /// a column by name. A null entry denotes that it is unknown (e.g. missing statistics).
+    fn may_match<'a, F>(&self, statistics: &F, num_rows: &UInt64Array) -> BooleanArray
+    where
+        F: Fn(&str) -> Option<StatisticsRef<'a>>,
+    {
+        let unknown = || BooleanArray::new_null(DataType::Boolean, num_rows.len());
+        match self {
+            Self::Compare(column, op, value) => statistics(column)
+                .map(|stats| stats.compare(*op, value.as_ref()))
+                .unwrap_or_else(unknown),
+            Self::IsNull(column) => statistics(column)
+                .map(|stats| {
+                    comparison::gt_scalar(stats.null_count, &PrimitiveScalar::from(Some(0u64)))
+                })
+                .unwrap_or_else(unknown),
+            Self::IsNotNull(column) => statistics(column)
+                .map(|stats| comparison::lt(stats.null_count, num_rows))
+                .unwrap_or_else(unknown),
+            Self::In(column, values) => statistics(column)
+                .map(|stats| {
+                    values.iter().fold(none(num_rows.len()), |acc, value| {
+                        or(&acc, &stats.compare(Comparison::Eq, value.as_ref()))
+                    })
+                })
+                .unwrap_or_else(unknown),
+            Self::And(lhs, rhs) => and(
+                &lhs.may_match(statistics, num_rows),
+                &rhs.may_match(statistics, num_rows),
+            ),
+            Self::Or(lhs, rhs) => or(
+                &lhs.may_match(statistics, num_rows),
+                &rhs.may_match(statistics, num_rows),
+            ),
+            Self::Not(_) => unreachable!("predicates are normalized"),
+        }
+    }
+
+    /// Returns the row groups of `row_groups` that may contain rows matching this predicate,
+    /// based on their statistics. Row groups without statistics are always kept.
+    ///
+    /// `fields` are the fields of the file, e.g. from [`super::infer_schema`].
+    /// # Errors
+    /// Errors if the predicate is not valid for `fields` or if the statistics
+    /// can't be deserialized.
+    pub fn prune_row_groups(
+        &self,
+        fields: &[Field],
+        row_groups: Vec<RowGroupMetaData>,
+    ) -> Result<Vec<RowGroupMetaData>> {
+        self.validate(fields)?;
+        let predicate = self.normalize();
+
+        let statistics = predicate
+            .columns()
+            .into_iter()
+            .map(|column| {
+                let field = fields.iter().find(|field| field.name == column).unwrap();
+                statistics::deserialize(field, &row_groups).map(|stats| (column, stats))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let get = |column: &str| {
+            statistics
+                .iter()
+                .find(|(name, _)| *name == column)
+                .and_then(|(_, stats)| {
+                    Some(StatisticsRef {
+                        min: stats.min_value.as_ref(),
+                        max: stats.max_value.as_ref(),
+                        null_count: stats.null_count.as_any().downcast_ref::<UInt64Array>()?,
+                    })
+                })
+        };
+
+        let num_rows = row_groups
+            .iter()
+            .map(|row_group| row_group.num_rows() as u64)
+            .collect::<Vec<_>>();
+        let num_rows = UInt64Array::from_vec(num_rows);
+
+        let selected = predicate.may_match(&get, &num_rows);
+        Ok(row_groups
+            .into_iter()
+            .zip(is_possible(&selected).iter())
+            .filter_map(|(row_group, selected)| if selected { Some(row_group) } else { None })
+            .collect())
+    }
+
+    /// Returns the pages of each field of `fields` that may contain rows matching this
+    /// predicate, based on the page index of `row_group`. The result can be passed to
+    /// [`super::read_columns_many`] and [`super::FileReader::new`].
+    ///
+    /// Returns `None` when the row group has no page index, in which case all its pages
+    /// must be read.
+    ///
+    /// The columns of this predicate must be part of `fields`.
+    /// # Errors
+    /// Errors if the predicate is not valid for `fields` or if the page index can't be read.
+    pub fn read_pages<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        row_group: &RowGroupMetaData,
+        fields: &[Field],
+    ) -> Result<Option<Vec<Vec<Vec<FilteredPage>>>>> {
+        self.validate(fields)?;
+        if !has_indexes(row_group) {
+            return Ok(None);
+        }
+        let predicate = self.normalize();
+        let columns = predicate.columns();
+
+        let predicate_fields = fields
+            .iter()
+            .filter(|field| columns.contains(&field.name.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let (statistics, intervals) = read_page_statistics(reader, row_group, &predicate_fields)?;
+
+        let intervals = predicate.select_intervals(
+            &predicate_fields,
+            &statistics,
+            &intervals,
+            row_group.num_rows(),
+        );
+
+        select_field_pages(reader, row_group, fields, &intervals).map(Some)
+    }
+
+    /// Returns the (sorted and disjoint) intervals of rows that may match this normalized
+    /// predicate, given the page statistics and page intervals of each field of `fields`.
+    fn select_intervals(
+        &self,
+        fields: &[Field],
+        statistics: &[FieldPageStatistics],
+        intervals: &[Vec<Vec<Interval>>],
+        num_rows: usize,
+    ) -> Vec<Interval> {
+        let column_pages = |column: &str| {
+            let index = fields.iter().position(|field| field.name == column)?;
+            match (&statistics[index], intervals[index].first()) {
+                (FieldPageStatistics::Single(stats), Some(intervals)) => Some((stats, intervals)),
+                _ => None,
+            }
+        };
+        let all_rows = || vec![Interval::new(0, num_rows)];
+
+        match self {
+            Self::And(lhs, rhs) => intersection(
+                &lhs.select_intervals(fields, statistics, intervals, num_rows),
+                &rhs.select_intervals(fields, statistics, intervals, num_rows),
+            ),
+            Self::Or(lhs, rhs) => union(
+                &lhs.select_intervals(fields, statistics, intervals, num_rows),
+                &rhs.select_intervals(fields, statistics, intervals, num_rows),
+            ),
+            Self::Compare(column, _, _)
+            | Self::IsNull(column)
+            | Self::IsNotNull(column)
+            | Self::In(column, _) => {
+                let (stats, pages) = if let Some(pages) = column_pages(column) {
+                    pages
+                } else {
+                    return all_rows();
+                };
+                let ColumnPageStatistics {
+                    min,
+                    max,
+                    null_count,
+                } = stats;
+                let get = |name: &str| {
+                    if name == column {
+                        Some(StatisticsRef {
+                            min: min.as_ref(),
+                            max: max.as_ref(),
+                            null_count,
+                        })
+                    } else {
+                        None
+                    }
+                };
+                let num_rows = pages
+                    .iter()
+                    .map(|page| page.length as u64)
+                    .collect::<Vec<_>>();
+                let num_rows = UInt64Array::from_vec(num_rows);
+
+                let selected = self.may_match(&get, &num_rows);
+                let selected = pages
+                    .iter()
+                    .zip(is_possible(&selected).iter())
+                    .filter_map(|(page, selected)| if selected { Some(*page) } else { None })
+                    .collect::<Vec<_>>();
+                union(&selected, &[])
+            }
+            Self::Not(_) => unreachable!("predicates are normalized"),
+        }
+    }
+
+    /// Evaluates this predicate on `chunk`, whose arrays correspond to `fields`, returning
+    /// whether each row matches it.
+    /// # Errors
+    /// Errors if the predicate is not valid for `fields`.
+    pub fn evaluate<A: AsRef<dyn Array>>(
+        &self,
+        fields: &[Field],
+        chunk: &Chunk<A>,
+    ) -> Result<BooleanArray> {
+        self.validate(fields)?;
+        let array = |column: &str| {
+            let index = fields
+                .iter()
+                .position(|field| field.name == column)
+                .unwrap();
+            chunk.arrays()[index].as_ref()
+        };
+        let mask = self.normalize().evaluate_normalized(&array, chunk.len());
+
+        // null values do not match
+        let values = match mask.validity() {
+            Some(validity) => mask.values() & validity,
+            None => mask.values().clone(),
+        };
+        Ok(BooleanArray::new(DataType::Boolean, values, None))
+    }
+
+    fn evaluate_normalized<'a, F>(&self, array: &F, len: usize) -> BooleanArray
+    where
+        F: Fn(&str) -> &'a dyn Array,
+    {
+        match self {
+            Self::Compare(column, op, value) => compare(array(column), *op, value.as_ref()),
+            Self::IsNull(column) => is_null(array(column)),
+            Self::IsNotNull(column) => is_not_null(array(column)),
+            Self::In(column, values) => values.iter().fold(none(len), |acc, value| {
+                or(
+                    &acc,
+                    &compare(array(column), Comparison::Eq, value.as_ref()),
+                )
+            }),
+            Self::And(lhs, rhs) => and(
+                &lhs.evaluate_normalized(array, len),
+                &rhs.evaluate_normalized(array, len),
+            ),
+            Self::Or(lhs, rhs) => or(
+                &lhs.evaluate_normalized(array, len),
+                &rhs.evaluate_normalized(array, len),
+            ),
+            Self::Not(_) => unreachable!("predicates are normalized"),
+        }
+    }
+
+    /// Returns a new [`Chunk`] with the rows of `chunk`, whose arrays correspond to `fields`,
+    /// that match this predicate.
+    /// # Errors
+    /// Errors if the predicate is not valid for `fields`.
+    pub fn filter<A: AsRef<dyn Array>>(
+        &self,
+        fields: &[Field],
+        chunk: &Chunk<A>,
+    ) -> Result<Chunk<Box<dyn Array>>> {
+        let mask = self.evaluate(fields, chunk)?;
+        filter_chunk(chunk, &mask)
+    }
+}
+
+impl std::ops::Not for Predicate {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+}
+
+/// The minimum, maximum and null count of a column over a set of items (row groups or pages)
+struct StatisticsRef<'a> {
+    min: &'a dyn Array,
+    max: &'a dyn Array,
+    null_count: &'a UInt64Array,
+}
+
+impl<'a> StatisticsRef<'a> {
+    /// Returns whether each item may contain values for which `value <op> literal` holds
+    fn compare(&self, op: Comparison, literal: &dyn Scalar) -> BooleanArray {
+        match op {
+            Comparison::Eq => and(
+                &comparison::lt_eq_scalar(self.min, literal),
+                &comparison::gt_eq_scalar(self.max, literal),
+            ),
+            // only items whose values are all equal to `literal` can be skipped
+            Comparison::NotEq => not(&and(
+                &comparison::eq_scalar(self.min, literal),
+                &comparison::eq_scalar(self.max, literal),
+            )),
+            Comparison::Lt => comparison::lt_scalar(self.min, literal),
+            Comparison::LtEq => comparison::lt_eq_scalar(self.min, literal),
+            Comparison::Gt => comparison::gt_scalar(self.max, literal),
+            Comparison::GtEq => comparison::gt_eq_scalar(self.max, literal),
+        }
+    }
+}
+
+fn compare(array: &dyn Array, op: Comparison, value: &dyn Scalar) -> BooleanArray {
+    match op {
+        Comparison::Eq => comparison::eq_scalar(array, value),
+        Comparison::NotEq => comparison::neq_scalar(array, value),
+        Comparison::Lt => comparison::lt_scalar(array, value),
+        Comparison::LtEq => comparison::lt_eq_scalar(array, value),
+        Comparison::Gt => comparison::gt_scalar(array, value),
+        Comparison::GtEq => comparison::gt_eq_scalar(array, value),
+    }
+}
+
+/// Returns a [`BooleanArray`] of `len` `false` values
+fn none(len: usize) -> BooleanArray {
+    BooleanArray::new(DataType::Boolean, Bitmap::new_zeroed(len), None)
+}
+
+/// Returns whether each item may match, i.e. is not known to not match
+fn is_possible(may_match: &BooleanArray) -> Bitmap {
+    match may_match.validity() {
+        Some(validity) => may_match.values() | &!validity,
+        None => may_match.values().clone(),
+    }
+}
+
+fn field_data_type<'a>(fields: &'a [Field], column: &str) -> Result<&'a DataType> {
+    let field = fields
+        .iter()
+        .find(|field| field.name == column)
+        .ok_or_else(|| {
+            Error::InvalidArgumentError(format!(
+                "The predicate's column \"{column}\" is not part of the fields"
+            ))
+        })?;
+    match field.data_type.to_physical_type() {
+        PhysicalType::List
+        | PhysicalType::LargeList
+        | PhysicalType::FixedSizeList
+        | PhysicalType::Struct
+        | PhysicalType::Map
+        | PhysicalType::Union
+        | PhysicalType::Dictionary(_) => Err(Error::nyi(format!(
+            "Predicates on columns of type {:?}",
+            field.data_type
+        ))),
+        _ => Ok(&field.data_type),
+    }
+}
+
+fn check_literal(column: &str, data_type: &DataType, value: &dyn Scalar) -> Result<()> {
+    if value.data_type().to_logical_type() != data_type.to_logical_type() {
+        return Err(Error::InvalidArgumentError(format!(
+            "The literal {value:?} compared to column \"{column}\" must be of type {data_type:?}"
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the union of two sets of sorted and disjoint intervals, merging adjacent ones
+fn union(lhs: &[Interval], rhs: &[Interval]) -> Vec<Interval> {
+    let mut all = lhs.iter().chain(rhs.iter()).copied().collect::<Vec<_>>();
+    all.sort_by_key(|interval| interval.start);
+
+    let mut result: Vec<Interval> = Vec::with_capacity(all.len());
+    for interval in all.into_iter().filter(|interval| interval.length > 0) {
+        match result.last_mut() {
+            Some(last) if interval.start <= last.start + last.length => {
+                let end = (last.start + last.length).max(interval.start + interval.length);
+                last.length = end - last.start;
+            }
+            _ => result.push(interval),
+        }
+    }
+    result
+}
+
+/// Returns the intersection of two sets of sorted and disjoint intervals
+fn intersection(lhs: &[Interval], rhs: &[Interval]) -> Vec<Interval> {
+    let mut result = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < lhs.len() && j < rhs.len() {
+        let (a, b) = (lhs[i], rhs[j]);
+        let start = a.start.max(b.start);
+        let end = (a.start + a.length).min(b.start + b.length);
+        if start < end {
+            result.push(Interval::new(start, end - start));
+        }
+        if a.start + a.length < b.start + b.length {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals() {
+        let lhs = vec![Interval::new(0, 10), Interval::new(20, 10)];
+        let rhs = vec![Interval::new(5, 20), Interval::new(40, 5)];
+        assert_eq!(
+            intersection(&lhs, &rhs),
+            vec![Interval::new(5, 5), Interval::new(20, 5)]
+        );
+        assert_eq!(
+            union(&lhs, &rhs),
+            vec![Interval::new(0, 30), Interval::new(40, 5)]
+        );
+        assert_eq!(
+            union(&[Interval::new(0, 2), Interval::new(2, 2)], &[]),
+            vec![Interval::new(0, 4)]
+        );
+    }
+}
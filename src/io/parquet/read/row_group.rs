@@ -30,6 +30,8 @@ pub struct RowGroupDeserializer {
     num_rows: usize,
     remaining_rows: usize,
     column_chunks: Vec<ArrayIter<'static>>,
+    #[cfg(feature = "io_parquet_predicate")]
+    predicate: Option<(super::predicate::Predicate, Vec<Field>)>,
 }
 
 impl RowGroupDeserializer {
@@ -47,19 +49,29 @@ impl RowGroupDeserializer {
             num_rows,
             remaining_rows: limit.unwrap_or(usize::MAX).min(num_rows),
             column_chunks,
+            #[cfg(feature = "io_parquet_predicate")]
+            predicate: None,
         }
     }
 
-    /// Returns the number of rows on this row group
-    pub fn num_rows(&self) -> usize {
-        self.num_rows
+    /// Returns this [`RowGroupDeserializer`] yielding only the rows that match `predicate`,
+    /// where `fields` are the fields of the `column_chunks`. Chunks without matching rows
+    /// are skipped, and `limit` applies to the rows before they are filtered.
+    /// # Errors
+    /// Errors if the predicate is not valid for `fields`.
+    #[cfg(feature = "io_parquet_predicate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "io_parquet_predicate")))]
+    pub fn with_predicate(
+        mut self,
+        predicate: super::predicate::Predicate,
+        fields: Vec<Field>,
+    ) -> Result<Self> {
+        predicate.validate(&fields)?;
+        self.predicate = Some((predicate, fields));
+        Ok(self)
     }
-}
 
-impl Iterator for RowGroupDeserializer {
-    type Item = Result<Chunk<Box<dyn Array>>>;
-
-    fn next(&mut self) -> Option<Self::Item> {
+    fn next_chunk(&mut self) -> Option<Result<Chunk<Box<dyn Array>>>> {
         if self.remaining_rows == 0 {
             return None;
         }
@@ -78,6 +90,33 @@ impl Iterator for RowGroupDeserializer {
 
         Some(chunk)
     }
+
+    /// Returns the number of rows on this row group
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+}
+
+impl Iterator for RowGroupDeserializer {
+    type Item = Result<Chunk<Box<dyn Array>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        #[cfg(feature = "io_parquet_predicate")]
+        if self.predicate.is_some() {
+            loop {
+                let chunk = match self.next_chunk()? {
+                    Ok(chunk) => chunk,
+                    Err(e) => return Some(Err(e)),
+                };
+                let (predicate, fields) = self.predicate.as_ref().unwrap();
+                match predicate.filter(fields, &chunk) {
+                    Ok(chunk) if chunk.is_empty() => continue,
+                    other => return Some(other),
+                }
+            }
+        }
+        self.next_chunk()
+    }
 }
 
 /// Returns all [`ColumnChunkMetaData`] associated to `field_name`.
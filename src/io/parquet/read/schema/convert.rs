@@ -219,7 +219,7 @@ fn non_repeated_group(
     match (logical_type, converted_type) {
         (Some(GroupLogicalType::List), _) => to_list(fields, parent_name),
         (None, Some(GroupConvertedType::List)) => to_list(fields, parent_name),
-        (Some(GroupLogicalType::Map), _) => to_list(fields, parent_name),
+        (Some(GroupLogicalType::Map), _) => to_map(fields),
         (None, Some(GroupConvertedType::Map) | Some(GroupConvertedType::MapKeyValue)) => {
             to_map(fields)
         }
@@ -238,10 +238,18 @@ fn to_struct(fields: &[ParquetType]) -> Option<DataType> {
     }
 }
 
-/// Converts a parquet group type to an arrow [`DataType::Struct`].
+/// Converts a parquet group type to an arrow [`DataType::Map`].
 /// Returns [`None`] if all its fields are empty
 fn to_map(fields: &[ParquetType]) -> Option<DataType> {
-    let inner = to_field(&fields[0])?;
+    let inner = match &fields[0] {
+        // the repeated `key_value` group holds the entries of the map
+        ParquetType::GroupType {
+            field_info, fields, ..
+        } if field_info.repetition == Repetition::Repeated => {
+            Field::new(&field_info.name, to_struct(fields)?, false)
+        }
+        other => to_field(other)?,
+    };
     Some(DataType::Map(Box::new(inner), false))
 }
 
@@ -1,5 +1,5 @@
 use crate::array::*;
-use crate::datatypes::DataType;
+use crate::datatypes::{DataType, Field};
 use crate::error::Result;
 
 use super::make_mutable;
@@ -38,20 +38,28 @@ impl MutableArray for DynMutableListArray {
     fn as_box(&mut self) -> Box<dyn Array> {
         let inner = self.inner.as_box();
 
+        // the inner statistics of fixed-size lists are lists, so the child field is rebuilt
+        let field = |field: &Field| {
+            Box::new(Field::new(
+                &field.name,
+                inner.data_type().clone(),
+                field.is_nullable,
+            ))
+        };
         match self.data_type.to_logical_type() {
-            DataType::List(_) => {
+            DataType::List(f) => {
                 let offsets = vec![0, inner.len() as i32].into();
                 Box::new(ListArray::<i32>::new(
-                    self.data_type.clone(),
+                    DataType::List(field(f)),
                     offsets,
                     inner,
                     None,
                 ))
             }
-            DataType::LargeList(_) => {
+            DataType::LargeList(f) => {
                 let offsets = vec![0, inner.len() as i64].into();
                 Box::new(ListArray::<i64>::new(
-                    self.data_type.clone(),
+                    DataType::LargeList(field(f)),
                     offsets,
                     inner,
                     None,
@@ -182,6 +182,17 @@ fn make_mutable(data_type: &DataType, capacity: usize) -> Result<Box<dyn Mutable
         PhysicalType::LargeList | PhysicalType::List => Box::new(
             DynMutableListArray::try_with_capacity(data_type.clone(), capacity)?,
         ) as Box<dyn MutableArray>,
+        // parquet has no fixed-size lists: their statistics are read as lists
+        PhysicalType::FixedSizeList => {
+            if let DataType::FixedSizeList(inner, _) = data_type.to_logical_type() {
+                Box::new(DynMutableListArray::try_with_capacity(
+                    DataType::List(inner.clone()),
+                    capacity,
+                )?) as Box<dyn MutableArray>
+            } else {
+                unreachable!()
+            }
+        }
         PhysicalType::Dictionary(_) => Box::new(
             dictionary::DynMutableDictionary::try_with_capacity(data_type.clone(), capacity)?,
         ),
@@ -221,6 +232,12 @@ fn create_dt(data_type: &DataType) -> DataType {
             create_dt(&f.data_type),
             f.is_nullable,
         )))
+    } else if let DataType::FixedSizeList(f, _) = data_type.to_logical_type() {
+        DataType::List(Box::new(Field::new(
+            &f.name,
+            create_dt(&f.data_type),
+            f.is_nullable,
+        )))
     } else if let DataType::LargeList(f) = data_type.to_logical_type() {
         DataType::LargeList(Box::new(Field::new(
             &f.name,
@@ -0,0 +1,187 @@
+use std::io::Write;
+
+use parquet2::bloom_filter::{hash_byte, hash_native, insert};
+use parquet_format_safe::thrift::protocol::{TCompactOutputProtocol, TOutputProtocol};
+use parquet_format_safe::{
+    BloomFilterAlgorithm, BloomFilterCompression, BloomFilterHash, BloomFilterHeader,
+    SplitBlockAlgorithm, Uncompressed, XxHash,
+};
+
+use crate::array::*;
+use crate::datatypes::{DataType, PhysicalType};
+use crate::error::{Error, Result};
+use crate::types::NativeType;
+
+use super::pages::to_leafs;
+use super::{decimal_length_from_precision, BloomFilterOptions};
+
+/// The size of a block of a split-block bloom filter
+const BLOCK_SIZE: usize = 32;
+
+/// Returns the bloom filter of each leaf column of `array`, in the order in which they are
+/// written to parquet. Leafs whose physical type has no bloom filter (e.g. booleans) return `None`.
+pub fn array_to_bloom_filters(
+    array: &dyn Array,
+    options: BloomFilterOptions,
+) -> Vec<Option<Vec<u8>>> {
+    to_leafs(array)
+        .into_iter()
+        .map(|leaf| {
+            let mut hashes = vec![];
+            hash_array(leaf, &mut hashes).then(|| to_bitset(hashes, options))
+        })
+        .collect()
+}
+
+/// Builds a bitset from `hashes`, sized for the number of distinct hashes.
+fn to_bitset(mut hashes: Vec<u64>, options: BloomFilterOptions) -> Vec<u8> {
+    hashes.sort_unstable();
+    hashes.dedup();
+
+    let mut bitset = vec![0; num_bytes(hashes.len(), options.max_bytes)];
+    hashes
+        .into_iter()
+        .for_each(|hash| insert(&mut bitset, hash));
+    bitset
+}
+
+/// The number of bytes of a split-block bloom filter with a false positive probability of 1%
+/// for `num_distinct` values: `-8 * ndv / ln(1 - 0.01^(1/8))` ~= 9.7 bits per value.
+fn num_bytes(num_distinct: usize, max_bytes: usize) -> usize {
+    let bits = num_distinct as f64 * 9.7;
+    let bytes = ((bits / 8.0).ceil() as usize).next_power_of_two();
+    let max_bytes = if max_bytes.is_power_of_two() {
+        max_bytes
+    } else {
+        max_bytes.next_power_of_two() / 2
+    };
+    bytes.min(max_bytes).max(BLOCK_SIZE)
+}
+
+fn hash_primitive<T: NativeType, P: parquet2::types::NativeType>(
+    array: &dyn Array,
+    hashes: &mut Vec<u64>,
+    f: impl Fn(T) -> P,
+) {
+    let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    hashes.extend(array.iter().flatten().map(|x| hash_native(f(*x))))
+}
+
+fn hash_binary<'a>(values: impl Iterator<Item = Option<&'a [u8]>>, hashes: &mut Vec<u64>) {
+    hashes.extend(values.flatten().map(hash_byte))
+}
+
+/// Hashes the non-null values of `array` according to their parquet physical type.
+/// Casts below MUST match the casts done when writing pages.
+/// Returns whether the data type has a bloom filter.
+fn hash_array(array: &dyn Array, hashes: &mut Vec<u64>) -> bool {
+    if let PhysicalType::Dictionary(key_type) = array.data_type().to_physical_type() {
+        // the values of a dictionary are a superset of the values of the column
+        return match_integer_type!(key_type, |$T| {
+            let array = array
+                .as_any()
+                .downcast_ref::<DictionaryArray<$T>>()
+                .unwrap();
+            hash_array(array.values().as_ref(), hashes)
+        });
+    }
+
+    match array.data_type().to_logical_type() {
+        DataType::UInt8 => hash_primitive(array, hashes, |x: u8| x as i32),
+        DataType::UInt16 => hash_primitive(array, hashes, |x: u16| x as i32),
+        DataType::UInt32 => hash_primitive(array, hashes, |x: u32| x as i32),
+        DataType::UInt64 => hash_primitive(array, hashes, |x: u64| x as i64),
+        DataType::Int8 => hash_primitive(array, hashes, |x: i8| x as i32),
+        DataType::Int16 => hash_primitive(array, hashes, |x: i16| x as i32),
+        DataType::Int32 | DataType::Date32 | DataType::Time32(_) => {
+            hash_primitive(array, hashes, |x: i32| x)
+        }
+        DataType::Int64
+        | DataType::Date64
+        | DataType::Time64(_)
+        | DataType::Timestamp(_, _)
+        | DataType::Duration(_) => hash_primitive(array, hashes, |x: i64| x),
+        DataType::Float32 => hash_primitive(array, hashes, |x: f32| x),
+        DataType::Float64 => hash_primitive(array, hashes, |x: f64| x),
+        DataType::Decimal(precision, _) => {
+            let precision = *precision;
+            if precision <= 9 {
+                hash_primitive(array, hashes, |x: i128| x as i32)
+            } else if precision <= 18 {
+                hash_primitive(array, hashes, |x: i128| x as i64)
+            } else {
+                let size = decimal_length_from_precision(precision);
+                let array = array
+                    .as_any()
+                    .downcast_ref::<PrimitiveArray<i128>>()
+                    .unwrap();
+                hashes.extend(
+                    array
+                        .iter()
+                        .flatten()
+                        .map(|x| hash_byte(&x.to_be_bytes()[16 - size..])),
+                )
+            }
+        }
+        DataType::Utf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+            hash_binary(array.iter().map(|x| x.map(|x| x.as_bytes())), hashes)
+        }
+        DataType::LargeUtf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i64>>().unwrap();
+            hash_binary(array.iter().map(|x| x.map(|x| x.as_bytes())), hashes)
+        }
+        DataType::Binary => {
+            let array = array.as_any().downcast_ref::<BinaryArray<i32>>().unwrap();
+            hash_binary(array.iter(), hashes)
+        }
+        DataType::LargeBinary => {
+            let array = array.as_any().downcast_ref::<BinaryArray<i64>>().unwrap();
+            hash_binary(array.iter(), hashes)
+        }
+        DataType::FixedSizeBinary(_) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryArray>()
+                .unwrap();
+            hash_binary(array.iter(), hashes)
+        }
+        _ => return false,
+    };
+    true
+}
+
+/// Writes the header and the bitset of a bloom filter to `writer`, returning the number of
+/// bytes written.
+pub fn write_bloom_filter<W: Write>(writer: &mut W, bitset: &[u8]) -> Result<u64> {
+    let header = BloomFilterHeader {
+        num_bytes: bitset.len().try_into().map_err(|_| Error::Overflow)?,
+        algorithm: BloomFilterAlgorithm::BLOCK(SplitBlockAlgorithm {}),
+        hash: BloomFilterHash::XXHASH(XxHash {}),
+        compression: BloomFilterCompression::UNCOMPRESSED(Uncompressed {}),
+    };
+
+    let mut protocol = TCompactOutputProtocol::new(&mut *writer);
+    let header_len = header
+        .write_to_out_protocol(&mut protocol)
+        .and_then(|len| protocol.flush().map(|_| len))
+        .map_err(parquet2::error::Error::from)?;
+
+    writer.write_all(bitset)?;
+    Ok((header_len + bitset.len()) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes() {
+        let options = BloomFilterOptions { max_bytes: 1024 };
+        assert_eq!(num_bytes(0, options.max_bytes), 32);
+        assert_eq!(num_bytes(10, options.max_bytes), 32);
+        assert_eq!(num_bytes(100, options.max_bytes), 128);
+        assert_eq!(num_bytes(1_000_000, options.max_bytes), 1024);
+        assert_eq!(num_bytes(1_000_000, 1000), 512);
+    }
+}
@@ -0,0 +1,213 @@
+use parquet2::schema::types::ParquetType;
+
+use crate::error::{Error, Result};
+
+use super::{CompressionOptions, Encoding, WriteOptions};
+
+/// Options to dictionary-encode a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DictionaryOptions {
+    /// Whether the column is dictionary-encoded. When `false`, dictionary arrays are
+    /// written with the encoding of the column (`Plain` by default) instead.
+    pub enabled: bool,
+    /// The maximum size in bytes of the values of a dictionary of a column chunk. Column
+    /// chunks whose dictionary would be larger are written with the encoding of the column.
+    pub max_bytes: usize,
+}
+
+impl Default for DictionaryOptions {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_bytes: 1024 * 1024,
+        }
+    }
+}
+
+/// Options of a column that override the [`WriteOptions`] of the file and the encoding
+/// of the column. `None` keeps the file's (or a less specific column's) option.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ColumnOptions {
+    /// The compression (codec and level) of the pages of the column
+    pub compression: Option<CompressionOptions>,
+    /// The encoding of the column
+    pub encoding: Option<Encoding>,
+    /// Whether and how to dictionary-encode the column
+    pub dictionary: Option<DictionaryOptions>,
+    /// Whether to write statistics of the column
+    pub write_statistics: Option<bool>,
+}
+
+impl ColumnOptions {
+    /// Returns `self` with the options of `other` that are set
+    fn merge(self, other: &Self) -> Self {
+        Self {
+            compression: other.compression.or(self.compression),
+            encoding: other.encoding.or(self.encoding),
+            dictionary: other.dictionary.or(self.dictionary),
+            write_statistics: other.write_statistics.or(self.write_statistics),
+        }
+    }
+}
+
+/// [`ColumnOptions`] keyed by the path of columns in the parquet schema.
+///
+/// A path is the sequence of names from a field to a column (e.g. `["a", "b"]` for the field
+/// `b` of the struct `a`, `["a", "list", "item"]` for the values of the list `a`) and applies
+/// to all columns it is a prefix of, so that `["a"]` applies to all columns of the field `a`.
+/// When more than one path applies to a column, the options of longer paths take precedence.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnWriteOptions {
+    columns: Vec<(Vec<String>, ColumnOptions)>,
+}
+
+impl ColumnWriteOptions {
+    /// Returns a new [`ColumnWriteOptions`] without overrides
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `self` with `options` applied to the columns of `path`.
+    pub fn with_column<I: IntoIterator<Item = S>, S: Into<String>>(
+        mut self,
+        path: I,
+        options: ColumnOptions,
+    ) -> Self {
+        let path = path.into_iter().map(|x| x.into()).collect();
+        self.columns.push((path, options));
+        self
+    }
+
+    /// Returns the [`ColumnOptions`] of the column at `path`
+    pub fn get<S: AsRef<str>>(&self, path: &[S]) -> ColumnOptions {
+        let mut matches = self
+            .columns
+            .iter()
+            .filter(|(prefix, _)| {
+                prefix.len() <= path.len()
+                    && prefix.iter().zip(path.iter()).all(|(a, b)| a == b.as_ref())
+            })
+            .collect::<Vec<_>>();
+        // stable: among paths of the same length, the last one takes precedence
+        matches.sort_by_key(|(prefix, _)| prefix.len());
+        matches
+            .into_iter()
+            .fold(ColumnOptions::default(), |acc, (_, options)| {
+                acc.merge(options)
+            })
+    }
+
+    /// Whether these options disable the statistics of any column
+    fn disables_statistics(&self) -> bool {
+        self.columns
+            .iter()
+            .any(|(_, options)| options.write_statistics == Some(false))
+    }
+
+    /// Whether a file written with `options` and these column options has page indexes.
+    /// Page indexes require the statistics of every page of every column, so they are only
+    /// written when statistics are written and no column disables them.
+    pub(super) fn write_page_indexes(&self, options: WriteOptions) -> bool {
+        options.write_statistics && !self.disables_statistics()
+    }
+
+    /// Errors if these options disable the statistics of a column of a file with page
+    /// indexes, i.e. when [`WriteOptions::write_statistics`] is `true`. Used by the APIs
+    /// that encode row groups independently of the file they are written to.
+    pub(super) fn check_statistics(&self, options: WriteOptions) -> Result<()> {
+        if options.write_statistics != self.write_page_indexes(options) {
+            return Err(Error::InvalidArgumentError(
+                "Columns can only disable statistics when `WriteOptions::write_statistics` is \
+                false, since page indexes require the statistics of every column. Enable the \
+                statistics of columns with `ColumnOptions::write_statistics` instead"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the options of each column of `type_`, given the file's `options` and the
+    /// `encodings` of its columns.
+    pub(super) fn leaf_options(
+        &self,
+        type_: &ParquetType,
+        options: WriteOptions,
+        encodings: &[Encoding],
+    ) -> Vec<LeafOptions> {
+        let mut paths = vec![];
+        leaf_paths(type_, &mut vec![], &mut paths);
+        paths
+            .iter()
+            .zip(encodings.iter())
+            .map(|(path, encoding)| {
+                let column = self.get(path);
+                LeafOptions {
+                    options: WriteOptions {
+                        write_statistics: column
+                            .write_statistics
+                            .unwrap_or(options.write_statistics),
+                        compression: column.compression.unwrap_or(options.compression),
+                        ..options
+                    },
+                    encoding: column.encoding.unwrap_or(*encoding),
+                    dictionary: column.dictionary,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The options with which a leaf column is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct LeafOptions {
+    pub options: WriteOptions,
+    pub encoding: Encoding,
+    pub dictionary: Option<DictionaryOptions>,
+}
+
+fn leaf_paths(type_: &ParquetType, path: &mut Vec<String>, paths: &mut Vec<Vec<String>>) {
+    path.push(type_.name().to_string());
+    match type_ {
+        ParquetType::PrimitiveType(_) => paths.push(path.clone()),
+        ParquetType::GroupType { fields, .. } => fields
+            .iter()
+            .for_each(|type_| leaf_paths(type_, path, paths)),
+    }
+    path.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precedence() {
+        let zstd = CompressionOptions::Zstd(None);
+        let columns = ColumnWriteOptions::new()
+            .with_column(
+                ["a"],
+                ColumnOptions {
+                    compression: Some(CompressionOptions::Snappy),
+                    write_statistics: Some(false),
+                    ..Default::default()
+                },
+            )
+            .with_column(
+                ["a", "b"],
+                ColumnOptions {
+                    compression: Some(zstd),
+                    ..Default::default()
+                },
+            );
+
+        let options = columns.get(&["a", "b"]);
+        assert_eq!(options.compression, Some(zstd));
+        assert_eq!(options.write_statistics, Some(false));
+
+        let options = columns.get(&["a", "c"]);
+        assert_eq!(options.compression, Some(CompressionOptions::Snappy));
+
+        assert_eq!(columns.get(&["ab"]), ColumnOptions::default());
+        assert_eq!(columns.get::<&str>(&[]), ColumnOptions::default());
+    }
+}
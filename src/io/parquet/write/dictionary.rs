@@ -6,9 +6,17 @@ use parquet2::{
     write::DynIter,
 };
 
+use std::collections::HashMap;
+
+use crate::compute::aggregate::estimated_bytes_size;
+use crate::datatypes::{IntegerType, PhysicalType};
 use crate::io::parquet::write::utils;
+use crate::types::NativeType;
 use crate::{
-    array::{Array, DictionaryArray, DictionaryKey},
+    array::{
+        growable::make_growable, Array, BinaryArray, DictionaryArray, DictionaryKey,
+        FixedSizeBinaryArray, PrimitiveArray, Utf8Array,
+    },
     io::parquet::read::schema::is_nullable,
 };
 use crate::{bitmap::Bitmap, datatypes::DataType};
@@ -19,13 +27,14 @@ use crate::{
 
 use super::fixed_len_bytes::build_statistics as fixed_binary_build_statistics;
 use super::fixed_len_bytes::encode_plain as fixed_binary_encode_plain;
+use super::pages::{map_pages, page_ranges};
 use super::primitive::build_statistics as primitive_build_statistics;
 use super::primitive::encode_plain as primitive_encode_plain;
 use super::utf8::build_statistics as utf8_build_statistics;
 use super::utf8::encode_plain as utf8_encode_plain;
-use super::WriteOptions;
 use super::{binary::build_statistics as binary_build_statistics, Nested};
 use super::{binary::encode_plain as binary_encode_plain, nested};
+use super::{DictionaryOptions, WriteOptions};
 
 fn serialize_def_levels_simple(
     validity: Option<&Bitmap>,
@@ -104,7 +113,6 @@ fn serialize_keys<K: DictionaryKey>(
     array: &DictionaryArray<K>,
     type_: PrimitiveType,
     nested: &[Nested],
-    statistics: ParquetStatistics,
     options: WriteOptions,
 ) -> Result<EncodedPage> {
     let mut buffer = vec![];
@@ -130,6 +138,13 @@ fn serialize_keys<K: DictionaryKey>(
         (nested::num_values(nested), nested[0].len())
     };
 
+    // the statistics of the values of the page, which may be a subset of the dictionary
+    let statistics = if options.write_statistics {
+        Some(build_statistics(to_values(array).as_ref(), type_.clone())?)
+    } else {
+        None
+    };
+
     utils::build_plain_page(
         buffer,
         num_values,
@@ -137,7 +152,7 @@ fn serialize_keys<K: DictionaryKey>(
         array.null_count(),
         repetition_levels_byte_length,
         definition_levels_byte_length,
-        Some(statistics),
+        statistics,
         type_,
         options,
         Encoding::RleDictionary,
@@ -146,17 +161,66 @@ fn serialize_keys<K: DictionaryKey>(
 }
 
 macro_rules! dyn_prim {
-    ($from:ty, $to:ty, $array:expr, $options:expr, $type_:expr) => {{
+    ($from:ty, $to:ty, $array:expr) => {{
         let values = $array.values().as_any().downcast_ref().unwrap();
 
         let mut buffer = vec![];
         primitive_encode_plain::<$from, $to>(values, false, &mut buffer);
-        let stats = primitive_build_statistics::<$from, $to>(values, $type_.clone());
-        let stats = serialize_statistics(&stats);
-        (DictPage::new(buffer, values.len(), false), stats)
+        DictPage::new(buffer, values.len(), false)
+    }};
+}
+
+macro_rules! prim_statistics {
+    ($from:ty, $to:ty, $array:expr, $type_:expr) => {{
+        let array = $array.as_any().downcast_ref().unwrap();
+        serialize_statistics(&primitive_build_statistics::<$from, $to>(array, $type_))
     }};
 }
 
+/// Returns the statistics of `array`, the values of a page of a dictionary-encoded column
+fn build_statistics(array: &dyn Array, type_: PrimitiveType) -> Result<ParquetStatistics> {
+    Ok(match array.data_type().to_logical_type() {
+        DataType::Int8 => prim_statistics!(i8, i32, array, type_),
+        DataType::Int16 => prim_statistics!(i16, i32, array, type_),
+        DataType::Int32 | DataType::Date32 | DataType::Time32(_) => {
+            prim_statistics!(i32, i32, array, type_)
+        }
+        DataType::Int64
+        | DataType::Date64
+        | DataType::Time64(_)
+        | DataType::Timestamp(_, _)
+        | DataType::Duration(_) => prim_statistics!(i64, i64, array, type_),
+        DataType::UInt8 => prim_statistics!(u8, i32, array, type_),
+        DataType::UInt16 => prim_statistics!(u16, i32, array, type_),
+        DataType::UInt32 => prim_statistics!(u32, i32, array, type_),
+        DataType::UInt64 => prim_statistics!(u64, i64, array, type_),
+        DataType::Float32 => prim_statistics!(f32, f32, array, type_),
+        DataType::Float64 => prim_statistics!(f64, f64, array, type_),
+        DataType::Utf8 => {
+            utf8_build_statistics::<i32>(array.as_any().downcast_ref().unwrap(), type_)
+        }
+        DataType::LargeUtf8 => {
+            utf8_build_statistics::<i64>(array.as_any().downcast_ref().unwrap(), type_)
+        }
+        DataType::Binary => {
+            binary_build_statistics::<i32>(array.as_any().downcast_ref().unwrap(), type_)
+        }
+        DataType::LargeBinary => {
+            binary_build_statistics::<i64>(array.as_any().downcast_ref().unwrap(), type_)
+        }
+        DataType::FixedSizeBinary(_) => {
+            let array = array.as_any().downcast_ref().unwrap();
+            serialize_statistics(&fixed_binary_build_statistics(array, type_))
+        }
+        other => {
+            return Err(Error::NotYetImplemented(format!(
+                "Writing dictionary arrays to parquet only support data type {:?}",
+                other
+            )))
+        }
+    })
+}
+
 pub fn array_to_pages<K: DictionaryKey>(
     array: &DictionaryArray<K>,
     type_: PrimitiveType,
@@ -167,62 +231,56 @@ pub fn array_to_pages<K: DictionaryKey>(
     match encoding {
         Encoding::PlainDictionary | Encoding::RleDictionary => {
             // write DictPage
-            let (dict_page, statistics) = match array.values().data_type().to_logical_type() {
-                DataType::Int8 => dyn_prim!(i8, i32, array, options, type_),
-                DataType::Int16 => dyn_prim!(i16, i32, array, options, type_),
+            let dict_page = match array.values().data_type().to_logical_type() {
+                DataType::Int8 => dyn_prim!(i8, i32, array),
+                DataType::Int16 => dyn_prim!(i16, i32, array),
                 DataType::Int32 | DataType::Date32 | DataType::Time32(_) => {
-                    dyn_prim!(i32, i32, array, options, type_)
+                    dyn_prim!(i32, i32, array)
                 }
                 DataType::Int64
                 | DataType::Date64
                 | DataType::Time64(_)
                 | DataType::Timestamp(_, _)
-                | DataType::Duration(_) => dyn_prim!(i64, i64, array, options, type_),
-                DataType::UInt8 => dyn_prim!(u8, i32, array, options, type_),
-                DataType::UInt16 => dyn_prim!(u16, i32, array, options, type_),
-                DataType::UInt32 => dyn_prim!(u32, i32, array, options, type_),
-                DataType::UInt64 => dyn_prim!(u64, i64, array, options, type_),
-                DataType::Float32 => dyn_prim!(f32, f32, array, options, type_),
-                DataType::Float64 => dyn_prim!(f64, f64, array, options, type_),
+                | DataType::Duration(_) => dyn_prim!(i64, i64, array),
+                DataType::UInt8 => dyn_prim!(u8, i32, array),
+                DataType::UInt16 => dyn_prim!(u16, i32, array),
+                DataType::UInt32 => dyn_prim!(u32, i32, array),
+                DataType::UInt64 => dyn_prim!(u64, i64, array),
+                DataType::Float32 => dyn_prim!(f32, f32, array),
+                DataType::Float64 => dyn_prim!(f64, f64, array),
                 DataType::Utf8 => {
                     let array = array.values().as_any().downcast_ref().unwrap();
 
                     let mut buffer = vec![];
                     utf8_encode_plain::<i32>(array, false, &mut buffer);
-                    let stats = utf8_build_statistics(array, type_.clone());
-                    (DictPage::new(buffer, array.len(), false), stats)
+                    DictPage::new(buffer, array.len(), false)
                 }
                 DataType::LargeUtf8 => {
                     let array = array.values().as_any().downcast_ref().unwrap();
 
                     let mut buffer = vec![];
                     utf8_encode_plain::<i64>(array, false, &mut buffer);
-                    let stats = utf8_build_statistics(array, type_.clone());
-                    (DictPage::new(buffer, array.len(), false), stats)
+                    DictPage::new(buffer, array.len(), false)
                 }
                 DataType::Binary => {
                     let array = array.values().as_any().downcast_ref().unwrap();
 
                     let mut buffer = vec![];
                     binary_encode_plain::<i32>(array, false, &mut buffer);
-                    let stats = binary_build_statistics(array, type_.clone());
-                    (DictPage::new(buffer, array.len(), false), stats)
+                    DictPage::new(buffer, array.len(), false)
                 }
                 DataType::LargeBinary => {
                     let array = array.values().as_any().downcast_ref().unwrap();
 
                     let mut buffer = vec![];
                     binary_encode_plain::<i64>(array, false, &mut buffer);
-                    let stats = binary_build_statistics(array, type_.clone());
-                    (DictPage::new(buffer, array.len(), false), stats)
+                    DictPage::new(buffer, array.len(), false)
                 }
                 DataType::FixedSizeBinary(_) => {
                     let mut buffer = vec![];
                     let array = array.values().as_any().downcast_ref().unwrap();
                     fixed_binary_encode_plain(array, false, &mut buffer);
-                    let stats = fixed_binary_build_statistics(array, type_.clone());
-                    let stats = serialize_statistics(&stats);
-                    (DictPage::new(buffer, array.len(), false), stats)
+                    DictPage::new(buffer, array.len(), false)
                 }
                 other => {
                     return Err(Error::NotYetImplemented(format!(
@@ -233,10 +291,14 @@ pub fn array_to_pages<K: DictionaryKey>(
             };
             let dict_page = EncodedPage::Dict(dict_page);
 
-            // write DataPage pointing to DictPage
-            let data_page = serialize_keys(array, type_, nested, statistics, options)?;
+            // write DataPages pointing to DictPage
+            let ranges = page_ranges(array.keys(), nested, options);
+            let data_pages = map_pages(array, nested, &ranges, |array, nested| {
+                let array = array.as_any().downcast_ref::<DictionaryArray<K>>().unwrap();
+                serialize_keys(array, type_.clone(), nested, options)
+            })?;
 
-            let iter = std::iter::once(Ok(dict_page)).chain(std::iter::once(Ok(data_page)));
+            let iter = std::iter::once(dict_page).chain(data_pages).map(Ok);
             Ok(DynIter::new(Box::new(iter)))
         }
         _ => Err(Error::NotYetImplemented(
@@ -244,3 +306,168 @@ pub fn array_to_pages<K: DictionaryKey>(
         )),
     }
 }
+
+fn is_dictionary_encoding(encoding: Encoding) -> bool {
+    matches!(
+        encoding,
+        Encoding::PlainDictionary | Encoding::RleDictionary
+    )
+}
+
+/// Returns `array` dictionary-encoded or not according to `dictionary`, together with the
+/// encoding to write it with.
+pub(super) fn apply_options(
+    array: &dyn Array,
+    encoding: Encoding,
+    dictionary: Option<DictionaryOptions>,
+) -> (Box<dyn Array>, Encoding) {
+    let dictionary = if let Some(dictionary) = dictionary {
+        dictionary
+    } else {
+        return (array.to_boxed(), encoding);
+    };
+    let fallback = if is_dictionary_encoding(encoding) {
+        Encoding::Plain
+    } else {
+        encoding
+    };
+    let dictionary_encoding = if is_dictionary_encoding(encoding) {
+        encoding
+    } else {
+        Encoding::RleDictionary
+    };
+
+    if let PhysicalType::Dictionary(key_type) = array.data_type().to_physical_type() {
+        match_integer_type!(key_type, |$T| {
+            let array = array.as_any().downcast_ref::<DictionaryArray<$T>>().unwrap();
+            if dictionary.enabled
+                && estimated_bytes_size(array.values().as_ref()) <= dictionary.max_bytes
+            {
+                (array.to_boxed(), dictionary_encoding)
+            } else {
+                (to_values(array), fallback)
+            }
+        })
+    } else if dictionary.enabled {
+        match to_dictionary(array, dictionary.max_bytes) {
+            Some(array) => (array, dictionary_encoding),
+            None => (array.to_boxed(), fallback),
+        }
+    } else {
+        (array.to_boxed(), fallback)
+    }
+}
+
+/// Returns the values of `array`, i.e. its dictionary "unpacked"
+fn to_values<K: DictionaryKey>(array: &DictionaryArray<K>) -> Box<dyn Array> {
+    let mut growable = make_growable(&[array.values().as_ref()], true, array.len());
+    array.keys_iter().for_each(|key| match key {
+        Some(key) => growable.extend(0, key, 1),
+        None => growable.extend_validity(1),
+    });
+    growable.as_box()
+}
+
+fn primitive_to_bytes<T: NativeType>(array: &dyn Array) -> Vec<Option<&[u8]>> {
+    let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    let values = bytemuck::cast_slice::<_, u8>(array.values().as_slice());
+    let values = values.chunks_exact(std::mem::size_of::<T>());
+    match array.validity() {
+        Some(validity) => values
+            .zip(validity.iter())
+            .map(|(value, is_valid)| if is_valid { Some(value) } else { None })
+            .collect(),
+        None => values.map(Some).collect(),
+    }
+}
+
+/// Returns `array` dictionary-encoded, or `None` if the values of its dictionary
+/// are larger than `max_bytes` or its data type can't be dictionary-encoded.
+fn to_dictionary(array: &dyn Array, max_bytes: usize) -> Option<Box<dyn Array>> {
+    // the types supported by `array_to_pages`
+    let values: Vec<Option<&[u8]>> = match array.data_type().to_logical_type() {
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64
+        | DataType::Float32
+        | DataType::Float64
+        | DataType::Date32
+        | DataType::Date64
+        | DataType::Time32(_)
+        | DataType::Time64(_)
+        | DataType::Timestamp(_, _)
+        | DataType::Duration(_) => match array.data_type().to_physical_type() {
+            PhysicalType::Primitive(primitive) => with_match_primitive_type!(primitive, |$T| {
+                primitive_to_bytes::<$T>(array)
+            }),
+            _ => unreachable!(),
+        },
+        DataType::Utf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+            array.iter().map(|x| x.map(|x| x.as_bytes())).collect()
+        }
+        DataType::LargeUtf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i64>>().unwrap();
+            array.iter().map(|x| x.map(|x| x.as_bytes())).collect()
+        }
+        DataType::Binary => {
+            let array = array.as_any().downcast_ref::<BinaryArray<i32>>().unwrap();
+            array.iter().collect()
+        }
+        DataType::LargeBinary => {
+            let array = array.as_any().downcast_ref::<BinaryArray<i64>>().unwrap();
+            array.iter().collect()
+        }
+        DataType::FixedSizeBinary(_) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryArray>()
+                .unwrap();
+            array.iter().collect()
+        }
+        _ => return None,
+    };
+
+    // the index of the first occurrence of each distinct value, and the key of each value
+    let mut dictionary = HashMap::<&[u8], u32>::new();
+    let mut indices = vec![];
+    let mut size = 0;
+    let mut keys = Vec::with_capacity(values.len());
+    for (index, value) in values.into_iter().enumerate() {
+        let key = match value {
+            Some(value) => *dictionary.entry(value).or_insert_with(|| {
+                indices.push(index);
+                size += value.len();
+                indices.len() as u32 - 1
+            }),
+            None => 0,
+        };
+        if size > max_bytes {
+            return None;
+        }
+        keys.push(key);
+    }
+
+    let mut growable = make_growable(&[array], false, indices.len());
+    indices
+        .into_iter()
+        .for_each(|index| growable.extend(0, index, 1));
+    let values = growable.as_box();
+
+    let keys = PrimitiveArray::<u32>::new(DataType::UInt32, keys.into(), array.validity().cloned());
+    let data_type = DataType::Dictionary(
+        IntegerType::UInt32,
+        Box::new(array.data_type().clone()),
+        false,
+    );
+    Some(
+        DictionaryArray::<u32>::try_new(data_type, keys, values)
+            .unwrap()
+            .boxed(),
+    )
+}
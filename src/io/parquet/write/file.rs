@@ -1,14 +1,21 @@
 use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use parquet2::metadata::KeyValue;
 use parquet2::metadata::SchemaDescriptor;
 use parquet2::write::RowGroupIter;
 use parquet2::write::WriteOptions as FileWriteOptions;
 
+use crate::array::Array;
+use crate::chunk::Chunk;
 use crate::datatypes::Schema;
 use crate::error::{Error, Result};
 
-use super::{schema::schema_to_metadata_key, to_parquet_schema, ThriftFileMetaData, WriteOptions};
+use super::{
+    row_group::row_group_iter_with_leaf_options, schema::schema_to_metadata_key, to_parquet_schema,
+    ColumnWriteOptions, Encoding, ThriftFileMetaData, WriteOptions,
+};
 
 /// Attaches [`Schema`] to `key_value_metadata`
 pub fn add_arrow_schema(
@@ -23,11 +30,59 @@ pub fn add_arrow_schema(
         .or_else(|| Some(vec![schema_to_metadata_key(schema)]))
 }
 
+/// A [`Write`] that holds back the bytes written to it while `hold` is set.
+/// Used to write bloom filters after the page indexes and before the file's footer.
+struct FooterWriter<W: Write> {
+    writer: W,
+    hold: Arc<AtomicBool>,
+    held: Vec<u8>,
+}
+
+impl<W: Write> Write for FooterWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.hold.load(Ordering::Relaxed) {
+            self.held.write(buf)
+        } else {
+            self.writer.write(buf)
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+fn new_writer<W: Write>(
+    writer: FooterWriter<W>,
+    parquet_schema: SchemaDescriptor,
+    options: WriteOptions,
+    write_statistics: bool,
+) -> parquet2::write::FileWriter<FooterWriter<W>> {
+    let created_by = Some("Arrow2 - Native Rust implementation of Arrow".to_string());
+    parquet2::write::FileWriter::new(
+        writer,
+        parquet_schema,
+        FileWriteOptions {
+            version: options.version,
+            write_statistics,
+        },
+        created_by,
+    )
+}
+
 /// An interface to write a parquet to a [`Write`]
 pub struct FileWriter<W: Write> {
-    writer: parquet2::write::FileWriter<W>,
+    // `None` after the footer was written by this writer (and not by parquet2)
+    writer: Option<parquet2::write::FileWriter<FooterWriter<W>>>,
+    ended: Option<(W, ThriftFileMetaData)>,
+    parquet_schema: SchemaDescriptor,
     schema: Schema,
     options: WriteOptions,
+    #[cfg_attr(not(feature = "io_parquet_bloom_filter"), allow(dead_code))]
+    hold: Arc<AtomicBool>,
+    // the bloom filters of every column chunk of every row group
+    bloom_filters: Vec<Vec<Option<Vec<u8>>>>,
+    columns: ColumnWriteOptions,
 }
 
 // Accessors
@@ -39,7 +94,7 @@ impl<W: Write> FileWriter<W> {
 
     /// The [`SchemaDescriptor`] assigned to this file
     pub fn parquet_schema(&self) -> &SchemaDescriptor {
-        self.writer.schema()
+        &self.parquet_schema
     }
 
     /// The [`Schema`] assigned to this file
@@ -55,43 +110,229 @@ impl<W: Write> FileWriter<W> {
     pub fn try_new(writer: W, schema: Schema, options: WriteOptions) -> Result<Self> {
         let parquet_schema = to_parquet_schema(&schema)?;
 
-        let created_by = Some("Arrow2 - Native Rust implementation of Arrow".to_string());
+        let hold = Arc::new(AtomicBool::new(false));
+        let writer = FooterWriter {
+            writer,
+            hold: hold.clone(),
+            held: vec![],
+        };
 
         Ok(Self {
-            writer: parquet2::write::FileWriter::new(
+            writer: Some(new_writer(
                 writer,
-                parquet_schema,
-                FileWriteOptions {
-                    version: options.version,
-                    write_statistics: options.write_statistics,
-                },
-                created_by,
-            ),
+                parquet_schema.clone(),
+                options,
+                options.write_statistics,
+            )),
+            ended: None,
+            parquet_schema,
             schema,
             options,
+            hold,
+            bloom_filters: vec![],
+            columns: ColumnWriteOptions::default(),
         })
     }
 
+    /// Returns this [`FileWriter`] with the options and encodings of its columns overridden
+    /// by `columns` in [`Self::write_chunk`].
+    ///
+    /// Page indexes are only written when every column writes statistics.
+    /// # Error
+    /// Errors if a row group was already written.
+    pub fn with_column_options(mut self, columns: ColumnWriteOptions) -> Result<Self> {
+        if !self.bloom_filters.is_empty() || self.writer.is_none() {
+            return Err(Error::InvalidArgumentError(
+                "The options of columns must be set before writing row groups".to_string(),
+            ));
+        }
+        let writer = self.writer.take().unwrap().into_inner();
+        self.writer = Some(new_writer(
+            writer,
+            self.parquet_schema.clone(),
+            self.options,
+            columns.write_page_indexes(self.options),
+        ));
+        self.columns = columns;
+        Ok(self)
+    }
+
+    fn writer(&mut self) -> Result<&mut parquet2::write::FileWriter<FooterWriter<W>>> {
+        self.writer
+            .as_mut()
+            .ok_or_else(|| Error::InvalidArgumentError("The file has already ended".to_string()))
+    }
+
     /// Writes a row group to the file.
+    /// # Error
+    /// Errors if [`WriteOptions::bloom_filter`] is set, since bloom filters are built from the
+    /// arrays of a row group (see [`Self::write_chunk`]), not from its encoded pages.
     pub fn write(&mut self, row_group: RowGroupIter<'_, Error>) -> Result<()> {
-        Ok(self.writer.write(row_group)?)
+        if self.options.bloom_filter.is_some() {
+            return Err(Error::InvalidArgumentError(
+                "Bloom filters can only be built by `FileWriter::write_chunk`".to_string(),
+            ));
+        }
+        self.writer()?.write(row_group)?;
+        self.bloom_filters.push(vec![]);
+        Ok(())
+    }
+
+    /// Writes a [`Chunk`] to the file as a row group, encoding each of its arrays with
+    /// `encodings` and the options of [`Self::with_column_options`], like
+    /// [`super::RowGroupIterator`].
+    /// Unlike [`Self::write`], this also builds the bloom filters of the row group when
+    /// [`WriteOptions::bloom_filter`] is set.
+    /// # Error
+    /// Errors if the number of arrays or encodings differs from the number of fields, or if
+    /// bloom filters are requested and the feature `io_parquet_bloom_filter` is not active.
+    pub fn write_chunk<A: AsRef<dyn Array> + 'static + Send + Sync>(
+        &mut self,
+        chunk: Chunk<A>,
+        encodings: Vec<Vec<Encoding>>,
+    ) -> Result<()> {
+        if encodings.len() != self.schema.fields.len()
+            || chunk.arrays().len() != self.schema.fields.len()
+        {
+            return Err(Error::InvalidArgumentError(
+                "The number of arrays and encodings must equal the number of fields".to_string(),
+            ));
+        }
+        let bloom_filters = self.chunk_to_bloom_filters(&chunk)?;
+
+        let row_group = row_group_iter_with_leaf_options(
+            chunk,
+            encodings,
+            self.parquet_schema.fields().to_vec(),
+            self.options,
+            &self.columns,
+        );
+        self.writer()?.write(row_group)?;
+        self.bloom_filters.push(bloom_filters);
+        Ok(())
+    }
+
+    #[cfg(feature = "io_parquet_bloom_filter")]
+    fn chunk_to_bloom_filters<A: AsRef<dyn Array>>(
+        &self,
+        chunk: &Chunk<A>,
+    ) -> Result<Vec<Option<Vec<u8>>>> {
+        Ok(if let Some(options) = self.options.bloom_filter {
+            chunk
+                .arrays()
+                .iter()
+                .flat_map(|array| {
+                    super::bloom_filter::array_to_bloom_filters(array.as_ref(), options)
+                })
+                .collect()
+        } else {
+            vec![]
+        })
+    }
+
+    #[cfg(not(feature = "io_parquet_bloom_filter"))]
+    fn chunk_to_bloom_filters<A: AsRef<dyn Array>>(
+        &self,
+        _: &Chunk<A>,
+    ) -> Result<Vec<Option<Vec<u8>>>> {
+        if self.options.bloom_filter.is_some() {
+            Err(Error::InvalidArgumentError(
+                "Writing parquet bloom filters requires the feature \"io_parquet_bloom_filter\""
+                    .to_string(),
+            ))
+        } else {
+            Ok(vec![])
+        }
     }
 
     /// Writes the footer of the parquet file. Returns the total size of the file.
     pub fn end(&mut self, key_value_metadata: Option<Vec<KeyValue>>) -> Result<u64> {
         let key_value_metadata = add_arrow_schema(&self.schema, key_value_metadata);
-        Ok(self.writer.end(key_value_metadata)?)
+        if self
+            .bloom_filters
+            .iter()
+            .flatten()
+            .all(|filter| filter.is_none())
+        {
+            return Ok(self.writer()?.end(key_value_metadata)?);
+        }
+        self.end_with_bloom_filters(key_value_metadata)
+    }
+
+    /// Writes the bloom filters between the page indexes and the file metadata written by
+    /// parquet2, and sets their offsets in the metadata.
+    #[cfg(feature = "io_parquet_bloom_filter")]
+    fn end_with_bloom_filters(&mut self, key_value_metadata: Option<Vec<KeyValue>>) -> Result<u64> {
+        use parquet_format_safe::thrift::protocol::{TCompactOutputProtocol, TOutputProtocol};
+
+        let mut writer = self
+            .writer
+            .take()
+            .ok_or_else(|| Error::InvalidArgumentError("The file has already ended".to_string()))?;
+        self.hold.store(true, Ordering::Relaxed);
+        let len = writer.end(key_value_metadata)?;
+        let (writer, mut metadata) = writer.into_inner_and_metadata();
+        let FooterWriter {
+            mut writer, held, ..
+        } = writer;
+
+        // `held` is the page indexes followed by the metadata and the footer, the
+        // metadata's length followed by `PAR1`
+        let footer = &held[held.len() - 8..];
+        let metadata_len = i32::from_le_bytes(footer[..4].try_into().unwrap()) as usize;
+        let indexes = &held[..held.len() - 8 - metadata_len];
+        writer.write_all(indexes)?;
+        let mut offset = len - (held.len() - indexes.len()) as u64;
+
+        for (row_group, bloom_filters) in metadata.row_groups.iter_mut().zip(&self.bloom_filters) {
+            for (column, bitset) in row_group.columns.iter_mut().zip(bloom_filters) {
+                if let (Some(bitset), Some(column)) = (bitset, column.meta_data.as_mut()) {
+                    column.bloom_filter_offset = Some(offset as i64);
+                    offset += super::bloom_filter::write_bloom_filter(&mut writer, bitset)?;
+                }
+            }
+        }
+
+        let mut protocol = TCompactOutputProtocol::new(&mut writer);
+        let metadata_len = metadata
+            .write_to_out_protocol(&mut protocol)
+            .and_then(|len| protocol.flush().map(|_| len))
+            .map_err(parquet2::error::Error::from)?;
+        writer.write_all(&(metadata_len as i32).to_le_bytes())?;
+        writer.write_all(&footer[4..])?;
+        offset += metadata_len as u64 + 8;
+
+        self.ended = Some((writer, metadata));
+        Ok(offset)
+    }
+
+    #[cfg(not(feature = "io_parquet_bloom_filter"))]
+    fn end_with_bloom_filters(&mut self, _: Option<Vec<KeyValue>>) -> Result<u64> {
+        Err(Error::InvalidArgumentError(
+            "Writing parquet bloom filters requires the feature \"io_parquet_bloom_filter\""
+                .to_string(),
+        ))
     }
 
     /// Consumes this writer and returns the inner writer
     pub fn into_inner(self) -> W {
-        self.writer.into_inner()
+        self.into_inner_and_metadata_opt().0
     }
 
     /// Returns the underlying writer and [`ThriftFileMetaData`]
     /// # Panics
     /// This function panics if [`Self::end`] has not yet been called
     pub fn into_inner_and_metadata(self) -> (W, ThriftFileMetaData) {
-        self.writer.into_inner_and_metadata()
+        let (writer, metadata) = self.into_inner_and_metadata_opt();
+        (writer, metadata.expect("File to have ended"))
+    }
+
+    fn into_inner_and_metadata_opt(self) -> (W, Option<ThriftFileMetaData>) {
+        if let Some((writer, metadata)) = self.ended {
+            return (writer, Some(metadata));
+        }
+        let writer = self.writer.unwrap();
+        let metadata = writer.metadata().cloned();
+        (writer.into_inner().writer, metadata)
     }
 }
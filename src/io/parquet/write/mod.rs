@@ -1,6 +1,9 @@
 //! APIs to write to Parquet format.
 mod binary;
+#[cfg(feature = "io_parquet_bloom_filter")]
+mod bloom_filter;
 mod boolean;
+mod column_options;
 mod dictionary;
 mod file;
 mod fixed_len_bytes;
@@ -43,18 +46,48 @@ pub struct WriteOptions {
     pub version: Version,
     /// The compression to apply to every page
     pub compression: CompressionOptions,
+    /// Whether to write a bloom filter per column chunk. Only [`FileWriter::write_chunk`] builds
+    /// bloom filters: the other APIs to write row groups error when this is set.
+    pub bloom_filter: Option<BloomFilterOptions>,
+    /// The target size in bytes of data pages. Columns are split in pages of approximately
+    /// this size, so that the column indexes of the file locate rows to a few pages.
+    /// `None` writes a single data page per column chunk (up to the maximum page size of parquet).
+    pub data_page_size: Option<usize>,
+    /// The maximum number of rows of a data page. `None` does not limit it.
+    pub data_page_max_rows: Option<usize>,
 }
 
-use crate::compute::aggregate::estimated_bytes_size;
+/// The maximum size of a data page (2^31, i.e. `i32::MAX`), split at 2^31 - 2^25 to err on
+/// the safe side
+const MAX_DATA_PAGE_SIZE: usize = (1 << 31) - (1 << 25);
+
+/// Options to build split-block bloom filters.
+/// Each filter is sized for a false positive probability of 1% given the number of
+/// distinct values in its column chunk, rounded up to a power of two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BloomFilterOptions {
+    /// The maximum size in bytes of a filter. Filters are never smaller than 32 bytes.
+    pub max_bytes: usize,
+}
+
+impl Default for BloomFilterOptions {
+    fn default() -> Self {
+        Self {
+            max_bytes: 1024 * 1024,
+        }
+    }
+}
+
+pub use column_options::{ColumnOptions, ColumnWriteOptions, DictionaryOptions};
 pub use file::FileWriter;
-pub use row_group::{row_group_iter, RowGroupIterator};
+pub use row_group::{row_group_iter, row_group_iter_with_column_options, RowGroupIterator};
 pub use schema::to_parquet_type;
 pub use sink::FileSink;
 
 pub use pages::array_to_columns;
 pub use pages::Nested;
 
-pub(self) fn decimal_length_from_precision(precision: usize) -> usize {
+pub(crate) fn decimal_length_from_precision(precision: usize) -> usize {
     // digits = floor(log_10(2^(8*n - 1) - 1))
     // ceil(digits) = log10(2^(8*n - 1) - 1)
     // 10^ceil(digits) = 2^(8*n - 1) - 1
@@ -92,6 +125,8 @@ pub fn can_encode(data_type: &DataType, encoding: Encoding) -> bool {
 }
 
 /// Returns an iterator of [`EncodedPage`].
+/// The rows of `array` are split in data pages according to [`WriteOptions::data_page_size`]
+/// and [`WriteOptions::data_page_max_rows`].
 pub fn array_to_pages(
     array: &dyn Array,
     type_: ParquetPrimitiveType,
@@ -99,35 +134,24 @@ pub fn array_to_pages(
     options: WriteOptions,
     encoding: Encoding,
 ) -> Result<DynIter<'static, Result<EncodedPage>>> {
-    // maximum page size is 2^31 e.g. i32::MAX
-    // we split at 2^31 - 2^25 to err on the safe side
-    // we also check for an array.len > 3 to prevent infinite recursion
-    // still have to figure out how to deal with values that are i32::MAX size, such as very large
-    // strings or a list column with many elements
-    if (estimated_bytes_size(array)) >= (2u32.pow(31) - 2u32.pow(25)) as usize && array.len() > 3 {
-        let split_at = array.len() / 2;
-        let left = array.slice(0, split_at);
-        let right = array.slice(split_at, array.len() - split_at);
-
-        Ok(DynIter::new(
-            array_to_pages(&*left, type_.clone(), nested, options, encoding)?
-                .chain(array_to_pages(&*right, type_, nested, options, encoding)?),
-        ))
-    } else {
-        match array.data_type() {
-            DataType::Dictionary(key_type, _, _) => {
-                match_integer_type!(key_type, |$T| {
-                    dictionary::array_to_pages::<$T>(
-                        array.as_any().downcast_ref().unwrap(),
-                        type_,
-                        nested,
-                        options,
-                        encoding,
-                    )
-                })
-            }
-            _ => array_to_page(array, type_, nested, options, encoding)
-                .map(|page| DynIter::new(std::iter::once(Ok(page)))),
+    match array.data_type() {
+        DataType::Dictionary(key_type, _, _) => {
+            match_integer_type!(key_type, |$T| {
+                dictionary::array_to_pages::<$T>(
+                    array.as_any().downcast_ref().unwrap(),
+                    type_,
+                    nested,
+                    options,
+                    encoding,
+                )
+            })
+        }
+        _ => {
+            let ranges = pages::page_ranges(array, nested, options);
+            let pages = pages::map_pages(array, nested, &ranges, |array, nested| {
+                array_to_page(array, type_.clone(), nested, options, encoding)
+            })?;
+            Ok(DynIter::new(pages.into_iter().map(Ok)))
         }
     }
 }
@@ -482,8 +506,14 @@ fn transverse_recursive<T, F: Fn(&DataType) -> T + Clone>(
                 unreachable!()
             }
         }
+        Map => {
+            if let DataType::Map(inner, _) = data_type.to_logical_type() {
+                transverse_recursive(&inner.data_type, map, encodings)
+            } else {
+                unreachable!()
+            }
+        }
         Union => todo!(),
-        Map => todo!(),
     }
 }
 
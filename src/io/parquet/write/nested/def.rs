@@ -144,7 +144,17 @@ impl<'a> Iterator for DefLevelsIter<'a> {
             *validity = is_valid;
             if length == 0 {
                 self.remaining_values -= 1;
-                return Some(self.total + is_valid / 2);
+                let r = Some(self.total + is_valid / 2);
+                // an empty item still consumes one item of its parent
+                while self.current_level > 0 {
+                    self.remaining[self.current_level - 1] -= 1;
+                    if self.remaining[self.current_level - 1] > 0 {
+                        break;
+                    }
+                    self.current_level -= 1;
+                    self.total -= self.validity[self.current_level];
+                }
+                return r;
             }
             *remaining = length;
             self.current_level += 1;
@@ -316,6 +326,27 @@ mod tests {
         test(nested, expected)
     }
 
+    #[test]
+    fn l2_empty_before_values() {
+        // [[[1, 2], []], [], [[3]]]
+        let nested = vec![
+            Nested::List(ListNested::<i32> {
+                is_optional: false,
+                offsets: &[0, 2, 2, 3],
+                validity: None,
+            }),
+            Nested::List(ListNested::<i32> {
+                is_optional: false,
+                offsets: &[0, 2, 2, 3],
+                validity: None,
+            }),
+            Nested::Primitive(None, false, 3),
+        ];
+        let expected = vec![2, 2, 1, 0, 2];
+
+        test(nested, expected)
+    }
+
     #[test]
     fn l2_optional_optional_optional() {
         let a = Bitmap::from([true, false, true]);
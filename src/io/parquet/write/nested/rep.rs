@@ -62,6 +62,9 @@ pub struct RepLevelsIter<'a> {
 
     // the total number of items that this iterator will return
     remaining_values: usize,
+    // the repetition level of each level: the number of lists up to it.
+    // Structs are levels of `iter` but not repetition levels.
+    levels: Vec<u32>, /* < levels.len() == iter.len() + 1 */
 }
 
 impl<'a> RepLevelsIter<'a> {
@@ -70,6 +73,17 @@ impl<'a> RepLevelsIter<'a> {
 
         let iter = iter(nested);
         let remaining = std::iter::repeat(0).take(iter.len()).collect();
+        let levels = std::iter::once(0)
+            .chain(
+                nested
+                    .iter()
+                    .filter(|nested| !matches!(nested, Nested::Primitive(_, _, _)))
+                    .scan(0, |level, nested| {
+                        *level += matches!(nested, Nested::List(_) | Nested::LargeList(_)) as u32;
+                        Some(*level)
+                    }),
+            )
+            .collect();
 
         Self {
             iter,
@@ -77,6 +91,7 @@ impl<'a> RepLevelsIter<'a> {
             total: 0,
             current_level: 0,
             remaining_values,
+            levels,
         }
     }
 }
@@ -90,7 +105,7 @@ impl<'a> Iterator for RepLevelsIter<'a> {
 
             let total = self.total;
             self.total = 0;
-            let r = Some((self.current_level - total) as u32);
+            let r = Some(self.levels[self.current_level - total]);
 
             for level in 0..self.current_level - 1 {
                 let level = self.remaining.len() - level - 1;
@@ -116,7 +131,17 @@ impl<'a> Iterator for RepLevelsIter<'a> {
             let length: usize = iter.next()?;
             if length == 0 {
                 self.remaining_values -= 1;
-                return Some(self.current_level as u32);
+                let r = Some(self.levels[self.current_level - self.total]);
+                self.total = 0;
+                // an empty item still consumes one item of its parent
+                while self.current_level > 0 {
+                    self.remaining[self.current_level - 1] -= 1;
+                    if self.remaining[self.current_level - 1] > 0 {
+                        break;
+                    }
+                    self.current_level -= 1;
+                }
+                return r;
             }
             *remaining = length;
             self.current_level += 1;
@@ -203,6 +228,49 @@ mod tests {
         test(nested, expected)
     }
 
+    #[test]
+    fn l2_empty() {
+        // [[[1, 2], []], [], [[3]]]
+        let nested = vec![
+            Nested::List(ListNested::<i32> {
+                is_optional: false,
+                offsets: &[0, 2, 2, 3],
+                validity: None,
+            }),
+            Nested::List(ListNested::<i32> {
+                is_optional: false,
+                offsets: &[0, 2, 2, 3],
+                validity: None,
+            }),
+            Nested::Primitive(None, false, 3),
+        ];
+        let expected = vec![0, 2, 1, 0, 0];
+
+        test(nested, expected)
+    }
+
+    #[test]
+    fn list_of_struct_of_list() {
+        // [[{"a": [1, 2]}, {"a": []}], [{"a": [3]}]]
+        let nested = vec![
+            Nested::List(ListNested::<i32> {
+                is_optional: false,
+                offsets: &[0, 2, 3],
+                validity: None,
+            }),
+            Nested::Struct(None, false, 3),
+            Nested::List(ListNested::<i32> {
+                is_optional: false,
+                offsets: &[0, 2, 2, 3],
+                validity: None,
+            }),
+            Nested::Primitive(None, false, 3),
+        ];
+        let expected = vec![0, 2, 1, 0];
+
+        test(nested, expected)
+    }
+
     #[test]
     fn list_of_struct() {
         /*
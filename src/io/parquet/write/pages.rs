@@ -1,16 +1,20 @@
 use parquet2::schema::types::{ParquetType, PrimitiveType as ParquetPrimitiveType};
 use parquet2::{page::EncodedPage, write::DynIter};
 
-use crate::array::{ListArray, Offset, StructArray};
+use crate::array::{
+    growable::make_growable, FixedSizeListArray, ListArray, MapArray, Offset, StructArray,
+};
 use crate::bitmap::Bitmap;
-use crate::datatypes::PhysicalType;
+use crate::compute::aggregate::estimated_bytes_size;
+use crate::datatypes::{DataType, Field, PhysicalType};
 use crate::io::parquet::read::schema::is_nullable;
 use crate::{
     array::Array,
     error::{Error, Result},
 };
 
-use super::{array_to_pages, Encoding, WriteOptions};
+use super::column_options::LeafOptions;
+use super::{array_to_pages, dictionary, Encoding, WriteOptions, MAX_DATA_PAGE_SIZE};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ListNested<'a, O: Offset> {
@@ -134,6 +138,39 @@ fn to_nested_recursive<'a>(
             )));
             to_nested_recursive(array.values().as_ref(), type_, nested, parents)?;
         }
+        Map => {
+            let array = array.as_any().downcast_ref::<MapArray>().unwrap();
+            let fields = if let ParquetType::GroupType { fields, .. } = type_ {
+                if let ParquetType::GroupType { fields, .. } = &fields[0] {
+                    fields
+                } else {
+                    return Err(Error::InvalidArgumentError(
+                        "Parquet type must be a group for a map array".to_string(),
+                    ));
+                }
+            } else {
+                return Err(Error::InvalidArgumentError(
+                    "Parquet type must be a group for a map array".to_string(),
+                ));
+            };
+            let entries = array
+                .field()
+                .as_any()
+                .downcast_ref::<StructArray>()
+                .unwrap();
+
+            parents.push(Nested::List(ListNested::new(
+                array.offsets(),
+                array.validity(),
+                is_optional,
+            )));
+            // the entries are the repeated group itself and thus have no definition level
+            parents.push(Nested::Struct(entries.validity(), false, entries.len()));
+
+            for (type_, array) in fields.iter().zip(entries.values()) {
+                to_nested_recursive(array.as_ref(), type_, nested, parents.clone())?;
+            }
+        }
         _ => {
             parents.push(Nested::Primitive(
                 array.validity(),
@@ -146,7 +183,7 @@ fn to_nested_recursive<'a>(
     Ok(())
 }
 
-fn to_leafs(array: &dyn Array) -> Vec<&dyn Array> {
+pub(super) fn to_leafs(array: &dyn Array) -> Vec<&dyn Array> {
     let mut leafs = vec![];
     to_leafs_recursive(array, &mut leafs);
     leafs
@@ -170,12 +207,259 @@ fn to_leafs_recursive<'a>(array: &'a dyn Array, leafs: &mut Vec<&'a dyn Array>)
             let array = array.as_any().downcast_ref::<ListArray<i64>>().unwrap();
             to_leafs_recursive(array.values().as_ref(), leafs);
         }
+        Map => {
+            let array = array.as_any().downcast_ref::<MapArray>().unwrap();
+            to_leafs_recursive(array.field().as_ref(), leafs);
+        }
+        FixedSizeList => {
+            let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+            to_leafs_recursive(array.values().as_ref(), leafs);
+        }
         Null | Boolean | Primitive(_) | Binary | FixedSizeBinary | LargeBinary | Utf8
         | LargeUtf8 | Dictionary(_) => leafs.push(array),
         other => todo!("Writing {:?} to parquet not yet implemented", other),
     }
 }
 
+/// Returns the rows, as `(offset, length)`, of each data page of a column whose levels are
+/// `nested` and whose values are `array`, given the page size and row limits of `options`.
+pub(super) fn page_ranges(
+    array: &dyn Array,
+    nested: &[Nested],
+    options: WriteOptions,
+) -> Vec<(usize, usize)> {
+    let num_rows = nested[0].len();
+    if num_rows == 0 {
+        return vec![(0, 0)];
+    }
+
+    let page_size = options
+        .data_page_size
+        .unwrap_or(MAX_DATA_PAGE_SIZE)
+        .clamp(1, MAX_DATA_PAGE_SIZE);
+    let row_size = (estimated_bytes_size(array) / num_rows).max(1);
+    let rows_per_page = (page_size / row_size)
+        .min(options.data_page_max_rows.unwrap_or(usize::MAX))
+        .max(1);
+
+    (0..num_rows)
+        .step_by(rows_per_page)
+        .map(|offset| (offset, rows_per_page.min(num_rows - offset)))
+        .collect()
+}
+
+/// Returns the validity and the range of each level of `nested` sliced to the rows
+/// `[offset, offset + length)`. The range of the last level is the range of its values.
+fn slice_levels(
+    nested: &[Nested],
+    mut offset: usize,
+    mut length: usize,
+) -> (Vec<Option<Bitmap>>, Vec<(usize, usize)>) {
+    fn slice(validity: Option<&Bitmap>, offset: usize, length: usize) -> Option<Bitmap> {
+        validity.map(|x| x.clone().slice(offset, length))
+    }
+
+    fn to_values<O: Offset>(offsets: &[O], offset: usize, length: usize) -> (usize, usize) {
+        let start = offsets[offset].to_usize();
+        (start, offsets[offset + length].to_usize() - start)
+    }
+
+    let mut validities = Vec::with_capacity(nested.len());
+    let mut ranges = Vec::with_capacity(nested.len());
+    for level in nested {
+        ranges.push((offset, length));
+        match level {
+            Nested::Primitive(validity, _, _) | Nested::Struct(validity, _, _) => {
+                validities.push(slice(*validity, offset, length));
+            }
+            Nested::List(list) => {
+                validities.push(slice(list.validity, offset, length));
+                let values = to_values(list.offsets, offset, length);
+                offset = values.0;
+                length = values.1;
+            }
+            Nested::LargeList(list) => {
+                validities.push(slice(list.validity, offset, length));
+                let values = to_values(list.offsets, offset, length);
+                offset = values.0;
+                length = values.1;
+            }
+        }
+    }
+    (validities, ranges)
+}
+
+/// Returns `nested` sliced according to the `validities` and `ranges` of [`slice_levels`]
+fn to_sliced<'a>(
+    nested: &[Nested<'a>],
+    validities: &'a [Option<Bitmap>],
+    ranges: &[(usize, usize)],
+) -> Vec<Nested<'a>> {
+    nested
+        .iter()
+        .zip(validities.iter())
+        .zip(ranges.iter())
+        .map(|((level, validity), (offset, length))| match level {
+            Nested::Primitive(_, is_optional, _) => {
+                Nested::Primitive(validity.as_ref(), *is_optional, *length)
+            }
+            Nested::Struct(_, is_optional, _) => {
+                Nested::Struct(validity.as_ref(), *is_optional, *length)
+            }
+            Nested::List(list) => Nested::List(ListNested::new(
+                &list.offsets[*offset..*offset + length + 1],
+                validity.as_ref(),
+                list.is_optional,
+            )),
+            Nested::LargeList(list) => Nested::LargeList(ListNested::new(
+                &list.offsets[*offset..*offset + length + 1],
+                validity.as_ref(),
+                list.is_optional,
+            )),
+        })
+        .collect()
+}
+
+/// Returns the pages built by `f` from `array` and `nested` sliced to each of the row
+/// `ranges` of [`page_ranges`].
+pub(super) fn map_pages<F>(
+    array: &dyn Array,
+    nested: &[Nested],
+    ranges: &[(usize, usize)],
+    mut f: F,
+) -> Result<Vec<EncodedPage>>
+where
+    F: FnMut(&dyn Array, &[Nested]) -> Result<EncodedPage>,
+{
+    if let [(0, length)] = ranges {
+        if *length == nested[0].len() {
+            return Ok(vec![f(array, nested)?]);
+        }
+    }
+
+    ranges
+        .iter()
+        .map(|(offset, length)| {
+            let (validities, ranges) = slice_levels(nested, *offset, *length);
+            let nested = to_sliced(nested, &validities, &ranges);
+            let (offset, length) = ranges[ranges.len() - 1];
+            f(array.slice(offset, length).as_ref(), &nested)
+        })
+        .collect()
+}
+
+fn has_fixed_size_list(data_type: &DataType) -> bool {
+    match data_type.to_logical_type() {
+        DataType::FixedSizeList(_, _) => true,
+        DataType::List(inner) | DataType::LargeList(inner) | DataType::Map(inner, _) => {
+            has_fixed_size_list(inner.data_type())
+        }
+        DataType::Struct(fields) => fields.iter().any(|x| has_fixed_size_list(x.data_type())),
+        _ => false,
+    }
+}
+
+fn with_data_type(field: &Field, data_type: DataType) -> Box<Field> {
+    Box::new(
+        Field::new(field.name.clone(), data_type, field.is_nullable)
+            .with_metadata(field.metadata.clone()),
+    )
+}
+
+fn list_to_list<O: Offset>(array: &ListArray<O>) -> Box<dyn Array> {
+    let values = fixed_size_list_to_list(array.values().as_ref());
+    let field = with_data_type(
+        ListArray::<O>::get_child_field(array.data_type()),
+        values.data_type().clone(),
+    );
+    let data_type = if O::IS_LARGE {
+        DataType::LargeList(field)
+    } else {
+        DataType::List(field)
+    };
+    ListArray::<O>::new(
+        data_type,
+        array.offsets().clone(),
+        values,
+        array.validity().cloned(),
+    )
+    .boxed()
+}
+
+/// Returns `array` with every [`FixedSizeListArray`] replaced by an equivalent [`ListArray`].
+///
+/// Parquet has no fixed-size lists, so these are written as lists. Unlike in a
+/// [`FixedSizeListArray`], null entries of the returned [`ListArray`] have no values,
+/// which is what the rep and def levels of a null list require.
+fn fixed_size_list_to_list(array: &dyn Array) -> Box<dyn Array> {
+    if !has_fixed_size_list(array.data_type()) {
+        return array.to_boxed();
+    }
+    match array.data_type().to_logical_type() {
+        DataType::Struct(fields) => {
+            let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+            let values = array
+                .values()
+                .iter()
+                .map(|x| fixed_size_list_to_list(x.as_ref()))
+                .collect::<Vec<_>>();
+            let fields = fields
+                .iter()
+                .zip(values.iter())
+                .map(|(field, values)| *with_data_type(field, values.data_type().clone()))
+                .collect();
+            StructArray::new(DataType::Struct(fields), values, array.validity().cloned()).boxed()
+        }
+        DataType::List(_) => list_to_list::<i32>(array.as_any().downcast_ref().unwrap()),
+        DataType::LargeList(_) => list_to_list::<i64>(array.as_any().downcast_ref().unwrap()),
+        DataType::Map(field, is_sorted) => {
+            let array = array.as_any().downcast_ref::<MapArray>().unwrap();
+            let values = fixed_size_list_to_list(array.field().as_ref());
+            let field = with_data_type(field, values.data_type().clone());
+            MapArray::new(
+                DataType::Map(field, *is_sorted),
+                array.offsets().clone(),
+                values,
+                array.validity().cloned(),
+            )
+            .boxed()
+        }
+        DataType::FixedSizeList(field, size) => {
+            let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+            let size = *size;
+            let values = fixed_size_list_to_list(array.values().as_ref());
+
+            let (offsets, values) = if let Some(validity) = array.validity() {
+                let mut offsets = Vec::with_capacity(array.len() + 1);
+                offsets.push(0i32);
+                let mut growable = make_growable(&[values.as_ref()], false, values.len());
+                let mut length = 0;
+                for (index, is_valid) in validity.iter().enumerate() {
+                    if is_valid {
+                        growable.extend(0, index * size, size);
+                        length += size;
+                    }
+                    offsets.push(length as i32);
+                }
+                (offsets, growable.as_box())
+            } else {
+                let offsets = (0..=array.len()).map(|x| (x * size) as i32).collect();
+                (offsets, values)
+            };
+
+            let field = with_data_type(field, values.data_type().clone());
+            ListArray::<i32>::new(
+                DataType::List(field),
+                offsets.into(),
+                values,
+                array.validity().cloned(),
+            )
+            .boxed()
+        }
+        _ => unreachable!(),
+    }
+}
+
 fn to_parquet_leafs(type_: ParquetType) -> Vec<ParquetPrimitiveType> {
     let mut leafs = vec![];
     to_parquet_leafs_recursive(type_, &mut leafs);
@@ -200,6 +484,25 @@ pub fn array_to_columns<A: AsRef<dyn Array> + Send + Sync>(
     options: WriteOptions,
     encoding: &[Encoding],
 ) -> Result<Vec<DynIter<'static, Result<EncodedPage>>>> {
+    let leafs = encoding
+        .iter()
+        .map(|encoding| LeafOptions {
+            options,
+            encoding: *encoding,
+            dictionary: None,
+        })
+        .collect::<Vec<_>>();
+    array_to_columns_with_options(array, type_, &leafs)
+}
+
+/// Returns a vector of iterators of [`EncodedPage`], one per leaf column in the array,
+/// each written with its own [`LeafOptions`]
+pub(super) fn array_to_columns_with_options<A: AsRef<dyn Array> + Send + Sync>(
+    array: A,
+    type_: ParquetType,
+    leafs: &[LeafOptions],
+) -> Result<Vec<DynIter<'static, Result<EncodedPage>>>> {
+    let array = fixed_size_list_to_list(array.as_ref());
     let array = array.as_ref();
     let nested = to_nested(array, &type_)?;
 
@@ -207,15 +510,17 @@ pub fn array_to_columns<A: AsRef<dyn Array> + Send + Sync>(
 
     let values = to_leafs(array);
 
-    assert_eq!(encoding.len(), types.len());
+    assert_eq!(leafs.len(), types.len());
 
     values
         .iter()
         .zip(nested.into_iter())
         .zip(types.into_iter())
-        .zip(encoding.iter())
-        .map(|(((values, nested), type_), encoding)| {
-            array_to_pages(*values, type_, &nested, options, *encoding)
+        .zip(leafs.iter())
+        .map(|(((values, nested), type_), leaf)| {
+            let (values, encoding) =
+                dictionary::apply_options(*values, leaf.encoding, leaf.dictionary);
+            array_to_pages(values.as_ref(), type_, &nested, leaf.options, encoding)
         })
         .collect()
 }
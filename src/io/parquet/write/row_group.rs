@@ -11,8 +11,8 @@ use crate::{
 };
 
 use super::{
-    array_to_columns, to_parquet_schema, DynIter, DynStreamingIterator, Encoding, RowGroupIter,
-    SchemaDescriptor, WriteOptions,
+    pages::array_to_columns_with_options, to_parquet_schema, ColumnWriteOptions, DynIter,
+    DynStreamingIterator, Encoding, RowGroupIter, SchemaDescriptor, WriteOptions,
 };
 
 /// Maps a [`Chunk`] and parquet-specific options to an [`RowGroupIter`] used to
@@ -26,20 +26,67 @@ pub fn row_group_iter<A: AsRef<dyn Array> + 'static + Send + Sync>(
     encodings: Vec<Vec<Encoding>>,
     fields: Vec<ParquetType>,
     options: WriteOptions,
+) -> RowGroupIter<'static, Error> {
+    row_group_iter_with_leaf_options(
+        chunk,
+        encodings,
+        fields,
+        options,
+        &ColumnWriteOptions::default(),
+    )
+}
+
+/// Maps a [`Chunk`] and parquet-specific options to an [`RowGroupIter`] used to
+/// write to parquet, overriding `options` and `encodings` of each column by `columns`.
+/// # Errors
+/// Iff `columns` disables the statistics of a column while `options.write_statistics` is
+/// `true`: the file's page indexes require the statistics of every column.
+/// # Panics
+/// Iff
+/// * `encodings.len() != fields.len()` or
+/// * `encodings.len() != chunk.arrays().len()`
+pub fn row_group_iter_with_column_options<A: AsRef<dyn Array> + 'static + Send + Sync>(
+    chunk: Chunk<A>,
+    encodings: Vec<Vec<Encoding>>,
+    fields: Vec<ParquetType>,
+    options: WriteOptions,
+    columns: &ColumnWriteOptions,
+) -> Result<RowGroupIter<'static, Error>> {
+    columns.check_statistics(options)?;
+    Ok(row_group_iter_with_leaf_options(
+        chunk, encodings, fields, options, columns,
+    ))
+}
+
+/// [`row_group_iter_with_column_options`] without checking that the statistics of `columns`
+/// are consistent with `options`, for writers that reconcile them (see
+/// [`FileWriter::with_column_options`](super::FileWriter::with_column_options)).
+pub(super) fn row_group_iter_with_leaf_options<A: AsRef<dyn Array> + 'static + Send + Sync>(
+    chunk: Chunk<A>,
+    encodings: Vec<Vec<Encoding>>,
+    fields: Vec<ParquetType>,
+    options: WriteOptions,
+    columns: &ColumnWriteOptions,
 ) -> RowGroupIter<'static, Error> {
     assert_eq!(encodings.len(), fields.len());
     assert_eq!(encodings.len(), chunk.arrays().len());
+    let leafs = fields
+        .iter()
+        .zip(encodings.iter())
+        .map(|(type_, encodings)| columns.leaf_options(type_, options, encodings))
+        .collect::<Vec<_>>();
     DynIter::new(
         chunk
             .into_arrays()
             .into_iter()
             .zip(fields.into_iter())
-            .zip(encodings.into_iter())
-            .flat_map(move |((array, type_), encoding)| {
-                let encoded_columns = array_to_columns(array, type_, options, &encoding).unwrap();
+            .zip(leafs)
+            .flat_map(move |((array, type_), leafs)| {
+                let encoded_columns = array_to_columns_with_options(array, type_, &leafs).unwrap();
                 encoded_columns
                     .into_iter()
-                    .map(|encoded_pages| {
+                    .zip(leafs)
+                    .map(|(encoded_pages, leaf)| {
                         let pages = encoded_pages;
 
                         let pages = DynIter::new(
@@ -48,8 +95,9 @@ pub fn row_group_iter<A: AsRef<dyn Array> + 'static + Send + Sync>(
                                 .map(|x| x.map_err(|e| ParquetError::General(e.to_string()))),
                         );
 
-                        let compressed_pages = Compressor::new(pages, options.compression, vec![])
-                            .map_err(Error::from);
+                        let compressed_pages =
+                            Compressor::new(pages, leaf.options.compression, vec![])
+                                .map_err(Error::from);
                         Ok(DynStreamingIterator::new(compressed_pages))
                     })
                     .collect::<Vec<_>>()
@@ -65,6 +113,7 @@ pub struct RowGroupIterator<A: AsRef<dyn Array> + 'static, I: Iterator<Item = Re
     options: WriteOptions,
     parquet_schema: SchemaDescriptor,
     encodings: Vec<Vec<Encoding>>,
+    columns: ColumnWriteOptions,
 }
 
 impl<A: AsRef<dyn Array> + 'static, I: Iterator<Item = Result<Chunk<A>>>> RowGroupIterator<A, I> {
@@ -74,6 +123,8 @@ impl<A: AsRef<dyn Array> + 'static, I: Iterator<Item = Result<Chunk<A>>>> RowGro
     /// Iff
     /// * the Arrow schema can't be converted to a valid Parquet schema.
     /// * the length of the encodings is different from the number of fields in schema
+    /// * [`WriteOptions::bloom_filter`] is set, since row groups written by
+    ///   [`FileWriter::write`](super::FileWriter::write) do not have bloom filters
     pub fn try_new(
         iter: I,
         schema: &Schema,
@@ -85,6 +136,11 @@ impl<A: AsRef<dyn Array> + 'static, I: Iterator<Item = Result<Chunk<A>>>> RowGro
                 "The number of encodings must equal the number of fields".to_string(),
             ));
         }
+        if options.bloom_filter.is_some() {
+            return Err(Error::InvalidArgumentError(
+                "Bloom filters can only be built by `FileWriter::write_chunk`".to_string(),
+            ));
+        }
         let parquet_schema = to_parquet_schema(schema)?;
 
         Ok(Self {
@@ -92,9 +148,22 @@ impl<A: AsRef<dyn Array> + 'static, I: Iterator<Item = Result<Chunk<A>>>> RowGro
             options,
             parquet_schema,
             encodings,
+            columns: ColumnWriteOptions::default(),
         })
     }
 
+    /// Returns this [`RowGroupIterator`] with the options and encodings of its columns
+    /// overridden by `columns`.
+    /// # Errors
+    /// Iff `columns` disables the statistics of a column while the `write_statistics` of
+    /// this iterator's [`WriteOptions`] is `true`: the file's page indexes require the
+    /// statistics of every column. Set it to `false` and enable statistics per column instead.
+    pub fn with_column_options(mut self, columns: ColumnWriteOptions) -> Result<Self> {
+        columns.check_statistics(self.options)?;
+        self.columns = columns;
+        Ok(self)
+    }
+
     /// Returns the [`SchemaDescriptor`] of the [`RowGroupIterator`].
     pub fn parquet_schema(&self) -> &SchemaDescriptor {
         &self.parquet_schema
@@ -118,11 +187,12 @@ impl<A: AsRef<dyn Array> + 'static + Send + Sync, I: Iterator<Item = Result<Chun
                 ));
             };
             let encodings = self.encodings.clone();
-            Ok(row_group_iter(
+            Ok(row_group_iter_with_leaf_options(
                 chunk,
                 encodings,
                 self.parquet_schema.fields().to_vec(),
                 options,
+                &self.columns,
             ))
         })
     }
@@ -2,8 +2,8 @@ use parquet2::{
     metadata::KeyValue,
     schema::{
         types::{
-            GroupLogicalType, IntegerType, ParquetType, PhysicalType, PrimitiveConvertedType,
-            PrimitiveLogicalType, TimeUnit as ParquetTimeUnit,
+            GroupConvertedType, GroupLogicalType, IntegerType, ParquetType, PhysicalType,
+            PrimitiveConvertedType, PrimitiveLogicalType, TimeUnit as ParquetTimeUnit,
         },
         Repetition,
     },
@@ -319,6 +319,33 @@ pub fn to_parquet_type(field: &Field) -> Result<ParquetType> {
                 None,
             ))
         }
+        DataType::Map(f, _) => {
+            let fields = if let DataType::Struct(fields) = f.data_type().to_logical_type() {
+                fields
+                    .iter()
+                    .map(to_parquet_type)
+                    .collect::<Result<Vec<_>>>()?
+            } else {
+                return Err(Error::InvalidArgumentError(
+                    "The entries of a map must be a struct".to_string(),
+                ));
+            };
+            Ok(ParquetType::from_group(
+                name,
+                repetition,
+                Some(GroupConvertedType::Map),
+                Some(GroupLogicalType::Map),
+                vec![ParquetType::from_group(
+                    f.name.clone(),
+                    Repetition::Repeated,
+                    None,
+                    None,
+                    fields,
+                    None,
+                )],
+                None,
+            ))
+        }
         other => Err(Error::NotYetImplemented(format!(
             "Writing the data type {:?} is not yet implemented",
             other
@@ -35,6 +35,9 @@ use super::{Encoding, SchemaDescriptor, WriteOptions};
 ///     write_statistics: true,
 ///     compression: CompressionOptions::Uncompressed,
 ///     version: Version::V2,
+///     bloom_filter: None,
+///     data_page_size: None,
+///     data_page_max_rows: None,
 /// };
 ///
 /// let mut buffer = vec![];
@@ -76,6 +79,7 @@ where
     /// Iff
     /// * the Arrow schema can't be converted to a valid Parquet schema.
     /// * the length of the encodings is different from the number of fields in schema
+    /// * [`WriteOptions::bloom_filter`] is set, since this sink does not write bloom filters
     pub fn try_new(
         writer: W,
         schema: Schema,
@@ -87,6 +91,11 @@ where
                 "The number of encodings must equal the number of fields".to_string(),
             ));
         }
+        if options.bloom_filter.is_some() {
+            return Err(Error::InvalidArgumentError(
+                "Bloom filters can only be built by `FileWriter::write_chunk`".to_string(),
+            ));
+        }
 
         let parquet_schema = crate::io::parquet::write::to_parquet_schema(&schema)?;
         let created_by = Some("Arrow2 - Native Rust implementation of Arrow".to_string());
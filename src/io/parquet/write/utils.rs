@@ -71,6 +71,14 @@ pub fn build_plain_page(
     options: WriteOptions,
     encoding: Encoding,
 ) -> Result<DataPage> {
+    // a page without values (e.g. of null or empty lists) is a null page: all its values
+    // count as null so that its column index entry does not require a min and max value
+    let statistics = statistics.map(|mut statistics| {
+        if statistics.min_value.is_none() && statistics.max_value.is_none() {
+            statistics.null_count = Some(num_values as i64);
+        }
+        statistics
+    });
     let header = match options.version {
         Version::V1 => DataPageHeader::V1(DataPageHeaderV1 {
             num_values: num_values as i32,
@@ -2,19 +2,22 @@
 use std::collections::VecDeque;
 use std::sync::Arc;
 
+use ahash::AHashSet;
+
 use crate::array::Array;
 use crate::chunk::Chunk;
 use crate::datatypes::{DataType, Field};
 use crate::error::Error;
 use crate::ffi::mmap;
 
+use crate::io::ipc::endianess::is_native_little_endian;
 use crate::io::ipc::read::file::{get_dictionary_batch, get_record_batch};
-use crate::io::ipc::read::{first_dict_field, Dictionaries, FileMetadata};
-use crate::io::ipc::read::{IpcBuffer, Node, OutOfSpecKind};
+use crate::io::ipc::read::{first_dict_field, insert_dictionary, Dictionaries, FileMetadata};
+use crate::io::ipc::read::{skip, IpcBuffer, Node, OutOfSpecKind};
 use crate::io::ipc::{IpcField, CONTINUATION_MARKER};
 
 use arrow_format::ipc::planus::ReadAsRoot;
-use arrow_format::ipc::{Block, MessageRef, RecordBatchRef};
+use arrow_format::ipc::{Block, CompressionType, MessageRef, RecordBatchRef};
 
 fn read_message(
     mut bytes: &[u8],
@@ -50,15 +53,22 @@ fn read_message(
     Ok((message, offset + block_length))
 }
 
-fn get_buffers_nodes(
-    batch: RecordBatchRef,
-) -> Result<(VecDeque<IpcBuffer>, VecDeque<Node>), Error> {
-    let compression = batch.compression()?;
-    if compression.is_some() {
-        return Err(Error::nyi(
-            "mmap can only be done on uncompressed IPC files",
-        ));
-    }
+type BuffersNodes<'a> = (
+    VecDeque<IpcBuffer<'a>>,
+    VecDeque<Node<'a>>,
+    Option<CompressionType>,
+);
+
+fn get_buffers_nodes(batch: RecordBatchRef) -> Result<BuffersNodes, Error> {
+    let compression = batch
+        .compression()
+        .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferCompression(err)))?
+        .map(|compression| {
+            compression
+                .codec()
+                .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferCompression(err)))
+        })
+        .transpose()?;
 
     let buffers = batch
         .buffers()
@@ -72,9 +82,11 @@ fn get_buffers_nodes(
         .ok_or_else(|| Error::from(OutOfSpecKind::MissingMessageNodes))?;
     let field_nodes = field_nodes.iter().collect::<VecDeque<_>>();
 
-    Ok((buffers, field_nodes))
+    Ok((buffers, field_nodes, compression))
 }
 
+/// Memory maps the columns of `batch` in `projection` (all columns when `None`), in the order
+/// of the projection. Compressed buffers of columns outside the projection are not decompressed.
 unsafe fn _mmap_record<T: AsRef<[u8]>>(
     fields: &[Field],
     ipc_fields: &[IpcField],
@@ -82,27 +94,58 @@ unsafe fn _mmap_record<T: AsRef<[u8]>>(
     batch: RecordBatchRef,
     offset: usize,
     dictionaries: &Dictionaries,
+    projection: Option<&[usize]>,
 ) -> Result<Chunk<Box<dyn Array>>, Error> {
-    let (mut buffers, mut field_nodes) = get_buffers_nodes(batch)?;
+    let (mut buffers, mut field_nodes, compression) = get_buffers_nodes(batch)?;
+
+    let mut selected = vec![projection.is_none(); fields.len()];
+    if let Some(projection) = projection {
+        for &index in projection {
+            *selected.get_mut(index).ok_or_else(|| {
+                Error::InvalidArgumentError(format!(
+                    "the projection index {} is out of bounds for a schema with {} fields",
+                    index,
+                    fields.len()
+                ))
+            })? = true;
+        }
+    }
 
-    fields
+    let mut arrays = fields
         .iter()
-        .map(|f| &f.data_type)
-        .cloned()
         .zip(ipc_fields)
-        .map(|(data_type, ipc_field)| {
-            mmap::mmap(
-                data.clone(),
-                offset,
-                data_type,
-                ipc_field,
-                dictionaries,
-                &mut field_nodes,
-                &mut buffers,
-            )
+        .zip(selected)
+        .map(|((field, ipc_field), selected)| {
+            if selected {
+                mmap::mmap(
+                    data.clone(),
+                    offset,
+                    field.data_type.clone(),
+                    ipc_field,
+                    dictionaries,
+                    &mut field_nodes,
+                    &mut buffers,
+                    compression,
+                )
+                .map(Some)
+            } else {
+                skip(&mut field_nodes, &field.data_type, &mut buffers).map(|_| None)
+            }
         })
-        .collect::<Result<_, Error>>()
-        .and_then(Chunk::try_new)
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let arrays = if let Some(projection) = projection {
+        projection
+            .iter()
+            .map(|&index| arrays[index].clone().unwrap())
+            .collect()
+    } else {
+        arrays
+            .iter_mut()
+            .map(|array| array.take().unwrap())
+            .collect()
+    };
+    Chunk::try_new(arrays)
 }
 
 unsafe fn _mmap_unchecked<T: AsRef<[u8]>>(
@@ -121,16 +164,22 @@ unsafe fn _mmap_unchecked<T: AsRef<[u8]>>(
         batch,
         offset,
         dictionaries,
+        None,
     )
 }
 
 /// Memory maps an record batch from an IPC file into a [`Chunk`].
+///
+/// Uncompressed buffers are memory mapped without copies; compressed buffers are decompressed
+/// into memory when mapped.
 /// # Errors
 /// This function errors when:
 /// * The IPC file is not valid
 /// * the buffers on the file are un-aligned with their corresponding data. This can happen when:
 ///     * the file was written with 8-bit alignment
 ///     * the file contains type decimal 128 or 256
+/// * the file is compressed and this crate was compiled without `io_ipc_compression`
+/// * the file's byte order is not the native byte order
 /// # Safety
 /// The caller must ensure that `data` contains a valid buffers, for example:
 /// * Offsets in variable-sized containers must be in-bounds and increasing
@@ -141,6 +190,42 @@ pub unsafe fn mmap_unchecked<T: AsRef<[u8]>>(
     data: Arc<T>,
     chunk: usize,
 ) -> Result<Chunk<Box<dyn Array>>, Error> {
+    mmap_chunk(metadata, dictionaries, data, chunk, None)
+}
+
+/// Memory maps the columns in `projection` of a record batch from an IPC file into a [`Chunk`]
+/// whose arrays are in the order of `projection`.
+///
+/// Only the buffers of the projected columns are read: columns outside of the projection
+/// are neither accessed nor decompressed. Decompression is eager: every compressed buffer of
+/// a projected column is decompressed into memory by this function, before the column is
+/// accessed.
+/// # Errors
+/// This function errors when:
+/// * any of the conditions of [`mmap_unchecked`] happens
+/// * an index in `projection` is out of bounds of the schema
+/// # Safety
+/// The caller must ensure that `data` contains a valid buffers, for example:
+/// * Offsets in variable-sized containers must be in-bounds and increasing
+/// * Utf8 data is valid
+pub unsafe fn mmap_projected_unchecked<T: AsRef<[u8]>>(
+    metadata: &FileMetadata,
+    dictionaries: &Dictionaries,
+    data: Arc<T>,
+    chunk: usize,
+    projection: &[usize],
+) -> Result<Chunk<Box<dyn Array>>, Error> {
+    mmap_chunk(metadata, dictionaries, data, chunk, Some(projection))
+}
+
+unsafe fn mmap_chunk<T: AsRef<[u8]>>(
+    metadata: &FileMetadata,
+    dictionaries: &Dictionaries,
+    data: Arc<T>,
+    chunk: usize,
+    projection: Option<&[usize]>,
+) -> Result<Chunk<Box<dyn Array>>, Error> {
+    check_endianness(metadata)?;
     let block = metadata.blocks[chunk];
 
     let (message, offset) = read_message(data.as_ref().as_ref(), block)?;
@@ -152,21 +237,41 @@ pub unsafe fn mmap_unchecked<T: AsRef<[u8]>>(
         batch,
         offset,
         dictionaries,
+        projection,
     )
 }
 
+fn check_endianness(metadata: &FileMetadata) -> Result<(), Error> {
+    if metadata.ipc_schema.is_little_endian != is_native_little_endian() {
+        return Err(Error::nyi(
+            "Memory mapping IPC files of a non-native endianness",
+        ));
+    }
+    Ok(())
+}
+
 unsafe fn mmap_dictionary<T: AsRef<[u8]>>(
     metadata: &FileMetadata,
     data: Arc<T>,
     block: Block,
     dictionaries: &mut Dictionaries,
+    ids: Option<&AHashSet<i64>>,
 ) -> Result<(), Error> {
     let (message, offset) = read_message(data.as_ref().as_ref(), block)?;
     let batch = get_dictionary_batch(&message)?;
 
+    let is_delta = batch
+        .is_delta()
+        .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferIsDelta(err)))?;
+
     let id = batch
         .id()
         .map_err(|err| Error::from(OutOfSpecKind::InvalidFlatbufferId(err)))?;
+    if let Some(ids) = ids {
+        if !ids.contains(&id) {
+            return Ok(());
+        }
+    }
     let (first_field, first_ipc_field) =
         first_dict_field(id, &metadata.schema.fields, &metadata.ipc_schema.fields)?;
 
@@ -194,14 +299,21 @@ unsafe fn mmap_dictionary<T: AsRef<[u8]>>(
         batch,
         offset,
         dictionaries,
+        None,
     )?;
 
-    dictionaries.insert(id, chunk.into_arrays().pop().unwrap());
-
-    Ok(())
+    // deltas are copied into a new (not memory-mapped) dictionary
+    insert_dictionary(
+        dictionaries,
+        id,
+        chunk.into_arrays().pop().unwrap(),
+        is_delta,
+    )
 }
 
 /// Memory maps dictionaries from an IPC file into
+/// # Errors
+/// This function errors when the file's byte order is not the native byte order
 /// # Safety
 /// The caller must ensure that `data` contains a valid buffers, for example:
 /// * Offsets in variable-sized containers must be in-bounds and increasing
@@ -210,6 +322,52 @@ pub unsafe fn mmap_dictionaries_unchecked<T: AsRef<[u8]>>(
     metadata: &FileMetadata,
     data: Arc<T>,
 ) -> Result<Dictionaries, Error> {
+    mmap_dictionaries(metadata, data, None)
+}
+
+/// Memory maps the dictionaries used by the columns in `projection` from an IPC file into
+/// [`Dictionaries`]. The dictionaries of the remaining columns are not read.
+/// # Errors
+/// This function errors when:
+/// * an index in `projection` is out of bounds of the schema
+/// * the file's byte order is not the native byte order
+/// # Safety
+/// The caller must ensure that `data` contains a valid buffers, for example:
+/// * Offsets in variable-sized containers must be in-bounds and increasing
+/// * Utf8 data is valid
+pub unsafe fn mmap_projected_dictionaries_unchecked<T: AsRef<[u8]>>(
+    metadata: &FileMetadata,
+    data: Arc<T>,
+    projection: &[usize],
+) -> Result<Dictionaries, Error> {
+    let mut ids = AHashSet::new();
+    for &index in projection {
+        let ipc_field = metadata.ipc_schema.fields.get(index).ok_or_else(|| {
+            Error::InvalidArgumentError(format!(
+                "the projection index {} is out of bounds for a schema with {} fields",
+                index,
+                metadata.ipc_schema.fields.len()
+            ))
+        })?;
+        collect_dictionary_ids(ipc_field, &mut ids);
+    }
+    mmap_dictionaries(metadata, data, Some(&ids))
+}
+
+fn collect_dictionary_ids(ipc_field: &IpcField, ids: &mut AHashSet<i64>) {
+    ids.extend(ipc_field.dictionary_id);
+    ipc_field
+        .fields
+        .iter()
+        .for_each(|field| collect_dictionary_ids(field, ids));
+}
+
+unsafe fn mmap_dictionaries<T: AsRef<[u8]>>(
+    metadata: &FileMetadata,
+    data: Arc<T>,
+    ids: Option<&AHashSet<i64>>,
+) -> Result<Dictionaries, Error> {
+    check_endianness(metadata)?;
     let blocks = if let Some(blocks) = &metadata.dictionaries {
         blocks
     } else {
@@ -218,9 +376,8 @@ pub unsafe fn mmap_dictionaries_unchecked<T: AsRef<[u8]>>(
 
     let mut dictionaries = Default::default();
 
-    blocks
-        .iter()
-        .cloned()
-        .try_for_each(|block| mmap_dictionary(metadata, data.clone(), block, &mut dictionaries))?;
+    blocks.iter().cloned().try_for_each(|block| {
+        mmap_dictionary(metadata, data.clone(), block, &mut dictionaries, ids)
+    })?;
     Ok(dictionaries)
 }
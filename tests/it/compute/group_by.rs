@@ -0,0 +1,166 @@
+use arrow2::array::*;
+use arrow2::chunk::Chunk;
+use arrow2::compute::group_by::*;
+use arrow2::datatypes::DataType;
+use arrow2::error::Result;
+
+#[test]
+fn groups_single_column() -> Result<()> {
+    let keys = Int32Array::from_slice([1, 2, 1, 3, 2]);
+
+    let result = groups(&[&keys])?;
+
+    assert_eq!(result.first, UInt32Array::from_slice([0, 1, 3]));
+    assert_eq!(result.ids, vec![0, 1, 0, 2, 1]);
+    Ok(())
+}
+
+#[test]
+fn groups_null_keys() -> Result<()> {
+    let keys = Int32Array::from([Some(1), None, Some(1), None]);
+
+    let result = groups(&[&keys])?;
+
+    assert_eq!(result.first, UInt32Array::from_slice([0, 1]));
+    assert_eq!(result.ids, vec![0, 1, 0, 1]);
+    Ok(())
+}
+
+#[test]
+fn groups_empty_keys() {
+    assert!(groups(&[]).is_err());
+}
+
+#[test]
+fn multiple_keys() -> Result<()> {
+    let key1 = Int64Array::from_slice([1, 1, 2, 1, 2]);
+    let key2 = Utf8Array::<i32>::from([Some("a"), Some("b"), Some("a"), Some("a"), None]);
+    let values = Float64Array::from([Some(1.0), Some(2.0), Some(3.0), None, Some(5.0)]);
+    let chunk = Chunk::new(vec![key1.boxed(), key2.boxed(), values.boxed()]);
+
+    let result = group_by(
+        &chunk,
+        &[0, 1],
+        &[
+            (2, Aggregation::Sum),
+            (2, Aggregation::Count),
+            (2, Aggregation::Mean),
+        ],
+    )?;
+
+    let expected = Chunk::new(vec![
+        Int64Array::from_slice([1, 1, 2, 2]).boxed(),
+        Utf8Array::<i32>::from([Some("a"), Some("b"), Some("a"), None]).boxed(),
+        Float64Array::from_slice([1.0, 2.0, 3.0, 5.0]).boxed(),
+        UInt64Array::from_slice([1, 1, 1, 1]).boxed(),
+        Float64Array::from_slice([1.0, 2.0, 3.0, 5.0]).boxed(),
+    ]);
+    assert_eq!(result, expected);
+    Ok(())
+}
+
+#[test]
+fn dictionary_keys() -> Result<()> {
+    let mut keys = MutableDictionaryArray::<i32, MutableUtf8Array<i32>>::new();
+    keys.try_extend([Some("a"), Some("b"), None, Some("a"), None])?;
+    let keys: DictionaryArray<i32> = keys.into();
+    let values = Int32Array::from([Some(1), Some(2), Some(3), Some(4), None]);
+    let chunk = Chunk::new(vec![keys.clone().boxed(), values.boxed()]);
+
+    let result = group_by(
+        &chunk,
+        &[0],
+        &[
+            (1, Aggregation::Min),
+            (1, Aggregation::Max),
+            (1, Aggregation::Count),
+        ],
+    )?;
+
+    let expected_keys = arrow2::compute::take::take(&keys, &UInt32Array::from_slice([0, 1, 2]))?;
+    let expected = Chunk::new(vec![
+        expected_keys,
+        Int32Array::from_slice([1, 2, 3]).boxed(),
+        Int32Array::from_slice([4, 2, 3]).boxed(),
+        UInt64Array::from_slice([2, 1, 1]).boxed(),
+    ]);
+    assert_eq!(result, expected);
+    Ok(())
+}
+
+#[test]
+fn all_null_group() -> Result<()> {
+    let keys = Utf8Array::<i32>::from_slice(["a", "b", "a"]);
+    let values = Int32Array::from([Some(1), None, Some(2)]);
+
+    let groups = groups(&[&keys])?;
+
+    let result = aggregate(&values, &groups, Aggregation::Sum)?;
+    assert_eq!(
+        result.as_ref(),
+        &Int32Array::from([Some(3), None]) as &dyn Array
+    );
+    let result = aggregate(&values, &groups, Aggregation::Mean)?;
+    assert_eq!(
+        result.as_ref(),
+        &Float64Array::from([Some(1.5), None]) as &dyn Array
+    );
+    Ok(())
+}
+
+#[test]
+fn sum_wraps_on_overflow() -> Result<()> {
+    let keys = Int32Array::from_slice([1, 1, 2]);
+    let values = Int8Array::from_slice([i8::MAX, i8::MAX, i8::MIN]);
+    let groups = groups(&[&keys])?;
+
+    let result = aggregate(&values, &groups, Aggregation::Sum)?;
+    assert_eq!(
+        result.as_ref(),
+        &Int8Array::from_slice([-2, i8::MIN]) as &dyn Array
+    );
+    Ok(())
+}
+
+#[test]
+fn count_null_array() -> Result<()> {
+    let keys = Int32Array::from_slice([1, 2, 1]);
+    let values = NullArray::new(DataType::Null, 3);
+    let groups = groups(&[&keys])?;
+
+    let result = aggregate(&values, &groups, Aggregation::Count)?;
+    assert_eq!(
+        result.as_ref(),
+        &UInt64Array::from_slice([0, 0]) as &dyn Array
+    );
+    Ok(())
+}
+
+#[test]
+fn logical_type_is_preserved() -> Result<()> {
+    let keys = BooleanArray::from_slice([true, false, true]);
+    let values = Int128Array::from_slice([1, 2, 3]).to(DataType::Decimal(10, 2));
+
+    let groups = groups(&[&keys])?;
+
+    let result = aggregate(&values, &groups, Aggregation::Sum)?;
+    assert_eq!(
+        result.as_ref(),
+        &Int128Array::from_slice([4, 2]).to(DataType::Decimal(10, 2)) as &dyn Array
+    );
+    Ok(())
+}
+
+#[test]
+fn unsupported() -> Result<()> {
+    let keys = Int32Array::from_slice([1, 2]);
+    let values = Utf8Array::<i32>::from_slice(["a", "b"]);
+    let chunk = Chunk::new(vec![keys.boxed(), values.boxed()]);
+
+    assert!(!can_aggregate(&DataType::Utf8, Aggregation::Sum));
+    assert!(can_aggregate(&DataType::Utf8, Aggregation::Count));
+    assert!(!can_aggregate(&DataType::Decimal(10, 2), Aggregation::Mean));
+    assert!(group_by(&chunk, &[0], &[(1, Aggregation::Sum)]).is_err());
+    assert!(group_by(&chunk, &[0], &[(2, Aggregation::Count)]).is_err());
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use arrow2::array::new_null_array;
+use arrow2::array::{new_null_array, Int32Array, Utf8Array};
 use arrow2::compute::hash::*;
 use arrow2::datatypes::DataType::*;
 use arrow2::datatypes::TimeUnit;
@@ -47,3 +47,23 @@ fn consistency() {
         }
     });
 }
+
+#[test]
+fn rows() {
+    let a = Int32Array::from([Some(1), None, Some(1), None]);
+    let b = Utf8Array::<i32>::from([Some("a"), Some("a"), Some("a"), Some("b")]);
+
+    let hashes = hash_rows(&[&a, &b]).unwrap();
+
+    assert_eq!(hashes[0], hashes[2]);
+    assert_ne!(hashes[0], hashes[1]);
+    assert_ne!(hashes[1], hashes[3]);
+}
+
+#[test]
+fn rows_different_lengths() {
+    let a = Int32Array::from_slice([1, 2]);
+    let b = Int32Array::from_slice([1]);
+
+    assert!(hash_rows(&[&a, &b]).is_err());
+}
@@ -0,0 +1,146 @@
+use arrow2::array::*;
+use arrow2::chunk::Chunk;
+use arrow2::compute::join::*;
+use arrow2::error::Result;
+
+fn indices(join_type: JoinType) -> Result<(PrimitiveArray<u32>, Option<PrimitiveArray<u32>>)> {
+    let left = Int32Array::from([Some(1), Some(2), None, Some(4)]);
+    let right = Int32Array::from([Some(2), None, Some(3), Some(2), Some(1)]);
+    join_indices::<u32>(&[&left], &[&right], join_type)
+}
+
+#[test]
+fn inner() -> Result<()> {
+    let (left, right) = indices(JoinType::Inner)?;
+    let right = right.unwrap();
+    assert_eq!(left, UInt32Array::from_slice([0, 1, 1]));
+    assert_eq!(right, UInt32Array::from_slice([4, 0, 3]));
+    Ok(())
+}
+
+#[test]
+fn left() -> Result<()> {
+    let (left, right) = indices(JoinType::Left)?;
+    let right = right.unwrap();
+    assert_eq!(left, UInt32Array::from_slice([0, 1, 1, 2, 3]));
+    assert_eq!(
+        right,
+        UInt32Array::from([Some(4), Some(0), Some(3), None, None])
+    );
+    Ok(())
+}
+
+#[test]
+fn right() -> Result<()> {
+    let (left, right) = indices(JoinType::Right)?;
+    let right = right.unwrap();
+    assert_eq!(
+        left,
+        UInt32Array::from([Some(0), Some(1), Some(1), None, None])
+    );
+    assert_eq!(right, UInt32Array::from_slice([4, 0, 3, 1, 2]));
+    Ok(())
+}
+
+#[test]
+fn full() -> Result<()> {
+    let (left, right) = indices(JoinType::Full)?;
+    let right = right.unwrap();
+    assert_eq!(
+        left,
+        UInt32Array::from([Some(0), Some(1), Some(1), Some(2), Some(3), None, None])
+    );
+    assert_eq!(
+        right,
+        UInt32Array::from([Some(4), Some(0), Some(3), None, None, Some(1), Some(2)])
+    );
+    Ok(())
+}
+
+#[test]
+fn semi() -> Result<()> {
+    let (left, right) = indices(JoinType::Semi)?;
+    assert_eq!(left, UInt32Array::from_slice([0, 1]));
+    assert_eq!(right, None);
+    Ok(())
+}
+
+#[test]
+fn anti() -> Result<()> {
+    let (left, right) = indices(JoinType::Anti)?;
+    assert_eq!(left, UInt32Array::from_slice([2, 3]));
+    assert_eq!(right, None);
+    Ok(())
+}
+
+#[test]
+fn multiple_keys() -> Result<()> {
+    let left1 = Utf8Array::<i32>::from_slice(["a", "a", "b"]);
+    let left2 = Int64Array::from_slice([1, 2, 1]);
+    let right1 = Utf8Array::<i32>::from_slice(["b", "a", "a"]);
+    let right2 = Int64Array::from_slice([1, 1, 3]);
+
+    let (left, right) =
+        join_indices::<u64>(&[&left1, &left2], &[&right1, &right2], JoinType::Inner)?;
+
+    assert_eq!(left, UInt64Array::from_slice([0, 2]));
+    assert_eq!(right, Some(UInt64Array::from_slice([1, 0])));
+    Ok(())
+}
+
+#[test]
+fn dictionaries() -> Result<()> {
+    let mut left = MutableDictionaryArray::<i32, MutableUtf8Array<i32>>::new();
+    left.try_extend([Some("a"), Some("b"), Some("c")])?;
+    let left: DictionaryArray<i32> = left.into();
+    let mut right = MutableDictionaryArray::<i32, MutableUtf8Array<i32>>::new();
+    right.try_extend([Some("c"), Some("a")])?;
+    let right: DictionaryArray<i32> = right.into();
+
+    let (left, right) = join_indices::<u32>(&[&left], &[&right], JoinType::Inner)?;
+
+    assert_eq!(left, UInt32Array::from_slice([0, 2]));
+    assert_eq!(right, Some(UInt32Array::from_slice([1, 0])));
+    Ok(())
+}
+
+#[test]
+fn chunks() -> Result<()> {
+    let left = Chunk::new(vec![
+        Int32Array::from_slice([1, 2, 3]).boxed(),
+        Utf8Array::<i32>::from_slice(["a", "b", "c"]).boxed(),
+    ]);
+    let right = Chunk::new(vec![
+        Float32Array::from_slice([0.5, 1.5]).boxed(),
+        Int32Array::from_slice([3, 1]).boxed(),
+    ]);
+
+    let result = join(&left, &[0], &right, &[1], JoinType::Left)?;
+
+    let expected = Chunk::new(vec![
+        Int32Array::from_slice([1, 2, 3]).boxed(),
+        Utf8Array::<i32>::from_slice(["a", "b", "c"]).boxed(),
+        Float32Array::from([Some(1.5), None, Some(0.5)]).boxed(),
+        Int32Array::from([Some(1), None, Some(3)]).boxed(),
+    ]);
+    assert_eq!(result, expected);
+
+    let result = join(&left, &[0], &right, &[1], JoinType::Anti)?;
+
+    let expected = Chunk::new(vec![
+        Int32Array::from_slice([2]).boxed(),
+        Utf8Array::<i32>::from_slice(["b"]).boxed(),
+    ]);
+    assert_eq!(result, expected);
+    Ok(())
+}
+
+#[test]
+fn errors() {
+    let left = Int32Array::from_slice([1]);
+    let right = Int64Array::from_slice([1]);
+
+    assert!(join_indices::<u32>(&[&left], &[&right], JoinType::Inner).is_err());
+    assert!(join_indices::<u32>(&[&left], &[], JoinType::Inner).is_err());
+    assert!(join_indices::<u32>(&[], &[], JoinType::Inner).is_err());
+}
@@ -18,10 +18,14 @@ mod concatenate;
 mod contains;
 #[cfg(feature = "compute_filter")]
 mod filter;
+#[cfg(feature = "compute_group_by")]
+mod group_by;
 #[cfg(feature = "compute_hash")]
 mod hash;
 #[cfg(feature = "compute_if_then_else")]
 mod if_then_else;
+#[cfg(feature = "compute_join")]
+mod join;
 #[cfg(feature = "compute_length")]
 mod length;
 #[cfg(feature = "compute_like")]
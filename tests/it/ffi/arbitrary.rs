@@ -0,0 +1,444 @@
+//! Property-based round trips of arbitrary (nested) fields and arrays through the C data interface
+use proptest::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use arrow2::array::*;
+use arrow2::bitmap::Bitmap;
+use arrow2::datatypes::{DataType, Field, IntegerType, IntervalUnit, TimeUnit, UnionMode};
+use arrow2::datatypes::{PhysicalType, PrimitiveType};
+use arrow2::types::{days_ms, months_days_ns, NativeType, Offset};
+use arrow2::{error::Result, ffi};
+
+fn leaf_strategy() -> impl Strategy<Value = DataType> {
+    prop_oneof![
+        Just(DataType::Boolean),
+        Just(DataType::Int8),
+        Just(DataType::Int16),
+        Just(DataType::Int32),
+        Just(DataType::Int64),
+        Just(DataType::UInt8),
+        Just(DataType::UInt16),
+        Just(DataType::UInt32),
+        Just(DataType::UInt64),
+        Just(DataType::Float32),
+        Just(DataType::Float64),
+        Just(DataType::Date32),
+        Just(DataType::Timestamp(
+            TimeUnit::Millisecond,
+            Some("+01:00".to_string())
+        )),
+        Just(DataType::Decimal(10, 2)),
+        Just(DataType::Interval(IntervalUnit::DayTime)),
+        Just(DataType::Interval(IntervalUnit::MonthDayNano)),
+        Just(DataType::Utf8),
+        Just(DataType::LargeUtf8),
+        Just(DataType::Binary),
+        Just(DataType::LargeBinary),
+        (1usize..4).prop_map(DataType::FixedSizeBinary),
+    ]
+}
+
+fn key_type_strategy() -> impl Strategy<Value = IntegerType> {
+    prop_oneof![
+        Just(IntegerType::Int8),
+        Just(IntegerType::UInt16),
+        Just(IntegerType::Int32),
+        Just(IntegerType::UInt64),
+    ]
+}
+
+fn dictionary_strategy() -> impl Strategy<Value = DataType> {
+    (key_type_strategy(), leaf_strategy(), any::<bool>()).prop_map(|(key, values, is_ordered)| {
+        DataType::Dictionary(key, Box::new(values), is_ordered)
+    })
+}
+
+fn field(name: &str, data_type: DataType, is_nullable: bool) -> Field {
+    Field::new(name, data_type, is_nullable)
+}
+
+fn fields(data_types: Vec<(DataType, bool)>) -> Vec<Field> {
+    data_types
+        .into_iter()
+        .enumerate()
+        .map(|(i, (data_type, is_nullable))| field(&format!("f{}", i), data_type, is_nullable))
+        .collect()
+}
+
+/// Whether `data_type` is or contains a map
+fn contains_map(data_type: &DataType) -> bool {
+    match data_type.to_logical_type() {
+        DataType::Map(..) => true,
+        DataType::List(field) | DataType::LargeList(field) | DataType::FixedSizeList(field, _) => {
+            contains_map(field.data_type())
+        }
+        DataType::Struct(fields) | DataType::Union(fields, _, _) => {
+            fields.iter().any(|field| contains_map(field.data_type()))
+        }
+        _ => false,
+    }
+}
+
+fn union(data_types: Vec<(DataType, bool)>, is_sparse: bool, shift: Option<i32>) -> DataType {
+    let fields = fields(data_types);
+    // type ids equal to the fields' positions are imported as no type ids
+    let ids = shift
+        .map(|shift| {
+            (0..fields.len() as i32)
+                .rev()
+                .map(|i| i * 2 + shift)
+                .collect::<Vec<_>>()
+        })
+        .filter(|ids| !ids.iter().copied().eq(0..fields.len() as i32));
+    DataType::Union(fields, ids, UnionMode::sparse(is_sparse))
+}
+
+fn map(keys: DataType, values: DataType, keys_sorted: bool) -> DataType {
+    let entries = DataType::Struct(vec![
+        field("key", keys, false),
+        field("value", values, true),
+    ]);
+    DataType::Map(Box::new(field("entries", entries, false)), keys_sorted)
+}
+
+fn extension(name: String, inner: DataType, metadata: Option<String>) -> DataType {
+    if let DataType::Extension(..) = inner {
+        // extensions can't be nested
+        inner
+    } else {
+        DataType::Extension(name, Box::new(inner), metadata)
+    }
+}
+
+fn data_type_strategy() -> impl Strategy<Value = DataType> {
+    let leaf = prop_oneof![
+        4 => leaf_strategy(),
+        1 => Just(DataType::Null),
+        1 => dictionary_strategy(),
+    ];
+    leaf.prop_recursive(3, 24, 3, |inner| {
+        let child = (inner.clone(), any::<bool>());
+        prop_oneof![
+            child
+                .clone()
+                .prop_map(|(dt, nullable)| DataType::List(Box::new(field("item", dt, nullable)))),
+            child
+                .clone()
+                .prop_map(|(dt, nullable)| DataType::LargeList(Box::new(field(
+                    "item", dt, nullable
+                )))),
+            (child.clone(), 1usize..3).prop_map(|((dt, nullable), size)| {
+                DataType::FixedSizeList(Box::new(field("item", dt, nullable)), size)
+            }),
+            prop::collection::vec(child.clone(), 1..4)
+                .prop_map(|children| DataType::Struct(fields(children))),
+            (
+                // unions are compared by their scalars, which are not implemented for maps
+                prop::collection::vec(
+                    child.prop_filter("map in union", |(dt, _)| !contains_map(dt)),
+                    1..4
+                ),
+                any::<bool>(),
+                prop::option::of(0i32..3)
+            )
+                .prop_map(|(children, is_sparse, shift)| union(children, is_sparse, shift)),
+            (leaf_strategy(), inner.clone(), any::<bool>())
+                .prop_map(|(keys, values, keys_sorted)| map(keys, values, keys_sorted)),
+            ("[a-z]{1,5}", inner, prop::option::of("[a-z]{0,5}"))
+                .prop_map(|(name, inner, metadata)| extension(name, inner, metadata)),
+        ]
+    })
+}
+
+fn validity(rng: &mut StdRng, length: usize) -> Option<Bitmap> {
+    if rng.gen() {
+        Some((0..length).map(|_| rng.gen_bool(0.7)).collect())
+    } else {
+        None
+    }
+}
+
+/// Returns offsets of `length` slots with between 0 and 2 values each
+fn offsets<O: Offset>(rng: &mut StdRng, length: usize) -> Vec<O> {
+    let mut total = 0;
+    std::iter::once(0)
+        .chain((0..length).map(|_| {
+            total += rng.gen_range(0..3);
+            total
+        }))
+        .map(|offset| O::from_usize(offset).unwrap())
+        .collect()
+}
+
+fn primitive<T: NativeType>(
+    rng: &mut StdRng,
+    data_type: &DataType,
+    length: usize,
+    value: impl Fn(&mut StdRng) -> T,
+) -> Box<dyn Array> {
+    let values = (0..length).map(|_| value(rng)).collect::<Vec<_>>();
+    PrimitiveArray::new(data_type.clone(), values.into(), validity(rng, length)).boxed()
+}
+
+fn binary<O: Offset>(rng: &mut StdRng, data_type: &DataType, length: usize) -> Box<dyn Array> {
+    let values = (0..length)
+        .map(|_| {
+            (0..rng.gen_range(0..4))
+                .map(|_| rng.gen())
+                .collect::<Vec<u8>>()
+        })
+        .collect::<Vec<_>>();
+    let array = BinaryArray::<O>::from_iter_values(values.iter());
+    BinaryArray::<O>::new(
+        data_type.clone(),
+        array.offsets().clone(),
+        array.values().clone(),
+        validity(rng, length),
+    )
+    .boxed()
+}
+
+fn utf8<O: Offset>(rng: &mut StdRng, data_type: &DataType, length: usize) -> Box<dyn Array> {
+    let values = (0..length)
+        .map(|_| {
+            (0..rng.gen_range(0..4))
+                .map(|_| rng.gen_range('a'..='e'))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    let array = Utf8Array::<O>::from_iter_values(values.iter());
+    Utf8Array::<O>::new(
+        data_type.clone(),
+        array.offsets().clone(),
+        array.values().clone(),
+        validity(rng, length),
+    )
+    .boxed()
+}
+
+fn list<O: Offset>(rng: &mut StdRng, data_type: &DataType, length: usize) -> Box<dyn Array> {
+    let offsets = offsets::<O>(rng, length);
+    let values = array(
+        rng,
+        ListArray::<O>::get_child_type(data_type),
+        offsets.last().unwrap().to_usize(),
+    );
+    ListArray::<O>::new(
+        data_type.clone(),
+        offsets.into(),
+        values,
+        validity(rng, length),
+    )
+    .boxed()
+}
+
+fn dictionary<K: DictionaryKey>(
+    rng: &mut StdRng,
+    data_type: &DataType,
+    length: usize,
+) -> Box<dyn Array> {
+    let values = if let DataType::Dictionary(_, values, _) = data_type.to_logical_type() {
+        values.as_ref()
+    } else {
+        unreachable!()
+    };
+    let values_length = rng.gen_range(1..4);
+    let values = array(rng, values, values_length);
+    let keys = primitive(rng, &K::PRIMITIVE.into(), length, |rng| {
+        K::try_from(rng.gen_range(0..values.len())).ok().unwrap()
+    });
+    let keys = keys
+        .as_any()
+        .downcast_ref::<PrimitiveArray<K>>()
+        .unwrap()
+        .clone();
+    DictionaryArray::try_new(data_type.clone(), keys, values)
+        .unwrap()
+        .boxed()
+}
+
+fn union_array(rng: &mut StdRng, data_type: &DataType, length: usize) -> Box<dyn Array> {
+    let (fields, ids, mode) =
+        if let DataType::Union(fields, ids, mode) = data_type.to_logical_type() {
+            (fields, ids, mode)
+        } else {
+            unreachable!()
+        };
+    let indices = (0..length)
+        .map(|_| rng.gen_range(0..fields.len()))
+        .collect::<Vec<_>>();
+    let types = indices
+        .iter()
+        .map(|&index| ids.as_ref().map(|ids| ids[index]).unwrap_or(index as i32) as i8)
+        .collect::<Vec<_>>();
+
+    if mode.is_sparse() {
+        let fields = fields
+            .iter()
+            .map(|field| array(rng, field.data_type(), length))
+            .collect();
+        UnionArray::new(data_type.clone(), types.into(), fields, None).boxed()
+    } else {
+        let mut lengths = vec![0; fields.len()];
+        let offsets = indices
+            .iter()
+            .map(|&index| {
+                lengths[index] += 1;
+                lengths[index] - 1
+            })
+            .collect::<Vec<i32>>();
+        let fields = fields
+            .iter()
+            .zip(lengths)
+            .map(|(field, length)| array(rng, field.data_type(), length as usize))
+            .collect();
+        UnionArray::new(
+            data_type.clone(),
+            types.into(),
+            fields,
+            Some(offsets.into()),
+        )
+        .boxed()
+    }
+}
+
+/// Returns an array of `data_type` with `length` random values
+fn array(rng: &mut StdRng, data_type: &DataType, length: usize) -> Box<dyn Array> {
+    use PhysicalType::*;
+    match data_type.to_physical_type() {
+        Null => NullArray::new(data_type.clone(), length).boxed(),
+        Boolean => {
+            let values = (0..length).map(|_| rng.gen()).collect();
+            BooleanArray::new(data_type.clone(), values, validity(rng, length)).boxed()
+        }
+        Primitive(primitive_type) => match primitive_type {
+            PrimitiveType::Int8 => primitive(rng, data_type, length, |rng| rng.gen::<i8>()),
+            PrimitiveType::Int16 => primitive(rng, data_type, length, |rng| rng.gen::<i16>()),
+            PrimitiveType::Int32 => primitive(rng, data_type, length, |rng| rng.gen::<i32>()),
+            PrimitiveType::Int64 => primitive(rng, data_type, length, |rng| rng.gen::<i64>()),
+            PrimitiveType::Int128 => primitive(rng, data_type, length, |rng| {
+                rng.gen_range(-10i128.pow(10) + 1..10i128.pow(10))
+            }),
+            PrimitiveType::UInt8 => primitive(rng, data_type, length, |rng| rng.gen::<u8>()),
+            PrimitiveType::UInt16 => primitive(rng, data_type, length, |rng| rng.gen::<u16>()),
+            PrimitiveType::UInt32 => primitive(rng, data_type, length, |rng| rng.gen::<u32>()),
+            PrimitiveType::UInt64 => primitive(rng, data_type, length, |rng| rng.gen::<u64>()),
+            PrimitiveType::Float32 => primitive(rng, data_type, length, |rng| rng.gen::<f32>()),
+            PrimitiveType::Float64 => primitive(rng, data_type, length, |rng| rng.gen::<f64>()),
+            PrimitiveType::DaysMs => primitive(rng, data_type, length, |rng| {
+                days_ms::new(rng.gen(), rng.gen())
+            }),
+            PrimitiveType::MonthDayNano => primitive(rng, data_type, length, |rng| {
+                months_days_ns::new(rng.gen(), rng.gen(), rng.gen())
+            }),
+            PrimitiveType::Float16 => unimplemented!("Float16 is not generated"),
+        },
+        Binary => binary::<i32>(rng, data_type, length),
+        LargeBinary => binary::<i64>(rng, data_type, length),
+        Utf8 => utf8::<i32>(rng, data_type, length),
+        LargeUtf8 => utf8::<i64>(rng, data_type, length),
+        FixedSizeBinary => {
+            let size = match data_type.to_logical_type() {
+                DataType::FixedSizeBinary(size) => *size,
+                _ => unreachable!(),
+            };
+            let values = (0..length * size).map(|_| rng.gen()).collect::<Vec<u8>>();
+            FixedSizeBinaryArray::new(data_type.clone(), values.into(), validity(rng, length))
+                .boxed()
+        }
+        List => list::<i32>(rng, data_type, length),
+        LargeList => list::<i64>(rng, data_type, length),
+        FixedSizeList => {
+            let (field, size) = match data_type.to_logical_type() {
+                DataType::FixedSizeList(field, size) => (field, *size),
+                _ => unreachable!(),
+            };
+            let values = array(rng, field.data_type(), length * size);
+            FixedSizeListArray::new(data_type.clone(), values, validity(rng, length)).boxed()
+        }
+        Struct => {
+            let values = StructArray::get_fields(data_type)
+                .iter()
+                .map(|field| array(rng, field.data_type(), length))
+                .collect();
+            StructArray::new(data_type.clone(), values, validity(rng, length)).boxed()
+        }
+        Union => union_array(rng, data_type, length),
+        Map => {
+            let field = match data_type.to_logical_type() {
+                DataType::Map(field, _) => field,
+                _ => unreachable!(),
+            };
+            let offsets = offsets::<i32>(rng, length);
+            let field = array(rng, field.data_type(), *offsets.last().unwrap() as usize);
+            MapArray::new(
+                data_type.clone(),
+                offsets.into(),
+                field,
+                validity(rng, length),
+            )
+            .boxed()
+        }
+        Dictionary(key_type) => match key_type {
+            IntegerType::Int8 => dictionary::<i8>(rng, data_type, length),
+            IntegerType::UInt16 => dictionary::<u16>(rng, data_type, length),
+            IntegerType::Int32 => dictionary::<i32>(rng, data_type, length),
+            IntegerType::UInt64 => dictionary::<u64>(rng, data_type, length),
+            IntegerType::Int16 | IntegerType::Int64 | IntegerType::UInt8 | IntegerType::UInt32 => {
+                unimplemented!("dictionaries with {:?} keys are not generated", key_type)
+            }
+        },
+    }
+}
+
+/// A random field, an array of its type and a slice of it (offset and length)
+fn case_strategy() -> impl Strategy<Value = (Field, Box<dyn Array>, usize, usize)> {
+    (
+        data_type_strategy(),
+        any::<bool>(),
+        0usize..10,
+        any::<u64>(),
+    )
+        .prop_flat_map(|(data_type, is_nullable, length, seed)| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let array = array(&mut rng, &data_type, length);
+            let field = Field::new("a", data_type, is_nullable);
+            (Just(field), Just(array), 0..=length)
+        })
+        .prop_flat_map(|(field, array, offset)| {
+            let length = array.len() - offset;
+            (Just(field), Just(array), Just(offset), 0..=length)
+        })
+}
+
+fn round_trip(field: &Field, array: Box<dyn Array>) -> Result<(Field, Box<dyn Array>)> {
+    let schema = ffi::export_field_to_c(field);
+    let array = ffi::export_array_to_c(array);
+
+    let field = unsafe { ffi::import_field_from_c(&schema)? };
+    let array = unsafe { ffi::import_array_from_c(array, field.data_type.clone())? };
+    Ok((field, array))
+}
+
+proptest! {
+    #[test]
+    #[cfg_attr(miri, ignore)] // miri and proptest do not work well :(
+    fn round_trip_field(data_type in data_type_strategy(), is_nullable in any::<bool>()) {
+        let field = Field::new("a", data_type, is_nullable);
+        let schema = ffi::export_field_to_c(&field);
+        let result = unsafe { ffi::import_field_from_c(&schema) }.unwrap();
+        prop_assert_eq!(result, field);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // miri and proptest do not work well :(
+    fn round_trip_array((field, array, offset, length) in case_strategy()) {
+        let (result_field, result) = round_trip(&field, array.clone()).unwrap();
+        prop_assert_eq!(&result_field, &field);
+        prop_assert_eq!(&result, &array);
+
+        let array = array.slice(offset, length);
+        let (_, result) = round_trip(&field, array.clone()).unwrap();
+        prop_assert_eq!(&result, &array);
+    }
+}
@@ -1,6 +1,7 @@
 use arrow2::array::*;
 use arrow2::bitmap::Bitmap;
-use arrow2::datatypes::{DataType, Field, TimeUnit};
+use arrow2::datatypes::{DataType, Field, IntervalUnit, TimeUnit, UnionMode};
+use arrow2::types::months_days_ns;
 use arrow2::{error::Result, ffi};
 use std::collections::BTreeMap;
 
@@ -310,6 +311,113 @@ fn dict() -> Result<()> {
     test_round_trip(array)
 }
 
+#[test]
+fn dict_ordered() -> Result<()> {
+    let values = Utf8Array::<i32>::from_slice(["a", "b"]).boxed();
+    let data_type = DataType::Dictionary(u8::KEY_TYPE, Box::new(DataType::Utf8), true);
+    let keys = PrimitiveArray::<u8>::from([Some(1), None, Some(0), Some(1)]);
+    let array = DictionaryArray::try_new(data_type, keys, values)?;
+
+    test_round_trip(array)
+}
+
+#[test]
+fn interval_month_day_nano() -> Result<()> {
+    let data = MonthsDaysNsArray::from([
+        Some(months_days_ns::new(1, 2, 3)),
+        None,
+        Some(months_days_ns::new(-1, 0, i64::MAX)),
+    ])
+    .to(DataType::Interval(IntervalUnit::MonthDayNano));
+    test_round_trip(data)
+}
+
+fn union_fields() -> Vec<Field> {
+    vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ]
+}
+
+#[test]
+fn sparse_union() -> Result<()> {
+    let data_type = DataType::Union(union_fields(), Some(vec![4, 7]), UnionMode::Sparse);
+    let types = vec![4, 7, 4, 7].into();
+    let fields = vec![
+        Int32Array::from([Some(1), None, None, Some(2)]).boxed(),
+        Utf8Array::<i32>::from([Some("a"), Some("b"), Some("c"), None]).boxed(),
+    ];
+    let array = UnionArray::try_new(data_type, types, fields, None)?;
+
+    test_round_trip(array)
+}
+
+#[test]
+fn dense_union() -> Result<()> {
+    let data_type = DataType::Union(union_fields(), Some(vec![4, 7]), UnionMode::Dense);
+    let types = vec![4, 7, 4, 7].into();
+    let fields = vec![
+        Int32Array::from([Some(1), None]).boxed(),
+        Utf8Array::<i32>::from([Some("a"), None]).boxed(),
+    ];
+    let offsets = Some(vec![0, 0, 1, 1].into());
+    let array = UnionArray::try_new(data_type, types, fields, offsets)?;
+
+    test_round_trip(array)
+}
+
+#[test]
+fn map() -> Result<()> {
+    let entries = DataType::Struct(vec![
+        Field::new("key", DataType::Utf8, false),
+        Field::new("value", DataType::Int32, true),
+    ]);
+    let data_type = DataType::Map(
+        Box::new(Field::new("entries", entries.clone(), false)),
+        true,
+    );
+    let field = StructArray::new(
+        entries,
+        vec![
+            Utf8Array::<i32>::from_slice(["a", "b", "a", "c"]).boxed(),
+            Int32Array::from([Some(1), None, Some(3), Some(4)]).boxed(),
+        ],
+        None,
+    );
+    let validity = Bitmap::from([true, false, true, true]);
+    let array = MapArray::try_new(
+        data_type,
+        vec![0, 2, 2, 3, 4].into(),
+        field.boxed(),
+        Some(validity),
+    )?;
+
+    test_round_trip(array)
+}
+
+#[test]
+fn extension_struct() -> Result<()> {
+    let inner = DataType::Struct(vec![Field::new("a", DataType::Int32, true)]);
+    let data_type =
+        DataType::Extension("point".to_string(), Box::new(inner), Some("{}".to_string()));
+    let values = vec![Int32Array::from([Some(1), None, Some(3)]).boxed()];
+    let validity = Bitmap::from([true, false, true]);
+    let array = StructArray::new(data_type, values, validity.into());
+
+    test_round_trip(array)
+}
+
+#[test]
+fn extension_dict() -> Result<()> {
+    let values = Utf8Array::<i32>::from_slice(["a", "b"]).boxed();
+    let inner = DataType::Dictionary(i32::KEY_TYPE, Box::new(DataType::Utf8), true);
+    let data_type = DataType::Extension("tag".to_string(), Box::new(inner), None);
+    let keys = PrimitiveArray::<i32>::from([Some(1), None, Some(0)]);
+    let array = DictionaryArray::try_new(data_type, keys, values)?;
+
+    test_round_trip(array)
+}
+
 #[test]
 fn schema() -> Result<()> {
     let field = Field::new(
@@ -346,3 +454,33 @@ fn extension() -> Result<()> {
     );
     test_round_trip_schema(field)
 }
+
+#[test]
+fn schema_nested() -> Result<()> {
+    let union = DataType::Union(union_fields(), Some(vec![1, 5]), UnionMode::Dense);
+    test_round_trip_schema(Field::new("a", union, true))?;
+
+    let union = DataType::Union(vec![], None, UnionMode::Sparse);
+    test_round_trip_schema(Field::new("a", union, true))?;
+
+    let entries = DataType::Struct(vec![
+        Field::new("key", DataType::Utf8, false),
+        Field::new("value", DataType::Int32, true),
+    ]);
+    for keys_sorted in [true, false] {
+        let map = DataType::Map(
+            Box::new(Field::new("entries", entries.clone(), false)),
+            keys_sorted,
+        );
+        test_round_trip_schema(Field::new("a", map, false))?;
+    }
+
+    let dict = DataType::Dictionary(i64::KEY_TYPE, Box::new(DataType::LargeUtf8), true);
+    test_round_trip_schema(Field::new("a", dict, true))?;
+
+    let list = DataType::LargeList(Box::new(Field::new("item", DataType::Utf8, false)));
+    let extension = DataType::Extension("a".to_string(), Box::new(list), None);
+    let mut metadata = BTreeMap::new();
+    metadata.insert("some".to_string(), "stuff".to_string());
+    test_round_trip_schema(Field::new("a", extension, true).with_metadata(metadata))
+}
@@ -1,6 +1,8 @@
 use arrow2::array::*;
-use arrow2::datatypes::Field;
-use arrow2::{error::Result, ffi};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Metadata, Schema};
+use arrow2::error::{Error, Result};
+use arrow2::ffi;
 
 fn _test_round_trip(arrays: Vec<Box<dyn Array>>) -> Result<()> {
     let field = Field::new("a", arrays[0].data_type().clone(), true);
@@ -30,3 +32,80 @@ fn round_trip() -> Result<()> {
 
     _test_round_trip(vec![array.clone(), array.clone(), array])
 }
+
+fn chunk_stream(
+    chunks: Vec<Result<Chunk<Box<dyn Array>>>>,
+    schema: Schema,
+) -> Result<ffi::ArrowChunkStreamReader> {
+    let iter = Box::new(chunks.into_iter()) as _;
+
+    let mut stream = Box::new(ffi::ArrowArrayStream::empty());
+    *stream = ffi::export_chunk_iterator(iter, schema);
+
+    unsafe { ffi::ArrowChunkStreamReader::try_new(stream) }
+}
+
+#[test]
+fn chunk_round_trip() -> Result<()> {
+    let a = Int32Array::from(&[Some(2), None, Some(1)]).boxed();
+    let b = Utf8Array::<i32>::from([Some("a"), Some("bb"), None]).boxed();
+    let chunks = vec![
+        Chunk::new(vec![a.clone(), b.clone()]),
+        Chunk::new(vec![a.slice(1, 2), b.slice(0, 2)]),
+    ];
+    let mut metadata = Metadata::new();
+    metadata.insert("key".to_string(), "value".to_string());
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ])
+    .with_metadata(metadata);
+
+    let mut stream = chunk_stream(chunks.iter().cloned().map(Ok).collect(), schema.clone())?;
+    assert_eq!(stream.schema(), &schema);
+
+    let mut produced = vec![];
+    while let Some(chunk) = unsafe { stream.next() } {
+        produced.push(chunk?);
+    }
+    assert_eq!(produced, chunks);
+    Ok(())
+}
+
+#[test]
+fn chunk_error() -> Result<()> {
+    let a = Int32Array::from_slice([1, 2]).boxed();
+    let schema = Schema::from(vec![Field::new("a", DataType::Int32, true)]);
+    let chunks = vec![
+        Ok(Chunk::new(vec![a])),
+        Err(Error::ExternalFormat("producer failed".to_string())),
+    ];
+
+    let mut stream = chunk_stream(chunks, schema)?;
+    assert!(unsafe { stream.next() }.unwrap().is_ok());
+    let error = unsafe { stream.next() }.unwrap().unwrap_err();
+    assert!(error.to_string().contains("producer failed"));
+    Ok(())
+}
+
+#[test]
+fn chunk_of_wrong_type() -> Result<()> {
+    let a = Int64Array::from_slice([1, 2]).boxed();
+    let schema = Schema::from(vec![Field::new("a", DataType::Int32, true)]);
+
+    let mut stream = chunk_stream(vec![Ok(Chunk::new(vec![a]))], schema)?;
+    assert!(unsafe { stream.next() }.unwrap().is_err());
+    Ok(())
+}
+
+#[test]
+fn chunk_from_non_struct() -> Result<()> {
+    let array = Int32Array::from_slice([1, 2]).boxed();
+    let field = Field::new("a", DataType::Int32, true);
+
+    let mut stream = Box::new(ffi::ArrowArrayStream::empty());
+    *stream = ffi::export_iterator(Box::new(vec![Ok(array)].into_iter()), field);
+
+    assert!(unsafe { ffi::ArrowChunkStreamReader::try_new(stream) }.is_err());
+    Ok(())
+}
@@ -3,9 +3,11 @@ use proptest::prelude::*;
 use std::io::Cursor;
 
 use arrow2::array::*;
+use arrow2::chunk::Chunk;
 use arrow2::datatypes::*;
-use arrow2::error::Result;
+use arrow2::error::{Error, Result};
 use arrow2::io::csv::read::*;
+use arrow2::types::{days_ms, months_days_ns};
 
 #[test]
 fn read() -> Result<()> {
@@ -371,6 +373,557 @@ fn deserialize_timestamp() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn time32() -> Result<()> {
+    let input = "00:00:01,\n12:34:56.789,\n,\n25:00:00,";
+
+    let data_type = DataType::Time32(TimeUnit::Millisecond);
+    let expected =
+        Int32Array::from([Some(1_000), Some(45_296_789), None, None]).to(data_type.clone());
+    let result = test_deserialize(input, data_type)?;
+    assert_eq!(expected, result.as_ref());
+
+    let data_type = DataType::Time32(TimeUnit::Second);
+    let expected = Int32Array::from([Some(1), Some(45_296), None, None]).to(data_type.clone());
+    let result = test_deserialize(input, data_type)?;
+    assert_eq!(expected, result.as_ref());
+    Ok(())
+}
+
+#[test]
+fn time64() -> Result<()> {
+    let input = "00:00:01.000001\n12:34:56.123456789";
+
+    let data_type = DataType::Time64(TimeUnit::Microsecond);
+    let expected = Int64Array::from([Some(1_000_001), Some(45_296_123_456)]).to(data_type.clone());
+    let result = test_deserialize(input, data_type)?;
+    assert_eq!(expected, result.as_ref());
+
+    let data_type = DataType::Time64(TimeUnit::Nanosecond);
+    let expected =
+        Int64Array::from([Some(1_000_001_000), Some(45_296_123_456_789)]).to(data_type.clone());
+    let result = test_deserialize(input, data_type)?;
+    assert_eq!(expected, result.as_ref());
+    Ok(())
+}
+
+#[test]
+fn infer_offsets() {
+    let offsets = [
+        ("2021-01-01T10:00:00+01:00", "+01:00"),
+        ("2021-01-01T10:00:00+00:00", "+00:00"),
+        ("2021-01-01T10:00:00-01:30", "-01:30"),
+        ("2021-01-01T10:00:00+05:45", "+05:45"),
+    ];
+    for (datetime, offset) in offsets {
+        assert_eq!(
+            infer(datetime.as_bytes()),
+            DataType::Timestamp(TimeUnit::Millisecond, Some(offset.to_string()))
+        );
+    }
+}
+
+#[test]
+fn infer_time() -> Result<()> {
+    let input = "a\n12:34:56\n01:02:03.5";
+    let mut reader = ReaderBuilder::new().from_reader(Cursor::new(input));
+    let (fields, _) = infer_schema(&mut reader, None, true, &infer)?;
+    assert_eq!(
+        fields[0].data_type(),
+        &DataType::Time32(TimeUnit::Millisecond)
+    );
+
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows)?;
+    let chunk = deserialize_batch(&rows[..rows_read], &fields, None, 0, deserialize_column)?;
+
+    let expected = Int32Array::from([Some(45_296_000), Some(3_723_500)])
+        .to(DataType::Time32(TimeUnit::Millisecond));
+    assert_eq!(chunk.arrays()[0].as_ref(), &expected as &dyn Array);
+    Ok(())
+}
+
+#[test]
+fn duration() -> Result<()> {
+    let input = "1,\n-20,\n,\n1.5,";
+
+    let data_type = DataType::Duration(TimeUnit::Millisecond);
+    let expected = Int64Array::from([Some(1), Some(-20), None, None]).to(data_type.clone());
+    let result = test_deserialize(input, data_type)?;
+    assert_eq!(expected, result.as_ref());
+    Ok(())
+}
+
+#[test]
+fn interval_month_day_nano() -> Result<()> {
+    let input = "P1Y2M3DT4H5M6.5S\n-P1W\nPT0.000000001S\nP\n1D\nPT";
+
+    let data_type = DataType::Interval(IntervalUnit::MonthDayNano);
+    let expected = PrimitiveArray::<months_days_ns>::from([
+        Some(months_days_ns::new(14, 3, 14_706_500_000_000)),
+        Some(months_days_ns::new(0, -7, 0)),
+        Some(months_days_ns::new(0, 0, 1)),
+        None,
+        None,
+        None,
+    ])
+    .to(data_type.clone());
+    let result = test_deserialize(input, data_type)?;
+    assert_eq!(expected, result.as_ref());
+    Ok(())
+}
+
+#[test]
+fn interval_year_month_day_time() -> Result<()> {
+    let input = "P1Y2M\nP2DT0.5S\nPT0.0005S";
+
+    let data_type = DataType::Interval(IntervalUnit::YearMonth);
+    let expected = Int32Array::from([Some(14), None, None]).to(data_type.clone());
+    let result = test_deserialize(input, data_type)?;
+    assert_eq!(expected, result.as_ref());
+
+    let data_type = DataType::Interval(IntervalUnit::DayTime);
+    let expected = PrimitiveArray::<days_ms>::from([None, Some(days_ms::new(2, 500)), None])
+        .to(data_type.clone());
+    let result = test_deserialize(input, data_type)?;
+    assert_eq!(expected, result.as_ref());
+    Ok(())
+}
+
+#[test]
+fn dictionary() -> Result<()> {
+    let input = "a,\nb,\na,\n,";
+
+    let data_type = DataType::Dictionary(IntegerType::UInt8, Box::new(DataType::Utf8), false);
+    let result = test_deserialize(input, data_type.clone())?;
+    assert_eq!(result.data_type(), &data_type);
+
+    let result = result
+        .as_any()
+        .downcast_ref::<DictionaryArray<u8>>()
+        .unwrap();
+    assert_eq!(
+        result.keys(),
+        &PrimitiveArray::<u8>::from_slice([0, 1, 0, 2])
+    );
+    assert_eq!(
+        result.values().as_ref(),
+        &Utf8Array::<i32>::from_slice(["a", "b", ""]) as &dyn Array
+    );
+
+    let data_type = DataType::Dictionary(IntegerType::Int32, Box::new(DataType::LargeUtf8), false);
+    let result = test_deserialize(input, data_type.clone())?;
+    assert_eq!(result.data_type(), &data_type);
+    Ok(())
+}
+
+#[test]
+fn dictionary_overflow() {
+    let input = (0..200)
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(Cursor::new(input));
+    let mut rows = vec![ByteRecord::default(); 200];
+    let rows_read = read_rows(&mut reader, 0, &mut rows).unwrap();
+
+    let data_type = DataType::Dictionary(IntegerType::Int8, Box::new(DataType::Utf8), false);
+    assert!(deserialize_column(&rows[..rows_read], 0, data_type, 0).is_err());
+}
+
+#[test]
+fn fixed_size_binary() -> Result<()> {
+    let input = "ab,\ncd,\n,\nabc,";
+
+    let expected = FixedSizeBinaryArray::from([Some([b'a', b'b']), Some([b'c', b'd']), None, None]);
+    let result = test_deserialize(input, expected.data_type().clone())?;
+    assert_eq!(expected, result.as_ref());
+    Ok(())
+}
+
+#[test]
+fn null() -> Result<()> {
+    let input = ",\n,\n,";
+
+    let result = test_deserialize(input, DataType::Null)?;
+    assert_eq!(NullArray::new(DataType::Null, 3), result.as_ref());
+    Ok(())
+}
+
+#[test]
+fn lenient_new_types() -> Result<()> {
+    let input = "a,b,c,d\n12:00:00,P1D,ab,\n25:00:00,1D,abc,x";
+    let fields = vec![
+        Field::new("a", DataType::Time32(TimeUnit::Second), true),
+        Field::new("b", DataType::Interval(IntervalUnit::MonthDayNano), true),
+        Field::new("c", DataType::FixedSizeBinary(2), true),
+        Field::new("d", DataType::Null, true),
+    ];
+    let options = DeserializeOptions {
+        mode: ParseMode::Lenient,
+        ..Default::default()
+    };
+    let (chunk, errors) = read_with_options(input, &fields, &options)?;
+    assert_eq!(chunk.len(), 2);
+
+    let errors = errors
+        .into_iter()
+        .map(|error| (error.row, error.column, error.bytes))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        vec![
+            (3, "a".to_string(), b"25:00:00".to_vec()),
+            (3, "b".to_string(), b"1D".to_vec()),
+            (3, "c".to_string(), b"abc".to_vec()),
+            (3, "d".to_string(), b"x".to_vec()),
+        ]
+    );
+    Ok(())
+}
+
+type ChunkAndErrors = (Chunk<Box<dyn Array>>, Vec<ParseError>);
+
+fn read_with_options(
+    input: &str,
+    fields: &[Field],
+    options: &DeserializeOptions,
+) -> Result<ChunkAndErrors> {
+    let mut reader = ReaderBuilder::new().from_reader(Cursor::new(input));
+
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows)?;
+    // the first row after the header is on line 2
+    deserialize_batch_with_options(&rows[..rows_read], fields, None, 2, options)
+}
+
+fn parse_fields() -> (&'static str, Vec<Field>) {
+    let input = "a,b,c\n1,true,x\n12a,true,y\n3,yes,z\n,,\n5,false,w";
+    let fields = vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Boolean, true),
+        Field::new("c", DataType::Utf8, true),
+    ];
+    (input, fields)
+}
+
+#[test]
+fn permissive() -> Result<()> {
+    let (input, fields) = parse_fields();
+    let (chunk, errors) = read_with_options(input, &fields, &Default::default())?;
+
+    assert!(errors.is_empty());
+    let expected = Int64Array::from([Some(1), None, Some(3), None, Some(5)]);
+    assert_eq!(chunk.arrays()[0].as_ref(), &expected as &dyn Array);
+    let expected = BooleanArray::from([Some(true), Some(true), None, None, Some(false)]);
+    assert_eq!(chunk.arrays()[1].as_ref(), &expected as &dyn Array);
+    Ok(())
+}
+
+#[test]
+fn strict() -> Result<()> {
+    let (input, fields) = parse_fields();
+    let options = DeserializeOptions {
+        mode: ParseMode::Strict,
+        ..Default::default()
+    };
+    let error = read_with_options(input, &fields, &options).unwrap_err();
+
+    let error = match error {
+        Error::External(message, error) => {
+            assert_eq!(message, " in column \"a\" at row 3");
+            *error.downcast::<ParseError>().unwrap()
+        }
+        other => panic!("unexpected error {:?}", other),
+    };
+    assert_eq!(
+        error,
+        ParseError {
+            row: 3,
+            column: "a".to_string(),
+            bytes: b"12a".to_vec(),
+            data_type: DataType::Int64,
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "could not parse \"12a\" as Int64 in column \"a\" at row 3"
+    );
+
+    // empty cells are nulls, not errors
+    let input = "a,b\n1,x\n,y\n3,z";
+    let (chunk, errors) = read_with_options(input, &fields[..1], &options)?;
+    assert!(errors.is_empty());
+    let expected = Int64Array::from([Some(1), None, Some(3)]);
+    assert_eq!(chunk.arrays()[0].as_ref(), &expected as &dyn Array);
+    Ok(())
+}
+
+#[test]
+fn lenient() -> Result<()> {
+    let (input, fields) = parse_fields();
+    let options = DeserializeOptions {
+        mode: ParseMode::Lenient,
+        ..Default::default()
+    };
+    let (chunk, errors) = read_with_options(input, &fields, &options)?;
+
+    let (expected, _) = read_with_options(input, &fields, &Default::default())?;
+    assert_eq!(chunk, expected);
+
+    let errors = errors
+        .into_iter()
+        .map(|error| (error.row, error.column, error.bytes, error.data_type))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        vec![
+            (3, "a".to_string(), b"12a".to_vec(), DataType::Int64),
+            (4, "b".to_string(), b"yes".to_vec(), DataType::Boolean),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn lenient_utf8() -> Result<()> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(Cursor::new(b"a\n\xff\xfe\nb".as_ref()));
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows)?;
+
+    let field = Field::new("c", DataType::Utf8, true);
+    let options = DeserializeOptions {
+        mode: ParseMode::Lenient,
+        ..Default::default()
+    };
+    let (array, errors) =
+        deserialize_column_with_options(&rows[..rows_read], 0, &field, 0, &options)?;
+
+    let expected = Utf8Array::<i32>::from([Some("a"), None, Some("b")]);
+    assert_eq!(array.as_ref(), &expected as &dyn Array);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].row, 1);
+    assert_eq!(errors[0].bytes, vec![0xff, 0xfe]);
+    Ok(())
+}
+
+#[test]
+fn null_values() -> Result<()> {
+    let input = "a,b\n1,x\nNA,NA\n\\N,\n4,y";
+    let fields = vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Utf8, true),
+    ];
+    let options = DeserializeOptions {
+        mode: ParseMode::Strict,
+        null_values: vec!["NA".to_string(), "\\N".to_string()],
+        ..Default::default()
+    };
+    let (chunk, _) = read_with_options(input, &fields, &options)?;
+
+    let expected = Int64Array::from([Some(1), None, None, Some(4)]);
+    assert_eq!(chunk.arrays()[0].as_ref(), &expected as &dyn Array);
+    let expected = Utf8Array::<i32>::from([Some("x"), None, Some(""), Some("y")]);
+    assert_eq!(chunk.arrays()[1].as_ref(), &expected as &dyn Array);
+    Ok(())
+}
+
+#[test]
+fn formats() -> Result<()> {
+    let input = "a,b,c,d,e\n31.12.2020,31.12.2020 23:59,31.12.2020 23:59,23.59,31.12.2020\n";
+    let fields = vec![
+        Field::new("a", DataType::Date32, true),
+        Field::new("b", DataType::Timestamp(TimeUnit::Second, None), true),
+        Field::new(
+            "c",
+            DataType::Timestamp(TimeUnit::Second, Some("+01:00".to_string())),
+            true,
+        ),
+        Field::new("d", DataType::Time32(TimeUnit::Second), true),
+        Field::new("e", DataType::Date64, true),
+    ];
+    let formats = [
+        ("a", "%d.%m.%Y"),
+        ("b", "%d.%m.%Y %H:%M"),
+        ("c", "%d.%m.%Y %H:%M"),
+        ("d", "%H.%M"),
+        ("e", "%d.%m.%Y"),
+    ];
+    let options = DeserializeOptions {
+        mode: ParseMode::Strict,
+        formats: formats
+            .iter()
+            .map(|(column, format)| (column.to_string(), format.to_string()))
+            .collect(),
+        ..Default::default()
+    };
+    let (chunk, _) = read_with_options(input, &fields, &options)?;
+
+    let expected = Int32Array::from_slice([18627]).to(DataType::Date32);
+    assert_eq!(chunk.arrays()[0].as_ref(), &expected as &dyn Array);
+    let expected = Int64Array::from_slice([1609459140]).to(fields[1].data_type().clone());
+    assert_eq!(chunk.arrays()[1].as_ref(), &expected as &dyn Array);
+    // without an offset in the format, the datetime is local to the timezone of the column
+    let expected = Int64Array::from_slice([1609455540]).to(fields[2].data_type().clone());
+    assert_eq!(chunk.arrays()[2].as_ref(), &expected as &dyn Array);
+    let expected = Int32Array::from_slice([86340]).to(fields[3].data_type().clone());
+    assert_eq!(chunk.arrays()[3].as_ref(), &expected as &dyn Array);
+    let expected = Int64Array::from_slice([1609372800000]).to(DataType::Date64);
+    assert_eq!(chunk.arrays()[4].as_ref(), &expected as &dyn Array);
+
+    // the default formats do not apply to columns with a format
+    let input = "a,b,c,d,e\n2020-12-31,,,,\n";
+    assert!(read_with_options(input, &fields, &options).is_err());
+    Ok(())
+}
+
+#[test]
+fn timestamp_out_of_range() -> Result<()> {
+    // nanoseconds since the epoch overflow `i64` after 2262
+    let input = "a,b\n2020-12-31T23:59:00+01:00,2020-12-31 23:59\n2300-01-01T00:00:00+01:00,2300-01-01 00:00\n";
+    let fields = vec![
+        Field::new(
+            "a",
+            DataType::Timestamp(TimeUnit::Second, Some("+01:00".to_string())),
+            true,
+        ),
+        Field::new(
+            "b",
+            DataType::Timestamp(TimeUnit::Second, Some("+01:00".to_string())),
+            true,
+        ),
+    ];
+    let options = DeserializeOptions {
+        mode: ParseMode::Lenient,
+        formats: [("b".to_string(), "%Y-%m-%d %H:%M".to_string())]
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    };
+    let (chunk, errors) = read_with_options(input, &fields, &options)?;
+
+    for (array, field) in chunk.arrays().iter().zip(fields.iter()) {
+        let expected = Int64Array::from([Some(1609455540), None]).to(field.data_type().clone());
+        assert_eq!(array.as_ref(), &expected as &dyn Array);
+    }
+    let errors = errors
+        .into_iter()
+        .map(|error| (error.row, error.column))
+        .collect::<Vec<_>>();
+    assert_eq!(errors, vec![(3, "a".to_string()), (3, "b".to_string())]);
+    Ok(())
+}
+
+#[test]
+fn boolean_values() -> Result<()> {
+    let input = "a\nJa\nnein\ntrue\nJ";
+    let fields = vec![Field::new("a", DataType::Boolean, true)];
+    let options = DeserializeOptions {
+        mode: ParseMode::Lenient,
+        true_values: vec!["ja".to_string(), "j".to_string()],
+        false_values: vec!["nein".to_string()],
+        ..Default::default()
+    };
+    let (chunk, errors) = read_with_options(input, &fields, &options)?;
+
+    let expected = BooleanArray::from([Some(true), Some(false), None, Some(true)]);
+    assert_eq!(chunk.arrays()[0].as_ref(), &expected as &dyn Array);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].bytes, b"true".to_vec());
+    Ok(())
+}
+
+#[test]
+fn decimal_comma() -> Result<()> {
+    let input = "a;b\n1,5;12,34\n-0,25;1\n1.5;1.5";
+    let mut reader = ReaderBuilder::new()
+        .delimiter(b';')
+        .from_reader(Cursor::new(input));
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows)?;
+
+    let fields = vec![
+        Field::new("a", DataType::Float64, true),
+        Field::new("b", DataType::Decimal(4, 2), true),
+    ];
+    let options = DeserializeOptions {
+        mode: ParseMode::Lenient,
+        decimal_comma: true,
+        ..Default::default()
+    };
+    let (chunk, errors) =
+        deserialize_batch_with_options(&rows[..rows_read], &fields, None, 0, &options)?;
+
+    let expected = Float64Array::from([Some(1.5), Some(-0.25), None]);
+    assert_eq!(chunk.arrays()[0].as_ref(), &expected as &dyn Array);
+    let expected = Int128Array::from([Some(1234), None, None]).to(DataType::Decimal(4, 2));
+    assert_eq!(chunk.arrays()[1].as_ref(), &expected as &dyn Array);
+    assert_eq!(errors.len(), 3);
+    Ok(())
+}
+
+#[test]
+fn infer_with_options() -> Result<()> {
+    let input = "datum;wert;aktiv;name;zeit\n\
+        31.12.2020;1,5;ja;x;31.12.2020 10:00 -0100\n\
+        NA;2;nein;NA;NA\n\
+        01.01.2021;-0,25;NA;y;01.01.2021 11:30 -0100";
+    let mut reader = ReaderBuilder::new()
+        .delimiter(b';')
+        .from_reader(Cursor::new(input));
+
+    let formats = [("datum", "%d.%m.%Y"), ("zeit", "%d.%m.%Y %H:%M %z")];
+    let options = DeserializeOptions {
+        mode: ParseMode::Strict,
+        null_values: vec!["NA".to_string()],
+        formats: formats
+            .iter()
+            .map(|(column, format)| (column.to_string(), format.to_string()))
+            .collect(),
+        true_values: vec!["ja".to_string()],
+        false_values: vec!["nein".to_string()],
+        decimal_comma: true,
+    };
+    let (fields, rows_inferred) = infer_schema_with_options(&mut reader, None, true, &options)?;
+    assert_eq!(rows_inferred, 3);
+    let data_types = fields
+        .iter()
+        .map(|field| field.data_type().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        data_types,
+        vec![
+            DataType::Date32,
+            DataType::Float64,
+            DataType::Boolean,
+            DataType::Utf8,
+            DataType::Timestamp(TimeUnit::Millisecond, Some("-01:00".to_string())),
+        ]
+    );
+
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows)?;
+    let (chunk, _) =
+        deserialize_batch_with_options(&rows[..rows_read], &fields, None, 0, &options)?;
+
+    let expected = Int32Array::from([Some(18627), None, Some(18628)]).to(DataType::Date32);
+    assert_eq!(chunk.arrays()[0].as_ref(), &expected as &dyn Array);
+    let expected = Float64Array::from([Some(1.5), Some(2.0), Some(-0.25)]);
+    assert_eq!(chunk.arrays()[1].as_ref(), &expected as &dyn Array);
+    let expected = BooleanArray::from([Some(true), Some(false), None]);
+    assert_eq!(chunk.arrays()[2].as_ref(), &expected as &dyn Array);
+    let expected = Utf8Array::<i32>::from([Some("x"), None, Some("y")]);
+    assert_eq!(chunk.arrays()[3].as_ref(), &expected as &dyn Array);
+    let expected = Int64Array::from([Some(1609412400000), None, Some(1609504200000)])
+        .to(data_types[4].clone());
+    assert_eq!(chunk.arrays()[4].as_ref(), &expected as &dyn Array);
+    Ok(())
+}
+
 proptest! {
     #[test]
     #[cfg_attr(miri, ignore)] // miri and proptest do not work well :(
@@ -394,3 +947,248 @@ proptest! {
         assert_eq!(infer(v.as_bytes()), DataType::Timestamp(TimeUnit::Millisecond, Some("-02:00".to_string())));
     }
 }
+
+fn split_data() -> String {
+    let mut data = "a,\"b\nb\",c\n".to_string();
+    for i in 0..100 {
+        match i % 4 {
+            0 => data.push_str(&format!("{},\"multi\nline, {}\",x\n", i, i)),
+            1 => data.push_str(&format!("{},\"quoted \"\"{}\"\"\",y\r\n", i, i)),
+            // followed by an empty line
+            2 => data.push_str(&format!("{},plain {},\n\n", i, i)),
+            _ => data.push_str(&format!("{},\"\",z\n", i)),
+        }
+    }
+    data
+}
+
+fn split_fields() -> Vec<Field> {
+    vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Utf8, true),
+        Field::new("c", DataType::Utf8, true),
+    ]
+}
+
+fn read_splits(
+    data: &str,
+    n: usize,
+    split_options: &SplitOptions,
+    options: &DeserializeOptions,
+) -> Result<Vec<Chunk<Box<dyn Array>>>> {
+    let splits = split_file(&mut Cursor::new(data), n, split_options)?;
+    assert!(splits.len() <= n);
+    for window in splits.windows(2) {
+        assert_eq!(window[0].range.end, window[1].range.start);
+    }
+
+    let mut chunks = vec![];
+    for split in &splits {
+        let reader = SplitReader::try_new(
+            Cursor::new(data),
+            split,
+            split_options,
+            split_fields(),
+            None,
+            7,
+            options.clone(),
+        )?;
+        for chunk in reader {
+            chunks.push(chunk?);
+        }
+    }
+    Ok(chunks)
+}
+
+#[test]
+fn split_quoted_line_breaks() -> Result<()> {
+    let data = split_data();
+
+    let mut reader = ReaderBuilder::new().from_reader(Cursor::new(data.as_str()));
+    let mut rows = vec![ByteRecord::default(); 200];
+    let rows_read = read_rows(&mut reader, 0, &mut rows)?;
+    assert_eq!(rows_read, 100);
+    let expected = deserialize_batch(
+        &rows[..rows_read],
+        &split_fields(),
+        None,
+        0,
+        deserialize_column,
+    )?;
+
+    for n in [1, 2, 3, 7, 16, 1000] {
+        let chunks = read_splits(&data, n, &Default::default(), &Default::default())?;
+        for (column, expected) in expected.arrays().iter().enumerate() {
+            let arrays = chunks
+                .iter()
+                .map(|chunk| chunk.arrays()[column].as_ref())
+                .collect::<Vec<_>>();
+            let result = arrow2::compute::concatenate::concatenate(&arrays)?;
+            assert_eq!(&result, expected, "column {} with {} splits", column, n);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn split_boundaries() -> Result<()> {
+    let data = split_data();
+    let options = SplitOptions::default();
+    let splits = split_file(&mut Cursor::new(data.as_str()), 5, &options)?;
+    assert_eq!(splits.len(), 5);
+    // the first split starts after the header
+    assert_eq!(splits[0].range.start, "a,\"b\nb\",c\n".len() as u64);
+    assert_eq!(splits[4].range.end, data.len() as u64);
+
+    let mut rows_read = 0;
+    for split in splits {
+        let mut reader = split_reader(Cursor::new(data.as_str()), &split, &options)?;
+        let mut rows = vec![ByteRecord::default(); 200];
+        rows_read += read_rows(&mut reader, 0, &mut rows)?;
+    }
+    assert_eq!(rows_read, 100);
+    Ok(())
+}
+
+#[test]
+fn split_speculation() -> Result<()> {
+    // a quoted field longer than the speculation window, without quotes
+    let field = format!("{}x", "x\n".repeat(2 * 1024 * 1024));
+    let data = format!("a,b,c\n0,\"{}\",x\n1,y,z\n", field);
+    let options = SplitOptions::default();
+    let splits = split_file(&mut Cursor::new(data.as_str()), 2, &options)?;
+    assert_eq!(splits.len(), 2);
+
+    // the speculated boundary is within the quoted field: reading the first split errors
+    let mut reader = split_reader(Cursor::new(data.as_str()), &splits[0], &options)?;
+    let mut rows = vec![ByteRecord::default(); 10];
+    assert!(read_rows(&mut reader, 0, &mut rows).is_err());
+
+    // a closing quote within the window resynchronizes the parser
+    let field = format!("{}x", "x\n".repeat(1024));
+    let data = format!("a,b,c\n0,\"{}\",x\n1,y,z\n", field);
+    let chunks = read_splits(&data, 2, &options, &Default::default())?;
+    assert_eq!(chunks.iter().map(Chunk::len).sum::<usize>(), 2);
+    Ok(())
+}
+
+#[test]
+fn split_without_header() -> Result<()> {
+    let data = "1,a,x\n2,b,y\n3,c,z";
+    let options = SplitOptions {
+        has_header: false,
+        ..Default::default()
+    };
+    let chunks = read_splits(data, 2, &options, &Default::default())?;
+    let rows = chunks.iter().map(|chunk| chunk.len()).sum::<usize>();
+    assert_eq!(rows, 3);
+
+    assert!(split_file(&mut Cursor::new(data), 0, &options).is_err());
+    let options = SplitOptions::default();
+    assert!(split_file(&mut Cursor::new("a,b,c"), 2, &options)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn split_errors() -> Result<()> {
+    let data = split_data().replace("57,", "5x,");
+    let options = DeserializeOptions {
+        mode: ParseMode::Strict,
+        ..Default::default()
+    };
+    let split_options = SplitOptions::default();
+    let splits = split_file(&mut Cursor::new(data.as_str()), 4, &split_options)?;
+
+    // the rows of the errors are relative to their split
+    let mut first_row = 0;
+    let mut error = None;
+    for split in &splits {
+        let reader = SplitReader::try_new(
+            Cursor::new(data.as_str()),
+            split,
+            &split_options,
+            split_fields(),
+            None,
+            7,
+            options.clone(),
+        )?;
+        for chunk in reader {
+            match chunk {
+                Ok(chunk) => first_row += chunk.len(),
+                Err(Error::External(_, e)) => {
+                    error = Some(*e.downcast::<ParseError>().unwrap());
+                    break;
+                }
+                Err(other) => panic!("unexpected error {:?}", other),
+            }
+        }
+        if error.is_some() {
+            break;
+        }
+    }
+    let error = error.unwrap();
+    assert_eq!(first_row + error.row, 57);
+    assert_eq!(error.bytes, b"5x".to_vec());
+    Ok(())
+}
+
+#[test]
+fn split_dialects() -> Result<()> {
+    let escaped = SplitOptions {
+        has_header: false,
+        delimiter: b';',
+        quote: b'\'',
+        double_quote: false,
+        escape: Some(b'\\'),
+        comment: Some(b'#'),
+        ..Default::default()
+    };
+    let mut escaped_data = String::new();
+    for i in 0..40 {
+        match i % 4 {
+            // an escaped quote and a record terminated by `\r`
+            0 => escaped_data.push_str(&format!("{};'multi \\'\nline {}';x\r", i, i)),
+            // a comment with an unmatched quote
+            1 => escaped_data.push_str(&format!("# a;'comment\n{};plain;y\n", i)),
+            2 => escaped_data.push_str(&format!("{};'a;b\r\n';z\r\n", i)),
+            // a quote within an unquoted field
+            _ => escaped_data.push_str(&format!("{};ab'c;\n\n", i)),
+        }
+    }
+
+    let terminated = SplitOptions {
+        has_header: false,
+        terminator: Some(b'|'),
+        ..Default::default()
+    };
+    let terminated_data = (0..40)
+        .map(|i| format!("{},\"a|\nb\",x|", i))
+        .collect::<String>();
+
+    for (options, data) in [(escaped, escaped_data), (terminated, terminated_data)] {
+        let mut reader = options
+            .reader_builder()
+            .from_reader(Cursor::new(data.as_str()));
+        let mut rows = vec![ByteRecord::default(); 100];
+        let rows_read = read_rows(&mut reader, 0, &mut rows)?;
+        assert_eq!(rows_read, 40);
+        let expected = deserialize_batch(
+            &rows[..rows_read],
+            &split_fields(),
+            None,
+            0,
+            deserialize_column,
+        )?;
+
+        for n in [1, 2, 3, 7, 100] {
+            let chunks = read_splits(&data, n, &options, &Default::default())?;
+            let arrays = chunks
+                .iter()
+                .map(|chunk| chunk.arrays()[1].as_ref())
+                .collect::<Vec<_>>();
+            let result = arrow2::compute::concatenate::concatenate(&arrays)?;
+            assert_eq!(&result, &expected.arrays()[1], "{} splits", n);
+        }
+    }
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use futures::io::Cursor;
 
 use arrow2::array::*;
-use arrow2::error::Result;
+use arrow2::datatypes::{DataType, Field, IntegerType, TimeUnit};
+use arrow2::error::{Error, Result};
 use arrow2::io::csv::read_async::*;
 
 #[tokio::test]
@@ -48,3 +49,100 @@ async fn read() -> Result<()> {
     assert_eq!("Aberdeen, Aberdeen City, UK", city.value(13));
     Ok(())
 }
+
+#[tokio::test]
+async fn parse_modes() -> Result<()> {
+    let data = "a,b\n1,x\n2.5,y";
+    let mut reader = AsyncReaderBuilder::new().create_reader(Cursor::new(data.as_bytes()));
+
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows).await?;
+
+    let fields = vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ];
+    let options = DeserializeOptions {
+        mode: ParseMode::Lenient,
+        ..Default::default()
+    };
+    let (_, errors) =
+        deserialize_batch_with_options(&rows[..rows_read], &fields, None, 0, &options)?;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].row, 1);
+    assert_eq!(errors[0].column, "a");
+
+    let options = DeserializeOptions {
+        mode: ParseMode::Strict,
+        ..Default::default()
+    };
+    let error = deserialize_batch_with_options(&rows[..rows_read], &fields, None, 0, &options);
+    assert!(matches!(error, Err(Error::External(_, _))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn time_and_dictionary() -> Result<()> {
+    let data = "a,b\n12:34:56,x\n01:02:03.5,y\n00:00:00,x";
+    let mut reader = AsyncReaderBuilder::new().create_reader(Cursor::new(data.as_bytes()));
+
+    let (fields, _) = infer_schema(&mut reader, None, true, &infer).await?;
+    assert_eq!(
+        fields[0].data_type(),
+        &DataType::Time32(TimeUnit::Millisecond)
+    );
+
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows).await?;
+
+    let fields = vec![
+        fields[0].clone(),
+        Field::new(
+            "b",
+            DataType::Dictionary(IntegerType::UInt32, Box::new(DataType::Utf8), false),
+            true,
+        ),
+    ];
+    let chunk = deserialize_batch(&rows[..rows_read], &fields, None, 0, deserialize_column)?;
+
+    let expected = Int32Array::from([Some(45_296_000), Some(3_723_500), Some(0)])
+        .to(DataType::Time32(TimeUnit::Millisecond));
+    assert_eq!(chunk.arrays()[0].as_ref(), &expected as &dyn Array);
+
+    let b = chunk.arrays()[1]
+        .as_any()
+        .downcast_ref::<DictionaryArray<u32>>()
+        .unwrap();
+    assert_eq!(b.keys(), &PrimitiveArray::<u32>::from_slice([0, 1, 0]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn infer_with_options() -> Result<()> {
+    let data = "a;b\n1,5;NULL\nNULL;wahr";
+    let mut reader = AsyncReaderBuilder::new()
+        .delimiter(b';')
+        .create_reader(Cursor::new(data.as_bytes()));
+
+    let options = DeserializeOptions {
+        mode: ParseMode::Strict,
+        null_values: vec!["NULL".to_string()],
+        true_values: vec!["wahr".to_string()],
+        decimal_comma: true,
+        ..Default::default()
+    };
+    let (fields, _) = infer_schema_with_options(&mut reader, None, true, &options).await?;
+    assert_eq!(fields[0].data_type(), &DataType::Float64);
+    assert_eq!(fields[1].data_type(), &DataType::Boolean);
+
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows).await?;
+    let (chunk, _) =
+        deserialize_batch_with_options(&rows[..rows_read], &fields, None, 0, &options)?;
+
+    let expected = Float64Array::from([Some(1.5), None]);
+    assert_eq!(chunk.arrays()[0].as_ref(), &expected as &dyn Array);
+    let expected = BooleanArray::from([None, Some(true)]);
+    assert_eq!(chunk.arrays()[1].as_ref(), &expected as &dyn Array);
+    Ok(())
+}
@@ -265,6 +265,32 @@ fn data_array(column: &str) -> (Chunk<Box<dyn Array>>, Vec<&'static str>) {
             let array = DictionaryArray::try_from_keys(keys, values).unwrap();
             (array.boxed(), vec!["d", "c", "a b"])
         }
+        "dictionary[i8]" => {
+            let keys = Int8Array::from([Some(2), None, Some(0)]);
+            let values = Utf8Array::<i32>::from_slice(["a b", "c", "d"]).boxed();
+            let array = DictionaryArray::try_from_keys(keys, values).unwrap();
+            (array.boxed(), vec!["d", "", "a b"])
+        }
+        "dictionary[i32,i64]" => {
+            let keys = Int32Array::from_slice([1, 1, 0]);
+            let values = Int64Array::from([Some(3), None]).boxed();
+            let array = DictionaryArray::try_from_keys(keys, values).unwrap();
+            (array.boxed(), vec!["", "", "3"])
+        }
+        "decimal" => {
+            let array = Int128Array::from([Some(12345), Some(-5), Some(0), None])
+                .to(DataType::Decimal(5, 2));
+            (array.boxed(), vec!["123.45", "-0.05", "0.00", ""])
+        }
+        "decimal[scale=0]" => {
+            let array = Int128Array::from_slice([-12345]).to(DataType::Decimal(5, 0));
+            (array.boxed(), vec!["-12345"])
+        }
+        "fixed_size_binary" => {
+            let array = FixedSizeBinaryArray::from([Some([b'a', b'b']), Some([b'c', b'd'])]);
+            (array.boxed(), vec!["ab", "cd"])
+        }
+        "null" => (NullArray::new(DataType::Null, 2).boxed(), vec!["", ""]),
         _ => todo!(),
     };
 
@@ -326,6 +352,12 @@ fn write_each() -> Result<()> {
         "ts[ns,offset]",
         "dictionary[u32]",
         "dictionary[u64]",
+        "dictionary[i8]",
+        "dictionary[i32,i64]",
+        "decimal",
+        "decimal[scale=0]",
+        "fixed_size_binary",
+        "null",
     ] {
         write_single(i)?;
     }
@@ -443,3 +475,133 @@ fn serialize_vec() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn write_binary_formats() -> Result<()> {
+    let array = BinaryArray::<i32>::from([Some(&[0x0a, 0xff][..]), Some(b"ab"), Some(b"")]);
+    let chunk = Chunk::new(vec![array.boxed()]);
+
+    let options = SerializeOptions {
+        binary_format: BinaryFormat::Hex,
+        ..Default::default()
+    };
+    test_array(chunk.clone(), vec!["0aff", "6162", "\"\""], options)?;
+
+    let options = SerializeOptions {
+        binary_format: BinaryFormat::Base64,
+        ..Default::default()
+    };
+    test_array(chunk, vec!["Cv8=", "YWI=", "\"\""], options)?;
+
+    let array = FixedSizeBinaryArray::from([Some([0x0a, 0xff, 0x00])]);
+    let options = SerializeOptions {
+        binary_format: BinaryFormat::Base64,
+        ..Default::default()
+    };
+    test_array(Chunk::new(vec![array.boxed()]), vec!["Cv8A"], options)
+}
+
+#[test]
+#[cfg(feature = "io_json")]
+fn write_nested_as_json() -> Result<()> {
+    let data = vec![
+        Some(vec![Some(1), Some(2)]),
+        None,
+        Some(vec![Some(3), None]),
+    ];
+    let mut list = MutableListArray::<i32, MutablePrimitiveArray<i32>>::new();
+    list.try_extend(data)?;
+    let list: ListArray<i32> = list.into();
+
+    let fields = vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ];
+    let values = vec![
+        Int32Array::from([Some(1), None, Some(3)]).boxed(),
+        Utf8Array::<i32>::from_slice(["x", "y", "z"]).boxed(),
+    ];
+    let validity = Some([true, true, false].into());
+    let structs = StructArray::new(DataType::Struct(fields), values, validity);
+
+    let chunk = Chunk::new(vec![list.boxed(), structs.boxed()]);
+    let options = SerializeOptions {
+        null: "NA".to_string(),
+        ..Default::default()
+    };
+    let mut writer = vec![];
+    write_chunk(&mut writer, &chunk, &options)?;
+
+    assert_eq!(
+        std::str::from_utf8(&writer).unwrap(),
+        "\"[1,2]\",\"{\"\"a\"\":1,\"\"b\"\":\"\"x\"\"}\"\n\
+         NA,\"{\"\"a\"\":null,\"\"b\"\":\"\"y\"\"}\"\n\
+         \"[3,null]\",NA\n"
+    );
+    Ok(())
+}
+
+fn quoting_data() -> Chunk<Box<dyn Array>> {
+    Chunk::new(vec![
+        Utf8Array::<i32>::from([Some("a"), Some("b,c"), None, Some("")]).boxed(),
+        Int32Array::from([Some(1), Some(2), None, Some(4)]).boxed(),
+        BooleanArray::from([Some(true), None, Some(false), Some(true)]).boxed(),
+    ])
+}
+
+fn write_with(options: &SerializeOptions) -> Result<String> {
+    let mut writer = vec![];
+    write_header(&mut writer, &["a", "b", "c d"], options)?;
+    write_chunk(&mut writer, &quoting_data(), options)?;
+    Ok(String::from_utf8(writer).unwrap())
+}
+
+#[test]
+fn write_null_and_line_terminator() -> Result<()> {
+    let options = SerializeOptions {
+        null: "NULL".to_string(),
+        line_terminator: "\r\n".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        write_with(&options)?,
+        "a,b,c d\r\na,1,true\r\n\"b,c\",2,NULL\r\nNULL,NULL,false\r\n\"\",4,true\r\n"
+    );
+
+    let rows = serialize(&quoting_data(), &options)?;
+    assert_eq!(rows[2], b"NULL,NULL,false\r\n".to_vec());
+    Ok(())
+}
+
+#[test]
+fn write_quote_styles() -> Result<()> {
+    let options = SerializeOptions {
+        quote_style: QuoteStyle::Always,
+        ..Default::default()
+    };
+    assert_eq!(
+        write_with(&options)?,
+        "\"a\",\"b\",\"c d\"\n\"a\",\"1\",\"true\"\n\"b,c\",\"2\",\n,,\"false\"\n\"\",\"4\",\"true\"\n"
+    );
+
+    let options = SerializeOptions {
+        quote_style: QuoteStyle::NonNumeric,
+        ..Default::default()
+    };
+    assert_eq!(
+        write_with(&options)?,
+        "\"a\",\"b\",\"c d\"\n\"a\",1,\"true\"\n\"b,c\",2,\n,,\"false\"\n\"\",4,\"true\"\n"
+    );
+
+    let mut writer = vec![];
+    write_header(&mut writer, &["a", "b,c"], &SerializeOptions::default())?;
+    assert_eq!(writer, b"a,\"b,c\"\n".to_vec());
+    Ok(())
+}
+
+#[test]
+fn write_unsupported() {
+    let array = Int32Array::from_slice([1]).to(DataType::Interval(IntervalUnit::YearMonth));
+    let options = SerializeOptions::default();
+    assert!(new_serializer(&array, &options).is_err());
+}
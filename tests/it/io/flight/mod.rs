@@ -1,7 +1,9 @@
-use arrow2::array::Array;
+use arrow2::array::*;
 use arrow2::chunk::Chunk;
-use arrow2::datatypes::Schema;
+use arrow2::datatypes::{DataType, Field, Schema};
 use arrow2::error::Error;
+use arrow_format::flight::data::FlightData;
+use futures::{StreamExt, TryStreamExt};
 
 use arrow2::io::flight::*;
 use arrow2::io::ipc::write::{default_ipc_fields, WriteOptions};
@@ -14,7 +16,14 @@ fn round_trip(schema: Schema, chunk: Chunk<Box<dyn Array>>) -> Result<(), Error>
     let (result, ipc_schema) = deserialize_schemas(&serialized.data_header)?;
     assert_eq!(schema, result);
 
-    let (_, batch) = serialize_batch(&chunk, &fields, &WriteOptions { compression: None })?;
+    let (_, batch) = serialize_batch(
+        &chunk,
+        &fields,
+        &WriteOptions {
+            compression: None,
+            ..Default::default()
+        },
+    )?;
 
     let result = deserialize_batch(&batch, &result.fields, &ipc_schema, &Default::default())?;
     assert_eq!(result, chunk);
@@ -30,3 +39,159 @@ fn generated_nested_dictionary() -> Result<(), Error> {
 
     Ok(())
 }
+
+fn nested_chunks() -> (Schema, Vec<Chunk<Box<dyn Array>>>) {
+    let dictionary = |keys: &[u32], values: &[&str]| -> Box<dyn Array> {
+        let keys = UInt32Array::from_slice(keys);
+        let values = Utf8Array::<i32>::from_slice(values).boxed();
+        DictionaryArray::try_from_keys(keys, values)
+            .unwrap()
+            .boxed()
+    };
+    let list = |values: Box<dyn Array>| -> Box<dyn Array> {
+        let data_type = ListArray::<i32>::default_datatype(values.data_type().clone());
+        let offsets = vec![0, 1, 1, values.len() as i32].into();
+        ListArray::<i32>::new(data_type, offsets, values, None).boxed()
+    };
+    let struct_ = |values: Box<dyn Array>| -> Box<dyn Array> {
+        let fields = vec![Field::new("v", values.data_type().clone(), true)];
+        StructArray::new(DataType::Struct(fields), vec![values], None).boxed()
+    };
+
+    let chunks = vec![
+        Chunk::new(vec![
+            dictionary(&[0, 1, 0], &["a", "b"]),
+            list(dictionary(&[1, 0, 0], &["c", "d"])),
+            struct_(Int32Array::from([Some(1), None, Some(3)]).boxed()),
+        ]),
+        // the first dictionary is extended and the second replaced
+        Chunk::new(vec![
+            dictionary(&[2, 1, 2], &["a", "b", "e"]),
+            list(dictionary(&[0, 0, 1], &["f", "g"])),
+            struct_(Int32Array::from([None, Some(5), Some(6)]).boxed()),
+        ]),
+    ];
+    let schema = Schema::from(
+        chunks[0]
+            .arrays()
+            .iter()
+            .enumerate()
+            .map(|(i, array)| Field::new(i.to_string(), array.data_type().clone(), true))
+            .collect::<Vec<_>>(),
+    );
+    (schema, chunks)
+}
+
+async fn deserialize(messages: Vec<FlightData>) -> Result<(Schema, Vec<FlightChunk>), Error> {
+    let stream = futures::stream::iter(messages.into_iter().map(Ok));
+    let (schema, _, chunks) = deserialize_stream(stream).await?;
+    Ok((schema, chunks.try_collect().await?))
+}
+
+#[tokio::test]
+async fn deserialize_stream_from_batches() -> Result<(), Error> {
+    let (schema, chunks) = nested_chunks();
+    let fields = default_ipc_fields(&schema.fields);
+    let options = WriteOptions {
+        compression: None,
+        ..Default::default()
+    };
+
+    let mut messages = vec![serialize_schema(&schema, Some(&fields))];
+    for (i, chunk) in chunks.iter().enumerate() {
+        let (dictionaries, batch) = serialize_batch(chunk, &fields, &options)?;
+        messages.extend(dictionaries);
+        messages.push(FlightData {
+            app_metadata: vec![i as u8],
+            ..batch
+        });
+    }
+
+    let (result, new_chunks) = deserialize(messages).await?;
+    assert_eq!(result, schema);
+    let expected = chunks
+        .into_iter()
+        .enumerate()
+        .map(|(i, chunk)| FlightChunk {
+            chunk,
+            app_metadata: vec![i as u8],
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(new_chunks, expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn stream_round_trip() -> Result<(), Error> {
+    let (schema, chunks) = nested_chunks();
+    let chunks = chunks
+        .into_iter()
+        .map(|chunk| FlightChunk {
+            chunk,
+            app_metadata: b"metadata".to_vec(),
+        })
+        .collect::<Vec<_>>();
+
+    let stream = futures::stream::iter(chunks.clone().into_iter().map(Ok));
+    let messages = serialize_stream(
+        stream,
+        &schema,
+        None,
+        WriteOptions {
+            compression: None,
+            ..Default::default()
+        },
+    )
+    .try_collect::<Vec<_>>()
+    .await?;
+    // schema, 2 dictionaries and a batch, then a delta, a replacement and a batch
+    assert_eq!(messages.len(), 7);
+
+    let (result, new_chunks) = deserialize(messages).await?;
+    assert_eq!(result, schema);
+    assert_eq!(new_chunks, chunks);
+    Ok(())
+}
+
+#[tokio::test]
+async fn serialize_stream_error() -> Result<(), Error> {
+    let (schema, mut chunks) = nested_chunks();
+    let chunks = vec![
+        Ok(chunks.remove(0).into()),
+        Err(Error::ExternalFormat("transport".to_string())),
+    ];
+
+    let messages = serialize_stream(
+        futures::stream::iter(chunks),
+        &schema,
+        None,
+        WriteOptions {
+            compression: None,
+            ..Default::default()
+        },
+    )
+    .collect::<Vec<_>>()
+    .await;
+    assert_eq!(messages.len(), 5);
+    assert!(messages[..4].iter().all(|message| message.is_ok()));
+    assert!(messages[4].is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn deserialize_stream_without_schema() -> Result<(), Error> {
+    let (schema, chunks) = nested_chunks();
+    let fields = default_ipc_fields(&schema.fields);
+    let (_, batch) = serialize_batch(
+        &chunks[0],
+        &fields,
+        &WriteOptions {
+            compression: None,
+            ..Default::default()
+        },
+    )?;
+
+    assert!(deserialize(vec![batch]).await.is_err());
+    assert!(deserialize(vec![]).await.is_err());
+    Ok(())
+}
@@ -2,9 +2,15 @@ use ahash::AHashMap;
 use std::{fs::File, io::Read};
 
 use arrow2::{
-    array::Array, chunk::Chunk, datatypes::Schema, error::Result,
-    io::ipc::read::read_stream_metadata, io::ipc::read::StreamReader, io::ipc::IpcField,
-    io::json_integration::read, io::json_integration::ArrowJson,
+    array::{Array, DictionaryArray, Int32Array, Utf8Array},
+    chunk::Chunk,
+    datatypes::Schema,
+    error::Result,
+    io::ipc::read::read_stream_metadata,
+    io::ipc::read::StreamReader,
+    io::ipc::IpcField,
+    io::json_integration::read,
+    io::json_integration::ArrowJson,
 };
 
 use flate2::read::GzDecoder;
@@ -74,3 +80,27 @@ pub fn read_arrow_stream(
             .unwrap(),
     )
 }
+
+fn pyarrow_dictionary_chunk(keys: &[Option<i32>], values: &[&str]) -> Chunk<Box<dyn Array>> {
+    let keys = Int32Array::from(keys);
+    let values = Utf8Array::<i32>::from_slice(values).boxed();
+    let array = DictionaryArray::try_from_keys(keys, values).unwrap();
+    Chunk::new(vec![array.boxed()])
+}
+
+/// Returns the path and the batches of the dictionary fixture `file_name`
+/// written by `tests/it/io/ipc/write_dictionaries.py`
+pub fn pyarrow_dictionary_fixture(file_name: &str) -> (String, Vec<Chunk<Box<dyn Array>>>) {
+    let batches = match file_name {
+        "delta.stream" | "delta.arrow_file" => vec![
+            pyarrow_dictionary_chunk(&[Some(0), Some(1), None], &["a", "b"]),
+            pyarrow_dictionary_chunk(&[Some(2), None, Some(0)], &["a", "b", "c"]),
+        ],
+        "replacement.stream" => vec![
+            pyarrow_dictionary_chunk(&[Some(0), Some(1), None], &["a", "b"]),
+            pyarrow_dictionary_chunk(&[Some(1), Some(0), None], &["c", "d"]),
+        ],
+        _ => unreachable!(),
+    };
+    (format!("fixtures/pyarrow3/ipc/{}", file_name), batches)
+}
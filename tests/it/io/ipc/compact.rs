@@ -0,0 +1,247 @@
+use std::io::Cursor;
+
+use arrow2::array::*;
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{Field, Schema};
+use arrow2::error::Result;
+use arrow2::io::ipc::compact::{compact_files, CompactOptions};
+use arrow2::io::ipc::read::{read_file_metadata, FileReader};
+use arrow2::io::ipc::write::Compression;
+
+use super::write::file::write;
+use super::write::stream::dictionary_chunk;
+
+fn int_chunk(values: &[i32]) -> Chunk<Box<dyn Array>> {
+    Chunk::new(vec![Int32Array::from_slice(values).boxed()])
+}
+
+fn schema_of(chunk: &Chunk<Box<dyn Array>>) -> Schema {
+    Schema::from(vec![Field::new(
+        "a",
+        chunk.arrays()[0].data_type().clone(),
+        true,
+    )])
+}
+
+fn concatenate(chunks: &[Chunk<Box<dyn Array>>]) -> Chunk<Box<dyn Array>> {
+    let arrays = chunks
+        .iter()
+        .map(|chunk| chunk.arrays()[0].as_ref())
+        .collect::<Vec<_>>();
+    Chunk::new(vec![
+        arrow2::compute::concatenate::concatenate(&arrays).unwrap()
+    ])
+}
+
+fn compact(
+    files: &[Vec<Chunk<Box<dyn Array>>>],
+    options: CompactOptions,
+) -> Result<Vec<Chunk<Box<dyn Array>>>> {
+    let schema = schema_of(&files[0][0]);
+    let readers = files
+        .iter()
+        .map(|chunks| write(chunks, &schema, None, None).map(Cursor::new))
+        .collect::<Result<Vec<_>>>()?;
+
+    let data = compact_files(readers, vec![], options)?;
+
+    let mut reader = Cursor::new(data);
+    let metadata = read_file_metadata(&mut reader)?;
+    FileReader::new(reader, metadata, None, None).collect()
+}
+
+#[test]
+fn concatenate_files() -> Result<()> {
+    let files = vec![
+        vec![int_chunk(&[1, 2]), int_chunk(&[3])],
+        vec![int_chunk(&[4, 5, 6])],
+        vec![],
+        vec![int_chunk(&[7])],
+    ];
+    let chunks = compact(&files, Default::default())?;
+
+    assert_eq!(chunks, files.concat());
+    Ok(())
+}
+
+#[test]
+fn coalesce() -> Result<()> {
+    let files = vec![
+        vec![int_chunk(&[1, 2]), int_chunk(&[3])],
+        vec![int_chunk(&[4, 5, 6, 7, 8, 9]), int_chunk(&[10])],
+        vec![int_chunk(&[11, 12]), int_chunk(&[13, 14])],
+    ];
+    let options = CompactOptions {
+        max_rows: Some(4),
+        ..Default::default()
+    };
+    let chunks = compact(&files, options)?;
+
+    assert_eq!(
+        chunks,
+        vec![
+            int_chunk(&[1, 2, 3]),
+            int_chunk(&[4, 5, 6, 7, 8, 9]),
+            int_chunk(&[10, 11, 12]),
+            int_chunk(&[13, 14]),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn unify_dictionaries() -> Result<()> {
+    let files = vec![
+        vec![dictionary_chunk(&[0, 1], &["a", "b"])],
+        // a prefix of the dictionary of the first file
+        vec![dictionary_chunk(&[0, 0], &["a"])],
+        // an extension of the dictionary of the first file
+        vec![dictionary_chunk(&[2, 1], &["a", "b", "d"])],
+        // a different dictionary
+        vec![dictionary_chunk(&[1, 0], &["c", "a"])],
+    ];
+
+    let expected = files.concat();
+    let cases = [
+        (None, expected.clone()),
+        (
+            Some(4),
+            vec![concatenate(&expected[..2]), concatenate(&expected[2..])],
+        ),
+        (Some(100), vec![concatenate(&expected)]),
+    ];
+    for (max_rows, expected) in cases {
+        let options = CompactOptions {
+            max_rows,
+            ..Default::default()
+        };
+        let chunks = compact(&files, options)?;
+        assert_eq!(chunks, expected);
+
+        // the dictionary of the first file, extended by the third file and by the new values
+        // of the last file
+        let array = chunks.last().unwrap().arrays()[0]
+            .as_any()
+            .downcast_ref::<DictionaryArray<u32>>()
+            .unwrap();
+        let values = Utf8Array::<i32>::from_slice(["a", "b", "d", "c"]);
+        assert_eq!(array.values().as_ref(), &values as &dyn Array);
+    }
+    Ok(())
+}
+
+#[test]
+fn unify_dictionaries_remaps_keys() -> Result<()> {
+    let files = vec![
+        vec![dictionary_chunk(&[0, 1], &["a", "b"])],
+        vec![dictionary_chunk(&[0, 1, 1], &["b", "a"])],
+        vec![dictionary_chunk(&[2, 0, 1], &["c", "b", "a"])],
+    ];
+
+    let chunks = compact(&files, Default::default())?;
+    assert_eq!(chunks, files.concat());
+
+    let keys_and_values = chunks
+        .iter()
+        .map(|chunk| {
+            let array = chunk.arrays()[0]
+                .as_any()
+                .downcast_ref::<DictionaryArray<u32>>()
+                .unwrap();
+            (array.keys().clone(), array.values().len())
+        })
+        .collect::<Vec<_>>();
+    // the written dictionary is ["a", "b", "c"]: values already in it are not appended again
+    assert_eq!(
+        keys_and_values,
+        vec![
+            (UInt32Array::from_slice([0, 1]), 3),
+            (UInt32Array::from_slice([1, 0, 0]), 3),
+            (UInt32Array::from_slice([0, 2, 1]), 3),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn unify_nested_dictionaries() -> Result<()> {
+    let list = |keys: &[u32], values: &[&str]| {
+        let values = dictionary_chunk(keys, values).into_arrays().pop().unwrap();
+        let data_type = ListArray::<i32>::default_datatype(values.data_type().clone());
+        let array = ListArray::<i32>::new(
+            data_type,
+            vec![0, 1, keys.len() as i32].into(),
+            values,
+            None,
+        );
+        Chunk::new(vec![array.boxed()])
+    };
+    let files = vec![
+        vec![list(&[0, 1], &["a", "b"])],
+        vec![list(&[0, 1], &["c", "a"]), list(&[1, 1], &["c", "a"])],
+    ];
+    let options = CompactOptions {
+        max_rows: Some(10),
+        ..Default::default()
+    };
+    let chunks = compact(&files, options)?;
+
+    assert_eq!(chunks, vec![concatenate(&files.concat())]);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // compression uses FFI, which miri does not support
+fn recompress() -> Result<()> {
+    let files = vec![
+        vec![dictionary_chunk(&[0, 1], &["a", "b"])],
+        vec![dictionary_chunk(&[1], &["c", "d"])],
+    ];
+    for compression in [Compression::LZ4, Compression::ZSTD] {
+        let mut options = CompactOptions::default();
+        options.write_options.compression = Some(compression);
+        let chunks = compact(&files, options)?;
+        assert_eq!(chunks, files.concat());
+    }
+    Ok(())
+}
+
+#[test]
+fn incompatible_schemas() -> Result<()> {
+    let chunk = int_chunk(&[1]);
+    let other = Chunk::new(vec![Int64Array::from_slice([1]).boxed()]);
+    let readers = vec![
+        Cursor::new(write(
+            std::slice::from_ref(&chunk),
+            &schema_of(&chunk),
+            None,
+            None,
+        )?),
+        Cursor::new(write(
+            std::slice::from_ref(&other),
+            &schema_of(&other),
+            None,
+            None,
+        )?),
+    ];
+    assert!(compact_files(readers, vec![], Default::default()).is_err());
+
+    let readers: Vec<Cursor<Vec<u8>>> = vec![];
+    assert!(compact_files(readers, vec![], Default::default()).is_err());
+    Ok(())
+}
+
+#[test]
+fn key_overflow() -> Result<()> {
+    let chunk = |start: usize| {
+        let values = (start..start + 100)
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>();
+        let values = Utf8Array::<i32>::from_slice(values).boxed();
+        let array = DictionaryArray::try_from_keys(Int8Array::from_slice([99]), values).unwrap();
+        Chunk::new(vec![array.boxed()])
+    };
+    // the unified dictionary has 200 values, which `i8` keys can't index
+    assert!(compact(&[vec![chunk(0)], vec![chunk(100)]], Default::default()).is_err());
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use std::io::Cursor;
+
+use arrow2::error::Result;
+use arrow2::io::ipc::convert::convert_file;
+use arrow2::io::ipc::read::{read_file_metadata, FileReader};
+use arrow2::io::ipc::write::{Compression, Endianness, WriteOptions};
+
+use super::write::file::{endianness_chunk, write_with_options};
+
+fn options(endianness: Endianness) -> WriteOptions {
+    WriteOptions {
+        compression: None,
+        endianness,
+    }
+}
+
+#[test]
+fn little_to_big() -> Result<()> {
+    let (schema, chunk) = endianness_chunk();
+    let batches = vec![chunk.clone(), chunk];
+    let little = write_with_options(&batches, &schema, None, options(Endianness::Little))?;
+    let big = write_with_options(&batches, &schema, None, options(Endianness::Big))?;
+
+    let converted = convert_file(Cursor::new(little), vec![], options(Endianness::Big))?;
+    assert_eq!(converted, big);
+    Ok(())
+}
+
+#[test]
+fn big_to_little() -> Result<()> {
+    let (schema, chunk) = endianness_chunk();
+    let batches = vec![chunk.clone(), chunk];
+    let little = write_with_options(&batches, &schema, None, options(Endianness::Little))?;
+    let big = write_with_options(&batches, &schema, None, options(Endianness::Big))?;
+
+    let converted = convert_file(Cursor::new(big), vec![], options(Endianness::Little))?;
+    assert_eq!(converted, little);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // compression uses FFI, which miri does not support
+fn compressed() -> Result<()> {
+    let (schema, chunk) = endianness_chunk();
+    let batches = vec![chunk];
+    let big = write_with_options(&batches, &schema, None, options(Endianness::Big))?;
+
+    let options = WriteOptions {
+        compression: Some(Compression::LZ4),
+        endianness: Endianness::Little,
+    };
+    let converted = convert_file(Cursor::new(big), vec![], options)?;
+
+    let mut reader = Cursor::new(converted);
+    let metadata = read_file_metadata(&mut reader)?;
+    assert!(metadata.ipc_schema.is_little_endian);
+    assert_eq!(metadata.schema, schema);
+    let reader = FileReader::new(reader, metadata, None, None);
+    assert_eq!(reader.collect::<Result<Vec<_>>>()?, batches);
+    Ok(())
+}
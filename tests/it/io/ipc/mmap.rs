@@ -3,15 +3,24 @@ use arrow2::chunk::Chunk;
 use arrow2::datatypes::{DataType, Field, Schema};
 use arrow2::error::Result;
 use arrow2::io::ipc::read::read_file_metadata;
+use arrow2::io::ipc::write::{Compression, Endianness, WriteOptions};
 use std::sync::Arc;
 
-use super::write::file::write;
+use super::common::pyarrow_dictionary_fixture;
+use super::write::file::{write, write_with_options};
+use super::write::stream::dictionary_chunk;
 
 fn round_trip(array: Box<dyn Array>) -> Result<()> {
+    _round_trip(array.clone(), None)?;
+    _round_trip(array.clone(), Some(Compression::LZ4))?;
+    _round_trip(array, Some(Compression::ZSTD))
+}
+
+fn _round_trip(array: Box<dyn Array>, compression: Option<Compression>) -> Result<()> {
     let schema = Schema::from(vec![Field::new("a", array.data_type().clone(), true)]);
     let columns = Chunk::try_new(vec![array.clone()])?;
 
-    let data = Arc::new(write(&[columns], &schema, None, None)?);
+    let data = Arc::new(write(&[columns], &schema, None, compression)?);
 
     let metadata = read_file_metadata(&mut std::io::Cursor::new(data.as_ref()))?;
 
@@ -117,3 +126,136 @@ fn dict() -> Result<()> {
 
     round_trip(array)
 }
+
+#[test]
+fn delta_dictionary() -> Result<()> {
+    let batches = vec![
+        dictionary_chunk(&[0, 1], &["a", "b"]),
+        dictionary_chunk(&[2, 0], &["a", "b", "c"]),
+    ];
+    let schema = Schema::from(vec![Field::new(
+        "a",
+        batches[0].arrays()[0].data_type().clone(),
+        false,
+    )]);
+
+    let data = Arc::new(write(&batches, &schema, None, None)?);
+    let metadata = read_file_metadata(&mut std::io::Cursor::new(data.as_ref()))?;
+
+    let dictionaries =
+        unsafe { arrow2::mmap::mmap_dictionaries_unchecked(&metadata, data.clone())? };
+
+    for (i, batch) in batches.into_iter().enumerate() {
+        let new_batch =
+            unsafe { arrow2::mmap::mmap_unchecked(&metadata, &dictionaries, data.clone(), i)? };
+        assert_eq!(new_batch, batch);
+    }
+    Ok(())
+}
+
+#[test]
+fn projection() -> Result<()> {
+    let a = PrimitiveArray::<i32>::from([Some(1), None, Some(3)]).boxed();
+    let b = dictionary_chunk(&[0, 1, 0], &["a", "b"])
+        .into_arrays()
+        .pop()
+        .unwrap();
+    let c = Utf8Array::<i32>::from([Some("x"), Some("yy"), None]).boxed();
+    let d = dictionary_chunk(&[1, 1, 0], &["c", "d"])
+        .into_arrays()
+        .pop()
+        .unwrap();
+    let schema = Schema::from(vec![
+        Field::new("a", a.data_type().clone(), true),
+        Field::new("b", b.data_type().clone(), true),
+        Field::new("c", c.data_type().clone(), true),
+        Field::new("d", d.data_type().clone(), true),
+    ]);
+    let chunk = Chunk::try_new(vec![a.clone(), b, c.clone(), d.clone()])?;
+
+    for compression in [None, Some(Compression::LZ4), Some(Compression::ZSTD)] {
+        let data = Arc::new(write(
+            std::slice::from_ref(&chunk),
+            &schema,
+            None,
+            compression,
+        )?);
+        let metadata = read_file_metadata(&mut std::io::Cursor::new(data.as_ref()))?;
+
+        let projection = [3, 0, 2];
+        // only the dictionary of the column "d" is read
+        let dictionaries = unsafe {
+            arrow2::mmap::mmap_projected_dictionaries_unchecked(
+                &metadata,
+                data.clone(),
+                &projection,
+            )?
+        };
+        assert_eq!(dictionaries.len(), 1);
+
+        let new_chunk = unsafe {
+            arrow2::mmap::mmap_projected_unchecked(&metadata, &dictionaries, data, 0, &projection)?
+        };
+        assert_eq!(
+            new_chunk,
+            Chunk::try_new(vec![d.clone(), a.clone(), c.clone()])?
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn projection_out_of_bounds() -> Result<()> {
+    let array = PrimitiveArray::<i32>::from_slice([1, 2]).boxed();
+    let schema = Schema::from(vec![Field::new("a", array.data_type().clone(), true)]);
+    let data = Arc::new(write(&[Chunk::try_new(vec![array])?], &schema, None, None)?);
+    let metadata = read_file_metadata(&mut std::io::Cursor::new(data.as_ref()))?;
+
+    let dictionaries = Default::default();
+    let result =
+        unsafe { arrow2::mmap::mmap_projected_unchecked(&metadata, &dictionaries, data, 0, &[1]) };
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[test]
+fn non_native_endianness() -> Result<()> {
+    let array = Int32Array::from_slice([1, 2]).boxed();
+    let schema = Schema::from(vec![Field::new("a", array.data_type().clone(), true)]);
+    let columns = Chunk::try_new(vec![array])?;
+
+    let endianness = match Endianness::native() {
+        Endianness::Little => Endianness::Big,
+        Endianness::Big => Endianness::Little,
+    };
+    let options = WriteOptions {
+        compression: None,
+        endianness,
+    };
+    let data = Arc::new(write_with_options(&[columns], &schema, None, options)?);
+    let metadata = read_file_metadata(&mut std::io::Cursor::new(data.as_ref()))?;
+
+    let dictionaries =
+        unsafe { arrow2::mmap::mmap_dictionaries_unchecked(&metadata, data.clone()) };
+    assert!(dictionaries.is_err());
+    let chunk = unsafe { arrow2::mmap::mmap_unchecked(&metadata, &Default::default(), data, 0) };
+    assert!(chunk.is_err());
+    Ok(())
+}
+
+#[test]
+fn pyarrow_delta_dictionary() -> Result<()> {
+    let (path, batches) = pyarrow_dictionary_fixture("delta.arrow_file");
+    let data = Arc::new(std::fs::read(path)?);
+    let metadata = read_file_metadata(&mut std::io::Cursor::new(data.as_ref()))?;
+
+    let dictionaries =
+        unsafe { arrow2::mmap::mmap_dictionaries_unchecked(&metadata, data.clone())? };
+
+    for (i, batch) in batches.into_iter().enumerate() {
+        let new_batch =
+            unsafe { arrow2::mmap::mmap_unchecked(&metadata, &dictionaries, data.clone(), i)? };
+        assert_eq!(new_batch, batch);
+    }
+    Ok(())
+}
@@ -1,4 +1,6 @@
 mod common;
+mod compact;
+mod convert;
 mod read;
 mod write;
 
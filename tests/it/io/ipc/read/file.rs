@@ -1,10 +1,14 @@
 use std::fs::File;
+use std::io::Cursor;
 
+use arrow2::array::*;
 use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
 use arrow2::error::Result;
 use arrow2::io::ipc::read::*;
 
-use super::super::common::read_gzip_json;
+use super::super::common::{pyarrow_dictionary_fixture, read_gzip_json};
+use super::super::write::file::write;
 
 fn test_file(version: &str, file_name: &str) -> Result<()> {
     let testdata = crate::test_util::arrow_test_data();
@@ -274,3 +278,118 @@ fn read_limited() -> Result<()> {
     test_limit("1.0.0-littleendian", "generated_nested", 2)?;
     Ok(())
 }
+
+fn row_range_chunk() -> Chunk<Box<dyn Array>> {
+    let a = Int32Array::from_iter((0..12).map(|x| if x % 5 == 0 { None } else { Some(x) }));
+    let b = Utf8Array::<i32>::from_iter((0..12).map(|x| Some(x.to_string())));
+    Chunk::new(vec![a.boxed(), b.boxed()])
+}
+
+fn row_range_file() -> Result<Vec<u8>> {
+    let chunk = row_range_chunk();
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ]);
+
+    // blocks with 3, 0, 4 and 5 rows
+    let slice = |offset: usize, length: usize| {
+        Chunk::new(
+            chunk
+                .arrays()
+                .iter()
+                .map(|array| array.slice(offset, length))
+                .collect(),
+        )
+    };
+    let batches = [slice(0, 3), slice(3, 0), slice(3, 4), slice(7, 5)];
+    write(&batches, &schema, None, None)
+}
+
+#[test]
+fn read_rows() -> Result<()> {
+    let chunk = row_range_chunk();
+    let mut reader = Cursor::new(row_range_file()?);
+    let metadata = read_file_metadata(&mut reader)?;
+    let dictionaries = read_file_dictionaries(&mut reader, &metadata, &mut Default::default())?;
+
+    let index = metadata.row_index(&mut reader)?;
+    assert_eq!(index.row_counts(), &[3, 0, 4, 5]);
+    assert_eq!(index.num_rows(), 12);
+
+    for (start, end) in [(0, 12), (1, 2), (3, 7), (2, 9), (6, 12), (5, 5), (12, 12)] {
+        let rows = metadata.read_rows(
+            &mut reader,
+            &dictionaries,
+            &index,
+            None,
+            start..end,
+            &mut Default::default(),
+            &mut Default::default(),
+        )?;
+        let expected = chunk
+            .arrays()
+            .iter()
+            .map(|array| array.slice(start, end - start))
+            .collect::<Vec<_>>();
+        assert_eq!(rows, Chunk::new(expected));
+    }
+
+    let rows = metadata.read_rows(
+        &mut reader,
+        &dictionaries,
+        &index,
+        Some(&[1]),
+        2..9,
+        &mut Default::default(),
+        &mut Default::default(),
+    )?;
+    assert_eq!(rows, Chunk::new(vec![chunk.arrays()[1].slice(2, 7)]));
+    Ok(())
+}
+
+#[test]
+fn read_rows_out_of_bounds() -> Result<()> {
+    let mut reader = Cursor::new(row_range_file()?);
+    let metadata = read_file_metadata(&mut reader)?;
+    let index = metadata.row_index(&mut reader)?;
+
+    let result = metadata.read_rows(
+        &mut reader,
+        &Default::default(),
+        &index,
+        None,
+        10..13,
+        &mut Default::default(),
+        &mut Default::default(),
+    );
+    assert!(result.is_err());
+
+    // the index of another file
+    let other = Cursor::new(write(&[], &metadata.schema, None, None)?);
+    let other_index = read_file_metadata(&mut other.clone())?.row_index(&mut other.clone())?;
+    let result = metadata.read_rows(
+        &mut reader,
+        &Default::default(),
+        &other_index,
+        None,
+        0..0,
+        &mut Default::default(),
+        &mut Default::default(),
+    );
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[test]
+fn read_pyarrow_delta_dictionary() -> Result<()> {
+    let (path, batches) = pyarrow_dictionary_fixture("delta.arrow_file");
+    let mut file = File::open(path)?;
+
+    let metadata = read_file_metadata(&mut file)?;
+    let reader = FileReader::new(file, metadata, None, None);
+
+    let items = reader.collect::<Result<Vec<_>>>()?;
+    assert_eq!(items, batches);
+    Ok(())
+}
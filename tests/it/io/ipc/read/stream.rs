@@ -4,7 +4,7 @@ use std::fs::File;
 use arrow2::error::Result;
 use arrow2::io::ipc::read::*;
 
-use crate::io::ipc::common::read_gzip_json;
+use crate::io::ipc::common::{pyarrow_dictionary_fixture, read_gzip_json};
 
 fn test_file(version: &str, file_name: &str) -> Result<()> {
     let testdata = crate::test_util::arrow_test_data();
@@ -152,3 +152,27 @@ fn read_projected() -> Result<()> {
     test_projection("1.0.0-littleendian", "generated_primitive", vec![2, 1])?;
     test_projection("1.0.0-littleendian", "generated_primitive", vec![0, 2, 1])
 }
+
+fn test_pyarrow_dictionary(file_name: &str) -> Result<()> {
+    let (path, batches) = pyarrow_dictionary_fixture(file_name);
+    let mut file = File::open(path)?;
+
+    let metadata = read_stream_metadata(&mut file)?;
+    let reader = StreamReader::new(file, metadata, None);
+
+    let items = reader
+        .map(|x| x.map(|x| x.unwrap()))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(items, batches);
+    Ok(())
+}
+
+#[test]
+fn read_pyarrow_delta_dictionary() -> Result<()> {
+    test_pyarrow_dictionary("delta.stream")
+}
+
+#[test]
+fn read_pyarrow_replacement_dictionary() -> Result<()> {
+    test_pyarrow_dictionary("replacement.stream")
+}
@@ -1,11 +1,21 @@
-use futures::StreamExt;
+use std::ops::Range;
+use std::sync::Mutex;
+
+use futures::{future::BoxFuture, FutureExt, StreamExt, TryStreamExt};
 use tokio::fs::File;
 use tokio_util::compat::*;
 
+use arrow2::array::*;
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
 use arrow2::error::Result;
 use arrow2::io::ipc::read::file_async::*;
+use arrow2::io::ipc::read::read_file_metadata;
+
+use crate::io::ipc::common::{pyarrow_dictionary_fixture, read_gzip_json};
 
-use crate::io::ipc::common::read_gzip_json;
+use super::write::file::write;
+use super::write::stream::dictionary_chunk;
 
 async fn test_file(version: &str, file_name: &str) -> Result<()> {
     let testdata = crate::test_util::arrow_test_data();
@@ -43,3 +53,186 @@ async fn test_file(version: &str, file_name: &str) -> Result<()> {
 async fn write_async() -> Result<()> {
     test_file("1.0.0-littleendian", "generated_primitive").await
 }
+
+/// An in-memory [`RangeRead`] that records the ranges read from it
+struct MemoryRangeReader {
+    data: Vec<u8>,
+    reads: Mutex<Vec<Range<u64>>>,
+}
+
+impl MemoryRangeReader {
+    fn new(data: Vec<u8>) -> Self {
+        Self {
+            data,
+            reads: Default::default(),
+        }
+    }
+
+    fn take_reads(&self) -> Vec<Range<u64>> {
+        std::mem::take(&mut self.reads.lock().unwrap())
+    }
+}
+
+impl RangeRead for MemoryRangeReader {
+    fn size(&self) -> BoxFuture<'_, Result<u64>> {
+        async move { Ok(self.data.len() as u64) }.boxed()
+    }
+
+    fn read_range(&self, range: Range<u64>) -> BoxFuture<'_, Result<Vec<u8>>> {
+        self.reads.lock().unwrap().push(range.clone());
+        let bytes = self.data[range.start as usize..range.end as usize].to_vec();
+        async move { Ok(bytes) }.boxed()
+    }
+}
+
+fn ranged_data() -> (Vec<Chunk<Box<dyn Array>>>, Vec<u8>) {
+    let chunk = |keys: &[u32], values: &[i32]| {
+        let mut arrays = dictionary_chunk(keys, &["a", "b", "c"]).into_arrays();
+        arrays.push(Int32Array::from_slice(values).boxed());
+        Chunk::new(arrays)
+    };
+    let batches = vec![
+        chunk(&[0, 1], &[1, 2]),
+        chunk(&[2], &[3]),
+        chunk(&[1, 1, 0], &[4, 5, 6]),
+    ];
+    let schema = Schema::from(vec![
+        Field::new("a", batches[0].arrays()[0].data_type().clone(), true),
+        Field::new("b", DataType::Int32, true),
+    ]);
+    let data = write(&batches, &schema, None, None).unwrap();
+    (batches, data)
+}
+
+#[tokio::test]
+async fn read_ranged() -> Result<()> {
+    let (batches, data) = ranged_data();
+    let expected_metadata = read_file_metadata(&mut std::io::Cursor::new(&data))?;
+    let reader = MemoryRangeReader::new(data);
+
+    let metadata = read_file_metadata_ranged(&reader).await?;
+    assert_eq!(metadata.schema, expected_metadata.schema);
+    assert_eq!(metadata.blocks, expected_metadata.blocks);
+    assert_eq!(metadata.size, expected_metadata.size);
+    // the footer of a small file is read with a single range read
+    assert_eq!(reader.take_reads().len(), 1);
+
+    let stream = FileStream::new_ranged(&reader, metadata, None, None);
+    let chunks = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(chunks, batches);
+    // one range read for the dictionary batch and one per record batch
+    assert_eq!(reader.take_reads().len(), 1 + batches.len());
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_ranged_projected() -> Result<()> {
+    let (batches, data) = ranged_data();
+    let reader = MemoryRangeReader::new(data);
+    let metadata = read_file_metadata_ranged(&reader).await?;
+
+    let stream = FileStream::new_ranged(&reader, metadata, Some(vec![1]), Some(4));
+    assert_eq!(stream.schema().fields.len(), 1);
+    let chunks = stream.try_collect::<Vec<_>>().await?;
+    let expected = batches
+        .iter()
+        .map(|chunk| chunk.arrays()[1].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        chunks,
+        vec![
+            Chunk::new(vec![expected[0].clone()]),
+            Chunk::new(vec![expected[1].clone()]),
+            Chunk::new(vec![expected[2].slice(0, 1)]),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_ranged_limit() -> Result<()> {
+    let (batches, data) = ranged_data();
+    let reader = MemoryRangeReader::new(data);
+    let metadata = read_file_metadata_ranged(&reader).await?;
+    reader.take_reads();
+
+    let stream = FileStream::new_ranged(&reader, metadata, None, Some(3));
+    let chunks = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(chunks, batches[..2].to_vec());
+    // batches past the limit are not read
+    assert_eq!(reader.take_reads().len(), 1 + 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_ranged_batch() -> Result<()> {
+    let (batches, data) = ranged_data();
+    let reader = MemoryRangeReader::new(data);
+    let metadata = read_file_metadata_ranged(&reader).await?;
+    let mut scratch = vec![];
+    let dictionaries = read_file_dictionaries_ranged(&reader, &metadata, &mut scratch).await?;
+    reader.take_reads();
+
+    let chunk = read_batch_ranged(
+        &reader,
+        &dictionaries,
+        &metadata,
+        None,
+        None,
+        2,
+        &mut scratch,
+    )
+    .await?;
+    assert_eq!(chunk, batches[2]);
+
+    // only the block of the record batch is read
+    let block = metadata.blocks[2];
+    let start = block.offset as u64;
+    let end = start + block.meta_data_length as u64 + block.body_length as u64;
+    assert_eq!(reader.take_reads(), vec![start..end]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_ranged_large_footer() -> Result<()> {
+    let array = Int32Array::from_slice([1, 2]).boxed();
+    let metadata = [("key".to_string(), "a".repeat(100_000))]
+        .into_iter()
+        .collect();
+    let schema = Schema::from(vec![Field::new("a", DataType::Int32, true)]).with_metadata(metadata);
+    let batches = vec![Chunk::new(vec![array])];
+    let data = write(&batches, &schema, None, None)?;
+    let reader = MemoryRangeReader::new(data);
+
+    let metadata = read_file_metadata_ranged(&reader).await?;
+    assert_eq!(metadata.schema, schema);
+    // the footer is larger than the first range read
+    assert_eq!(reader.take_reads().len(), 2);
+
+    let stream = FileStream::new_ranged(&reader, metadata, None, None);
+    assert_eq!(stream.try_collect::<Vec<_>>().await?, batches);
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_ranged_invalid() -> Result<()> {
+    let reader = MemoryRangeReader::new(b"ARROW1 not an arrow file".to_vec());
+    assert!(read_file_metadata_ranged(&reader).await.is_err());
+
+    let reader = MemoryRangeReader::new(vec![]);
+    assert!(read_file_metadata_ranged(&reader).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_pyarrow_delta_dictionary() -> Result<()> {
+    let (path, batches) = pyarrow_dictionary_fixture("delta.arrow_file");
+    let mut file = File::open(path).await?.compat();
+
+    let metadata = read_file_metadata_async(&mut file).await?;
+    let reader = FileStream::new(file, metadata, None, None);
+
+    let items = reader.try_collect::<Vec<_>>().await?;
+    assert_eq!(items, batches);
+    Ok(())
+}
@@ -2,10 +2,13 @@ use futures::StreamExt;
 use tokio::fs::File;
 use tokio_util::compat::*;
 
+use arrow2::datatypes::{Field, Schema};
 use arrow2::error::Result;
 use arrow2::io::ipc::read::stream_async::*;
+use arrow2::io::ipc::write::{StreamWriter, WriteOptions};
 
-use crate::io::ipc::common::read_gzip_json;
+use crate::io::ipc::common::{pyarrow_dictionary_fixture, read_gzip_json};
+use crate::io::ipc::write::stream::dictionary_chunk;
 
 async fn test_file(version: &str, file_name: &str) -> Result<()> {
     let testdata = crate::test_util::arrow_test_data();
@@ -43,3 +46,67 @@ async fn test_file(version: &str, file_name: &str) -> Result<()> {
 async fn write_async() -> Result<()> {
     test_file("1.0.0-littleendian", "generated_primitive").await
 }
+
+#[tokio::test]
+async fn read_delta_dictionaries() -> Result<()> {
+    let batches = vec![
+        dictionary_chunk(&[0, 1], &["a", "b"]),
+        dictionary_chunk(&[2, 0], &["a", "b", "c"]),
+        dictionary_chunk(&[3], &["a", "b", "c", "d"]),
+    ];
+    let schema = Schema::from(vec![Field::new(
+        "a",
+        batches[0].arrays()[0].data_type().clone(),
+        false,
+    )]);
+
+    let mut data = vec![];
+    let mut writer = StreamWriter::new(
+        &mut data,
+        WriteOptions {
+            compression: None,
+            ..Default::default()
+        },
+    );
+    writer.start(&schema, None)?;
+    for batch in &batches {
+        writer.write(batch, None)?;
+    }
+    writer.finish()?;
+
+    let mut reader = futures::io::Cursor::new(data);
+    let metadata = read_stream_metadata_async(&mut reader).await?;
+    let mut reader = AsyncStreamReader::new(reader, metadata);
+
+    let mut items = vec![];
+    while let Some(item) = reader.next().await {
+        items.push(item?)
+    }
+    assert_eq!(items, batches);
+    Ok(())
+}
+
+async fn test_pyarrow_dictionary(file_name: &str) -> Result<()> {
+    let (path, batches) = pyarrow_dictionary_fixture(file_name);
+    let mut file = File::open(path).await?.compat();
+
+    let metadata = read_stream_metadata_async(&mut file).await?;
+    let mut reader = AsyncStreamReader::new(file, metadata);
+
+    let mut items = vec![];
+    while let Some(item) = reader.next().await {
+        items.push(item?)
+    }
+    assert_eq!(items, batches);
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_pyarrow_delta_dictionary() -> Result<()> {
+    test_pyarrow_dictionary("delta.stream").await
+}
+
+#[tokio::test]
+async fn read_pyarrow_replacement_dictionary() -> Result<()> {
+    test_pyarrow_dictionary("replacement.stream").await
+}
@@ -2,7 +2,7 @@ use std::io::Cursor;
 
 use arrow2::array::*;
 use arrow2::chunk::Chunk;
-use arrow2::datatypes::{Field, Schema};
+use arrow2::datatypes::{DataType, Field, Schema, UnionMode};
 use arrow2::error::Result;
 use arrow2::io::ipc::read::{read_file_metadata, FileReader};
 use arrow2::io::ipc::{write::*, IpcField};
@@ -10,14 +10,28 @@ use arrow2::types::months_days_ns;
 
 use crate::io::ipc::common::read_gzip_json;
 
+use super::stream::dictionary_chunk;
+
 pub(crate) fn write(
     batches: &[Chunk<Box<dyn Array>>],
     schema: &Schema,
     ipc_fields: Option<Vec<IpcField>>,
     compression: Option<Compression>,
+) -> Result<Vec<u8>> {
+    let options = WriteOptions {
+        compression,
+        ..Default::default()
+    };
+    write_with_options(batches, schema, ipc_fields, options)
+}
+
+pub(crate) fn write_with_options(
+    batches: &[Chunk<Box<dyn Array>>],
+    schema: &Schema,
+    ipc_fields: Option<Vec<IpcField>>,
+    options: WriteOptions,
 ) -> Result<Vec<u8>> {
     let result = vec![];
-    let options = WriteOptions { compression };
     let mut writer = FileWriter::try_new(result, schema, ipc_fields.clone(), options)?;
     for batch in batches {
         writer.write(batch, ipc_fields.as_ref().map(|x| x.as_ref()))?;
@@ -373,3 +387,144 @@ fn write_months_days_ns() -> Result<()> {
     let columns = Chunk::try_new(vec![array])?;
     round_trip(columns, schema, None, None)
 }
+
+#[test]
+fn write_delta_dictionaries() -> Result<()> {
+    let batches = vec![
+        dictionary_chunk(&[0, 1], &["a", "b"]),
+        dictionary_chunk(&[2, 0], &["a", "b", "c"]),
+    ];
+    let schema = Schema::from(vec![Field::new(
+        "a",
+        batches[0].arrays()[0].data_type().clone(),
+        false,
+    )]);
+
+    let result = write(&batches, &schema, None, None)?;
+    let mut reader = Cursor::new(result);
+    let metadata = read_file_metadata(&mut reader)?;
+
+    let reader = FileReader::new(reader, metadata, None, None);
+    assert_eq!(reader.collect::<Result<Vec<_>>>()?, batches);
+
+    // files do not support replacing a dictionary
+    let batches = vec![
+        dictionary_chunk(&[0, 1], &["a", "b"]),
+        dictionary_chunk(&[0, 1], &["b", "a"]),
+    ];
+    assert!(write(&batches, &schema, None, None).is_err());
+    Ok(())
+}
+
+/// A chunk whose buffers have values of all widths, including offsets and dictionaries
+pub(crate) fn endianness_chunk() -> (Schema, Chunk<Box<dyn Array>>) {
+    let list = {
+        let data = vec![Some(vec![Some(1i64), None]), None, Some(vec![Some(-3)])];
+        let mut array = MutableListArray::<i32, MutablePrimitiveArray<i64>>::new();
+        array.try_extend(data).unwrap();
+        let array: ListArray<i32> = array.into();
+        array
+    };
+    let union = {
+        let fields = vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ];
+        let data_type = DataType::Union(fields, None, UnionMode::Dense);
+        let fields = vec![
+            Int32Array::from([Some(258), None]).boxed(),
+            Utf8Array::<i32>::from_slice(["c"]).boxed(),
+        ];
+        UnionArray::new(
+            data_type,
+            vec![0, 1, 0].into(),
+            fields,
+            Some(vec![0, 0, 1].into()),
+        )
+    };
+    let arrays = vec![
+        BooleanArray::from([Some(true), None, Some(false)]).boxed(),
+        Int16Array::from([Some(-2), None, Some(513)]).boxed(),
+        UInt32Array::from_slice([1, 1 << 16, u32::MAX]).boxed(),
+        Float64Array::from([Some(1.5), Some(-0.1), None]).boxed(),
+        Int128Array::from_slice([1, -1, 1 << 100])
+            .to(DataType::Decimal(38, 2))
+            .boxed(),
+        MonthsDaysNsArray::from_slice([
+            months_days_ns::new(1, -2, 3),
+            months_days_ns::new(0, 0, i64::MAX),
+            months_days_ns::new(-1, 1, 0),
+        ])
+        .boxed(),
+        Utf8Array::<i32>::from([Some("aa"), None, Some("ccc")]).boxed(),
+        BinaryArray::<i64>::from_slice([b"a".as_ref(), b"", b"bc"]).boxed(),
+        list.boxed(),
+        dictionary_chunk(&[2, 0, 1], &["a", "b", "c"])
+            .into_arrays()
+            .pop()
+            .unwrap(),
+        union.boxed(),
+    ];
+    let fields = arrays
+        .iter()
+        .enumerate()
+        .map(|(i, array)| Field::new(format!("c{}", i), array.data_type().clone(), true))
+        .collect::<Vec<_>>();
+    (Schema::from(fields), Chunk::new(arrays))
+}
+
+fn endianness_round_trip(compression: Option<Compression>) -> Result<()> {
+    let (schema, chunk) = endianness_chunk();
+    let batches = vec![chunk.clone(), chunk];
+
+    for endianness in [Endianness::Little, Endianness::Big] {
+        let options = WriteOptions {
+            compression,
+            endianness,
+        };
+        let data = write_with_options(&batches, &schema, None, options)?;
+
+        let mut reader = Cursor::new(data);
+        let metadata = read_file_metadata(&mut reader)?;
+        assert_eq!(
+            metadata.ipc_schema.is_little_endian,
+            endianness == Endianness::Little
+        );
+        assert_eq!(metadata.schema, schema);
+
+        let reader = FileReader::new(reader, metadata, None, None);
+        assert_eq!(reader.collect::<Result<Vec<_>>>()?, batches);
+    }
+    Ok(())
+}
+
+#[test]
+fn write_endianness() -> Result<()> {
+    endianness_round_trip(None)
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // compression uses FFI, which miri does not support
+fn write_compressed_endianness() -> Result<()> {
+    endianness_round_trip(Some(Compression::LZ4))?;
+    endianness_round_trip(Some(Compression::ZSTD))
+}
+
+#[test]
+fn write_big_endian_bytes() -> Result<()> {
+    let array = Int32Array::from_slice([0x01020304]).boxed();
+    let schema = Schema::from(vec![Field::new("a", array.data_type().clone(), false)]);
+    let batches = vec![Chunk::new(vec![array])];
+
+    let options = |endianness| WriteOptions {
+        compression: None,
+        endianness,
+    };
+    let little = write_with_options(&batches, &schema, None, options(Endianness::Little))?;
+    let big = write_with_options(&batches, &schema, None, options(Endianness::Big))?;
+
+    let find = |data: &[u8], value: &[u8]| data.windows(4).any(|window| window == value);
+    assert!(find(&little, &[4, 3, 2, 1]) && !find(&little, &[1, 2, 3, 4]));
+    assert!(find(&big, &[1, 2, 3, 4]) && !find(&big, &[4, 3, 2, 1]));
+    Ok(())
+}
@@ -3,9 +3,9 @@ use arrow2::chunk::Chunk;
 use arrow2::datatypes::*;
 use arrow2::error::Result;
 use arrow2::io::ipc::read;
-use arrow2::io::ipc::write::{FileWriter, WriteOptions};
+use arrow2::io::ipc::write::{Endianness, FileWriter, WriteOptions};
 
-use super::file::write;
+use super::file::{write, write_with_options};
 
 #[test]
 fn basic() -> Result<()> {
@@ -22,7 +22,14 @@ fn basic() -> Result<()> {
     // read the file to append
     let mut file = std::io::Cursor::new(result);
     let metadata = read::read_file_metadata(&mut file)?;
-    let mut writer = FileWriter::try_from_file(file, metadata, WriteOptions { compression: None })?;
+    let mut writer = FileWriter::try_from_file(
+        file,
+        metadata,
+        WriteOptions {
+            compression: None,
+            ..Default::default()
+        },
+    )?;
 
     // write a new column
     writer.write(&columns, None)?;
@@ -42,3 +49,32 @@ fn basic() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn big_endian() -> Result<()> {
+    let array = Int32Array::from([Some(1), None, Some(258)]).boxed();
+    let schema = Schema::from(vec![Field::new("a", array.data_type().clone(), true)]);
+    let columns = Chunk::try_new(vec![array])?;
+
+    let options = WriteOptions {
+        compression: None,
+        endianness: Endianness::Big,
+    };
+    let result = write_with_options(std::slice::from_ref(&columns), &schema, None, options)?;
+
+    // the appended messages follow the byte order of the file, not the options
+    let mut file = std::io::Cursor::new(result);
+    let metadata = read::read_file_metadata(&mut file)?;
+    let mut writer = FileWriter::try_from_file(file, metadata, Default::default())?;
+    writer.write(&columns, None)?;
+    writer.finish()?;
+
+    let mut reader = std::io::Cursor::new(writer.into_inner().into_inner());
+    let metadata = read::read_file_metadata(&mut reader)?;
+    assert!(!metadata.ipc_schema.is_little_endian);
+    let reader = read::FileReader::new(reader, metadata, None, None);
+
+    let chunks = reader.collect::<Result<Vec<_>>>()?;
+    assert_eq!(chunks, vec![columns.clone(), columns]);
+    Ok(())
+}
@@ -1,3 +1,3 @@
 pub mod file;
 mod file_append;
-mod stream;
+pub mod stream;
@@ -1,8 +1,10 @@
 use std::io::Cursor;
 
-use arrow2::array::Array;
+use arrow_format::ipc::planus::ReadAsRoot;
+
+use arrow2::array::{Array, DictionaryArray, UInt32Array, Utf8Array};
 use arrow2::chunk::Chunk;
-use arrow2::datatypes::Schema;
+use arrow2::datatypes::{Field, Schema};
 use arrow2::error::Result;
 use arrow2::io::ipc::read::read_stream_metadata;
 use arrow2::io::ipc::read::StreamReader;
@@ -19,7 +21,10 @@ fn write_(
 ) -> Vec<u8> {
     let mut result = vec![];
 
-    let options = WriteOptions { compression: None };
+    let options = WriteOptions {
+        compression: None,
+        ..Default::default()
+    };
     let mut writer = StreamWriter::new(&mut result, options);
     writer.start(schema, ipc_fields).unwrap();
     for batch in batches {
@@ -146,3 +151,99 @@ fn write_100_custom_metadata() {
 fn write_100_decimal() {
     test_file("1.0.0-littleendian", "generated_decimal");
 }
+
+pub(crate) fn dictionary_chunk(keys: &[u32], values: &[&str]) -> Chunk<Box<dyn Array>> {
+    let keys = UInt32Array::from_slice(keys);
+    let values = Utf8Array::<i32>::from_slice(values).boxed();
+    let array = DictionaryArray::try_from_keys(keys, values).unwrap();
+    Chunk::new(vec![array.boxed()])
+}
+
+/// Returns whether each dictionary batch of the stream `data` is a delta
+fn dictionary_batches_is_delta(data: &[u8]) -> Vec<bool> {
+    let mut reader = Cursor::new(data);
+    read_stream_metadata(&mut reader).unwrap();
+    let mut data = &data[reader.position() as usize..];
+
+    let mut is_delta = vec![];
+    loop {
+        let mut length = i32::from_le_bytes(data[..4].try_into().unwrap());
+        data = &data[4..];
+        if length == -1 {
+            length = i32::from_le_bytes(data[..4].try_into().unwrap());
+            data = &data[4..];
+        }
+        if length == 0 {
+            return is_delta;
+        }
+        let message =
+            arrow_format::ipc::MessageRef::read_as_root(&data[..length as usize]).unwrap();
+        if let arrow_format::ipc::MessageHeaderRef::DictionaryBatch(batch) =
+            message.header().unwrap().unwrap()
+        {
+            is_delta.push(batch.is_delta().unwrap());
+        }
+        data = &data[length as usize + message.body_length().unwrap() as usize..];
+    }
+}
+
+#[test]
+fn write_delta_dictionaries() -> Result<()> {
+    let batches = vec![
+        dictionary_chunk(&[0, 1], &["a", "b"]),
+        dictionary_chunk(&[2, 0], &["a", "b", "c"]),
+        dictionary_chunk(&[1], &["a", "b", "c"]),
+        dictionary_chunk(&[3, 4, 0], &["a", "b", "c", "d", "e"]),
+    ];
+    let schema = Schema::from(vec![Field::new(
+        "a",
+        batches[0].arrays()[0].data_type().clone(),
+        false,
+    )]);
+
+    let result = write_(&schema, None, &batches);
+    assert_eq!(
+        dictionary_batches_is_delta(&result),
+        vec![false, true, true]
+    );
+
+    let mut reader = Cursor::new(result);
+    let metadata = read_stream_metadata(&mut reader)?;
+    let reader = StreamReader::new(reader, metadata, None);
+    let read = reader
+        .map(|x| x.map(|x| x.unwrap()))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(read, batches);
+    Ok(())
+}
+
+#[test]
+fn write_replaced_dictionaries() -> Result<()> {
+    let batches = vec![
+        dictionary_chunk(&[0, 1], &["a", "b"]),
+        dictionary_chunk(&[0, 1], &["b", "a"]),
+        dictionary_chunk(&[1, 1], &["b", "a"]),
+        dictionary_chunk(&[0], &["c"]),
+        dictionary_chunk(&[1], &["c", "d"]),
+    ];
+    let schema = Schema::from(vec![Field::new(
+        "a",
+        batches[0].arrays()[0].data_type().clone(),
+        false,
+    )]);
+
+    let result = write_(&schema, None, &batches);
+    assert_eq!(
+        dictionary_batches_is_delta(&result),
+        vec![false, false, false, true]
+    );
+
+    let mut reader = Cursor::new(result);
+    let metadata = read_stream_metadata(&mut reader)?;
+    let reader = StreamReader::new(reader, metadata, None);
+    let read = reader
+        .map(|x| x.map(|x| x.unwrap()))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(read, batches);
+    Ok(())
+}
@@ -0,0 +1,43 @@
+import os
+
+import pyarrow as pa
+import pyarrow.ipc
+
+
+PATH = "fixtures/pyarrow3/ipc"
+
+schema = pa.schema([pa.field("dict", pa.dictionary(pa.int32(), pa.utf8()), nullable=True)])
+
+
+def _batch(indices, dictionary):
+    array = pa.DictionaryArray.from_arrays(
+        pa.array(indices, type=pa.int32()), pa.array(dictionary, type=pa.utf8())
+    )
+    return pa.record_batch([array], schema=schema)
+
+
+# the second dictionary extends the first one and is written as a delta dictionary batch
+delta = [
+    _batch([0, 1, None], ["a", "b"]),
+    _batch([2, None, 0], ["a", "b", "c"]),
+]
+
+# the second dictionary is unrelated to the first one and replaces it
+replacement = [
+    _batch([0, 1, None], ["a", "b"]),
+    _batch([1, 0, None], ["c", "d"]),
+]
+
+
+def _write(new_writer, batches, path, emit_dictionary_deltas):
+    options = pa.ipc.IpcWriteOptions(emit_dictionary_deltas=emit_dictionary_deltas)
+    with new_writer(path, schema, options=options) as writer:
+        for batch in batches:
+            writer.write_batch(batch)
+
+
+os.makedirs(PATH, exist_ok=True)
+_write(pa.ipc.new_stream, delta, f"{PATH}/delta.stream", True)
+_write(pa.ipc.new_stream, replacement, f"{PATH}/replacement.stream", False)
+# the file format does not support dictionary replacements
+_write(pa.ipc.new_file, delta, f"{PATH}/delta.arrow_file", True)
@@ -21,7 +21,10 @@ async fn write_(
 ) -> Result<Vec<u8>> {
     let mut result = AsyncCursor::new(vec![]);
 
-    let options = WriteOptions { compression: None };
+    let options = WriteOptions {
+        compression: None,
+        ..Default::default()
+    };
     let mut sink = FileSink::new(&mut result, schema, Some(ipc_fields.to_vec()), options);
     for batch in batches {
         sink.feed((batch, Some(ipc_fields)).into()).await?;
@@ -2,17 +2,19 @@ use std::io::Cursor;
 
 use arrow2::array::Array;
 use arrow2::chunk::Chunk;
-use arrow2::datatypes::Schema;
+use arrow2::datatypes::{Field, Schema};
 use arrow2::error::Result;
 use arrow2::io::ipc::read;
+use arrow2::io::ipc::write::default_ipc_fields;
 use arrow2::io::ipc::write::stream_async;
 use arrow2::io::ipc::write::stream_async::StreamSink;
 use arrow2::io::ipc::IpcField;
 use futures::io::Cursor as AsyncCursor;
-use futures::SinkExt;
+use futures::{SinkExt, TryStreamExt};
 
 use crate::io::ipc::common::read_arrow_stream;
 use crate::io::ipc::common::read_gzip_json;
+use crate::io::ipc::write::stream::dictionary_chunk;
 
 async fn write_(
     schema: &Schema,
@@ -21,7 +23,10 @@ async fn write_(
 ) -> Result<Vec<u8>> {
     let mut result = AsyncCursor::new(vec![]);
 
-    let options = stream_async::WriteOptions { compression: None };
+    let options = stream_async::WriteOptions {
+        compression: None,
+        ..Default::default()
+    };
     let mut sink = StreamSink::new(&mut result, schema, Some(ipc_fields.to_vec()), options);
     for batch in batches {
         sink.feed((batch, Some(ipc_fields)).into()).await?;
@@ -62,3 +67,27 @@ async fn test_file(version: &str, file_name: &str) -> Result<()> {
 async fn write_async() -> Result<()> {
     test_file("1.0.0-littleendian", "generated_primitive").await
 }
+
+#[tokio::test]
+async fn write_replaced_dictionaries() -> Result<()> {
+    let batches = vec![
+        dictionary_chunk(&[0, 1], &["a", "b"]),
+        dictionary_chunk(&[1, 0], &["c", "a"]),
+        dictionary_chunk(&[2], &["c", "a", "b"]),
+    ];
+    let schema = Schema::from(vec![Field::new(
+        "a",
+        batches[0].arrays()[0].data_type().clone(),
+        false,
+    )]);
+    let ipc_fields = default_ipc_fields(&schema.fields);
+
+    let result = write_(&schema, &ipc_fields, &batches).await?;
+
+    let mut reader = AsyncCursor::new(result);
+    let metadata = read::stream_async::read_stream_metadata_async(&mut reader).await?;
+    let reader = read::stream_async::AsyncStreamReader::new(reader, metadata);
+    let read = reader.try_collect::<Vec<_>>().await?;
+    assert_eq!(read, batches);
+    Ok(())
+}
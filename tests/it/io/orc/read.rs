@@ -1,4 +1,8 @@
+use std::io::Cursor;
+
 use arrow2::array::*;
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema, TimeUnit};
 use arrow2::error::Error;
 use arrow2::io::orc::{format, read};
 
@@ -13,7 +17,11 @@ fn infer() -> Result<(), Error> {
 }
 
 fn deserialize_column(column_name: &str) -> Result<Box<dyn Array>, Error> {
-    let mut reader = std::fs::File::open("fixtures/pyorc/test.orc").unwrap();
+    deserialize_file_column("fixtures/pyorc/test.orc", column_name)
+}
+
+fn deserialize_file_column(path: &str, column_name: &str) -> Result<Box<dyn Array>, Error> {
+    let mut reader = std::fs::File::open(path).unwrap();
     let metadata = format::read::read_metadata(&mut reader)?;
     let schema = read::infer_schema(&metadata.footer)?;
 
@@ -26,17 +34,25 @@ fn deserialize_column(column_name: &str) -> Result<Box<dyn Array>, Error> {
         .find(|f| f.1.name == column_name)
         .unwrap();
 
-    let data_type = field.data_type.clone();
-    let column = format::read::read_stripe_column(
-        &mut reader,
-        &metadata,
-        0,
-        footer,
-        1 + pos as u32,
-        vec![],
-    )?;
+    // the root struct is column 0 and nested types span multiple columns
+    let start = 1 + schema.fields[..pos]
+        .iter()
+        .map(|field| read::n_columns(&field.data_type))
+        .sum::<usize>();
+    let columns = (start..start + read::n_columns(&field.data_type))
+        .map(|column| {
+            format::read::read_stripe_column(
+                &mut reader,
+                &metadata,
+                0,
+                footer.clone(),
+                column as u32,
+                vec![],
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
-    read::deserialize(data_type, &column)
+    read::deserialize_nested(field.data_type.clone(), &columns)
 }
 
 #[test]
@@ -122,3 +138,265 @@ fn utf8() -> Result<(), Error> {
     );
     Ok(())
 }
+
+fn deserialize_nested_column(column_name: &str) -> Result<Box<dyn Array>, Error> {
+    deserialize_file_column("fixtures/pyorc/nested.orc", column_name)
+}
+
+#[test]
+fn infer_nested() -> Result<(), Error> {
+    let mut reader = std::fs::File::open("fixtures/pyorc/nested.orc").unwrap();
+    let metadata = format::read::read_metadata(&mut reader)?;
+    let schema = read::infer_schema(&metadata.footer)?;
+
+    let data_types = schema
+        .fields
+        .iter()
+        .map(|field| field.data_type.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        data_types,
+        vec![
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            DataType::Date32,
+            DataType::Decimal(10, 2),
+            DataType::Utf8,
+            DataType::Utf8,
+            DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
+            map_data_type(),
+            struct_data_type(),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn timestamp() -> Result<(), Error> {
+    assert_eq!(
+        deserialize_nested_column("timestamp_nullable")?,
+        Int64Array::from([
+            Some(1609459200000000000),
+            Some(-1500000000),
+            None,
+            Some(1609459201000001000),
+            Some(0),
+        ])
+        .to(DataType::Timestamp(TimeUnit::Nanosecond, None))
+        .boxed()
+    );
+    Ok(())
+}
+
+#[test]
+fn date() -> Result<(), Error> {
+    assert_eq!(
+        deserialize_nested_column("date_nullable")?,
+        Int32Array::from([Some(18628), Some(-1), None, Some(0), Some(11016)])
+            .to(DataType::Date32)
+            .boxed()
+    );
+    Ok(())
+}
+
+#[test]
+fn decimal() -> Result<(), Error> {
+    assert_eq!(
+        deserialize_nested_column("decimal_nullable")?,
+        Int128Array::from([Some(150), Some(-225), None, Some(0), Some(1234567890)])
+            .to(DataType::Decimal(10, 2))
+            .boxed()
+    );
+    Ok(())
+}
+
+#[test]
+fn varchar_and_char() -> Result<(), Error> {
+    assert_eq!(
+        deserialize_nested_column("varchar_nullable")?,
+        Utf8Array::<i32>::from([Some("a"), Some("bb"), None, Some("dddd"), Some("eeeee")]).boxed()
+    );
+
+    // chars are padded to their length
+    assert_eq!(
+        deserialize_nested_column("char_nullable")?,
+        Utf8Array::<i32>::from([Some("a  "), Some("bb "), None, Some("ddd"), Some("eee")]).boxed()
+    );
+    Ok(())
+}
+
+#[test]
+fn list() -> Result<(), Error> {
+    let data = vec![
+        Some(vec![Some(1), Some(2)]),
+        Some(vec![]),
+        None,
+        Some(vec![Some(3), None]),
+        Some(vec![Some(4)]),
+    ];
+    let mut expected = MutableListArray::<i32, MutablePrimitiveArray<i32>>::new();
+    expected.try_extend(data)?;
+    let expected: ListArray<i32> = expected.into();
+
+    assert_eq!(
+        deserialize_nested_column("list_nullable")?,
+        expected.boxed()
+    );
+    Ok(())
+}
+
+fn map_entries_data_type() -> DataType {
+    DataType::Struct(vec![
+        Field::new("key", DataType::Utf8, false),
+        Field::new("value", DataType::Int32, true),
+    ])
+}
+
+fn map_data_type() -> DataType {
+    let entries = Field::new("entries", map_entries_data_type(), false);
+    DataType::Map(Box::new(entries), false)
+}
+
+#[test]
+fn map() -> Result<(), Error> {
+    let keys = Utf8Array::<i32>::from_slice(["a", "b", "c", "d"]);
+    let values = Int32Array::from([Some(1), None, Some(3), Some(4)]);
+    let entries = StructArray::new(
+        map_entries_data_type(),
+        vec![keys.boxed(), values.boxed()],
+        None,
+    );
+    let expected = MapArray::new(
+        map_data_type(),
+        vec![0, 1, 1, 1, 3, 4].into(),
+        entries.boxed(),
+        Some([true, true, false, true, true].into()),
+    );
+
+    assert_eq!(deserialize_nested_column("map_nullable")?, expected.boxed());
+    Ok(())
+}
+
+fn struct_data_type() -> DataType {
+    DataType::Struct(vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ])
+}
+
+#[test]
+fn struct_() -> Result<(), Error> {
+    // the fields of null slots are not stored in ORC
+    let a = Int32Array::from([Some(1), None, None, None, Some(5)]);
+    let b = Utf8Array::<i32>::from([Some("a"), None, Some("c"), None, Some("e")]);
+    let expected = StructArray::new(
+        struct_data_type(),
+        vec![a.boxed(), b.boxed()],
+        Some([true, false, true, false, true].into()),
+    );
+
+    assert_eq!(
+        deserialize_nested_column("struct_nullable")?,
+        expected.boxed()
+    );
+    Ok(())
+}
+
+/// Writes two stripes of two columns, `a` with values 0..5 and 5..10, and `b` with strings
+fn write_two_stripes() -> Result<Vec<u8>, Error> {
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ]);
+    let chunks = [0, 5]
+        .into_iter()
+        .map(|start| {
+            let a = Int32Array::from_iter((start..start + 5).map(Some));
+            let b = Utf8Array::<i32>::from_iter((start..start + 5).map(|x| Some(x.to_string())));
+            Chunk::new(vec![a.boxed(), b.boxed()])
+        })
+        .collect::<Vec<_>>();
+    super::write::write_file(&schema, &chunks, Default::default())
+}
+
+#[test]
+fn file_reader() -> Result<(), Error> {
+    let data = write_two_stripes()?;
+    let mut reader = Cursor::new(data);
+    let metadata = format::read::read_metadata(&mut reader)?;
+    let schema = read::infer_schema(&metadata.footer)?;
+
+    let reader = read::FileReader::try_new(reader, metadata, schema, None, None)?;
+    let chunks = reader.collect::<Result<Vec<_>, Error>>()?;
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(
+        chunks[1].arrays()[0],
+        Int32Array::from_slice([5, 6, 7, 8, 9]).boxed()
+    );
+    Ok(())
+}
+
+#[test]
+fn file_reader_projection_and_limit() -> Result<(), Error> {
+    let data = write_two_stripes()?;
+    let mut reader = Cursor::new(data);
+    let metadata = format::read::read_metadata(&mut reader)?;
+    let schema = read::infer_schema(&metadata.footer)?;
+    let schema = schema.filter(|_, field| field.name == "b");
+
+    let reader = read::FileReader::try_new(reader, metadata, schema, None, Some(7))?;
+    assert_eq!(reader.schema().fields.len(), 1);
+    let chunks = reader.collect::<Result<Vec<_>, Error>>()?;
+
+    assert_eq!(
+        chunks,
+        vec![
+            Chunk::new(vec![Utf8Array::<i32>::from_slice([
+                "0", "1", "2", "3", "4"
+            ])
+            .boxed()]),
+            Chunk::new(vec![Utf8Array::<i32>::from_slice(["5", "6"]).boxed()]),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn file_reader_stripe_statistics() -> Result<(), Error> {
+    let data = write_two_stripes()?;
+    let mut reader = Cursor::new(data);
+    let metadata = format::read::read_metadata(&mut reader)?;
+    let schema = read::infer_schema(&metadata.footer)?;
+    let schema = schema.filter(|_, field| field.name == "a");
+
+    // select stripes that may contain a > 6
+    let stripes = read::filter_stripes(&metadata, &schema, |statistics| {
+        statistics[0]
+            .int_statistics
+            .as_ref()
+            .and_then(|x| x.maximum)
+            .map(|max| max > 6)
+            .unwrap_or(true)
+    })?;
+    assert_eq!(stripes, vec![1]);
+
+    let reader = read::FileReader::try_new(reader, metadata, schema, Some(stripes), None)?;
+    let chunks = reader.collect::<Result<Vec<_>, Error>>()?;
+    assert_eq!(
+        chunks,
+        vec![Chunk::new(vec![
+            Int32Array::from_slice([5, 6, 7, 8, 9]).boxed()
+        ])]
+    );
+    Ok(())
+}
+
+#[test]
+fn file_reader_invalid() -> Result<(), Error> {
+    let data = write_two_stripes()?;
+    let mut reader = Cursor::new(data);
+    let metadata = format::read::read_metadata(&mut reader)?;
+    let schema = Schema::from(vec![Field::new("c", DataType::Int32, true)]);
+
+    assert!(read::FileReader::try_new(reader, metadata, schema, None, None).is_err());
+    Ok(())
+}
@@ -1,3 +1,5 @@
+import datetime
+import decimal
 import os
 
 import pyorc
@@ -47,8 +49,10 @@ def _write(
     file_name: str,
     compression=pyorc.CompressionKind.NONE,
     dict_key_size_threshold=0.0,
+    schema=None,
 ):
-    schema = infer_schema(data)
+    if schema is None:
+        schema = infer_schema(data)
 
     output = open(file_name, "wb")
     writer = pyorc.Writer(
@@ -63,5 +67,50 @@ def _write(
         writer.write(row)
     writer.close()
 
+utc = datetime.timezone.utc
+
+nested_data = {
+    "timestamp_nullable": [
+        datetime.datetime(2021, 1, 1, tzinfo=utc),
+        datetime.datetime(1969, 12, 31, 23, 59, 58, 500000, tzinfo=utc),
+        None,
+        datetime.datetime(2021, 1, 1, 0, 0, 1, 1, tzinfo=utc),
+        datetime.datetime(1970, 1, 1, tzinfo=utc),
+    ],
+    "date_nullable": [
+        datetime.date(2021, 1, 1),
+        datetime.date(1969, 12, 31),
+        None,
+        datetime.date(1970, 1, 1),
+        datetime.date(2000, 2, 29),
+    ],
+    "decimal_nullable": [
+        decimal.Decimal("1.5"),
+        decimal.Decimal("-2.25"),
+        None,
+        decimal.Decimal("0"),
+        decimal.Decimal("12345678.90"),
+    ],
+    "varchar_nullable": ["a", "bb", None, "dddd", "eeeee"],
+    "char_nullable": ["a", "bb", None, "ddd", "eee"],
+    "list_nullable": [[1, 2], [], None, [3, None], [4]],
+    "map_nullable": [{"a": 1}, {}, None, {"b": None, "c": 3}, {"d": 4}],
+    "struct_nullable": [(1, "a"), None, (None, "c"), None, (5, "e")],
+}
+
+nested_schema = (
+    "struct<"
+    "timestamp_nullable:timestamp,"
+    "date_nullable:date,"
+    "decimal_nullable:decimal(10,2),"
+    "varchar_nullable:varchar(10),"
+    "char_nullable:char(3),"
+    "list_nullable:array<int>,"
+    "map_nullable:map<string,int>,"
+    "struct_nullable:struct<a:int,b:string>"
+    ">"
+)
+
 os.makedirs("fixtures/pyorc", exist_ok=True)
 _write(data, "fixtures/pyorc/test.orc")
+_write(nested_data, "fixtures/pyorc/nested.orc", schema=nested_schema)
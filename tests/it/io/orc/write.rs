@@ -0,0 +1,342 @@
+use std::io::Cursor;
+
+use arrow2::array::*;
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow2::error::Error;
+use arrow2::io::orc::{format, read, write};
+
+pub(super) fn write_file(
+    schema: &Schema,
+    chunks: &[Chunk<Box<dyn Array>>],
+    options: write::WriteOptions,
+) -> Result<Vec<u8>, Error> {
+    let mut writer = write::FileWriter::try_new(vec![], schema.clone(), options)?;
+    for chunk in chunks {
+        writer.write(chunk)?;
+    }
+    writer.finish()?;
+    Ok(writer.into_inner())
+}
+
+type Chunks = Vec<Chunk<Box<dyn Array>>>;
+
+fn read_file(data: &[u8]) -> Result<(Schema, Chunks), Error> {
+    let mut reader = Cursor::new(data);
+    let metadata = format::read::read_metadata(&mut reader)?;
+    let schema = read::infer_schema(&metadata.footer)?;
+
+    let reader = read::FileReader::try_new(reader, metadata, schema.clone(), None, None)?;
+    let chunks = reader.collect::<Result<Vec<_>, Error>>()?;
+    Ok((schema, chunks))
+}
+
+fn round_trip(arrays: Vec<Box<dyn Array>>, options: write::WriteOptions) -> Result<(), Error> {
+    let schema = Schema::from(
+        arrays
+            .iter()
+            .enumerate()
+            .map(|(i, array)| Field::new(format!("c{i}"), array.data_type().clone(), true))
+            .collect::<Vec<_>>(),
+    );
+    let chunk = Chunk::new(arrays);
+
+    let data = write_file(&schema, &[chunk.clone(), chunk.clone()], options)?;
+    let (read_schema, chunks) = read_file(&data)?;
+
+    assert_eq!(read_schema, schema);
+    assert_eq!(chunks, vec![chunk.clone(), chunk]);
+    Ok(())
+}
+
+fn primitives() -> Vec<Box<dyn Array>> {
+    vec![
+        BooleanArray::from([Some(true), None, Some(false), Some(true)]).boxed(),
+        Int8Array::from([Some(-1), None, Some(3), Some(i8::MAX)]).boxed(),
+        Int16Array::from([Some(-1), None, Some(3), Some(i16::MIN)]).boxed(),
+        Int32Array::from([Some(-1), None, Some(3), Some(i32::MAX)]).boxed(),
+        Int64Array::from([Some(i64::MIN), None, Some(3), Some(i64::MAX)]).boxed(),
+        Float32Array::from([Some(1.5), None, Some(-3.0), Some(f32::MAX)]).boxed(),
+        Float64Array::from([Some(1.5), None, Some(-3.0), Some(f64::MIN)]).boxed(),
+        Utf8Array::<i32>::from([Some("a"), None, Some(""), Some("bb")]).boxed(),
+        BinaryArray::<i32>::from([Some(b"a".as_ref()), None, Some(b""), Some(b"\x00\xff")]).boxed(),
+        Int32Array::from([Some(18_000), None, Some(-1), Some(0)])
+            .to(DataType::Date32)
+            .boxed(),
+        Int64Array::from([
+            Some(1_500_000_000),
+            None,
+            Some(-1_500_000_000),
+            Some(1_650_000_000_123_456_789),
+        ])
+        .to(DataType::Timestamp(TimeUnit::Nanosecond, None))
+        .boxed(),
+        Int128Array::from([Some(12345), None, Some(-1), Some(0)])
+            .to(DataType::Decimal(10, 2))
+            .boxed(),
+    ]
+}
+
+#[test]
+fn primitive() -> Result<(), Error> {
+    round_trip(primitives(), Default::default())
+}
+
+#[test]
+fn primitive_required() -> Result<(), Error> {
+    let arrays = primitives()
+        .into_iter()
+        .map(|array| array.slice(2, 2))
+        .collect();
+    round_trip(arrays, Default::default())
+}
+
+#[test]
+fn long_runs() -> Result<(), Error> {
+    let ints = (0..2000i64)
+        .map(|x| if x % 7 == 0 { None } else { Some(x / 100) })
+        .collect::<Int64Array>();
+    let sequence = (0..2000i32).map(Some).collect::<Int32Array>();
+    let bools = (0..2000)
+        .map(|x| Some(x % 300 < 150))
+        .collect::<BooleanArray>();
+    let bytes = (0..2000)
+        .map(|x| Some((x / 200) as i8))
+        .collect::<Int8Array>();
+    let strings = (0..2000)
+        .map(|x| Some(format!("value {}", x % 10)))
+        .collect::<Utf8Array<i32>>();
+    round_trip(
+        vec![
+            ints.boxed(),
+            sequence.boxed(),
+            bools.boxed(),
+            bytes.boxed(),
+            strings.boxed(),
+        ],
+        Default::default(),
+    )
+}
+
+#[test]
+fn utf8_encodings() -> Result<(), Error> {
+    let array = Utf8Array::<i32>::from([Some("b"), None, Some("a"), Some("b"), Some("b")]);
+    let schema = Schema::from(vec![Field::new("c0", array.data_type().clone(), true)]);
+    let chunk = Chunk::new(vec![array.boxed()]);
+
+    for (threshold, expected) in [
+        (0.8, format::proto::column_encoding::Kind::DictionaryV2),
+        (0.0, format::proto::column_encoding::Kind::DirectV2),
+    ] {
+        let options = write::WriteOptions {
+            dictionary_key_size_threshold: threshold,
+        };
+        let data = write_file(&schema, std::slice::from_ref(&chunk), options)?;
+
+        let mut reader = Cursor::new(&data);
+        let metadata = format::read::read_metadata(&mut reader)?;
+        let footer = format::read::read_stripe_footer(&mut reader, &metadata, 0, &mut vec![])?;
+        assert_eq!(footer.columns[1].kind(), expected);
+
+        let (_, chunks) = read_file(&data)?;
+        assert_eq!(chunks, vec![chunk.clone()]);
+    }
+    Ok(())
+}
+
+#[test]
+fn list() -> Result<(), Error> {
+    let data = vec![
+        Some(vec![Some(1), None, Some(3)]),
+        None,
+        Some(vec![]),
+        Some(vec![Some(4)]),
+    ];
+    let mut array = MutableListArray::<i32, MutablePrimitiveArray<i32>>::new();
+    array.try_extend(data)?;
+    let array: ListArray<i32> = array.into();
+
+    // a null list with values, that must not be written
+    let values = Int32Array::from_slice([1, 2, 3, 4, 5]).boxed();
+    let with_values_under_null = ListArray::<i32>::new(
+        ListArray::<i32>::default_datatype(DataType::Int32),
+        vec![0, 2, 4, 5].into(),
+        values,
+        Some([true, false, true].into()),
+    );
+    let expected = ListArray::<i32>::new(
+        ListArray::<i32>::default_datatype(DataType::Int32),
+        vec![0, 2, 2, 3].into(),
+        Int32Array::from_slice([1, 2, 5]).boxed(),
+        Some([true, false, true].into()),
+    );
+
+    round_trip(vec![array.boxed()], Default::default())?;
+
+    let schema = Schema::from(vec![Field::new(
+        "c0",
+        with_values_under_null.data_type().clone(),
+        true,
+    )]);
+    let data = write_file(
+        &schema,
+        &[Chunk::new(vec![with_values_under_null.boxed()])],
+        Default::default(),
+    )?;
+    let (_, chunks) = read_file(&data)?;
+    assert_eq!(chunks, vec![Chunk::new(vec![expected.boxed()])]);
+    Ok(())
+}
+
+#[test]
+fn struct_() -> Result<(), Error> {
+    let fields = vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ];
+    let array = StructArray::new(
+        DataType::Struct(fields),
+        vec![
+            Int32Array::from([Some(1), Some(2), None, Some(4)]).boxed(),
+            Utf8Array::<i32>::from([Some("a"), None, None, Some("d")]).boxed(),
+        ],
+        Some([true, false, true, true].into()),
+    );
+    // the values of the children under null slots are not written
+    let expected = StructArray::new(
+        array.data_type().clone(),
+        vec![
+            Int32Array::from([Some(1), None, None, Some(4)]).boxed(),
+            Utf8Array::<i32>::from([Some("a"), None, None, Some("d")]).boxed(),
+        ],
+        Some([true, false, true, true].into()),
+    );
+
+    let schema = Schema::from(vec![Field::new("c0", array.data_type().clone(), true)]);
+    let data = write_file(
+        &schema,
+        &[Chunk::new(vec![array.boxed()])],
+        Default::default(),
+    )?;
+    let (_, chunks) = read_file(&data)?;
+    assert_eq!(chunks, vec![Chunk::new(vec![expected.clone().boxed()])]);
+
+    round_trip(vec![expected.boxed()], Default::default())
+}
+
+#[test]
+fn map() -> Result<(), Error> {
+    let entries = DataType::Struct(vec![
+        Field::new("key", DataType::Utf8, false),
+        Field::new("value", DataType::Int32, true),
+    ]);
+    let data_type = DataType::Map(
+        Box::new(Field::new("entries", entries.clone(), false)),
+        false,
+    );
+    let field = StructArray::new(
+        entries.clone(),
+        vec![
+            Utf8Array::<i32>::from_slice(["a", "b", "c", "d"]).boxed(),
+            Int32Array::from([Some(1), None, Some(3), Some(4)]).boxed(),
+        ],
+        None,
+    );
+    let array = MapArray::new(
+        data_type,
+        vec![0, 2, 2, 3, 4].into(),
+        field.boxed(),
+        Some([true, true, false, true].into()),
+    );
+    // the entries of null slots are not written
+    let expected_field = StructArray::new(
+        entries,
+        vec![
+            Utf8Array::<i32>::from_slice(["a", "b", "d"]).boxed(),
+            Int32Array::from([Some(1), None, Some(4)]).boxed(),
+        ],
+        None,
+    );
+    let expected = MapArray::new(
+        array.data_type().clone(),
+        vec![0, 2, 2, 2, 3].into(),
+        expected_field.boxed(),
+        array.validity().cloned(),
+    );
+
+    let schema = Schema::from(vec![Field::new("c0", array.data_type().clone(), true)]);
+    let data = write_file(
+        &schema,
+        &[Chunk::new(vec![array.boxed()])],
+        Default::default(),
+    )?;
+    let (_, chunks) = read_file(&data)?;
+    assert_eq!(chunks, vec![Chunk::new(vec![expected.clone().boxed()])]);
+
+    round_trip(vec![expected.boxed()], Default::default())
+}
+
+#[test]
+fn statistics() -> Result<(), Error> {
+    let array = Int32Array::from([Some(3), None, Some(-1)]);
+    let strings = Utf8Array::<i32>::from([Some("b"), Some("a"), None]);
+    let schema = Schema::from(vec![
+        Field::new("c0", array.data_type().clone(), true),
+        Field::new("c1", strings.data_type().clone(), true),
+    ]);
+    let chunk = Chunk::new(vec![array.boxed(), strings.boxed()]);
+    let data = write_file(&schema, &[chunk.clone(), chunk], Default::default())?;
+
+    let mut reader = Cursor::new(&data);
+    let metadata = format::read::read_metadata(&mut reader)?;
+
+    assert_eq!(metadata.footer.number_of_rows, Some(6));
+    assert_eq!(metadata.footer.stripes.len(), 2);
+    assert_eq!(metadata.metadata.stripe_stats.len(), 2);
+
+    let stripe = &metadata.metadata.stripe_stats[0].col_stats;
+    assert_eq!(stripe[0].number_of_values, Some(3));
+    assert_eq!(stripe[1].number_of_values, Some(2));
+    assert_eq!(stripe[1].has_null, Some(true));
+    assert_eq!(
+        stripe[1].int_statistics,
+        Some(format::proto::IntegerStatistics {
+            minimum: Some(-1),
+            maximum: Some(3),
+            sum: Some(2),
+        })
+    );
+
+    let file = &metadata.footer.statistics;
+    assert_eq!(file[1].number_of_values, Some(4));
+    assert_eq!(file[1].int_statistics.as_ref().unwrap().sum, Some(4));
+    assert_eq!(
+        file[2].string_statistics,
+        Some(format::proto::StringStatistics {
+            minimum: Some("a".to_string()),
+            maximum: Some("b".to_string()),
+            sum: Some(4),
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn empty() -> Result<(), Error> {
+    let schema = Schema::from(vec![Field::new("c0", DataType::Int32, true)]);
+    let data = write_file(&schema, &[], Default::default())?;
+    let (read_schema, chunks) = read_file(&data)?;
+    assert_eq!(read_schema, schema);
+    assert!(chunks.is_empty());
+    Ok(())
+}
+
+#[test]
+fn unsupported() {
+    let schema = Schema::from(vec![Field::new(
+        "c0",
+        DataType::Time32(TimeUnit::Second),
+        true,
+    )]);
+    assert!(write::FileWriter::try_new(vec![], schema, Default::default()).is_err());
+}
@@ -0,0 +1,212 @@
+use std::io::Cursor;
+
+use arrow2::chunk::Chunk;
+use arrow2::io::parquet::read::bloom_filter;
+use arrow2::scalar::{PrimitiveScalar, Utf8Scalar};
+use arrow2::{array::*, datatypes::*, error::Result, io::parquet::read::*, io::parquet::write::*};
+
+fn options(bloom_filter: Option<BloomFilterOptions>) -> WriteOptions {
+    WriteOptions {
+        write_statistics: true,
+        compression: CompressionOptions::Uncompressed,
+        version: Version::V1,
+        bloom_filter,
+        data_page_size: None,
+        data_page_max_rows: None,
+    }
+}
+
+fn schema() -> Schema {
+    Schema::from(vec![
+        Field::new("id", DataType::Int64, true),
+        Field::new("key", DataType::Utf8, true),
+        Field::new("flag", DataType::Boolean, true),
+    ])
+}
+
+/// Writes 3 row groups with `id` in `group*1000..(group + 1)*1000` (every 100th value null)
+/// and `key` its string representation, but only every other id.
+fn write(bloom_filter: Option<BloomFilterOptions>) -> Result<Vec<u8>> {
+    let schema = schema();
+    let options = options(bloom_filter);
+    let mut writer = FileWriter::try_new(Cursor::new(vec![]), schema, options)?;
+
+    for group in 0..3i64 {
+        let id = Int64Array::from_iter((group * 1000..(group + 1) * 1000).step_by(2).map(|x| {
+            if x % 100 == 0 {
+                None
+            } else {
+                Some(x)
+            }
+        }));
+        let key = Utf8Array::<i32>::from_iter(id.iter().map(|x| x.map(|x| format!("key{x}"))));
+        let flag = BooleanArray::from_iter(id.iter().map(|x| x.map(|x| x % 3 == 0)));
+        let chunk = Chunk::new(vec![id.boxed(), key.boxed(), flag.boxed()]);
+        writer.write_chunk(chunk, vec![vec![Encoding::Plain]; 3])?;
+    }
+    writer.end(None)?;
+    Ok(writer.into_inner().into_inner())
+}
+
+fn bitset(data: &[u8], row_group: usize, column: usize) -> Result<Vec<u8>> {
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let mut bitset = vec![];
+    bloom_filter::read(
+        &metadata.row_groups[row_group].columns()[column],
+        &mut reader,
+        &mut bitset,
+    )?;
+    Ok(bitset)
+}
+
+#[test]
+fn round_trip() -> Result<()> {
+    let data = write(Some(BloomFilterOptions::default()))?;
+
+    // the file is readable and its page indexes are intact
+    let mut reader = Cursor::new(&data);
+    let metadata = read_metadata(&mut reader)?;
+    let schema = infer_schema(&metadata)?;
+    assert_eq!(schema, self::schema());
+    let pages = indexes::read_filtered_pages(
+        &mut reader,
+        &metadata.row_groups[1],
+        &schema.fields,
+        |_, intervals| intervals[0][0].clone(),
+    )?;
+    assert_eq!(pages.len(), 3);
+    let reader = FileReader::new(reader, metadata.row_groups, schema, None, None, None);
+    let rows = reader
+        .map(|chunk| chunk.map(|chunk| chunk.len()))
+        .sum::<Result<usize>>()?;
+    assert_eq!(rows, 1500);
+
+    for group in 0..3 {
+        let id = bitset(&data, group, 0)?;
+        // 500 distinct values require 1024 bytes for a false positive probability of 1%
+        assert_eq!(id.len(), 1024);
+        let key = bitset(&data, group, 1)?;
+        assert_eq!(key.len(), 1024);
+        // booleans have no bloom filter
+        assert!(bitset(&data, group, 2)?.is_empty());
+    }
+
+    let id = bitset(&data, 1, 0)?;
+    let key = bitset(&data, 1, 1)?;
+    for x in (1000..2000i64).step_by(2).filter(|x| x % 100 != 0) {
+        let value = PrimitiveScalar::from(Some(x));
+        assert!(bloom_filter::may_contain(&id, &value)?);
+        let value = Utf8Scalar::<i32>::new(Some(format!("key{x}")));
+        assert!(bloom_filter::may_contain(&key, &value)?);
+    }
+    // within the statistics of the row group but not in it
+    let misses = (1000..2000i64)
+        .filter(|x| x % 2 == 1)
+        .filter(|x| {
+            let value = PrimitiveScalar::from(Some(*x));
+            bloom_filter::may_contain(&id, &value).unwrap()
+        })
+        .count();
+    assert!(misses < 50, "{misses} false positives");
+
+    // nulls are never in the filters and can't be excluded by them
+    let value = PrimitiveScalar::<i64>::from(None);
+    assert!(bloom_filter::may_contain(&id, &value)?);
+    Ok(())
+}
+
+#[test]
+fn prune_row_groups() -> Result<()> {
+    let data = write(Some(BloomFilterOptions::default()))?;
+    let mut reader = Cursor::new(&data);
+    let metadata = read_metadata(&mut reader)?;
+    let schema = infer_schema(&metadata)?;
+
+    let value = Utf8Scalar::<i32>::new(Some("key1234"));
+    let row_groups = bloom_filter::prune_row_groups(
+        &mut reader,
+        &schema.fields[1],
+        metadata.row_groups.clone(),
+        &value,
+    )?;
+    assert_eq!(row_groups.len(), 1);
+    assert_eq!(row_groups[0].num_rows(), metadata.row_groups[1].num_rows());
+
+    let value = Utf8Scalar::<i32>::new(Some("key1235"));
+    let row_groups = bloom_filter::prune_row_groups(
+        &mut reader,
+        &schema.fields[1],
+        metadata.row_groups.clone(),
+        &value,
+    )?;
+    assert!(row_groups.is_empty());
+
+    // the value must be of the type of the field
+    let value = PrimitiveScalar::from(Some(1234i32));
+    assert!(bloom_filter::prune_row_groups(
+        &mut reader,
+        &schema.fields[0],
+        metadata.row_groups,
+        &value
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn max_bytes() -> Result<()> {
+    let data = write(Some(BloomFilterOptions { max_bytes: 100 }))?;
+    assert_eq!(bitset(&data, 0, 0)?.len(), 64);
+    Ok(())
+}
+
+#[test]
+fn without_bloom_filters() -> Result<()> {
+    let data = write(None)?;
+    assert!(bitset(&data, 0, 0)?.is_empty());
+
+    let mut reader = Cursor::new(&data);
+    let metadata = read_metadata(&mut reader)?;
+    let schema = infer_schema(&metadata)?;
+    let value = PrimitiveScalar::from(Some(1i64));
+    let row_groups = bloom_filter::prune_row_groups(
+        &mut reader,
+        &schema.fields[0],
+        metadata.row_groups,
+        &value,
+    )?;
+    assert_eq!(row_groups.len(), 3);
+    Ok(())
+}
+
+#[test]
+fn only_write_chunk_builds_bloom_filters() -> Result<()> {
+    let options = options(Some(BloomFilterOptions::default()));
+    let encodings = vec![vec![Encoding::Plain]; 3];
+    let id = Int64Array::from_slice([1, 2]);
+    let key = Utf8Array::<i32>::from_slice(["a", "b"]);
+    let flag = BooleanArray::from_slice([true, false]);
+    let chunk = Chunk::new(vec![id.boxed(), key.boxed(), flag.boxed()]);
+
+    let mut writer = FileWriter::try_new(Cursor::new(vec![]), schema(), options)?;
+    let row_group = row_group_iter(
+        chunk.clone(),
+        encodings.clone(),
+        writer.parquet_schema().fields().to_vec(),
+        options,
+    );
+    assert!(writer.write(row_group).is_err());
+
+    let chunks = vec![Ok(chunk)].into_iter();
+    assert!(RowGroupIterator::try_new(chunks, &schema(), options, encodings.clone()).is_err());
+
+    assert!(FileSink::try_new(
+        futures::io::Cursor::new(vec![]),
+        schema(),
+        encodings,
+        options
+    )
+    .is_err());
+    Ok(())
+}
@@ -13,12 +13,18 @@ use arrow2::{
     types::{days_ms, NativeType},
 };
 
+#[cfg(feature = "io_parquet_bloom_filter")]
+mod bloom_filter;
 #[cfg(feature = "io_json_integration")]
 mod integration;
 mod read;
 mod read_indexes;
+#[cfg(feature = "io_parquet_predicate")]
+mod read_predicate;
 mod write;
 mod write_async;
+mod write_column_options;
+mod write_pages;
 
 type ArrayStats = (Box<dyn Array>, Statistics);
 
@@ -460,7 +466,7 @@ pub fn pyarrow_nullable_statistics(column: &str) -> Statistics {
 
             Statistics {
                 distinct_count: UInt64Array::from([None]).boxed(),
-                null_count: UInt64Array::from([Some(0)]).boxed(),
+                null_count: UInt64Array::from([Some(1)]).boxed(),
                 min_value: new_dict(Box::new(Int32Array::from_slice([10]))),
                 max_value: new_dict(Box::new(Int32Array::from_slice([200]))),
             }
@@ -1128,6 +1134,9 @@ fn integration_write(schema: &Schema, chunks: &[Chunk<Box<dyn Array>>]) -> Resul
         write_statistics: true,
         compression: CompressionOptions::Uncompressed,
         version: Version::V1,
+        bloom_filter: None,
+        data_page_size: None,
+        data_page_max_rows: None,
     };
 
     let encodings = schema
@@ -1541,6 +1550,186 @@ fn nested_dict_limit() -> Result<()> {
     assert_roundtrip(schema, chunk, Some(2))
 }
 
+#[test]
+fn list_of_list() -> Result<()> {
+    // [[[1, 2], None, []], None, [[3]]]
+    let mut values = MutableListArray::<i32, MutablePrimitiveArray<i32>>::new();
+    values.try_extend(vec![
+        Some(vec![Some(1), Some(2)]),
+        None,
+        Some(vec![]),
+        Some(vec![Some(3)]),
+    ])?;
+    let values: ListArray<i32> = values.into();
+    let array = ListArray::<i32>::new(
+        DataType::List(Box::new(Field::new(
+            "item",
+            values.data_type().clone(),
+            true,
+        ))),
+        vec![0, 3, 3, 4].into(),
+        values.boxed(),
+        Some([true, false, true].into()),
+    );
+
+    let schema = Schema::from(vec![Field::new("c1", array.data_type().clone(), true)]);
+    let chunk = Chunk::try_new(vec![array.boxed()])?;
+
+    assert_roundtrip(schema, chunk, None)
+}
+
+fn map_data() -> MapArray {
+    // [{"a": 1, "b": None}, None, {}, {"c": 3}]
+    let keys = Utf8Array::<i32>::from_slice(["a", "b", "c"]).boxed();
+    let values = Int32Array::from([Some(1), None, Some(3)]).boxed();
+    let fields = vec![
+        Field::new("key", DataType::Utf8, false),
+        Field::new("value", DataType::Int32, true),
+    ];
+    let entries = StructArray::new(DataType::Struct(fields), vec![keys, values], None);
+    MapArray::new(
+        DataType::Map(
+            Box::new(Field::new("entries", entries.data_type().clone(), false)),
+            false,
+        ),
+        vec![0, 2, 2, 2, 3].into(),
+        entries.boxed(),
+        Some([true, false, true, true].into()),
+    )
+}
+
+#[test]
+fn map() -> Result<()> {
+    let array = map_data();
+
+    let schema = Schema::from(vec![Field::new("c1", array.data_type().clone(), true)]);
+    let chunk = Chunk::try_new(vec![array.boxed()])?;
+
+    assert_roundtrip(schema, chunk, None)
+}
+
+#[test]
+fn map_limit() -> Result<()> {
+    let array = map_data();
+
+    let schema = Schema::from(vec![Field::new("c1", array.data_type().clone(), true)]);
+    let chunk = Chunk::try_new(vec![array.boxed()])?;
+
+    assert_roundtrip(schema, chunk, Some(2))
+}
+
+#[test]
+fn map_of_list() -> Result<()> {
+    // [{"a": [1, 2]}, {"b": None, "c": []}]
+    let keys = Utf8Array::<i32>::from_slice(["a", "b", "c"]).boxed();
+    let mut values = MutableListArray::<i32, MutablePrimitiveArray<i64>>::new();
+    values.try_extend(vec![Some(vec![Some(1), Some(2)]), None, Some(vec![])])?;
+    let values: ListArray<i32> = values.into();
+    let fields = vec![
+        Field::new("key", DataType::Utf8, false),
+        Field::new("value", values.data_type().clone(), true),
+    ];
+    let entries = StructArray::new(DataType::Struct(fields), vec![keys, values.boxed()], None);
+    let array = MapArray::new(
+        DataType::Map(
+            Box::new(Field::new("entries", entries.data_type().clone(), false)),
+            false,
+        ),
+        vec![0, 1, 3].into(),
+        entries.boxed(),
+        None,
+    );
+
+    let schema = Schema::from(vec![Field::new("c1", array.data_type().clone(), false)]);
+    let chunk = Chunk::try_new(vec![array.boxed()])?;
+
+    assert_roundtrip(schema, chunk, None)
+}
+
+#[test]
+fn struct_of_map() -> Result<()> {
+    let map = map_data();
+    let ints = Int32Array::from([Some(1), Some(2), None, Some(4)]);
+    let fields = vec![
+        Field::new("map", map.data_type().clone(), true),
+        Field::new("int", DataType::Int32, true),
+    ];
+    let array = StructArray::new(
+        DataType::Struct(fields),
+        vec![map.boxed(), ints.boxed()],
+        None,
+    );
+
+    let schema = Schema::from(vec![Field::new("c1", array.data_type().clone(), false)]);
+    let chunk = Chunk::try_new(vec![array.boxed()])?;
+
+    assert_roundtrip(schema, chunk, None)
+}
+
+fn fixed_size_list_data() -> FixedSizeListArray {
+    // [[1, 2], None, [None, 4], [5, 6]]
+    let values = Int32Array::from([
+        Some(1),
+        Some(2),
+        Some(0),
+        Some(0),
+        None,
+        Some(4),
+        Some(5),
+        Some(6),
+    ]);
+    FixedSizeListArray::new(
+        DataType::FixedSizeList(Box::new(Field::new("item", DataType::Int32, true)), 2),
+        values.boxed(),
+        Some([true, false, true, true].into()),
+    )
+}
+
+#[test]
+fn fixed_size_list() -> Result<()> {
+    let array = fixed_size_list_data();
+
+    let schema = Schema::from(vec![Field::new("c1", array.data_type().clone(), true)]);
+    let chunk = Chunk::try_new(vec![array.boxed()])?;
+
+    assert_roundtrip(schema, chunk, None)
+}
+
+#[test]
+fn fixed_size_list_required() -> Result<()> {
+    let values = Float32Array::from_slice([1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let array = FixedSizeListArray::new(
+        DataType::FixedSizeList(Box::new(Field::new("item", DataType::Float32, false)), 3),
+        values.boxed(),
+        None,
+    );
+
+    let schema = Schema::from(vec![Field::new("c1", array.data_type().clone(), false)]);
+    let chunk = Chunk::try_new(vec![array.boxed()])?;
+
+    assert_roundtrip(schema, chunk, None)
+}
+
+#[test]
+fn list_of_fixed_size_list() -> Result<()> {
+    let values = fixed_size_list_data();
+    let array = ListArray::<i32>::new(
+        DataType::List(Box::new(Field::new(
+            "item",
+            values.data_type().clone(),
+            true,
+        ))),
+        vec![0, 3, 3, 4].into(),
+        values.boxed(),
+        Some([true, false, true].into()),
+    );
+
+    let schema = Schema::from(vec![Field::new("c1", array.data_type().clone(), true)]);
+    let chunk = Chunk::try_new(vec![array.boxed()])?;
+
+    assert_roundtrip(schema, chunk, None)
+}
+
 #[test]
 fn filter_chunk() -> Result<()> {
     let chunk1 = Chunk::new(vec![PrimitiveArray::from_slice([1i16, 3]).boxed()]);
@@ -0,0 +1,280 @@
+use std::io::Cursor;
+
+use arrow2::chunk::Chunk;
+use arrow2::error::Error;
+use arrow2::io::parquet::read::indexes::Interval;
+use arrow2::io::parquet::read::predicate::{Comparison, Predicate};
+use arrow2::scalar::{PrimitiveScalar, Scalar, Utf8Scalar};
+use arrow2::{array::*, datatypes::*, error::Result, io::parquet::read::*, io::parquet::write::*};
+
+fn options() -> WriteOptions {
+    WriteOptions {
+        write_statistics: true,
+        compression: CompressionOptions::Uncompressed,
+        version: Version::V1,
+        bloom_filter: None,
+        data_page_size: None,
+        data_page_max_rows: None,
+    }
+}
+
+fn schema() -> Schema {
+    Schema::from(vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ])
+}
+
+/// Writes a file with 3 row groups: `a` is 0..10, 10..20 and 20..30, with every 7th value
+/// null, and `b` is the string representation of `a`.
+fn write_row_groups() -> Result<Vec<u8>> {
+    let schema = schema();
+    let chunks = (0..3).map(|group| {
+        let a = Int32Array::from_iter((group * 10..(group + 1) * 10).map(|x| {
+            if x % 7 == 0 {
+                None
+            } else {
+                Some(x)
+            }
+        }));
+        let b = Utf8Array::<i32>::from_iter(a.iter().map(|x| x.map(|x| x.to_string())));
+        Ok(Chunk::new(vec![a.boxed(), b.boxed()]))
+    });
+
+    let encodings = vec![vec![Encoding::Plain], vec![Encoding::Plain]];
+    let row_groups = RowGroupIterator::try_new(chunks, &schema, options(), encodings)?;
+
+    let mut writer = FileWriter::try_new(Cursor::new(vec![]), schema, options())?;
+    for group in row_groups {
+        writer.write(group?)?;
+    }
+    writer.end(None)?;
+    Ok(writer.into_inner().into_inner())
+}
+
+/// Reads the column `a` of the rows matching `predicate`
+fn read_a(data: &[u8], predicate: Predicate, limit: Option<usize>) -> Result<Vec<Option<i32>>> {
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let schema = infer_schema(&metadata)?;
+
+    let reader = FileReader::new(reader, metadata.row_groups, schema, None, limit, None)
+        .with_predicate(predicate)?;
+
+    let mut values = vec![];
+    for chunk in reader {
+        let chunk = chunk?;
+        assert!(!chunk.is_empty());
+        let a = chunk.arrays()[0]
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        values.extend(a.iter().map(|x| x.copied()));
+    }
+    Ok(values)
+}
+
+fn int(value: i32) -> Box<dyn Scalar> {
+    Box::new(PrimitiveScalar::from(Some(value)))
+}
+
+#[test]
+fn prune_row_groups() -> Result<()> {
+    let data = write_row_groups()?;
+    let mut reader = Cursor::new(&data);
+    let metadata = read_metadata(&mut reader)?;
+    let schema = infer_schema(&metadata)?;
+
+    let predicate = Predicate::compare("a", Comparison::GtEq, PrimitiveScalar::from(Some(15))).and(
+        Predicate::compare("a", Comparison::Lt, PrimitiveScalar::from(Some(22))),
+    );
+    let row_groups = predicate.prune_row_groups(&schema.fields, metadata.row_groups.clone())?;
+    assert_eq!(row_groups.len(), 2);
+    assert_eq!(
+        row_groups[0].compressed_size(),
+        metadata.row_groups[1].compressed_size()
+    );
+
+    // the first row group has nulls (0 and 7) and the others have values
+    let row_groups =
+        Predicate::is_null("a").prune_row_groups(&schema.fields, metadata.row_groups)?;
+    assert_eq!(row_groups.len(), 3);
+    Ok(())
+}
+
+#[test]
+fn comparisons() -> Result<()> {
+    let data = write_row_groups()?;
+
+    let predicate = Predicate::compare("a", Comparison::GtEq, PrimitiveScalar::from(Some(15))).and(
+        Predicate::compare("a", Comparison::Lt, PrimitiveScalar::from(Some(22))),
+    );
+    let expected = vec![15, 16, 17, 18, 19, 20]
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+    assert_eq!(read_a(&data, predicate, None)?, expected);
+
+    let predicate = Predicate::compare("b", Comparison::Eq, Utf8Scalar::<i32>::new(Some("25")));
+    assert_eq!(read_a(&data, predicate, None)?, vec![Some(25)]);
+
+    let predicate = Predicate::compare("a", Comparison::Gt, PrimitiveScalar::from(Some(100)));
+    assert_eq!(read_a(&data, predicate, None)?, vec![]);
+    Ok(())
+}
+
+#[test]
+fn nulls_in_and_not() -> Result<()> {
+    let data = write_row_groups()?;
+
+    let predicate = Predicate::is_null("a");
+    assert_eq!(read_a(&data, predicate, None)?, vec![None; 5]);
+
+    let predicate = Predicate::is_in("a", vec![int(3), int(14), int(29)]);
+    assert_eq!(read_a(&data, predicate, None)?, vec![Some(3), Some(29)]);
+
+    // nulls never match a comparison, nor its negation
+    let predicate = !Predicate::compare("a", Comparison::Gt, PrimitiveScalar::from(Some(3)));
+    assert_eq!(
+        read_a(&data, predicate, None)?,
+        vec![Some(1), Some(2), Some(3)]
+    );
+
+    let predicate = !(Predicate::is_not_null("a").and(Predicate::compare(
+        "a",
+        Comparison::Gt,
+        PrimitiveScalar::from(Some(1)),
+    )));
+    assert_eq!(
+        read_a(&data, predicate, None)?,
+        vec![None, Some(1), None, None, None, None]
+    );
+    Ok(())
+}
+
+#[test]
+fn limit_applies_to_filtered_rows() -> Result<()> {
+    let data = write_row_groups()?;
+
+    let predicate = Predicate::compare("a", Comparison::NotEq, PrimitiveScalar::from(Some(1)));
+    let expected = vec![2, 3, 4].into_iter().map(Some).collect::<Vec<_>>();
+    assert_eq!(read_a(&data, predicate, Some(3))?, expected);
+
+    let predicate = Predicate::compare("a", Comparison::GtEq, PrimitiveScalar::from(Some(18)));
+    let expected = vec![18, 19, 20, 22]
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+    assert_eq!(read_a(&data, predicate, Some(4))?, expected);
+    Ok(())
+}
+
+#[test]
+fn prune_pages() -> Result<()> {
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int32, false),
+        Field::new("b", DataType::Utf8, false),
+    ]);
+    let parquet_schema = to_parquet_schema(&schema)?;
+
+    // `a` has 3 pages, with rows [0, 4), [4, 5) and [5, 6); `b` has a single page
+    let a = [
+        Int32Array::from_slice([1, 2, 3, 4]),
+        Int32Array::from_slice([5]),
+        Int32Array::from_slice([6]),
+    ];
+    let b = Utf8Array::<i32>::from_slice(["a", "b", "c", "d", "e", "f"]);
+    let type_ = |i: usize| {
+        parquet_schema.columns()[i]
+            .descriptor
+            .primitive_type
+            .clone()
+    };
+
+    let pages_a = a
+        .iter()
+        .map(|array| {
+            array_to_page(
+                array,
+                type_(0),
+                &[Nested::Primitive(None, false, array.len())],
+                options(),
+                Encoding::Plain,
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let pages_b = vec![array_to_page(
+        &b,
+        type_(1),
+        &[Nested::Primitive(None, false, b.len())],
+        options(),
+        Encoding::Plain,
+    )?];
+
+    let to_compressed = |pages: Vec<EncodedPage>| {
+        let pages = DynIter::new(pages.into_iter().map(Ok));
+        let pages = Compressor::new(pages, options().compression, vec![]).map_err(Error::from);
+        Result::Ok(DynStreamingIterator::new(pages))
+    };
+    let row_group = DynIter::new(vec![to_compressed(pages_a), to_compressed(pages_b)].into_iter());
+
+    let mut writer = FileWriter::try_new(vec![], schema, options())?;
+    writer.write(row_group)?;
+    writer.end(None)?;
+    let data = writer.into_inner();
+
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let schema = infer_schema(&metadata)?;
+
+    let predicate = Predicate::compare("a", Comparison::GtEq, PrimitiveScalar::from(Some(4))).and(
+        Predicate::compare("a", Comparison::LtEq, PrimitiveScalar::from(Some(5))),
+    );
+    let pages = predicate
+        .read_pages(&mut reader, &metadata.row_groups[0], &schema.fields)?
+        .unwrap();
+    let selected = pages
+        .iter()
+        .map(|field| {
+            field[0]
+                .iter()
+                .map(|page| page.selected_rows.clone())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    // the last page of `a` is not read, nor its rows in `b`
+    assert_eq!(
+        selected,
+        vec![
+            vec![vec![Interval::new(0, 4)], vec![Interval::new(0, 1)], vec![]],
+            vec![vec![Interval::new(0, 5)]]
+        ]
+    );
+
+    let reader = FileReader::new(reader, metadata.row_groups, schema, None, None, None)
+        .with_predicate(predicate)?;
+    let chunks = reader.collect::<Result<Vec<_>>>()?;
+    let expected = Chunk::new(vec![
+        Int32Array::from_slice([4, 5]).boxed(),
+        Utf8Array::<i32>::from_slice(["d", "e"]).boxed(),
+    ]);
+    assert_eq!(chunks, vec![expected]);
+    Ok(())
+}
+
+#[test]
+fn invalid() -> Result<()> {
+    let fields = schema().fields;
+
+    let predicate = Predicate::is_null("c");
+    assert!(predicate.validate(&fields).is_err());
+
+    // the literal must have the column's data type
+    let predicate = Predicate::compare("a", Comparison::Eq, PrimitiveScalar::from(Some(1i64)));
+    assert!(predicate.validate(&fields).is_err());
+
+    let predicate = Predicate::compare("a", Comparison::Eq, PrimitiveScalar::from(Some(1i32)));
+    assert!(predicate.validate(&fields).is_ok());
+    assert_eq!(predicate.columns(), vec!["a"]);
+    Ok(())
+}
@@ -36,6 +36,9 @@ fn round_trip(
         write_statistics: true,
         compression,
         version,
+        bloom_filter: None,
+        data_page_size: None,
+        data_page_max_rows: None,
     };
 
     let iter = vec![Chunk::try_new(vec![array.clone()])];
@@ -0,0 +1,343 @@
+use std::io::Cursor;
+
+use arrow2::chunk::Chunk;
+use arrow2::{array::*, datatypes::*, error::Result, io::parquet::read::*, io::parquet::write::*};
+
+fn options() -> WriteOptions {
+    WriteOptions {
+        write_statistics: true,
+        compression: CompressionOptions::Uncompressed,
+        version: Version::V2,
+        bloom_filter: None,
+        data_page_size: None,
+        data_page_max_rows: None,
+    }
+}
+
+fn write(
+    chunk: Chunk<Box<dyn Array>>,
+    schema: &Schema,
+    columns: ColumnWriteOptions,
+) -> Result<Vec<u8>> {
+    let encodings = schema
+        .fields
+        .iter()
+        .map(|field| transverse(&field.data_type, |_| Encoding::Plain))
+        .collect();
+    let mut writer = FileWriter::try_new(Cursor::new(vec![]), schema.clone(), options())?
+        .with_column_options(columns)?;
+    writer.write_chunk(chunk, encodings)?;
+    writer.end(None)?;
+    Ok(writer.into_inner().into_inner())
+}
+
+type Chunks = Vec<Chunk<Box<dyn Array>>>;
+
+fn read(data: &[u8]) -> Result<(FileMetaData, Chunks)> {
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let schema = infer_schema(&metadata)?;
+    let chunks = FileReader::new(
+        reader,
+        metadata.row_groups.clone(),
+        schema,
+        None,
+        None,
+        None,
+    )
+    .collect::<Result<Vec<_>>>()?;
+    Ok((metadata, chunks))
+}
+
+fn encodings(column: &ColumnChunkMetaData) -> Vec<Encoding> {
+    column
+        .column_encoding()
+        .iter()
+        .map(|encoding| Encoding::try_from(*encoding).unwrap())
+        .collect()
+}
+
+#[test]
+fn compression_and_encoding() -> Result<()> {
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int64, false),
+        Field::new("b", DataType::Utf8, true),
+    ]);
+    let a = Int64Array::from_vec(vec![1; 1000]);
+    let b = Utf8Array::<i32>::from_iter((0..1000).map(|x| Some(x.to_string())));
+    let chunk = Chunk::new(vec![a.boxed(), b.boxed()]);
+
+    let columns = ColumnWriteOptions::new()
+        .with_column(
+            ["a"],
+            ColumnOptions {
+                compression: Some(CompressionOptions::Snappy),
+                ..Default::default()
+            },
+        )
+        .with_column(
+            ["b"],
+            ColumnOptions {
+                encoding: Some(Encoding::DeltaLengthByteArray),
+                ..Default::default()
+            },
+        );
+    let data = write(chunk.clone(), &schema, columns)?;
+    let (metadata, chunks) = read(&data)?;
+    assert_eq!(chunks, vec![chunk]);
+
+    let columns = metadata.row_groups[0].columns();
+    assert!(columns[0].compressed_size() < columns[0].uncompressed_size() / 2);
+    assert_eq!(columns[1].compressed_size(), columns[1].uncompressed_size());
+    assert!(encodings(&columns[1]).contains(&Encoding::DeltaLengthByteArray));
+    assert!(!encodings(&columns[0]).contains(&Encoding::DeltaLengthByteArray));
+    Ok(())
+}
+
+#[test]
+fn nested_path() -> Result<()> {
+    let fields = vec![
+        Field::new("x", DataType::Int32, true),
+        Field::new("y", DataType::Utf8, true),
+    ];
+    let schema = Schema::from(vec![Field::new(
+        "a",
+        DataType::Struct(fields.clone()),
+        true,
+    )]);
+    let x = Int32Array::from_vec(vec![1; 1000]);
+    let y = Utf8Array::<i32>::from_slice(vec!["a"; 1000]);
+    let a = StructArray::new(DataType::Struct(fields), vec![x.boxed(), y.boxed()], None);
+    let chunk = Chunk::new(vec![a.boxed()]);
+
+    let columns = ColumnWriteOptions::new().with_column(
+        ["a", "y"],
+        ColumnOptions {
+            compression: Some(CompressionOptions::Snappy),
+            ..Default::default()
+        },
+    );
+    let data = write(chunk.clone(), &schema, columns)?;
+    let (metadata, chunks) = read(&data)?;
+    assert_eq!(chunks, vec![chunk]);
+
+    let columns = metadata.row_groups[0].columns();
+    assert_eq!(columns[0].compressed_size(), columns[0].uncompressed_size());
+    assert!(columns[1].compressed_size() < columns[1].uncompressed_size() / 2);
+    Ok(())
+}
+
+#[test]
+fn dictionary() -> Result<()> {
+    let schema = Schema::from(vec![
+        Field::new("low", DataType::Utf8, true),
+        Field::new("high", DataType::Utf8, true),
+        Field::new("int", DataType::Int64, true),
+    ]);
+    let low =
+        Utf8Array::<i32>::from_iter((0..1000).map(|x| (x % 7 != 0).then(|| format!("{}", x % 3))));
+    let high = Utf8Array::<i32>::from_iter((0..1000).map(|x| Some(format!("value{x}"))));
+    let int =
+        Int64Array::from_iter((0..1000).map(|x| if x % 5 != 0 { Some(x % 10) } else { None }));
+    let chunk = Chunk::new(vec![low.boxed(), high.boxed(), int.boxed()]);
+
+    // all columns are dictionary-encoded up to 100 bytes of values
+    let columns = ColumnWriteOptions::new().with_column(
+        Vec::<String>::new(),
+        ColumnOptions {
+            dictionary: Some(DictionaryOptions {
+                enabled: true,
+                max_bytes: 100,
+            }),
+            ..Default::default()
+        },
+    );
+    let data = write(chunk.clone(), &schema, columns)?;
+    let (metadata, chunks) = read(&data)?;
+    assert_eq!(chunks, vec![chunk]);
+
+    let columns = metadata.row_groups[0].columns();
+    assert!(encodings(&columns[0]).contains(&Encoding::RleDictionary));
+    // the dictionary of `high` would be larger than 100 bytes
+    assert!(!encodings(&columns[1]).contains(&Encoding::RleDictionary));
+    assert!(encodings(&columns[2]).contains(&Encoding::RleDictionary));
+    Ok(())
+}
+
+#[test]
+fn dictionary_disabled() -> Result<()> {
+    let values = Utf8Array::<i32>::from_slice(["a", "b"]);
+    let keys = PrimitiveArray::<u32>::from([Some(0), None, Some(1), Some(0)]);
+    let array = DictionaryArray::try_from_keys(keys, values.boxed())?;
+    let schema = Schema::from(vec![Field::new("a", array.data_type().clone(), true)]);
+    let chunk = Chunk::new(vec![array.boxed()]);
+
+    let columns = ColumnWriteOptions::new().with_column(
+        ["a"],
+        ColumnOptions {
+            dictionary: Some(DictionaryOptions {
+                enabled: false,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+    let data = write(chunk, &schema, columns)?;
+
+    let mut reader = Cursor::new(&data);
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+    assert!(!encodings(column).contains(&Encoding::RleDictionary));
+
+    // the values are read back as a plain array
+    let field = Field::new("a", DataType::Utf8, true);
+    let mut arrays = read_columns_many(
+        &mut reader,
+        &metadata.row_groups[0],
+        vec![field],
+        None,
+        None,
+        None,
+    )?;
+    let array = arrays.pop().unwrap().next().unwrap()?;
+    let expected = Utf8Array::<i32>::from([Some("a"), None, Some("b"), Some("a")]);
+    assert_eq!(array.as_ref(), &expected as &dyn Array);
+    Ok(())
+}
+
+#[test]
+fn statistics() -> Result<()> {
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Int64, true),
+    ]);
+    let a = Int64Array::from_slice([1, 2, 3]);
+    let chunk = Chunk::new(vec![a.clone().boxed(), a.boxed()]);
+
+    let columns = ColumnWriteOptions::new().with_column(
+        ["b"],
+        ColumnOptions {
+            write_statistics: Some(false),
+            ..Default::default()
+        },
+    );
+    let data = write(chunk.clone(), &schema, columns)?;
+    let (metadata, chunks) = read(&data)?;
+    assert_eq!(chunks, vec![chunk]);
+
+    let row_group = &metadata.row_groups[0];
+    assert!(row_group.columns()[0].statistics().is_some());
+    assert!(row_group.columns()[1].statistics().is_none());
+    // page indexes require the statistics of all columns
+    assert!(!indexes::has_indexes(row_group));
+    Ok(())
+}
+
+#[test]
+fn statistics_row_group_iterator() -> Result<()> {
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Int64, true),
+    ]);
+    let a = Int64Array::from_slice([1, 2, 3]);
+    let chunk = Chunk::new(vec![a.clone().boxed(), a.boxed()]);
+    let encodings = vec![vec![Encoding::Plain]; 2];
+
+    // the page indexes of the file would require the statistics of `b`
+    let columns = ColumnWriteOptions::new().with_column(
+        ["b"],
+        ColumnOptions {
+            write_statistics: Some(false),
+            ..Default::default()
+        },
+    );
+    let row_groups = RowGroupIterator::try_new(
+        vec![Ok(chunk.clone())].into_iter(),
+        &schema,
+        options(),
+        encodings.clone(),
+    )?;
+    assert!(row_groups.with_column_options(columns).is_err());
+
+    // instead, statistics are disabled for the file and enabled for `a`
+    let options = WriteOptions {
+        write_statistics: false,
+        ..options()
+    };
+    let columns = ColumnWriteOptions::new().with_column(
+        ["a"],
+        ColumnOptions {
+            write_statistics: Some(true),
+            ..Default::default()
+        },
+    );
+    let row_groups =
+        RowGroupIterator::try_new(vec![Ok(chunk)].into_iter(), &schema, options, encodings)?
+            .with_column_options(columns)?;
+    let mut writer = FileWriter::try_new(Cursor::new(vec![]), schema, options)?;
+    for group in row_groups {
+        writer.write(group?)?;
+    }
+    writer.end(None)?;
+    let data = writer.into_inner().into_inner();
+
+    let (metadata, _) = read(&data)?;
+    let row_group = &metadata.row_groups[0];
+    assert!(row_group.columns()[0].statistics().is_some());
+    assert!(row_group.columns()[1].statistics().is_none());
+    Ok(())
+}
+
+#[test]
+fn dictionary_statistics() -> Result<()> {
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Utf8, true),
+    ]);
+    let a = Int64Array::from([Some(5), None, Some(-3), Some(5), None, Some(10)]);
+    let b = Utf8Array::<i32>::from([Some("x"), None, Some("z"), Some("x"), None, None]);
+    let chunk = Chunk::new(vec![a.boxed(), b.boxed()]);
+
+    let columns = ColumnWriteOptions::new().with_column(
+        Vec::<String>::new(),
+        ColumnOptions {
+            dictionary: Some(DictionaryOptions::default()),
+            ..Default::default()
+        },
+    );
+    let data = write(chunk.clone(), &schema, columns)?;
+    let (metadata, chunks) = read(&data)?;
+    assert_eq!(chunks, vec![chunk]);
+    let row_groups = &metadata.row_groups;
+    assert!(encodings(&row_groups[0].columns()[0]).contains(&Encoding::RleDictionary));
+
+    // the statistics are those of the column, not of its dictionary
+    let statistics = statistics::deserialize(&schema.fields[0], row_groups)?;
+    assert_eq!(
+        statistics.null_count.as_ref(),
+        &UInt64Array::from_slice([2]) as &dyn Array
+    );
+    assert_eq!(
+        statistics.min_value.as_ref(),
+        &Int64Array::from_slice([-3]) as &dyn Array
+    );
+    assert_eq!(
+        statistics.max_value.as_ref(),
+        &Int64Array::from_slice([10]) as &dyn Array
+    );
+
+    let statistics = statistics::deserialize(&schema.fields[1], row_groups)?;
+    assert_eq!(
+        statistics.null_count.as_ref(),
+        &UInt64Array::from_slice([3]) as &dyn Array
+    );
+    assert_eq!(
+        statistics.min_value.as_ref(),
+        &Utf8Array::<i32>::from_slice(["x"]) as &dyn Array
+    );
+    assert_eq!(
+        statistics.max_value.as_ref(),
+        &Utf8Array::<i32>::from_slice(["z"]) as &dyn Array
+    );
+    Ok(())
+}
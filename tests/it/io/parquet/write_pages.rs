@@ -0,0 +1,189 @@
+use std::cell::RefCell;
+use std::io::Cursor;
+
+use arrow2::chunk::Chunk;
+use arrow2::io::parquet::read::indexes;
+#[cfg(feature = "io_parquet_predicate")]
+use arrow2::io::parquet::read::indexes::Interval;
+#[cfg(feature = "io_parquet_predicate")]
+use arrow2::io::parquet::read::predicate::{Comparison, Predicate};
+#[cfg(feature = "io_parquet_predicate")]
+use arrow2::scalar::PrimitiveScalar;
+use arrow2::{array::*, datatypes::*, error::Result, io::parquet::read::*, io::parquet::write::*};
+
+fn options(data_page_size: Option<usize>, data_page_max_rows: Option<usize>) -> WriteOptions {
+    WriteOptions {
+        write_statistics: true,
+        compression: CompressionOptions::Uncompressed,
+        version: Version::V1,
+        bloom_filter: None,
+        data_page_size,
+        data_page_max_rows,
+    }
+}
+
+fn write(
+    chunk: Chunk<Box<dyn Array>>,
+    encoding: Encoding,
+    options: WriteOptions,
+) -> Result<Vec<u8>> {
+    let schema = Schema::from(vec![Field::new(
+        "a",
+        chunk.arrays()[0].data_type().clone(),
+        true,
+    )]);
+    let encodings = vec![transverse(chunk.arrays()[0].data_type(), |_| encoding)];
+    let row_groups =
+        RowGroupIterator::try_new(vec![Ok(chunk)].into_iter(), &schema, options, encodings)?;
+
+    let mut writer = FileWriter::try_new(Cursor::new(vec![]), schema, options)?;
+    for group in row_groups {
+        writer.write(group?)?;
+    }
+    writer.end(None)?;
+    Ok(writer.into_inner().into_inner())
+}
+
+/// Returns the number of rows of each data page of the first column, from its offset index
+fn rows_per_page(data: &[u8]) -> Result<Vec<u64>> {
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let schema = infer_schema(&metadata)?;
+
+    let row_group = &metadata.row_groups[0];
+    assert!(indexes::has_indexes(row_group));
+    let rows = RefCell::new(vec![]);
+    indexes::read_filtered_pages(&mut reader, row_group, &schema.fields, |_, intervals| {
+        *rows.borrow_mut() = intervals[0][0].iter().map(|x| x.length as u64).collect();
+        intervals[0][0].clone()
+    })?;
+    Ok(rows.into_inner())
+}
+
+fn round_trip(data: &[u8]) -> Result<Vec<Chunk<Box<dyn Array>>>> {
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let schema = infer_schema(&metadata)?;
+    FileReader::new(reader, metadata.row_groups, schema, None, None, None).collect()
+}
+
+#[test]
+fn max_rows() -> Result<()> {
+    let array = Int32Array::from_iter((0..100).map(|x| if x % 7 == 0 { None } else { Some(x) }));
+    let chunk = Chunk::new(vec![array.boxed()]);
+
+    let data = write(chunk.clone(), Encoding::Plain, options(None, Some(30)))?;
+    assert_eq!(rows_per_page(&data)?, vec![30, 30, 30, 10]);
+    assert_eq!(round_trip(&data)?, vec![chunk]);
+    Ok(())
+}
+
+#[test]
+fn page_size() -> Result<()> {
+    // 8 bytes per row
+    let array = Int64Array::from_values(0..100);
+    let chunk = Chunk::new(vec![array.boxed()]);
+
+    let data = write(chunk.clone(), Encoding::Plain, options(Some(200), None))?;
+    assert_eq!(rows_per_page(&data)?, vec![25; 4]);
+    assert_eq!(round_trip(&data)?, vec![chunk.clone()]);
+
+    // the smallest of both limits applies
+    let data = write(chunk.clone(), Encoding::Plain, options(Some(200), Some(20)))?;
+    assert_eq!(rows_per_page(&data)?, vec![20; 5]);
+
+    // a single page by default
+    let data = write(chunk, Encoding::Plain, options(None, None))?;
+    assert_eq!(rows_per_page(&data)?, vec![100]);
+    Ok(())
+}
+
+#[test]
+fn nested() -> Result<()> {
+    let data = vec![
+        Some(vec![Some(1), None, Some(3)]),
+        None,
+        Some(vec![]),
+        Some(vec![Some(4)]),
+        Some(vec![Some(5), Some(6), Some(7), Some(8)]),
+        Some(vec![None]),
+        None,
+    ];
+    let mut array = MutableListArray::<i32, MutablePrimitiveArray<i32>>::new();
+    array.try_extend(data)?;
+    let array: ListArray<i32> = array.into();
+    let chunk = Chunk::new(vec![array.boxed()]);
+
+    let data = write(chunk.clone(), Encoding::Plain, options(None, Some(2)))?;
+    assert_eq!(rows_per_page(&data)?, vec![2, 2, 2, 1]);
+    assert_eq!(round_trip(&data)?, vec![chunk]);
+    Ok(())
+}
+
+#[test]
+fn nested_sliced() -> Result<()> {
+    let data = (0..20).map(|x| {
+        if x % 5 == 0 {
+            None
+        } else {
+            Some((0..x % 4).map(move |y| if y == 2 { None } else { Some(x + y) }))
+        }
+    });
+    let mut array = MutableListArray::<i32, MutablePrimitiveArray<i32>>::new();
+    array.try_extend(data)?;
+    let array: ListArray<i32> = array.into();
+    let array = array.slice(3, 15).boxed();
+    let chunk = Chunk::new(vec![array]);
+
+    let data = write(chunk.clone(), Encoding::Plain, options(None, Some(4)))?;
+    assert_eq!(rows_per_page(&data)?, vec![4, 4, 4, 3]);
+    assert_eq!(round_trip(&data)?, vec![chunk]);
+    Ok(())
+}
+
+#[test]
+fn dictionary() -> Result<()> {
+    let keys =
+        PrimitiveArray::<u32>::from_iter((0..10).map(|x| if x == 4 { None } else { Some(x % 3) }));
+    let values = Utf8Array::<i32>::from_slice(["a", "bb", "ccc"]).boxed();
+    let array = DictionaryArray::try_from_keys(keys, values)?;
+    let chunk = Chunk::new(vec![array.boxed()]);
+
+    let data = write(
+        chunk.clone(),
+        Encoding::RleDictionary,
+        options(None, Some(4)),
+    )?;
+    assert_eq!(rows_per_page(&data)?, vec![4, 4, 2]);
+    assert_eq!(round_trip(&data)?, vec![chunk]);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "io_parquet_predicate")]
+fn prune_pages() -> Result<()> {
+    let array = Int32Array::from_values(0..100);
+    let chunk = Chunk::new(vec![array.boxed()]);
+    let data = write(chunk, Encoding::Plain, options(None, Some(10)))?;
+
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let schema = infer_schema(&metadata)?;
+
+    let predicate = Predicate::compare("a", Comparison::GtEq, PrimitiveScalar::from(Some(42))).and(
+        Predicate::compare("a", Comparison::Lt, PrimitiveScalar::from(Some(55))),
+    );
+    let pages = predicate
+        .read_pages(&mut reader, &metadata.row_groups[0], &schema.fields)?
+        .unwrap();
+    // only the pages with rows [40, 50) and [50, 60) are read
+    let selected = pages[0][0]
+        .iter()
+        .map(|page| page.selected_rows.clone())
+        .collect::<Vec<_>>();
+    let mut expected = vec![vec![]; 10];
+    expected[4] = vec![Interval::new(0, 10)];
+    expected[5] = vec![Interval::new(0, 10)];
+    assert_eq!(selected, expected);
+    Ok(())
+}